@@ -11,6 +11,21 @@
  */
 typedef struct HmIcsRules HmIcsRules;
 
+/**
+ * QuinFlow保存一条连接上跨数据包的解析状态，由调用方按连接持有，连接结束时释放。
+ * 使用示例如下：
+ * ```
+ * use parsing_parser::*;
+ *
+ * let mut flow = QuinFlow::new();
+ * for input in [&[1u8, 2, 3][..], &[4, 5, 6][..]] {
+ *     let packet = QuinPacket::parse_from_flow(input, &QuinPacketOptions::default(), &mut flow);
+ *     println!("packet: {:?}", packet);
+ * }
+ * ```
+ */
+typedef struct QuinFlow QuinFlow;
+
 /**
  * QuinPacket是由 Level1 - Level5 Packet 构成的枚举结构，使用示例如下：
  * ```
@@ -98,6 +113,11 @@ bool detect_suricata_rules_rs(const struct VecSurules *rules_ptr,
  */
 void enable_tracing_rs(void);
 
+/**
+ * 释放连接解析上下文
+ */
+void free_flow_rs(struct QuinFlow *flow_ptr);
+
 /**
  * 清空ICS规则
  */
@@ -128,6 +148,11 @@ char *get_parsing_json_rs(const struct QuinPacket *packet_ptr,
  */
 uint8_t get_protocol_id_rs(const struct QuinPacket *packet_ptr);
 
+/**
+ * 初始化连接解析上下文，每条连接使用一个
+ */
+struct QuinFlow *init_flow_rs(void);
+
 /**
  * 初始化ICS规则结构体
  */
@@ -158,6 +183,16 @@ bool load_ics_rules_rs(struct HmIcsRules *rules_ptr, const char *file_ptr);
  */
 bool load_suricata_rules_rs(struct VecSurules *rules_ptr, const char *file_ptr);
 
+/**
+ * 在连接上下文中解析数据包
+ *
+ * Warning: 解析结果可能引用连接上下文中重组的数据，需在解析该连接的下一个数据包或释放上下文之前释放。
+ */
+struct QuinPacket *parse_packet_in_flow_rs(const uint8_t *input_ptr,
+                                           uint16_t input_len,
+                                           const struct QuinPacketOptions *option_ptr,
+                                           struct QuinFlow *flow_ptr);
+
 /**
  * 解析数据包
  */
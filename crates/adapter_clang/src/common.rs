@@ -54,15 +54,17 @@ impl PacketAdaptFirewall for NetworkProtocol {
         match self {
             NetworkProtocol::Ipv4  => 3,
             NetworkProtocol::Ipv6  => 4,
-            NetworkProtocol::Goose => 26,
             NetworkProtocol::Vlan  => 41,
+            NetworkProtocol::Empty => 0,
         }
     }
 
     fn is_ics_protocol(&self) -> bool {
         match self {
-            NetworkProtocol::Goose => true,
-            _  => false
+            NetworkProtocol::Ipv4  => false,
+            NetworkProtocol::Ipv6  => false,
+            NetworkProtocol::Vlan  => false,
+            NetworkProtocol::Empty => false,
         }
     }
 }
@@ -72,14 +74,15 @@ impl PacketAdaptFirewall for TransportProtocol {
         match self {
             TransportProtocol::Tcp => 5,
             TransportProtocol::Udp => 6,
-            TransportProtocol::Sv  => 25
+            TransportProtocol::Empty => 0
         }
     }
 
     fn is_ics_protocol(&self) -> bool {
         match self {
-            TransportProtocol::Sv  => true,
-            _ => false
+            TransportProtocol::Tcp   => false,
+            TransportProtocol::Udp   => false,
+            TransportProtocol::Empty => false,
         }
     }
 }
@@ -97,8 +100,14 @@ impl PacketAdaptFirewall for ApplicationNaiveProtocol {
             ApplicationNaiveProtocol::Modbus   => 22,
             ApplicationNaiveProtocol::Opcua    => 31,
             ApplicationNaiveProtocol::S7comm   => 23,
-            ApplicationNaiveProtocol::Goose    => 50, // 暂定
-            ApplicationNaiveProtocol::Sv       => 51, // 暂定
+            ApplicationNaiveProtocol::Goose    => 26,
+            ApplicationNaiveProtocol::Sv       => 25,
+            ApplicationNaiveProtocol::S7commPlus => 52, // 暂定
+            ApplicationNaiveProtocol::Iec101   => 53, // 暂定
+            ApplicationNaiveProtocol::Enip     => 54, // 暂定
+            ApplicationNaiveProtocol::Profinet => 55, // 暂定
+            ApplicationNaiveProtocol::Melsec   => 56, // 暂定
+            ApplicationNaiveProtocol::Tristation => 57, // 暂定
         }
     }
 
@@ -6,7 +6,7 @@ use parsing_parser::{parsers::{
     OpcuaHeader, opcua::{MessageTypeEnum, MsgVariantInfo, ServiceNodeidInfo}, 
     Iec104Header, iec104::{TypeBlock, IecAsdu}, 
     ModbusReqHeader, modbus_req, 
-    S7commHeader, s7comm, Dnp3Header, FinsTcpReqHeader, fins_tcp_req
+    S7commHeader, s7comm, Dnp3Header, FinsCommand, FinsResponse, FinsFrame, fins_command
 }, QuinPacket, LinkLayer, AppLevel, TransportLayer, NetworkLayer, TransLevel, ApplicationLayer, NetLevel, LinkLevel};
 
 use serde::{Serialize, Deserialize};
@@ -194,7 +194,14 @@ impl ReportFields {
             ApplicationLayer::ModbusReq(modbus_req) => modbus_req.get_fields(),
             ApplicationLayer::S7comm(s7comm) => s7comm.get_fields(),
             ApplicationLayer::Dnp3(dnp3) => dnp3.get_fields(),
-            ApplicationLayer::FinsTcpReq(fins) => fins.get_fields(),
+            ApplicationLayer::FinsTcpReq(_)
+            | ApplicationLayer::FinsTcpRsp(_)
+            | ApplicationLayer::FinsUdpReq(_)
+            | ApplicationLayer::FinsUdpRsp(_) => match FinsFrame::from_layer(app_layer) {
+                Some(FinsFrame::Command(fins)) => fins.get_fields(),
+                Some(FinsFrame::Response(fins)) => fins.get_fields(),
+                None => ReportFields::Empty
+            },
             _ => ReportFields::Empty
         }
     }
@@ -242,7 +249,9 @@ pub struct OpcuaFields {
     #[serde(rename = "type")]
     message_type: u32,
     #[serde(rename = "function_code", skip_serializing_if = "is_default")]
-    service_nodeid_numeric: Option<u32>
+    service_nodeid_numeric: Option<u32>,
+    #[serde(skip_serializing_if = "is_default")]
+    security_policy: Option<String>
 }
 
 impl<'a> ProtocolFields for OpcuaHeader<'a> {
@@ -265,6 +274,10 @@ impl<'a> ProtocolFields for OpcuaHeader<'a> {
                     }
                 }
                 _ => None
+            },
+            security_policy: match &self.message_type_enum {
+                MessageTypeEnum::OpenSecureChannel { security_policy_uri, .. } => Some(security_policy_uri.to_string()),
+                _ => None
             }
         };
 
@@ -357,7 +370,11 @@ impl<'a> ProtocolFields for ModbusReqHeader<'a> {
                 modbus_req::Data::ReadFIFOQueue { fifo_pointer_address, .. } => Some(fifo_pointer_address),
                 _ => None
             },
-            subfunction: None // TODO: Diagnostics / EncapsulatedInterfaceTransport
+            subfunction: match self.pdu.data {
+                modbus_req::Data::EncapsulatedInterfaceTransport { mei_type, .. } => Some(mei_type),
+                modbus_req::Data::Umas { umas_function_code, .. } => Some(umas_function_code),
+                _ => None // TODO: Diagnostics
+            }
         };
 
         ReportFields::Modbus(fields)
@@ -372,7 +389,38 @@ pub struct S7commFields {
     // address: Option<String>, // Job - ReadVar / Job - WriteVar ? List
     // value: Option<u32>, // Job - WriteVar / AckData - ReadVar ? List
     subfunction: Option<u8>, // Userdata
-    parameter_type: Option<u8> // Userdata
+    parameter_type: Option<u8>, // Userdata
+    #[serde(skip_serializing_if = "is_default")]
+    values: Option<Vec<S7commValueFields>> // Job - WriteVar / AckData - ReadVar
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Default)]
+pub struct S7commValueFields {
+    #[serde(skip_serializing_if = "is_default")]
+    area: Option<u8>,
+    #[serde(skip_serializing_if = "is_default")]
+    db_number: Option<u16>,
+    #[serde(skip_serializing_if = "is_default")]
+    byte_address: Option<u32>,
+    #[serde(skip_serializing_if = "is_default")]
+    bit_address: Option<u8>,
+    #[serde(skip_serializing_if = "is_default")]
+    value: Option<Vec<f64>>,
+    #[serde(skip_serializing_if = "is_default")]
+    text: Option<String>
+}
+
+impl S7commValueFields {
+    fn from_value(value: &s7comm::S7Value) -> Self {
+        let mut fields = S7commValueFields::default();
+        match value {
+            s7comm::S7Value::String(text) => {
+                fields.text = Some(String::from_utf8_lossy(text).into_owned());
+            }
+            _ => fields.value = value.numeric_values(),
+        }
+        fields
+    }
 }
 
 impl<'a> ProtocolFields for S7commHeader<'a> {
@@ -381,17 +429,40 @@ impl<'a> ProtocolFields for S7commHeader<'a> {
 
         fields.rosctr = self.header.rosctr;
 
-        match self.parameter {
-            s7comm::Parameter::AckData { function_code, ackdata_param: _ } => {
-                fields.function_code = Some(function_code);
+        match &self.parameter {
+            s7comm::Parameter::AckData { function_code, ackdata_param } => {
+                fields.function_code = Some(*function_code);
+                if let s7comm::AckdataParam::ReadVar { standard_items, .. } = ackdata_param {
+                    fields.values = Some(
+                        standard_items
+                            .iter()
+                            .map(|item| S7commValueFields::from_value(&item.value))
+                            .collect()
+                    );
+                }
             }
-            s7comm::Parameter::Job { function_code, job_param: _ } => {
-                fields.function_code = Some(function_code);
+            s7comm::Parameter::Job { function_code, job_param } => {
+                fields.function_code = Some(*function_code);
+                if let s7comm::JobParam::WriteVar { values, .. } = job_param {
+                    fields.values = Some(
+                        values
+                            .iter()
+                            .map(|item| {
+                                let mut value_fields = S7commValueFields::from_value(&item.value);
+                                value_fields.area = Some(item.area);
+                                value_fields.db_number = Some(item.db_number);
+                                value_fields.byte_address = Some(item.byte_address);
+                                value_fields.bit_address = Some(item.bit_address);
+                                value_fields
+                            })
+                            .collect()
+                    );
+                }
             }
             s7comm::Parameter::Userdata { parameter_type, function_group, subfunction, .. } => {
-                fields.function_code = Some(function_group);
-                fields.subfunction = Some(subfunction);
-                fields.parameter_type = Some(parameter_type);
+                fields.function_code = Some(*function_group);
+                fields.subfunction = Some(*subfunction);
+                fields.parameter_type = Some(*parameter_type);
             }
             _ => {}
         };
@@ -405,7 +476,7 @@ impl<'a> ProtocolFields for S7commHeader<'a> {
 pub struct Dnp3Fields {
     primary: bool,
     link_function_code: u8,
-    function_code: u8
+    function_code: Option<u8>
 }
 
 impl ProtocolFields for Dnp3Header {
@@ -413,7 +484,7 @@ impl ProtocolFields for Dnp3Header {
         let fields = Dnp3Fields {
             primary: if self.data_link_layer.dl_primary > 0 { true } else { false },
             link_function_code: self.data_link_layer.dl_function,
-            function_code: self.application_layer.function_code
+            function_code: self.application_layer.as_ref().map(|o| o.function_code)
         };
 
         ReportFields::Dnp3(fields)
@@ -424,31 +495,42 @@ impl ProtocolFields for Dnp3Header {
 #[derive(Serialize, Deserialize, Debug, PartialEq, Default)]
 pub struct FinsFields {
     function_code: Option<u16>,
-    code: Option<u16>
+    code: Option<u16>,
+    #[serde(skip_serializing_if = "is_default")]
+    end_code: Option<u16>
 }
 
-impl<'a> ProtocolFields for FinsTcpReqHeader<'a> {
+impl<'a> ProtocolFields for FinsCommand<'a> {
     fn get_fields(&self) -> ReportFields {
         let mut fields = FinsFields::default();
-
-        if let fins_tcp_req::State::Connected { fh: fins_tcp_req::FH { cmd_type, ..} } = &self.state {
-            fields.function_code = Some(cmd_type.cmd_code);
-
-            match cmd_type.order {
-                fins_tcp_req::Order::MemoryAreaRead { memory_area_code, .. } => { fields.code = Some(memory_area_code as u16) }
-                fins_tcp_req::Order::MemoryAreaWrite { memory_area_code, .. } => { fields.code = Some(memory_area_code as u16) }
-                fins_tcp_req::Order::MemoryAreaFill { memory_area_code, .. } => { fields.code = Some(memory_area_code as u16) }
-                // fins_tcp_req::Order::MultipleMemoryAreaRead { rst, .. } => { }
-                // fins_tcp_req::Order::MemoryAreaTransfer { memory_area_code_wc, .. } => { fields.code = Some(memory_area_code) }
-                fins_tcp_req::Order::ParameterAreaRead { parameter_area_code, .. } => { fields.code = Some(parameter_area_code) }
-                fins_tcp_req::Order::ParameterAreaWrite { parameter_area_code, .. } => { fields.code = Some(parameter_area_code) }
-                fins_tcp_req::Order::ParameterAreaClear { parameter_area_code, .. } => { fields.code = Some(parameter_area_code) }
-                fins_tcp_req::Order::Run { mode_code, .. } => { fields.code = Some(mode_code as u16) }
-                fins_tcp_req::Order::MultipleForcedStatusRead { memory_area_code, .. } => { fields.code = Some(memory_area_code as u16) }
-                _ => {}
-            }
+        fields.function_code = Some(self.cmd_type.cmd_code);
+
+        match self.cmd_type.order {
+            fins_command::Order::MemoryAreaRead { memory_area_code, .. } => { fields.code = Some(memory_area_code as u16) }
+            fins_command::Order::MemoryAreaWrite { memory_area_code, .. } => { fields.code = Some(memory_area_code as u16) }
+            fins_command::Order::MemoryAreaFill { memory_area_code, .. } => { fields.code = Some(memory_area_code as u16) }
+            // fins_command::Order::MultipleMemoryAreaRead { rst, .. } => { }
+            // fins_command::Order::MemoryAreaTransfer { memory_area_code_wc, .. } => { fields.code = Some(memory_area_code) }
+            fins_command::Order::ParameterAreaRead { parameter_area_code, .. } => { fields.code = Some(parameter_area_code) }
+            fins_command::Order::ParameterAreaWrite { parameter_area_code, .. } => { fields.code = Some(parameter_area_code) }
+            fins_command::Order::ParameterAreaClear { parameter_area_code, .. } => { fields.code = Some(parameter_area_code) }
+            fins_command::Order::Run { mode_code, .. } => { fields.code = Some(mode_code as u16) }
+            fins_command::Order::MultipleForcedStatusRead { memory_area_code, .. } => { fields.code = Some(memory_area_code as u16) }
+            _ => {}
         }
 
         ReportFields::Fins(fields)
     }
 }
+
+impl<'a> ProtocolFields for FinsResponse<'a> {
+    fn get_fields(&self) -> ReportFields {
+        let fields = FinsFields {
+            function_code: Some(self.cmd_type.cmd_code),
+            code: None,
+            end_code: Some(self.end_code())
+        };
+
+        ReportFields::Fins(fields)
+    }
+}
@@ -2,8 +2,8 @@ use core::slice;
 use crate::common::PacketAdaptFirewall;
 
 use parsing_parser::{
-    ApplicationLayer, L1Packet, LinkLevel, NetLevel, ParseError, QuinPacket, QuinPacketOptions,
-    TransLevel, AppLevel,
+    ApplicationLayer, L1Packet, LinkLevel, NetLevel, ParseError, QuinFlow, QuinPacket,
+    QuinPacketOptions, TransLevel, AppLevel,
 };
 
 /// 初始化数据包解析选项
@@ -48,6 +48,55 @@ pub extern "C" fn parse_packet_rs<'a>(
     packet
 }
 
+/// 初始化连接解析上下文，每条连接使用一个
+#[no_mangle]
+pub extern "C" fn init_flow_rs() -> *mut QuinFlow {
+    let flow_ptr = Box::into_raw(Box::new(QuinFlow::new()));
+
+    tracing::trace!("Flow init Done.");
+
+    flow_ptr
+}
+
+/// 释放连接解析上下文
+#[no_mangle]
+pub extern "C" fn free_flow_rs(flow_ptr: *mut QuinFlow) {
+    if flow_ptr.is_null() {
+        tracing::warn!("Flow free: flow ptr is null!");
+        return;
+    }
+    unsafe { Box::from_raw(flow_ptr) };
+
+    tracing::trace!("Flow free Done.");
+}
+
+/// 在连接上下文中解析数据包
+///
+/// Warning: 解析结果可能引用连接上下文中重组的数据，需在解析该连接的下一个数据包或释放上下文之前释放。
+#[no_mangle]
+pub extern "C" fn parse_packet_in_flow_rs<'a>(
+    input_ptr: *const u8,
+    input_len: u16,
+    option_ptr: *const QuinPacketOptions,
+    flow_ptr: *mut QuinFlow,
+) -> *mut QuinPacket<'a> {
+    if input_ptr.is_null() || option_ptr.is_null() || flow_ptr.is_null() {
+        tracing::warn!("Packet parsing: input, option or flow ptr is null!");
+        return Box::into_raw(Box::new(QuinPacket::L1(L1Packet {
+            error: Some(ParseError::Adaptor),
+            remain: &[],
+        })));
+    }
+
+    let input = unsafe { slice::from_raw_parts(input_ptr, input_len.into()) };
+
+    let option = unsafe { &*option_ptr };
+
+    let flow = unsafe { &mut *flow_ptr };
+
+    Box::into_raw(Box::new(QuinPacket::parse_from_flow(input, option, flow)))
+}
+
 /// 释放数据包解析结果内存
 #[no_mangle]
 pub extern "C" fn free_packet_rs(packet_ptr: *mut QuinPacket) {
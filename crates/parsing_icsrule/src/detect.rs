@@ -1,8 +1,9 @@
-use parsing_parser::{AppLevel, L5Packet, QuinPacket};
+use parsing_parser::{AppLevel, ApplicationNaiveProtocol, L5Packet, QuinPacket};
 use parsing_rule::*;
 use tracing::debug;
 
 use super::icsrule::HmIcsRules;
+use super::icsrule_arg::IcsRuleArg;
 
 pub trait IcsRuleDetector {
     fn detect(&self, l5: &L5Packet) -> bool;
@@ -15,13 +16,26 @@ impl RulesDetectorICS for HmIcsRules {
         // ics规则要求packet为L5，否则返回false
         if let &QuinPacket::L5(l5) = &packet {
             let app_native_type = l5.get_app_naive_type();
+            // 记录经过的 FINS 命令，供响应按 SID 关联请求
+            let fins_transactions = match app_native_type {
+                ApplicationNaiveProtocol::Fins => self.fins_transactions.lock().ok().map(|mut o| {
+                    o.push(l5);
+                    o
+                }),
+                _ => None,
+            };
             if let Some(vec_rid) = self.rules_map.get(&app_native_type) {
                 for rid in vec_rid {
                     if let Some(rule) = self.rules_inner.get(rid) {
                         if rule.basic.active {
                             debug!(target: "ICSRULE(HmIcsRules::detect)", "detecting ICS rule: {:?}", rule);
                             if rule.basic.detect(l5) {
-                                if rule.args.detect(l5) {
+                                let is_detected_args = match &rule.args {
+                                    IcsRuleArg::FINS(fins_arg) => fins_arg
+                                        .detect_with_transactions(l5, fins_transactions.as_deref()),
+                                    args => args.detect(l5),
+                                };
+                                if is_detected_args {
                                     return DetectResultICS::Hit(
                                         rule.basic.rid,
                                         rule.basic.action.clone(),
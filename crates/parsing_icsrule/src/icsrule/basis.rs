@@ -1,7 +1,8 @@
-use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, Ipv4Addr};
 
+use serde::{Deserialize, Serialize};
 use parsing_rule::{RuleAction, Direction};
-use parsing_parser::{L5Packet, NetLevel, TransLevel};
+use parsing_parser::{L5Packet, LinkLevel, MacAddress, NetLevel, TransLevel};
 
 use crate::{detect::IcsRuleDetector, rule_utils::*};
 
@@ -9,7 +10,6 @@ use crate::{detect::IcsRuleDetector, rule_utils::*};
 pub struct IcsRuleBasis {
     pub active: bool,
     pub rid: usize,
-    // mac
     pub action: RuleAction,
     #[serde(rename = "src")]
     pub src_ip: Option<Ipv4AddressVec>,
@@ -21,6 +21,10 @@ pub struct IcsRuleBasis {
     pub dst_ip: Option<Ipv4AddressVec>,
     #[serde(rename = "dport")]
     pub dst_port: Option<NumVec<u16>>,
+    #[serde(rename = "smac", default, skip_serializing_if = "Option::is_none")]
+    pub src_mac: Option<MacAddressVec>,
+    #[serde(rename = "dmac", default, skip_serializing_if = "Option::is_none")]
+    pub dst_mac: Option<MacAddressVec>,
     pub msg: String,
 }
 
@@ -30,21 +34,21 @@ impl IcsRuleDetector for IcsRuleBasis {
             return false;
         }
 
-        let packet_src_ip = match l5.get_src_ip() {
-            Some(o) => o,
-            None => return false
-        };
-        let packet_dst_ip =  match l5.get_dst_ip() {
-            Some(o) => o,
-            None => return false
+        // GOOSE/SV 等二层协议没有 IP 与端口，仅当规则未限定相应字段时继续匹配
+        let (packet_src_ip, packet_dst_ip) = match (l5.get_src_ip(), l5.get_dst_ip()) {
+            (Some(src), Some(dst)) => (src, dst),
+            _ if self.src_ip.is_some() || self.dst_ip.is_some() => return false,
+            _ => (IpAddr::V4(Ipv4Addr::UNSPECIFIED), IpAddr::V4(Ipv4Addr::UNSPECIFIED))
         };
-        let packet_src_port = match l5.get_src_port() {
-            Some(o) => o,
-            None => return false
+        let (packet_src_port, packet_dst_port) = match (l5.get_src_port(), l5.get_dst_port()) {
+            (Some(src), Some(dst)) => (src, dst),
+            _ if self.src_port.is_some() || self.dst_port.is_some() => return false,
+            _ => (0, 0)
         };
-        let packet_dst_port = match l5.get_dst_port() {
-            Some(o) => o,
-            None => return false
+        let (packet_src_mac, packet_dst_mac) = match (l5.get_src_mac(), l5.get_dst_mac()) {
+            (Some(src), Some(dst)) => (src, dst),
+            _ if self.src_mac.is_some() || self.dst_mac.is_some() => return false,
+            _ => (MacAddress::default(), MacAddress::default())
         };
 
         match self.dir {
@@ -62,6 +66,12 @@ impl IcsRuleDetector for IcsRuleBasis {
                 if self.dst_port.is_some() && !self.dst_port.as_ref().unwrap().contain(packet_dst_port) {
                     return false;
                 }
+                if self.src_mac.is_some() && !self.src_mac.as_ref().unwrap().contain(&packet_src_mac) {
+                    return false;
+                }
+                if self.dst_mac.is_some() && !self.dst_mac.as_ref().unwrap().contain(&packet_dst_mac) {
+                    return false;
+                }
             }
             Direction::Bi => {
                 if (self.src_ip.is_some() && !self.src_ip.as_ref().unwrap().contain(&packet_src_ip))
@@ -81,6 +91,15 @@ impl IcsRuleDetector for IcsRuleBasis {
                         return false;
                     }
                 }
+                if (self.src_mac.is_some() && !self.src_mac.as_ref().unwrap().contain(&packet_src_mac))
+                    && (self.dst_mac.is_some() && !self.dst_mac.as_ref().unwrap().contain(&packet_dst_mac))
+                {
+                    if !self.src_mac.as_ref().unwrap().contain(&packet_dst_mac)
+                        && !self.dst_mac.as_ref().unwrap().contain(&packet_src_mac)
+                    {
+                        return false;
+                    }
+                }
             }
         }
         // 所有Some(...)均和packet相应字段匹配 或 rules全为None，返回true
@@ -169,6 +188,8 @@ mod tests {
             dir: Direction::Uni,
             dst_ip: Some(Ipv4AddressVec(vec![Ipv4Address::Addr(Ipv4Addr::from_str("192.168.0.3").unwrap())])),
             dst_port: Some(NumVec(vec![Num::Single(53211u16)])),
+            src_mac: None,
+            dst_mac: None,
             msg: "".to_string(),
         };
 
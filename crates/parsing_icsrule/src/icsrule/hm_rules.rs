@@ -1,6 +1,6 @@
-use parsing_parser::ApplicationNaiveProtocol;
+use parsing_parser::{parsers::FinsTransactions, ApplicationNaiveProtocol};
 
-use std::{collections::HashMap, fs};
+use std::{collections::HashMap, fs, sync::Mutex};
 
 use super::IcsRule;
 
@@ -10,6 +10,8 @@ use super::IcsRule;
 pub struct HmIcsRules {
     pub rules_inner: HashMap<usize, IcsRule>,
     pub rules_map: HashMap<ApplicationNaiveProtocol, Vec<usize>>,
+    // 按 SID 关联 FINS 请求与响应，检测时更新
+    pub(crate) fins_transactions: Mutex<FinsTransactions>,
 }
 
 impl HmIcsRules {
@@ -17,6 +19,7 @@ impl HmIcsRules {
         Self {
             rules_inner: HashMap::new(),
             rules_map: HashMap::new(),
+            fins_transactions: Mutex::new(FinsTransactions::new()),
         }
     }
 
@@ -119,6 +122,8 @@ mod tests {
                         dir: Direction::Uni,
                         dst_ip: None,
                         dst_port: Some(NumVec(vec![Num::Single(502u16)])),
+                        src_mac: None,
+                        dst_mac: None,
                         msg: "Modbus Read Coils(1)".to_string(),
                     },
                     args: IcsRuleArg::Modbus(ModbusArg::ReadCoils {
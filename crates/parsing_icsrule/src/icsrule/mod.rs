@@ -29,6 +29,10 @@ impl IcsRule {
             IcsRuleArg::IEC104(..) => ApplicationNaiveProtocol::Iec104,
             IcsRuleArg::GOOSE(..) => ApplicationNaiveProtocol::Goose,
             IcsRuleArg::SV(..) => ApplicationNaiveProtocol::Sv,
+            IcsRuleArg::ENIP(..) => ApplicationNaiveProtocol::Enip,
+            IcsRuleArg::PROFINET(..) => ApplicationNaiveProtocol::Profinet,
+            IcsRuleArg::MELSEC(..) => ApplicationNaiveProtocol::Melsec,
+            IcsRuleArg::TRISTATION(..) => ApplicationNaiveProtocol::Tristation,
         }
     }
 }
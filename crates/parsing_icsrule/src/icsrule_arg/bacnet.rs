@@ -1,6 +1,7 @@
 use parsing_parser::{parsers::bacnet, ApplicationLayer, L5Packet};
 use serde::{Deserialize, Serialize};
 
+use crate::rule_utils::NumVec;
 use crate::{detect::IcsRuleDetector, detect_option_eq};
 
 #[derive(Serialize, Deserialize, Debug, PartialEq)]
@@ -8,6 +9,77 @@ pub struct BacnetArg {
     #[serde(rename = "type")]
     apdu_type: Option<u8>,
     service_choice: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    object_type: Option<NumVec<u16>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    instance: Option<NumVec<u32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    property_id: Option<NumVec<u32>>,
+}
+
+// 收集报文引用的 (对象标识, 属性标识)，无属性的对象(如 I-Am 设备、文件)属性为空
+fn object_properties(apdu_info: &bacnet::ApduInfo) -> Vec<(bacnet::BacnetObjectIdentifier, Option<u32>)> {
+    use bacnet::{ApduInfo, BacnetObjectIdentifier, ConfirmedServiceRequest, UnconfirmedServiceRequest};
+
+    let mut targets = Vec::new();
+    match apdu_info {
+        ApduInfo::ComfirmedServiceRequest { confirmed_service_request, .. } => match confirmed_service_request {
+            ConfirmedServiceRequest::ReadProperty { property_items } => {
+                let mut object = None;
+                let mut property = None;
+                for item in property_items {
+                    match item.bacnet_object_property_reference_info {
+                        bacnet::BacnetObjectPropertyReferenceInfo::ObjectIdentifier { object_type, instance_number } => {
+                            object = Some(BacnetObjectIdentifier { object_type, instance_number })
+                        }
+                        bacnet::BacnetObjectPropertyReferenceInfo::PropertyIdentifier { property_identifier } => {
+                            property = Some(property_identifier)
+                        }
+                        _ => {}
+                    }
+                }
+                if let Some(object) = object {
+                    targets.push((object, property));
+                }
+            }
+            ConfirmedServiceRequest::WriteProperty { object_identifier, property } => {
+                targets.push((*object_identifier, Some(property.property_identifier)))
+            }
+            ConfirmedServiceRequest::ReadPropertyMultiple { read_access_specs } => {
+                for spec in read_access_specs {
+                    for reference in &spec.property_references {
+                        targets.push((spec.object_identifier, Some(reference.property_identifier)));
+                    }
+                }
+            }
+            ConfirmedServiceRequest::WritePropertyMultiple { write_access_specs } => {
+                for spec in write_access_specs {
+                    for property in &spec.properties {
+                        targets.push((spec.object_identifier, Some(property.property_identifier)));
+                    }
+                }
+            }
+            ConfirmedServiceRequest::AtomicWriteFile { file_identifier, .. } => {
+                targets.push((*file_identifier, None))
+            }
+            _ => {}
+        },
+        ApduInfo::UnconfirmedServiceRequest { unconfirmed_service_request, .. } => {
+            match unconfirmed_service_request {
+                UnconfirmedServiceRequest::IAm { device_identifier, .. } => {
+                    targets.push((*device_identifier, None))
+                }
+                UnconfirmedServiceRequest::IHave { object_identifier, .. }
+                | UnconfirmedServiceRequest::WhoHas {
+                    object: bacnet::WhoHasObject::Identifier(object_identifier),
+                    ..
+                } => targets.push((*object_identifier, None)),
+                _ => {}
+            }
+        }
+        _ => {}
+    }
+    targets
 }
 
 impl IcsRuleDetector for BacnetArg {
@@ -24,6 +96,26 @@ impl IcsRuleDetector for BacnetArg {
                         &bacnet::ApduInfo::ComfirmedServiceRequest { service_choice, .. } => {
                             detect_option_eq!(self.service_choice, service_choice);
                         }
+                        &bacnet::ApduInfo::UnconfirmedServiceRequest { service_choice, .. } => {
+                            detect_option_eq!(self.service_choice, service_choice);
+                        }
+                        &bacnet::ApduInfo::SimpleAckPdu { service_choice, .. } => {
+                            detect_option_eq!(self.service_choice, service_choice);
+                        }
+                    }
+
+                    if self.object_type.is_some() || self.instance.is_some() || self.property_id.is_some() {
+                        // 任一对象同时满足对象类型、实例号与属性标识即匹配
+                        let matched = object_properties(apdu_info).iter().any(|(object, property)| {
+                            self.object_type.as_ref().map_or(true, |o| o.contain(object.object_type))
+                                && self.instance.as_ref().map_or(true, |o| o.contain(object.instance_number))
+                                && self.property_id.as_ref().map_or(true, |o| {
+                                    property.map_or(false, |property| o.contain(property))
+                                })
+                        });
+                        if !matched {
+                            return false;
+                        }
                     }
                 }
                 _ => return false
@@ -59,11 +151,16 @@ mod tests {
                 dir: Direction::Uni,
                 dst_ip: None,
                 dst_port: Some(NumVec(vec![Num::Single(20000u16)])),
+                src_mac: None,
+                dst_mac: None,
                 msg: "BACnet ReadProperty".to_string(),
             },
             args: IcsRuleArg::BACNET(BacnetArg {
                 apdu_type: Some(1),
-                service_choice: Some(2)
+                service_choice: Some(2),
+                object_type: None,
+                instance: None,
+                property_id: None,
             }),
         };
 
@@ -73,6 +170,25 @@ mod tests {
         )
     }
 
+    #[test]
+    fn serialize_bacnet_object_icsrule() {
+        let bacnet_arg = BacnetArg {
+            apdu_type: Some(0),
+            service_choice: Some(15),
+            object_type: Some(NumVec(vec![Num::Single(2u16)])),
+            instance: Some(NumVec(vec![Num::Range(NumRange { start: 1u32, end: 10 })])),
+            property_id: Some(NumVec(vec![Num::Single(85u32)])),
+        };
+
+        assert_eq!(
+            serde_json::from_str::<BacnetArg>(
+                r#"{"type":0,"service_choice":15,"object_type":["2"],"instance":["1:10"],"property_id":["85"]}"#
+            )
+            .unwrap(),
+            bacnet_arg
+        );
+    }
+
     #[test]
     fn deserialize_bacnet_icsrule() {
         tracing_subscriber::fmt()
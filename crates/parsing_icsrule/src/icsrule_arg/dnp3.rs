@@ -1,7 +1,7 @@
 use parsing_parser::{parsers::dnp3, ApplicationLayer, L5Packet};
 use serde::{Deserialize, Serialize};
 
-use crate::{detect::IcsRuleDetector, detect_option_eq};
+use crate::{detect::IcsRuleDetector, detect_option_eq, rule_utils::NumVec};
 
 #[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct Dnp3Arg {
@@ -12,6 +12,71 @@ pub struct Dnp3Arg {
     app_layer: Dnp3AppLayer,
 }
 
+/// 对象匹配参数: objs 为 group << 8 | variation, vsq 为限定词字节,
+/// index 与 control_code 作用于同一对象中的同一个点
+#[derive(Serialize, Deserialize, Debug, PartialEq, Default)]
+pub struct Dnp3ObjectArg {
+    objs: Option<u16>,
+    vsq: Option<u8>,
+    start: Option<u8>,
+    stop: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    index: Option<NumVec<u32>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    control_code: Option<u8>,
+}
+
+impl Dnp3ObjectArg {
+    fn detect(&self, objects: &[dnp3::DataObject]) -> bool {
+        if *self == Dnp3ObjectArg::default() {
+            return true;
+        }
+
+        objects.iter().any(|object| self.detect_object(object))
+    }
+
+    fn detect_object(&self, object: &dnp3::DataObject) -> bool {
+        detect_option_eq!(self.objs, object.obj);
+
+        detect_option_eq!(self.vsq, object.qualifier.to_u8());
+
+        if self.start.is_some() || self.stop.is_some() {
+            if let dnp3::NumOfItem::StartStop { start, stop } = object.num_of_item {
+                detect_option_eq!(self.start.map(u32::from), start);
+                detect_option_eq!(self.stop.map(u32::from), stop);
+            } else {
+                return false;
+            }
+        }
+
+        if self.index.is_none() && self.control_code.is_none() {
+            return true;
+        }
+
+        object.points.iter().any(|point| self.detect_point(point))
+    }
+
+    fn detect_point(&self, point: &dnp3::Dnp3Point) -> bool {
+        if let Some(index) = &self.index {
+            if !index.contain(point.index) {
+                return false;
+            }
+        }
+
+        if let Some(control_code) = self.control_code {
+            if let dnp3::Dnp3PointValue::Crob { control_code: code, .. } = point.value {
+                if code != control_code {
+                    return false;
+                }
+            } else {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq)]
 #[serde(tag = "function_code")]
 pub enum Dnp3AppLayer {
@@ -19,68 +84,248 @@ pub enum Dnp3AppLayer {
     Confirm {},
     #[serde(rename = "1", alias = "0x01")]
     Read {
-        objs: Option<u16>,
-        vsq: Option<u8>,
-        start: Option<u8>,
-        stop: Option<u8>,
+        #[serde(flatten)]
+        objects: Dnp3ObjectArg,
     },
     #[serde(rename = "2", alias = "0x02")]
     Write {
-        objs: Option<u16>,
-        vsq: Option<u8>,
-        start: Option<u8>,
-        stop: Option<u8>,
+        #[serde(flatten)]
+        objects: Dnp3ObjectArg,
     },
     #[serde(rename = "3", alias = "0x03")]
     Select {
-        objs: Option<u16>,
-        vsq: Option<u8>,
-        start: Option<u8>,
-        stop: Option<u8>,
+        #[serde(flatten)]
+        objects: Dnp3ObjectArg,
+    },
+    #[serde(rename = "4", alias = "0x04")]
+    Operate {
+        #[serde(flatten)]
+        objects: Dnp3ObjectArg,
+    },
+    #[serde(rename = "5", alias = "0x05")]
+    DirectOperate {
+        #[serde(flatten)]
+        objects: Dnp3ObjectArg,
+    },
+    #[serde(rename = "6", alias = "0x06")]
+    DirectOperateNoResponse {
+        #[serde(flatten)]
+        objects: Dnp3ObjectArg,
+    },
+    #[serde(rename = "7", alias = "0x07")]
+    ImmediateFreeze {
+        #[serde(flatten)]
+        objects: Dnp3ObjectArg,
+    },
+    #[serde(rename = "8", alias = "0x08")]
+    ImmediateFreezeNoResponse {
+        #[serde(flatten)]
+        objects: Dnp3ObjectArg,
+    },
+    #[serde(rename = "9", alias = "0x09")]
+    FreezeClear {
+        #[serde(flatten)]
+        objects: Dnp3ObjectArg,
+    },
+    #[serde(rename = "10", alias = "0x0a")]
+    FreezeClearNoResponse {
+        #[serde(flatten)]
+        objects: Dnp3ObjectArg,
+    },
+    #[serde(rename = "11", alias = "0x0b")]
+    FreezeAtTime {
+        #[serde(flatten)]
+        objects: Dnp3ObjectArg,
+    },
+    #[serde(rename = "12", alias = "0x0c")]
+    FreezeAtTimeNoResponse {
+        #[serde(flatten)]
+        objects: Dnp3ObjectArg,
     },
-    #[serde(rename = "14", alias = "0x0d")]
+    #[serde(rename = "13", alias = "0x0d")]
     ColdRestart {},
-    #[serde(rename = "15", alias = "0x0e")]
+    #[serde(rename = "14", alias = "0x0e")]
     WarmRestart {},
+    #[serde(rename = "15", alias = "0x0f")]
+    InitializeData {},
+    #[serde(rename = "16", alias = "0x10")]
+    InitializeApplication {
+        #[serde(flatten)]
+        objects: Dnp3ObjectArg,
+    },
+    #[serde(rename = "17", alias = "0x11")]
+    StartApplication {
+        #[serde(flatten)]
+        objects: Dnp3ObjectArg,
+    },
     #[serde(rename = "18", alias = "0x12")]
-    StopApplication {},
+    StopApplication {
+        #[serde(flatten)]
+        objects: Dnp3ObjectArg,
+    },
+    #[serde(rename = "19", alias = "0x13")]
+    SaveConfiguration {},
     #[serde(rename = "20", alias = "0x14")]
     EnableSpontaneousMessage {
-        objs: Option<u16>,
-        vsq: Option<u8>,
-        start: Option<u8>,
-        stop: Option<u8>,
+        #[serde(flatten)]
+        objects: Dnp3ObjectArg,
     },
     #[serde(rename = "21", alias = "0x15")]
     DisableSpontaneousMessage {
-        objs: Option<u16>,
-        vsq: Option<u8>,
-        start: Option<u8>,
-        stop: Option<u8>,
+        #[serde(flatten)]
+        objects: Dnp3ObjectArg,
     },
+    #[serde(rename = "22", alias = "0x16")]
+    AssignClass {
+        #[serde(flatten)]
+        objects: Dnp3ObjectArg,
+    },
+    #[serde(rename = "23", alias = "0x17")]
+    DelayMeasure {},
+    #[serde(rename = "24", alias = "0x18")]
+    RecordCurrentTime {},
     #[serde(rename = "25", alias = "0x19")]
     OpenFile {
-        objs: Option<u16>,
-        vsq: Option<u8>,
-        start: Option<u8>,
-        stop: Option<u8>,
+        #[serde(flatten)]
+        objects: Dnp3ObjectArg,
+    },
+    #[serde(rename = "26", alias = "0x1a")]
+    CloseFile {
+        #[serde(flatten)]
+        objects: Dnp3ObjectArg,
+    },
+    #[serde(rename = "27", alias = "0x1b")]
+    DeleteFile {
+        #[serde(flatten)]
+        objects: Dnp3ObjectArg,
+    },
+    #[serde(rename = "28", alias = "0x1c")]
+    GetFileInfo {
+        #[serde(flatten)]
+        objects: Dnp3ObjectArg,
+    },
+    #[serde(rename = "29", alias = "0x1d")]
+    AuthenticateFile {
+        #[serde(flatten)]
+        objects: Dnp3ObjectArg,
+    },
+    #[serde(rename = "30", alias = "0x1e")]
+    AbortFile {
+        #[serde(flatten)]
+        objects: Dnp3ObjectArg,
+    },
+    #[serde(rename = "31", alias = "0x1f")]
+    ActivateConfig {
+        #[serde(flatten)]
+        objects: Dnp3ObjectArg,
+    },
+    #[serde(rename = "32", alias = "0x20")]
+    AuthenticateRequest {
+        #[serde(flatten)]
+        objects: Dnp3ObjectArg,
+    },
+    #[serde(rename = "33", alias = "0x21")]
+    AuthenticateRequestNoAck {
+        #[serde(flatten)]
+        objects: Dnp3ObjectArg,
     },
     #[serde(rename = "129", alias = "0x81")]
     Response {
-        objs: Option<u16>,
-        vsq: Option<u8>,
-        start: Option<u8>,
-        stop: Option<u8>,
+        #[serde(flatten)]
+        objects: Dnp3ObjectArg,
     },
     #[serde(rename = "130", alias = "0x82")]
     UnsolicitedResponse {
-        objs: Option<u16>,
-        vsq: Option<u8>,
-        start: Option<u8>,
-        stop: Option<u8>,
+        #[serde(flatten)]
+        objects: Dnp3ObjectArg,
+    },
+    #[serde(rename = "131", alias = "0x83")]
+    AuthenticateResponse {
+        #[serde(flatten)]
+        objects: Dnp3ObjectArg,
     },
 }
 
+impl Dnp3AppLayer {
+    fn function_code(&self) -> u8 {
+        match self {
+            Dnp3AppLayer::Confirm {} => 0x00,
+            Dnp3AppLayer::Read { .. } => 0x01,
+            Dnp3AppLayer::Write { .. } => 0x02,
+            Dnp3AppLayer::Select { .. } => 0x03,
+            Dnp3AppLayer::Operate { .. } => 0x04,
+            Dnp3AppLayer::DirectOperate { .. } => 0x05,
+            Dnp3AppLayer::DirectOperateNoResponse { .. } => 0x06,
+            Dnp3AppLayer::ImmediateFreeze { .. } => 0x07,
+            Dnp3AppLayer::ImmediateFreezeNoResponse { .. } => 0x08,
+            Dnp3AppLayer::FreezeClear { .. } => 0x09,
+            Dnp3AppLayer::FreezeClearNoResponse { .. } => 0x0a,
+            Dnp3AppLayer::FreezeAtTime { .. } => 0x0b,
+            Dnp3AppLayer::FreezeAtTimeNoResponse { .. } => 0x0c,
+            Dnp3AppLayer::ColdRestart {} => 0x0d,
+            Dnp3AppLayer::WarmRestart {} => 0x0e,
+            Dnp3AppLayer::InitializeData {} => 0x0f,
+            Dnp3AppLayer::InitializeApplication { .. } => 0x10,
+            Dnp3AppLayer::StartApplication { .. } => 0x11,
+            Dnp3AppLayer::StopApplication { .. } => 0x12,
+            Dnp3AppLayer::SaveConfiguration {} => 0x13,
+            Dnp3AppLayer::EnableSpontaneousMessage { .. } => 0x14,
+            Dnp3AppLayer::DisableSpontaneousMessage { .. } => 0x15,
+            Dnp3AppLayer::AssignClass { .. } => 0x16,
+            Dnp3AppLayer::DelayMeasure {} => 0x17,
+            Dnp3AppLayer::RecordCurrentTime {} => 0x18,
+            Dnp3AppLayer::OpenFile { .. } => 0x19,
+            Dnp3AppLayer::CloseFile { .. } => 0x1a,
+            Dnp3AppLayer::DeleteFile { .. } => 0x1b,
+            Dnp3AppLayer::GetFileInfo { .. } => 0x1c,
+            Dnp3AppLayer::AuthenticateFile { .. } => 0x1d,
+            Dnp3AppLayer::AbortFile { .. } => 0x1e,
+            Dnp3AppLayer::ActivateConfig { .. } => 0x1f,
+            Dnp3AppLayer::AuthenticateRequest { .. } => 0x20,
+            Dnp3AppLayer::AuthenticateRequestNoAck { .. } => 0x21,
+            Dnp3AppLayer::Response { .. } => 0x81,
+            Dnp3AppLayer::UnsolicitedResponse { .. } => 0x82,
+            Dnp3AppLayer::AuthenticateResponse { .. } => 0x83,
+        }
+    }
+
+    fn objects(&self) -> Option<&Dnp3ObjectArg> {
+        match self {
+            Dnp3AppLayer::Read { objects }
+            | Dnp3AppLayer::Write { objects }
+            | Dnp3AppLayer::Select { objects }
+            | Dnp3AppLayer::Operate { objects }
+            | Dnp3AppLayer::DirectOperate { objects }
+            | Dnp3AppLayer::DirectOperateNoResponse { objects }
+            | Dnp3AppLayer::ImmediateFreeze { objects }
+            | Dnp3AppLayer::ImmediateFreezeNoResponse { objects }
+            | Dnp3AppLayer::FreezeClear { objects }
+            | Dnp3AppLayer::FreezeClearNoResponse { objects }
+            | Dnp3AppLayer::FreezeAtTime { objects }
+            | Dnp3AppLayer::FreezeAtTimeNoResponse { objects }
+            | Dnp3AppLayer::InitializeApplication { objects }
+            | Dnp3AppLayer::StartApplication { objects }
+            | Dnp3AppLayer::StopApplication { objects }
+            | Dnp3AppLayer::EnableSpontaneousMessage { objects }
+            | Dnp3AppLayer::DisableSpontaneousMessage { objects }
+            | Dnp3AppLayer::AssignClass { objects }
+            | Dnp3AppLayer::OpenFile { objects }
+            | Dnp3AppLayer::CloseFile { objects }
+            | Dnp3AppLayer::DeleteFile { objects }
+            | Dnp3AppLayer::GetFileInfo { objects }
+            | Dnp3AppLayer::AuthenticateFile { objects }
+            | Dnp3AppLayer::AbortFile { objects }
+            | Dnp3AppLayer::ActivateConfig { objects }
+            | Dnp3AppLayer::AuthenticateRequest { objects }
+            | Dnp3AppLayer::AuthenticateRequestNoAck { objects }
+            | Dnp3AppLayer::Response { objects }
+            | Dnp3AppLayer::UnsolicitedResponse { objects }
+            | Dnp3AppLayer::AuthenticateResponse { objects } => Some(objects),
+            _ => None,
+        }
+    }
+}
+
 impl IcsRuleDetector for Dnp3Arg {
     fn detect(&self, l5: &L5Packet) -> bool {
         if let ApplicationLayer::Dnp3(dnp3) = &l5.application_layer {
@@ -90,99 +335,19 @@ impl IcsRuleDetector for Dnp3Arg {
 
             detect_option_eq!(self.link_function_code, dnp3.data_link_layer.dl_function);
 
-            // TODO: detect objects
-            match self.app_layer {
-                Dnp3AppLayer::Confirm {} => {
-                    if let dnp3::Dnp3ApplicationData::Confirm {} = dnp3.application_layer.app_data {
-                    } else {
-                        return false;
-                    }
-                }
-                Dnp3AppLayer::Read { .. } => {
-                    if let dnp3::Dnp3ApplicationData::Read { .. } = dnp3.application_layer.app_data
-                    {
-                    } else {
-                        return false;
-                    }
-                }
-                Dnp3AppLayer::Write { .. } => {
-                    if let dnp3::Dnp3ApplicationData::Write { .. } = dnp3.application_layer.app_data
-                    {
-                    } else {
-                        return false;
-                    }
-                }
-                Dnp3AppLayer::Select { .. } => {
-                    if let dnp3::Dnp3ApplicationData::Select { .. } =
-                        dnp3.application_layer.app_data
-                    {
-                    } else {
-                        return false;
-                    }
-                }
-                Dnp3AppLayer::ColdRestart {} => {
-                    if let dnp3::Dnp3ApplicationData::ColdRestart {} =
-                        dnp3.application_layer.app_data
-                    {
-                    } else {
-                        return false;
-                    }
-                }
-                Dnp3AppLayer::WarmRestart {} => {
-                    if let dnp3::Dnp3ApplicationData::WarmRestart {} =
-                        dnp3.application_layer.app_data
-                    {
-                    } else {
-                        return false;
-                    }
-                }
-                Dnp3AppLayer::StopApplication {} => {
-                    if let dnp3::Dnp3ApplicationData::StopApplication {} =
-                        dnp3.application_layer.app_data
-                    {
-                    } else {
-                        return false;
-                    }
-                }
-                Dnp3AppLayer::EnableSpontaneousMessage { .. } => {
-                    if let dnp3::Dnp3ApplicationData::EnableSpontaneousMessage { .. } =
-                        dnp3.application_layer.app_data
-                    {
-                    } else {
-                        return false;
-                    }
-                }
-                Dnp3AppLayer::DisableSpontaneousMessage { .. } => {
-                    if let dnp3::Dnp3ApplicationData::DisableSpontaneousMessage { .. } =
-                        dnp3.application_layer.app_data
-                    {
-                    } else {
-                        return false;
-                    }
-                }
-                Dnp3AppLayer::OpenFile { .. } => {
-                    if let dnp3::Dnp3ApplicationData::OpenFile { .. } =
-                        dnp3.application_layer.app_data
-                    {
-                    } else {
-                        return false;
-                    }
-                }
-                Dnp3AppLayer::Response { .. } => {
-                    if let dnp3::Dnp3ApplicationData::Response { .. } =
-                        dnp3.application_layer.app_data
-                    {
-                    } else {
-                        return false;
-                    }
-                }
-                Dnp3AppLayer::UnsolicitedResponse { .. } => {
-                    if let dnp3::Dnp3ApplicationData::UnsolicitedResponse { .. } =
-                        dnp3.application_layer.app_data
-                    {
-                    } else {
-                        return false;
-                    }
+            // 分片未在本数据包内完整时无法匹配应用层
+            let application_layer = match &dnp3.application_layer {
+                Some(o) => o,
+                None => return false,
+            };
+
+            if self.app_layer.function_code() != application_layer.function_code {
+                return false;
+            }
+
+            if let Some(objects) = self.app_layer.objects() {
+                if !objects.detect(application_layer.app_data.objects()) {
+                    return false;
                 }
             }
 
@@ -216,6 +381,8 @@ mod tests {
                 dir: Direction::Uni,
                 dst_ip: None,
                 dst_port: Some(NumVec(vec![Num::Single(20000u16)])),
+                src_mac: None,
+                dst_mac: None,
                 msg: "DNP3 Read".to_string(),
             },
             args: IcsRuleArg::DNP3(Dnp3Arg {
@@ -223,10 +390,14 @@ mod tests {
                 dst: Some(2),
                 link_function_code: Some(1),
                 app_layer: Dnp3AppLayer::Read {
-                    objs: Some(0x1001),
-                    vsq: Some(1),
-                    start: Some(0),
-                    stop: Some(9),
+                    objects: Dnp3ObjectArg {
+                        objs: Some(0x1001),
+                        vsq: Some(1),
+                        start: Some(0),
+                        stop: Some(9),
+                        index: None,
+                        control_code: None,
+                    },
                 },
             }),
         };
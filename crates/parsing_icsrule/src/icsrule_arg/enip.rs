@@ -0,0 +1,154 @@
+use parsing_parser::{parsers::CipMessage, ApplicationLayer, L5Packet};
+use serde::{Deserialize, Serialize};
+
+use crate::detect::IcsRuleDetector;
+use crate::rule_utils::{NumVec, Wildcard};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct EnipArg {
+    // 封装命令，如 0x65 RegisterSession、0x6f SendRRData
+    #[serde(skip_serializing_if = "Option::is_none")]
+    command: Option<NumVec<u16>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    service: Option<NumVec<u8>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    class: Option<NumVec<u32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    instance: Option<NumVec<u32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    attribute: Option<NumVec<u32>>,
+    // Logix 标签名
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tag: Option<Wildcard>,
+    // 指定通用状态时规则只匹配 CIP 响应，响应不含路径
+    #[serde(skip_serializing_if = "Option::is_none")]
+    general_status: Option<NumVec<u8>>,
+}
+
+impl EnipArg {
+    fn has_cip_criteria(&self) -> bool {
+        self.service.is_some()
+            || self.class.is_some()
+            || self.instance.is_some()
+            || self.attribute.is_some()
+            || self.tag.is_some()
+            || self.general_status.is_some()
+    }
+
+    fn detect_message(&self, message: &CipMessage) -> bool {
+        if let Some(service) = &self.service {
+            if !service.contain(message.service()) {
+                return false;
+            }
+        }
+
+        if let Some(general_status) = &self.general_status {
+            return match message.general_status() {
+                Some(status) => general_status.contain(status),
+                None => false,
+            };
+        }
+
+        let path = match message.path() {
+            Some(path) => path,
+            None => return false,
+        };
+        if let Some(class) = &self.class {
+            if !path.class().map_or(false, |c| class.contain(c)) {
+                return false;
+            }
+        }
+        if let Some(instance) = &self.instance {
+            if !path.instance().map_or(false, |i| instance.contain(i)) {
+                return false;
+            }
+        }
+        if let Some(attribute) = &self.attribute {
+            if !path.attribute().map_or(false, |a| attribute.contain(a)) {
+                return false;
+            }
+        }
+        if let Some(tag) = &self.tag {
+            if !path.tag_name().map_or(false, |name| tag.matches(name.as_bytes())) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+impl IcsRuleDetector for EnipArg {
+    fn detect(&self, l5: &L5Packet) -> bool {
+        if let ApplicationLayer::Enip(enip) = &l5.application_layer {
+            if let Some(command) = &self.command {
+                match enip.command() {
+                    Some(c) if command.contain(c) => {}
+                    _ => return false,
+                }
+            }
+
+            if !self.has_cip_criteria() {
+                return true;
+            }
+            // 任一 CIP 消息(包括内嵌消息)满足全部条件即匹配
+            enip.cip_messages()
+                .iter()
+                .any(|message| self.detect_message(message))
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parsing_rule::{RuleAction, Direction};
+
+    use crate::{
+        icsrule_arg::IcsRuleArg,
+        HmIcsRules, IcsRule, IcsRuleBasis, rule_utils::*,
+    };
+
+    use super::*;
+
+    #[test]
+    fn serialize_enip_icsrule() {
+        let enip_rule = IcsRule {
+            basic: IcsRuleBasis {
+                active: true,
+                rid: 1,
+                action: RuleAction::Alert,
+                src_ip: None,
+                src_port: None,
+                dir: Direction::Uni,
+                dst_ip: None,
+                dst_port: Some(NumVec(vec![Num::Single(44818u16)])),
+                src_mac: None,
+                dst_mac: None,
+                msg: "Logix write tag".to_string(),
+            },
+            args: IcsRuleArg::ENIP(EnipArg {
+                command: None,
+                service: Some(NumVec(vec![Num::Single(0x4du8)])),
+                class: None,
+                instance: None,
+                attribute: None,
+                tag: Some(Wildcard("Valve*".to_string())),
+                general_status: None,
+            }),
+        };
+
+        assert_eq!(
+            serde_json::to_string(&enip_rule).unwrap(),
+            r#"{"active":true,"rid":1,"action":"alert","src":null,"sport":null,"dire":"->","dst":null,"dport":[44818],"msg":"Logix write tag","proname":"ENIP","args":{"service":[77],"tag":"Valve*"}}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_enip_icsrule() {
+        let mut enip_rule = HmIcsRules::new();
+
+        let file_str = "./tests/unitest_enip.json";
+        assert!(enip_rule.load_rules(file_str));
+    }
+}
@@ -1,7 +1,10 @@
-use parsing_parser::{parsers::fins_tcp_req, ApplicationLayer, L5Packet};
+use parsing_parser::{
+    parsers::{fins::FinsArea, FinsFrame, FinsTransactions},
+    L5Packet,
+};
 use serde::{Deserialize, Serialize};
 
-use crate::{detect::IcsRuleDetector, detect_address, detect_option_eq};
+use crate::{detect::IcsRuleDetector, detect_address, detect_option_eq, rule_utils::NumVec};
 
 #[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct FinsArg {
@@ -11,6 +14,9 @@ pub struct FinsArg {
     sna: Option<u8>,
     snn: Option<u8>,
     sua: Option<u8>,
+    // 指定结束码时规则只匹配响应
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end_code: Option<NumVec<u16>>,
     #[serde(flatten)]
     command_data: FinsCommandData,
 }
@@ -170,490 +176,154 @@ pub enum FinsCommandData {
     NameRead {},
 }
 
-impl IcsRuleDetector for FinsArg {
-    fn detect(&self, l5: &L5Packet) -> bool {
-        if let ApplicationLayer::FinsTcpReq(fins_req) = &l5.application_layer {
-            if let fins_tcp_req::State::Connected { fh } = &fins_req.state {
-                detect_option_eq!(self.dna, fh.dna);
-                detect_option_eq!(self.dnn, fh.dnn);
-                detect_option_eq!(self.dua, fh.dua);
-                detect_option_eq!(self.sna, fh.sna);
-                detect_option_eq!(self.snn, fh.snn);
-                detect_option_eq!(self.sua, fh.sua);
+impl FinsCommandData {
+    fn function_code(&self) -> u16 {
+        match self {
+            FinsCommandData::MemoryAreaRead { .. } => 0x0101,
+            FinsCommandData::MemoryAreaWrite { .. } => 0x0102,
+            FinsCommandData::MemoryAreaFill { .. } => 0x0103,
+            FinsCommandData::MultipleMemoryAreaRead { .. } => 0x0104,
+            FinsCommandData::MemoryAreaTransfer { .. } => 0x0105,
+            FinsCommandData::ParameterAreaRead { .. } => 0x0201,
+            FinsCommandData::ParameterAreaWrite { .. } => 0x0202,
+            FinsCommandData::ParameterAreaClear { .. } => 0x0203,
+            FinsCommandData::DataLinkTableRead { .. } => 0x0220,
+            FinsCommandData::DataLinkTableWrite { .. } => 0x0221,
+            FinsCommandData::ProgramAreaProtect { .. } => 0x0304,
+            FinsCommandData::ProgramAreaProtectClear { .. } => 0x0305,
+            FinsCommandData::ProgramAreaRead { .. } => 0x0306,
+            FinsCommandData::ProgramAreaWrite { .. } => 0x0307,
+            FinsCommandData::ProgramAreaClear { .. } => 0x0308,
+            FinsCommandData::Run { .. } => 0x0401,
+            FinsCommandData::Stop { .. } => 0x0402,
+            FinsCommandData::Reset { .. } => 0x0403,
+            FinsCommandData::ControllerDataRead { .. } => 0x0501,
+            FinsCommandData::ConnectionDataRead { .. } => 0x0502,
+            FinsCommandData::ControllerStatusRead { .. } => 0x0601,
+            FinsCommandData::NetworkStatusRead { .. } => 0x0602,
+            FinsCommandData::DataLinkStatusRead { .. } => 0x0603,
+            FinsCommandData::CycleTimeRead { .. } => 0x0620,
+            FinsCommandData::ClcokRead { .. } => 0x0701,
+            FinsCommandData::ClcokWrite { .. } => 0x0702,
+            FinsCommandData::LoopBackTest { .. } => 0x0801,
+            FinsCommandData::BroadcastTestResultsRead { .. } => 0x0802,
+            FinsCommandData::BroadcastTestDataSend { .. } => 0x0803,
+            FinsCommandData::MessageReadClearFALSRead { .. } => 0x0920,
+            FinsCommandData::AccessRightAcquire { .. } => 0x0c01,
+            FinsCommandData::AccessRightForcedAcquire { .. } => 0x0c02,
+            FinsCommandData::AccessRightRelease { .. } => 0x0c03,
+            FinsCommandData::ErrorClear { .. } => 0x2101,
+            FinsCommandData::ErrorLogRead { .. } => 0x2102,
+            FinsCommandData::ErrorLogClear { .. } => 0x2103,
+            FinsCommandData::FileNameRead { .. } => 0x2201,
+            FinsCommandData::SingleFileRead { .. } => 0x2202,
+            FinsCommandData::SingleFileWrite { .. } => 0x2203,
+            FinsCommandData::MemoryCardFormat { .. } => 0x2204,
+            FinsCommandData::FileDelete { .. } => 0x2205,
+            FinsCommandData::VolumeLabelCreateOrDelete { .. } => 0x2206,
+            FinsCommandData::FileCopy { .. } => 0x2207,
+            FinsCommandData::FileNameChange { .. } => 0x2208,
+            FinsCommandData::FileDataCheck { .. } => 0x2209,
+            FinsCommandData::MemoryAreaFileTransfer { .. } => 0x220a,
+            FinsCommandData::ParameterAreaFileTransfer { .. } => 0x220b,
+            FinsCommandData::ProgramAreaFileTransfer { .. } => 0x220c,
+            FinsCommandData::FileMemoryIndexRead { .. } => 0x220f,
+            FinsCommandData::FileMemoryRead { .. } => 0x2210,
+            FinsCommandData::FileMemoryWrite { .. } => 0x2211,
+            FinsCommandData::ForcedSetOrReset { .. } => 0x2301,
+            FinsCommandData::ForcedSetOrResetCancel { .. } => 0x2302,
+            FinsCommandData::MultipleForcedStatusRead { .. } => 0x230a,
+            FinsCommandData::NameSet { .. } => 0x2601,
+            FinsCommandData::NameDelete { .. } => 0x2602,
+            FinsCommandData::NameRead { .. } => 0x2603,
+        }
+    }
 
-                match self.command_data {
-                    FinsCommandData::MemoryAreaRead {
-                        code,
-                        start_address,
-                        end_address,
-                    } => {
-                        if let fins_tcp_req::Order::MemoryAreaRead {
-                            memory_area_code,
-                            beginning_address,
-                            ..
-                        } = fh.cmd_type.order
-                        {
-                            detect_option_eq!(code, memory_area_code as u16);
-                            detect_address!(start_address, end_address, beginning_address as u32);
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::MemoryAreaWrite {
-                        code,
-                        start_address,
-                        end_address,
-                    } => {
-                        if let fins_tcp_req::Order::MemoryAreaWrite {
-                            memory_area_code,
-                            beginning_address,
-                            ..
-                        } = fh.cmd_type.order
-                        {
-                            detect_option_eq!(code, memory_area_code as u16);
-                            detect_address!(start_address, end_address, beginning_address as u32);
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::MemoryAreaFill {
-                        code,
-                        start_address,
-                        end_address,
-                    } => {
-                        if let fins_tcp_req::Order::MemoryAreaFill {
-                            memory_area_code,
-                            beginning_address,
-                            ..
-                        } = fh.cmd_type.order
-                        {
-                            detect_option_eq!(code, memory_area_code as u16);
-                            detect_address!(start_address, end_address, beginning_address as u32);
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::MultipleMemoryAreaRead {
-                        code,
-                        start_address,
-                        end_address,
-                    } => {
-                        if let fins_tcp_req::Order::MultipleMemoryAreaRead { result: results } =
-                            &fh.cmd_type.order
-                        {
-                            for result in results {
-                                detect_option_eq!(code, result.memory_area_code as u16);
-                                detect_address!(
-                                    start_address,
-                                    end_address,
-                                    result.beginning_address as u32
-                                );
-                            }
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::MemoryAreaTransfer {
-                        code,
-                        start_address,
-                        end_address,
-                    } => {
-                        if let fins_tcp_req::Order::MemoryAreaTransfer {
-                            memory_area_code_wc,
-                            beginning_address,
-                            ..
-                        } = fh.cmd_type.order
-                        {
-                            detect_option_eq!(code, memory_area_code_wc as u16);
-                            detect_address!(start_address, end_address, beginning_address as u32);
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::ParameterAreaRead {
-                        code,
-                        start_address,
-                        end_address,
-                    } => {
-                        if let fins_tcp_req::Order::ParameterAreaRead {
-                            parameter_area_code,
-                            beginning_word,
-                            ..
-                        } = fh.cmd_type.order
-                        {
-                            detect_option_eq!(code, parameter_area_code);
-                            detect_address!(start_address, end_address, beginning_word as u32);
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::ParameterAreaWrite {
-                        code,
-                        start_address,
-                        end_address,
-                    } => {
-                        if let fins_tcp_req::Order::ParameterAreaWrite {
-                            parameter_area_code,
-                            beginning_word,
-                            ..
-                        } = fh.cmd_type.order
-                        {
-                            detect_option_eq!(code, parameter_area_code);
-                            detect_address!(start_address, end_address, beginning_word as u32);
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::ParameterAreaClear {
-                        code,
-                        start_address,
-                        end_address,
-                    } => {
-                        if let fins_tcp_req::Order::ParameterAreaClear {
-                            parameter_area_code,
-                            beginning_word,
-                            ..
-                        } = fh.cmd_type.order
-                        {
-                            detect_option_eq!(code, parameter_area_code);
-                            detect_address!(start_address, end_address, beginning_word as u32);
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::DataLinkTableRead {} => {
-                        if let fins_tcp_req::Order::DataLinkTableRead { .. } = fh.cmd_type.order {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::DataLinkTableWrite {} => {
-                        if let fins_tcp_req::Order::DataLinkTableWrite { .. } = fh.cmd_type.order {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::ProgramAreaProtect {} => {
-                        if let fins_tcp_req::Order::ProgramAreaProtect { .. } = fh.cmd_type.order {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::ProgramAreaProtectClear {} => {
-                        if let fins_tcp_req::Order::ProgramAreaProtectClear { .. } =
-                            fh.cmd_type.order
-                        {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::ProgramAreaRead {} => {
-                        if let fins_tcp_req::Order::ProgramAreaRead { .. } = fh.cmd_type.order {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::ProgramAreaWrite {} => {
-                        if let fins_tcp_req::Order::ProgramAreaWrite { .. } = fh.cmd_type.order {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::ProgramAreaClear {} => {
-                        if let fins_tcp_req::Order::ProgramAreaClear { .. } = fh.cmd_type.order {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::Run { code } => {
-                        if let fins_tcp_req::Order::Run { mode_code, .. } = fh.cmd_type.order {
-                            detect_option_eq!(code, mode_code as u16);
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::Stop {} => {
-                        if let fins_tcp_req::Order::Stop { .. } = fh.cmd_type.order {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::Reset {} => {
-                        if let fins_tcp_req::Order::Reset { .. } = fh.cmd_type.order {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::ControllerDataRead {} => {
-                        if let fins_tcp_req::Order::ControllerDataRead { .. } = fh.cmd_type.order {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::ConnectionDataRead {} => {
-                        if let fins_tcp_req::Order::ConnectionDataRead { .. } = fh.cmd_type.order {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::ControllerStatusRead {} => {
-                        if let fins_tcp_req::Order::ControllerStatusRead { .. } = fh.cmd_type.order
-                        {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::NetworkStatusRead {} => {
-                        if let fins_tcp_req::Order::NetworkStatusRead { .. } = fh.cmd_type.order {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::DataLinkStatusRead {} => {
-                        if let fins_tcp_req::Order::DataLinkStatusRead { .. } = fh.cmd_type.order {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::CycleTimeRead {} => {
-                        if let fins_tcp_req::Order::CycleTimeRead { .. } = fh.cmd_type.order {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::ClcokRead {} => {
-                        if let fins_tcp_req::Order::ClcokRead { .. } = fh.cmd_type.order {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::ClcokWrite {} => {
-                        if let fins_tcp_req::Order::ClcokWrite { .. } = fh.cmd_type.order {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::LoopBackTest {} => {
-                        if let fins_tcp_req::Order::LoopBackTest { .. } = fh.cmd_type.order {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::BroadcastTestResultsRead {} => {
-                        if let fins_tcp_req::Order::BroadcastTestResultsRead { .. } =
-                            fh.cmd_type.order
-                        {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::BroadcastTestDataSend {} => {
-                        if let fins_tcp_req::Order::BroadcastTestDataSend { .. } = fh.cmd_type.order
-                        {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::MessageReadClearFALSRead {} => {
-                        if let fins_tcp_req::Order::MessageReadClearFALSRead { .. } =
-                            fh.cmd_type.order
-                        {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::AccessRightAcquire {} => {
-                        if let fins_tcp_req::Order::AccessRightAcquire { .. } = fh.cmd_type.order {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::AccessRightForcedAcquire {} => {
-                        if let fins_tcp_req::Order::AccessRightForcedAcquire { .. } =
-                            fh.cmd_type.order
-                        {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::AccessRightRelease {} => {
-                        if let fins_tcp_req::Order::AccessRightRelease { .. } = fh.cmd_type.order {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::ErrorClear {} => {
-                        if let fins_tcp_req::Order::ErrorClear { .. } = fh.cmd_type.order {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::ErrorLogRead {} => {
-                        if let fins_tcp_req::Order::ErrorLogRead { .. } = fh.cmd_type.order {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::ErrorLogClear {} => {
-                        if let fins_tcp_req::Order::ErrorLogClear { .. } = fh.cmd_type.order {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::FileNameRead {} => {
-                        if let fins_tcp_req::Order::FileNameRead { .. } = fh.cmd_type.order {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::SingleFileRead {} => {
-                        if let fins_tcp_req::Order::SingleFileRead { .. } = fh.cmd_type.order {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::SingleFileWrite {} => {
-                        if let fins_tcp_req::Order::SingleFileWrite { .. } = fh.cmd_type.order {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::MemoryCardFormat {} => {
-                        if let fins_tcp_req::Order::MemoryCardFormat { .. } = fh.cmd_type.order {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::FileDelete {} => {
-                        if let fins_tcp_req::Order::FileDelete { .. } = fh.cmd_type.order {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::VolumeLabelCreateOrDelete {} => {
-                        if let fins_tcp_req::Order::VolumeLabelCreateOrDelete { .. } =
-                            fh.cmd_type.order
-                        {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::FileCopy {} => {
-                        if let fins_tcp_req::Order::FileCopy { .. } = fh.cmd_type.order {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::FileNameChange {} => {
-                        if let fins_tcp_req::Order::FileNameChange { .. } = fh.cmd_type.order {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::FileDataCheck {} => {
-                        if let fins_tcp_req::Order::FileDataCheck { .. } = fh.cmd_type.order {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::MemoryAreaFileTransfer {} => {
-                        if let fins_tcp_req::Order::MemoryAreaFileTransfer { .. } =
-                            fh.cmd_type.order
-                        {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::ParameterAreaFileTransfer {} => {
-                        if let fins_tcp_req::Order::ParameterAreaFileTransfer { .. } =
-                            fh.cmd_type.order
-                        {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::ProgramAreaFileTransfer {} => {
-                        if let fins_tcp_req::Order::ProgramAreaFileTransfer { .. } =
-                            fh.cmd_type.order
-                        {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::FileMemoryIndexRead {} => {
-                        if let fins_tcp_req::Order::FileMemoryIndexRead { .. } = fh.cmd_type.order {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::FileMemoryRead {} => {
-                        if let fins_tcp_req::Order::FileMemoryRead { .. } = fh.cmd_type.order {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::FileMemoryWrite {} => {
-                        if let fins_tcp_req::Order::FileMemoryWrite { .. } = fh.cmd_type.order {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::ForcedSetOrReset {} => {
-                        if let fins_tcp_req::Order::ForcedSetOrReset { .. } = fh.cmd_type.order {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::ForcedSetOrResetCancel {} => {
-                        if let fins_tcp_req::Order::ForcedSetOrResetCancel { .. } =
-                            fh.cmd_type.order
-                        {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::MultipleForcedStatusRead {
-                        code,
-                        start_address,
-                        end_address,
-                    } => {
-                        if let fins_tcp_req::Order::MultipleForcedStatusRead {
-                            memory_area_code,
-                            beginning_address,
-                            ..
-                        } = fh.cmd_type.order
-                        {
-                            detect_option_eq!(code, memory_area_code as u16);
-                            detect_address!(start_address, end_address, beginning_address);
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::NameSet {} => {
-                        if let fins_tcp_req::Order::NameSet { .. } = fh.cmd_type.order {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::NameDelete {} => {
-                        if let fins_tcp_req::Order::NameDelete { .. } = fh.cmd_type.order {
-                        } else {
-                            return false;
-                        }
-                    }
-                    FinsCommandData::NameRead {} => {
-                        if let fins_tcp_req::Order::NameRead { .. } = fh.cmd_type.order {
-                        } else {
-                            return false;
-                        }
-                    }
-                }
+    // 区域代码(Run 为运行模式)与地址范围
+    fn area(&self) -> (Option<u16>, Option<u32>, Option<u32>) {
+        match *self {
+            FinsCommandData::MemoryAreaRead { code, start_address, end_address }
+            | FinsCommandData::MemoryAreaWrite { code, start_address, end_address }
+            | FinsCommandData::MemoryAreaFill { code, start_address, end_address }
+            | FinsCommandData::MultipleMemoryAreaRead { code, start_address, end_address }
+            | FinsCommandData::MemoryAreaTransfer { code, start_address, end_address }
+            | FinsCommandData::ParameterAreaRead { code, start_address, end_address }
+            | FinsCommandData::ParameterAreaWrite { code, start_address, end_address }
+            | FinsCommandData::ParameterAreaClear { code, start_address, end_address }
+            | FinsCommandData::MultipleForcedStatusRead { code, start_address, end_address } => {
+                (code, start_address, end_address)
             }
+            FinsCommandData::Run { code } => (code, None, None),
+            _ => (None, None, None),
+        }
+    }
 
-            true
-        } else {
-            false
+    /// 命令访问的每个区域都需满足规则限定的区域代码与地址范围
+    fn detect_areas(&self, areas: &[FinsArea]) -> bool {
+        let (code, start_address, end_address) = self.area();
+        if code.is_none() && start_address.is_none() && end_address.is_none() {
+            return true;
         }
+        if areas.is_empty() {
+            return false;
+        }
+        for area in areas {
+            detect_option_eq!(code, area.code);
+            detect_address!(start_address, end_address, area.address);
+        }
+        true
+    }
+}
+
+impl FinsArg {
+    /// 响应的区域条件按`transactions`中 SID 关联到的请求匹配，未提供时不匹配
+    pub(crate) fn detect_with_transactions(
+        &self,
+        l5: &L5Packet,
+        transactions: Option<&FinsTransactions>,
+    ) -> bool {
+        let frame = match FinsFrame::from_layer(&l5.application_layer) {
+            Some(o) => o,
+            None => return false,
+        };
+        let header = frame.header();
+        detect_option_eq!(self.dna, header.dna);
+        detect_option_eq!(self.dnn, header.dnn);
+        detect_option_eq!(self.dua, header.dua);
+        detect_option_eq!(self.sna, header.sna);
+        detect_option_eq!(self.snn, header.snn);
+        detect_option_eq!(self.sua, header.sua);
+
+        if frame.cmd_code() != self.command_data.function_code() {
+            return false;
+        }
+
+        match frame {
+            // 未指定结束码的规则只匹配命令
+            FinsFrame::Command(command) => {
+                self.end_code.is_none() && self.command_data.detect_areas(&command.cmd_type.order.areas())
+            }
+            // 响应不含访问区域，区域条件按 SID 关联到的请求匹配
+            FinsFrame::Response(response) => {
+                match &self.end_code {
+                    Some(end_code) if end_code.contain(response.end_code()) => {}
+                    _ => return false,
+                }
+                if self.command_data.area() == (None, None, None) {
+                    return true;
+                }
+                match transactions.and_then(|o| o.request(l5)) {
+                    Some(request) => self.command_data.detect_areas(&request.areas),
+                    None => false,
+                }
+            }
+        }
+    }
+}
+
+impl IcsRuleDetector for FinsArg {
+    fn detect(&self, l5: &L5Packet) -> bool {
+        self.detect_with_transactions(l5, None)
     }
 }
 
@@ -680,6 +350,8 @@ mod tests {
                 dir: Direction::Uni,
                 dst_ip: None,
                 dst_port: Some(NumVec(vec![Num::Single(9600u16)])),
+                src_mac: None,
+                dst_mac: None,
                 msg: "Fins Memory Area Read".to_string(),
             },
             args: IcsRuleArg::FINS(FinsArg {
@@ -689,6 +361,7 @@ mod tests {
                 sna: None,
                 snn: None,
                 sua: None,
+                end_code: None,
                 command_data: FinsCommandData::MemoryAreaRead {
                     code: Some(1),
                     start_address: Some(1),
@@ -43,6 +43,8 @@ mod tests {
                 dir: Direction::Uni,
                 dst_ip: None,
                 dst_port: Some(NumVec(vec![Num::Single(20000u16)])),
+                src_mac: None,
+                dst_mac: None,
                 msg: "GOOSE".to_string(),
             },
             args: IcsRuleArg::GOOSE(GooseArg {
@@ -1,7 +1,7 @@
 use parsing_parser::{parsers::iec104, ApplicationLayer, L5Packet};
 use serde::{Deserialize, Serialize};
 
-use crate::{detect::IcsRuleDetector, detect_option_eq};
+use crate::{detect::IcsRuleDetector, detect_address, detect_option_eq, rule_utils::NumVec};
 
 #[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct IEC104Arg {
@@ -15,17 +15,110 @@ pub enum IEC104TypeEnum {
     U {
         utype: Option<u8>
     },
-    I,
+    I {
+        #[serde(flatten)]
+        asdu: IEC104AsduArg
+    },
     S
 }
 
+/// I格式ASDU匹配条件，`ioa`、`state`、`min_value`、`max_value`需由同一信息对象满足。
+/// `state`匹配单点/双点/步调节命令状态，`min_value`、`max_value`匹配设点命令设定值。
+#[derive(Serialize, Deserialize, Debug, PartialEq, Default)]
+pub struct IEC104AsduArg {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    type_id: Option<NumVec<u8>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cot: Option<NumVec<u8>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    negative: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    test: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    addr: Option<NumVec<u16>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ioa: Option<NumVec<u32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    state: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min_value: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_value: Option<f64>,
+}
+
+impl IEC104AsduArg {
+    fn detect(&self, asdu: &iec104::IecAsdu) -> bool {
+        if let Some(type_id) = &self.type_id {
+            if !type_id.contain(asdu.type_id) {
+                return false;
+            }
+        }
+
+        if let Some(cot) = &self.cot {
+            if !cot.contain(asdu.cause_tx) {
+                return false;
+            }
+        }
+
+        detect_option_eq!(self.negative, asdu.negative == 1);
+
+        detect_option_eq!(self.test, asdu.test == 1);
+
+        if let Some(addr) = &self.addr {
+            if !addr.contain(asdu.addr) {
+                return false;
+            }
+        }
+
+        if self.ioa.is_none()
+            && self.state.is_none()
+            && self.min_value.is_none()
+            && self.max_value.is_none()
+        {
+            return true;
+        }
+
+        asdu.ioa_array.iter().any(|ioa| self.detect_ioa(ioa))
+    }
+
+    fn detect_ioa(&self, ioa: &iec104::Ioa) -> bool {
+        if let Some(address) = &self.ioa {
+            if !address.contain(ioa.ioa) {
+                return false;
+            }
+        }
+
+        if self.state.is_some() {
+            match ioa.ioa_type_enum.command_state() {
+                Some(state) => detect_option_eq!(self.state, state),
+                None => return false,
+            }
+        }
+
+        if self.min_value.is_some() || self.max_value.is_some() {
+            match ioa.ioa_type_enum.setpoint_value() {
+                Some(value) => {
+                    detect_address!(self.min_value, self.max_value, value);
+                }
+                None => return false,
+            }
+        }
+
+        true
+    }
+}
+
 impl IcsRuleDetector for IEC104Arg {
     fn detect(&self, l5: &L5Packet) -> bool {
         if let ApplicationLayer::Iec104(iec104) = &l5.application_layer {
             if iec104.iec104_blocks.len() > 0 {
-                match iec104.iec104_blocks[0].type_block {
-                    iec104::TypeBlock::TypeI { .. } => {
-                        if self.apdu_type != IEC104TypeEnum::I { 
+                match &iec104.iec104_blocks[0].type_block {
+                    iec104::TypeBlock::TypeI { iec_asdu, .. } => {
+                        if let IEC104TypeEnum::I { asdu } = &self.apdu_type {
+                            if !asdu.detect(iec_asdu) {
+                                return false
+                            }
+                        } else {
                             return false
                         }
                     }
@@ -36,7 +129,7 @@ impl IcsRuleDetector for IEC104Arg {
                     }
                     iec104::TypeBlock::TypeU { apci_utype, .. } => {
                         if let IEC104TypeEnum::U { utype } = self.apdu_type {
-                            detect_option_eq!(utype, apci_utype);
+                            detect_option_eq!(utype, *apci_utype);
                         } else {
                             return false
                         }
@@ -76,6 +169,8 @@ mod tests {
                 dir: Direction::Uni,
                 dst_ip: None,
                 dst_port: Some(NumVec(vec![Num::Single(20000u16)])),
+                src_mac: None,
+                dst_mac: None,
                 msg: "MMS ConfirmedRequestPDU".to_string(),
             },
             args: IcsRuleArg::IEC104(IEC104Arg {
@@ -89,6 +184,40 @@ mod tests {
         )
     }
 
+    #[test]
+    fn serialize_iec104_asdu_icsrule() {
+        let iec104_rule = IcsRule {
+            basic: IcsRuleBasis {
+                active: true,
+                rid: 2,
+                action: RuleAction::Pass,
+                src_ip: None,
+                src_port: None,
+                dir: Direction::Uni,
+                dst_ip: None,
+                dst_port: Some(NumVec(vec![Num::Single(2404u16)])),
+                src_mac: None,
+                dst_mac: None,
+                msg: "IEC104 setpoint".to_string(),
+            },
+            args: IcsRuleArg::IEC104(IEC104Arg {
+                apdu_type: IEC104TypeEnum::I {
+                    asdu: IEC104AsduArg {
+                        type_id: Some(NumVec(vec![Num::Single(50u8)])),
+                        ioa: Some(NumVec(vec![Num::Range(NumRange { start: 4096u32, end: 4200u32 })])),
+                        max_value: Some(50.0),
+                        ..Default::default()
+                    }
+                }
+            }),
+        };
+
+        assert_eq!(
+            serde_json::to_string(&iec104_rule).unwrap(),
+            r#"{"active":true,"rid":2,"action":"pass","src":null,"sport":null,"dire":"->","dst":null,"dport":[2404],"msg":"IEC104 setpoint","proname":"IEC104","args":{"type":"I","type_id":[50],"ioa":[{"start":4096,"end":4200}],"max_value":50.0}}"#
+        )
+    }
+
     #[test]
     fn deserialize_iec104_icsrule() {
         let mut iec104_rule = HmIcsRules::new();
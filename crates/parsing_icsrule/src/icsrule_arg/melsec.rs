@@ -0,0 +1,145 @@
+use parsing_parser::{parsers::{MelsecPdu, MelsecRequest}, ApplicationLayer, L5Packet};
+use serde::{Deserialize, Serialize};
+
+use crate::{detect::IcsRuleDetector, detect_address, detect_option_eq, rule_utils::NumVec};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct MelsecArg {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    network: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pc: Option<u8>,
+    // 如 1025 批量读、5121 批量写、4098 远程 STOP
+    #[serde(skip_serializing_if = "Option::is_none")]
+    command: Option<NumVec<u16>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    subcommand: Option<NumVec<u16>>,
+    // 软元件代码，如 168 (D)、144 (M)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    device_code: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start_address: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end_address: Option<u32>,
+    // 指定结束码时规则只匹配响应，响应不含命令与软元件
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end_code: Option<NumVec<u16>>,
+}
+
+impl MelsecArg {
+    fn has_request_criteria(&self) -> bool {
+        self.command.is_some()
+            || self.subcommand.is_some()
+            || self.device_code.is_some()
+            || self.start_address.is_some()
+            || self.end_address.is_some()
+    }
+
+    /// 请求访问的每个软元件的首末地址都需在规则限定的范围内
+    fn detect_devices(&self, request: &MelsecRequest) -> bool {
+        if self.device_code.is_none() && self.start_address.is_none() && self.end_address.is_none() {
+            return true;
+        }
+        let devices = request.devices();
+        if devices.is_empty() {
+            return false;
+        }
+        for (device, points) in devices {
+            detect_option_eq!(self.device_code, device.code);
+            detect_address!(self.start_address, self.end_address, device.number);
+            let last = device.number.saturating_add((points as u32).saturating_sub(1));
+            detect_address!(self.start_address, self.end_address, last);
+        }
+        true
+    }
+
+    fn detect_request(&self, request: &MelsecRequest) -> bool {
+        if self.end_code.is_some() {
+            return false;
+        }
+        if let Some(command) = &self.command {
+            if !command.contain(request.command) {
+                return false;
+            }
+        }
+        if let Some(subcommand) = &self.subcommand {
+            if !subcommand.contain(request.subcommand) {
+                return false;
+            }
+        }
+        self.detect_devices(request)
+    }
+}
+
+impl IcsRuleDetector for MelsecArg {
+    fn detect(&self, l5: &L5Packet) -> bool {
+        let melsec = match &l5.application_layer {
+            ApplicationLayer::Melsec(melsec) => melsec,
+            _ => return false,
+        };
+        detect_option_eq!(self.network, melsec.network);
+        detect_option_eq!(self.pc, melsec.pc);
+
+        match &melsec.pdu {
+            MelsecPdu::Request(request) => self.detect_request(request),
+            MelsecPdu::Response { end_code, .. } => match &self.end_code {
+                Some(rule_end_code) => !self.has_request_criteria() && rule_end_code.contain(*end_code),
+                None => false,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parsing_rule::{RuleAction, Direction};
+
+    use crate::{
+        icsrule_arg::IcsRuleArg,
+        HmIcsRules, IcsRule, IcsRuleBasis, rule_utils::*,
+    };
+
+    use super::*;
+
+    #[test]
+    fn serialize_melsec_icsrule() {
+        let melsec_rule = IcsRule {
+            basic: IcsRuleBasis {
+                active: true,
+                rid: 1,
+                action: RuleAction::Alert,
+                src_ip: None,
+                src_port: None,
+                dir: Direction::Uni,
+                dst_ip: None,
+                dst_port: Some(NumVec(vec![Num::Single(5007u16)])),
+                src_mac: None,
+                dst_mac: None,
+                msg: "MC batch write D0-D999".to_string(),
+            },
+            args: IcsRuleArg::MELSEC(MelsecArg {
+                network: None,
+                pc: Some(0xff),
+                command: Some(NumVec(vec![Num::Single(0x1401u16)])),
+                subcommand: None,
+                device_code: Some(0xa8),
+                start_address: Some(0),
+                end_address: Some(999),
+                end_code: None,
+            }),
+        };
+
+        assert_eq!(
+            serde_json::to_string(&melsec_rule).unwrap(),
+            r#"{"active":true,"rid":1,"action":"alert","src":null,"sport":null,"dire":"->","dst":null,"dport":[5007],"msg":"MC batch write D0-D999","proname":"MELSEC","args":{"pc":255,"command":[5121],"device_code":168,"start_address":0,"end_address":999}}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_melsec_icsrule() {
+        let mut melsec_rule = HmIcsRules::new();
+
+        let file_str = "./tests/unitest_melsec.json";
+        assert!(melsec_rule.load_rules(file_str));
+    }
+}
@@ -1,11 +1,25 @@
-use parsing_parser::{parsers::mms::MmsPduEnum, ApplicationLayer, L5Packet};
+use parsing_parser::{
+    parsers::mms::{
+        ConfirmedServiceRequestEnum, ConfirmedServiceResponse, ConfirmedServiceResponseEnum,
+        MmsPduEnum, ObjectName, ObjectScope, ReadRequestEnum, UnConfirmedEnum,
+        VariableAccessSpecificationEnum, VariableSpecification,
+    },
+    ApplicationLayer, L5Packet,
+};
 use serde::{Deserialize, Serialize};
 
 use crate::detect::IcsRuleDetector;
+use crate::rule_utils::Wildcard;
 
 #[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct MmsArg {
-    tag: MmsTag
+    tag: MmsTag,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    service: Option<MmsService>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    domain: Option<Wildcard>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    item: Option<Wildcard>,
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq)]
@@ -24,39 +38,196 @@ pub enum MmsTag {
     ConcludeRequest
 }
 
+// ConfirmedService 编号
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy)]
+pub enum MmsService {
+    #[serde(rename = "0x01", alias = "1")]
+    GetNameList,
+    #[serde(rename = "0x02", alias = "2")]
+    Identify,
+    #[serde(rename = "0x04", alias = "4")]
+    Read,
+    #[serde(rename = "0x05", alias = "5")]
+    Write,
+    #[serde(rename = "0x0c", alias = "12")]
+    GetNamedVariableListAttributes,
+    #[serde(rename = "0x2e", alias = "46")]
+    ObtainFile,
+    #[serde(rename = "0x48", alias = "72")]
+    FileOpen,
+    #[serde(rename = "0x49", alias = "73")]
+    FileRead,
+    #[serde(rename = "0x4a", alias = "74")]
+    FileClose,
+    #[serde(rename = "0x4b", alias = "75")]
+    FileRename,
+    #[serde(rename = "0x4c", alias = "76")]
+    FileDelete,
+    #[serde(rename = "0x4d", alias = "77")]
+    FileDirectory,
+}
+
+impl From<&ConfirmedServiceRequestEnum<'_>> for MmsService {
+    fn from(service: &ConfirmedServiceRequestEnum) -> Self {
+        match service {
+            ConfirmedServiceRequestEnum::GetNameListRequest { .. } => MmsService::GetNameList,
+            ConfirmedServiceRequestEnum::IdentifyRequest { .. } => MmsService::Identify,
+            ConfirmedServiceRequestEnum::ReadRequest { .. } => MmsService::Read,
+            ConfirmedServiceRequestEnum::WriteRequest { .. } => MmsService::Write,
+            ConfirmedServiceRequestEnum::GetNamedVariableListAttributesRequest { .. } => {
+                MmsService::GetNamedVariableListAttributes
+            }
+            ConfirmedServiceRequestEnum::ObtainFileRequest { .. } => MmsService::ObtainFile,
+            ConfirmedServiceRequestEnum::FileOpenRequest { .. } => MmsService::FileOpen,
+            ConfirmedServiceRequestEnum::FileReadRequest { .. } => MmsService::FileRead,
+            ConfirmedServiceRequestEnum::FileCloseRequest { .. } => MmsService::FileClose,
+            ConfirmedServiceRequestEnum::FileRenameRequest { .. } => MmsService::FileRename,
+            ConfirmedServiceRequestEnum::FileDeleteRequest { .. } => MmsService::FileDelete,
+            ConfirmedServiceRequestEnum::FileDirectoryRequest { .. } => MmsService::FileDirectory,
+        }
+    }
+}
+
+impl From<&ConfirmedServiceResponseEnum<'_>> for MmsService {
+    fn from(service: &ConfirmedServiceResponseEnum) -> Self {
+        match service {
+            ConfirmedServiceResponseEnum::GetNameListResponse { .. } => MmsService::GetNameList,
+            ConfirmedServiceResponseEnum::IdentifyResponse { .. } => MmsService::Identify,
+            ConfirmedServiceResponseEnum::ReadResponse { .. } => MmsService::Read,
+            ConfirmedServiceResponseEnum::WriteResponse { .. } => MmsService::Write,
+            ConfirmedServiceResponseEnum::GetNamedVariableListAttributesResponse { .. } => {
+                MmsService::GetNamedVariableListAttributes
+            }
+            ConfirmedServiceResponseEnum::ObtainFileResponse { .. } => MmsService::ObtainFile,
+            ConfirmedServiceResponseEnum::FileOpenResponse { .. } => MmsService::FileOpen,
+            ConfirmedServiceResponseEnum::FileReadResponse { .. } => MmsService::FileRead,
+            ConfirmedServiceResponseEnum::FileCloseResponse { .. } => MmsService::FileClose,
+            ConfirmedServiceResponseEnum::FileRenameResponse { .. } => MmsService::FileRename,
+            ConfirmedServiceResponseEnum::FileDeleteResponse { .. } => MmsService::FileDelete,
+            ConfirmedServiceResponseEnum::FileDirectoryResponse { .. } => MmsService::FileDirectory,
+        }
+    }
+}
+
+// 收集报文中引用的对象名 (domain ID, item ID)，VMD/AA 范围的名称 domain ID 为空
+fn object_name_ids<'a>(object_name: &ObjectName<'a>) -> (&'a [u8], &'a [u8]) {
+    match *object_name {
+        ObjectName::ObjectNameVmd { object_name_vmd } => (&[], object_name_vmd),
+        ObjectName::ObjectNameDomain { object_name_domain_id, object_name_item_id } => {
+            (object_name_domain_id, object_name_item_id)
+        }
+        ObjectName::ObjectNameAaSpecific { object_name_aa_specific } => {
+            (&[], object_name_aa_specific)
+        }
+    }
+}
+
+fn variable_access_ids<'a>(
+    spec: &VariableAccessSpecificationEnum<'a>,
+    ids: &mut Vec<(&'a [u8], &'a [u8])>,
+) {
+    match spec {
+        VariableAccessSpecificationEnum::ListOfVariable { res } => {
+            for lovs in &res.lovs {
+                if let VariableSpecification::Name { object_name } = &lovs.variable_specification {
+                    ids.push(object_name_ids(object_name));
+                }
+            }
+        }
+        VariableAccessSpecificationEnum::VaribaleListName { object_name } => {
+            ids.push(object_name_ids(object_name));
+        }
+    }
+}
+
+fn object_ids<'a>(mms_pdu_enum: &MmsPduEnum<'a>) -> Vec<(&'a [u8], &'a [u8])> {
+    let mut ids = Vec::new();
+    match mms_pdu_enum {
+        MmsPduEnum::ConfirmedRequestPDU { service, .. } => match service {
+            ConfirmedServiceRequestEnum::ReadRequest { read_request_enum } => match read_request_enum {
+                ReadRequestEnum::Default { variable_access_specification_enum }
+                | ReadRequestEnum::Otherwise { variable_access_specification_enum, .. } => {
+                    variable_access_ids(variable_access_specification_enum, &mut ids)
+                }
+            },
+            ConfirmedServiceRequestEnum::WriteRequest { variable_access_specification_enum, .. } => {
+                variable_access_ids(variable_access_specification_enum, &mut ids)
+            }
+            ConfirmedServiceRequestEnum::GetNamedVariableListAttributesRequest { object_name } => {
+                ids.push(object_name_ids(object_name))
+            }
+            ConfirmedServiceRequestEnum::GetNameListRequest {
+                object_scope: ObjectScope::ObjectScopeDomain { object_scope_domain_id, .. },
+                ..
+            } => ids.push((object_scope_domain_id, &[])),
+            _ => {}
+        },
+        MmsPduEnum::UnConfirmedPDU {
+            service: UnConfirmedEnum::InformationReport { variable_access_specification_enum, .. },
+        } => variable_access_ids(variable_access_specification_enum, &mut ids),
+        _ => {}
+    }
+    ids
+}
+
 impl IcsRuleDetector for MmsArg {
     fn detect(&self, l5: &L5Packet) -> bool {
         if let ApplicationLayer::Mms(mms) = &l5.application_layer {
-            match mms.mms_pdu.mms_pdu_enum {
+            let mms_pdu_enum = &mms.mms_pdu.mms_pdu_enum;
+            let service = match mms_pdu_enum {
                 MmsPduEnum::ConcludeRequest { } => {
                     if self.tag != MmsTag::ConcludeRequest {
                         return false;    
                     }
+                    None
                 }
-                MmsPduEnum::ConfirmedRequestPDU { .. } => {
+                MmsPduEnum::ConfirmedRequestPDU { service, .. } => {
                     if self.tag != MmsTag::ConfirmedRequestPDU {
                         return false;    
                     }
+                    Some(MmsService::from(service))
                 }
-                MmsPduEnum::ConfirmedResponsePDU { .. } => {
+                MmsPduEnum::ConfirmedResponsePDU { service, .. } => {
                     if self.tag != MmsTag::ConfirmedResponsePDU {
                         return false;    
                     }
+                    match service {
+                        ConfirmedServiceResponse::WithData { service } => Some(MmsService::from(service)),
+                        ConfirmedServiceResponse::None {} => None,
+                    }
                 }
                 MmsPduEnum::InitiateRequestPDU { .. } => {
                     if self.tag != MmsTag::InitiateRequestPDU {
                         return false;    
                     }
+                    None
                 }
                 MmsPduEnum::InitiateResponsePDU { .. } => {
                     if self.tag != MmsTag::InitiateResponsePDU {
                         return false;    
                     }
+                    None
                 }
                 MmsPduEnum::UnConfirmedPDU { .. } => {
                     if self.tag != MmsTag::UnConfirmedPDU {
                         return false;    
                     }
+                    None
+                }
+            };
+
+            if self.service.is_some() && self.service != service {
+                return false;
+            }
+
+            if self.domain.is_some() || self.item.is_some() {
+                // 任一对象名同时满足 domain 与 item 即匹配
+                let matched = object_ids(mms_pdu_enum).iter().any(|(domain, item)| {
+                    self.domain.as_ref().map_or(true, |d| d.matches(domain))
+                        && self.item.as_ref().map_or(true, |i| i.matches(item))
+                });
+                if !matched {
+                    return false;
                 }
             }
             
@@ -90,10 +261,15 @@ mod tests {
                 dir: Direction::Uni,
                 dst_ip: None,
                 dst_port: Some(NumVec(vec![Num::Single(20000u16)])),
+                src_mac: None,
+                dst_mac: None,
                 msg: "MMS ConfirmedRequestPDU".to_string(),
             },
             args: IcsRuleArg::MMS(MmsArg {
-                tag: MmsTag::ConfirmedRequestPDU
+                tag: MmsTag::ConfirmedRequestPDU,
+                service: None,
+                domain: None,
+                item: None,
             }),
         };
 
@@ -103,6 +279,47 @@ mod tests {
         )
     }
 
+    #[test]
+    fn serialize_mms_service_icsrule() {
+        let mms_rule = IcsRule {
+            basic: IcsRuleBasis {
+                active: true,
+                rid: 2,
+                action: RuleAction::Alert,
+                src_ip: None,
+                src_port: None,
+                dir: Direction::Uni,
+                dst_ip: None,
+                dst_port: Some(NumVec(vec![Num::Single(102u16)])),
+                src_mac: None,
+                dst_mac: None,
+                msg: "MMS control operate".to_string(),
+            },
+            args: IcsRuleArg::MMS(MmsArg {
+                tag: MmsTag::ConfirmedRequestPDU,
+                service: Some(MmsService::Write),
+                domain: None,
+                item: Some(Wildcard("*$CO$*$Oper".to_string())),
+            }),
+        };
+
+        assert_eq!(
+            serde_json::to_string(&mms_rule).unwrap(),
+            r#"{"active":true,"rid":2,"action":"alert","src":null,"sport":null,"dire":"->","dst":null,"dport":[102],"msg":"MMS control operate","proname":"MMS","args":{"tag":"0x00","service":"0x05","item":"*$CO$*$Oper"}}"#
+        )
+    }
+
+    #[test]
+    fn wildcard_item_match() {
+        let item = Wildcard("*$CO$*$Oper".to_string());
+        assert!(item.matches(b"CSWI1$CO$Pos$Oper"));
+        assert!(item.matches(b"$CO$$Oper"));
+        assert!(!item.matches(b"CSWI1$CO$Pos$SBOw"));
+        assert!(!item.matches(b"CSWI1$ST$Pos$Oper"));
+        assert!(Wildcard("GGIO?".to_string()).matches(b"GGIO1"));
+        assert!(!Wildcard("GGIO?".to_string()).matches(b"GGIO"));
+    }
+
     #[test]
     fn deserialize_mms_icsrule() {
         let mut mms_rule = HmIcsRules::new();
@@ -8,9 +8,13 @@ pub(crate) mod mms;
 pub(crate) mod iec104;
 pub(crate) mod goose;
 pub(crate) mod sv;
+pub(crate) mod enip;
+pub(crate) mod profinet;
+pub(crate) mod melsec;
+pub(crate) mod tristation;
 
 pub use self::{dnp3::Dnp3Arg, modbus::ModbusArg, s7comm::S7CommArg};
-use self::{fins::FinsArg, opcua::OpcuaArg, bacnet::BacnetArg, mms::MmsArg, iec104::IEC104Arg, goose::GooseArg, sv::SvArg};
+use self::{fins::FinsArg, opcua::OpcuaArg, bacnet::BacnetArg, mms::MmsArg, iec104::IEC104Arg, goose::GooseArg, sv::SvArg, enip::EnipArg, profinet::ProfinetArg, melsec::MelsecArg, tristation::TristationArg};
 
 use super::detect::IcsRuleDetector;
 use parsing_parser::L5Packet;
@@ -29,6 +33,10 @@ pub enum IcsRuleArg {
     IEC104(IEC104Arg),
     GOOSE(GooseArg),
     SV(SvArg),
+    ENIP(EnipArg),
+    PROFINET(ProfinetArg),
+    MELSEC(MelsecArg),
+    TRISTATION(TristationArg),
 }
 
 impl IcsRuleDetector for IcsRuleArg {
@@ -44,6 +52,10 @@ impl IcsRuleDetector for IcsRuleArg {
             Self::IEC104(iec104_arg) => iec104_arg.detect(l5),
             Self::GOOSE(goose_arg) => goose_arg.detect(l5),
             Self::SV(sv_arg) => sv_arg.detect(l5),
+            Self::ENIP(enip_arg) => enip_arg.detect(l5),
+            Self::PROFINET(profinet_arg) => profinet_arg.detect(l5),
+            Self::MELSEC(melsec_arg) => melsec_arg.detect(l5),
+            Self::TRISTATION(tristation_arg) => tristation_arg.detect(l5),
         }
     }
 }
@@ -84,7 +84,13 @@ pub enum ModbusArg {
         end_address: Option<u16>,
     },
     #[serde(rename = "43", alias = "0x2b", alias = "0x2B")]
-    EncapsulatedInterfaceTransport { subfunction: Option<u8> },
+    EncapsulatedInterfaceTransport {
+        subfunction: Option<u8>,
+        read_device_id_code: Option<u8>,
+        object_id: Option<u8>,
+    },
+    #[serde(rename = "90", alias = "0x5a", alias = "0x5A")]
+    Umas { subfunction: Option<u8> },
     #[serde(other)]
     Unknow,
 }
@@ -358,10 +364,59 @@ impl IcsRuleDetector for ModbusArg {
                         }
                     }
                     ModbusArg::EncapsulatedInterfaceTransport {
-                        subfunction: _subfunction,
+                        subfunction,
+                        read_device_id_code,
+                        object_id,
                     } => {
-                        // TODO: impl Modbus EncapsulatedInterfaceTransport parsing
-                        return false;
+                        if let modbus_req::Data::EncapsulatedInterfaceTransport {
+                            mei_type: _mei_type,
+                            mei_data: _mei_data,
+                        } = &modbus_req_header.pdu.data
+                        {
+                            if let Some(subfunction) = subfunction {
+                                if subfunction != _mei_type {
+                                    return false;
+                                }
+                            }
+
+                            if read_device_id_code.is_some() || object_id.is_some() {
+                                if let modbus_req::MeiReqData::ReadDeviceIdentification {
+                                    read_device_id_code: _read_device_id_code,
+                                    object_id: _object_id,
+                                } = _mei_data
+                                {
+                                    if let Some(read_device_id_code) = read_device_id_code {
+                                        if read_device_id_code != _read_device_id_code {
+                                            return false;
+                                        }
+                                    }
+                                    if let Some(object_id) = object_id {
+                                        if object_id != _object_id {
+                                            return false;
+                                        }
+                                    }
+                                } else {
+                                    return false;
+                                }
+                            }
+                        } else {
+                            return false;
+                        }
+                    }
+                    ModbusArg::Umas { subfunction } => {
+                        if let modbus_req::Data::Umas {
+                            umas_function_code: _umas_function_code,
+                            ..
+                        } = &modbus_req_header.pdu.data
+                        {
+                            if let Some(subfunction) = subfunction {
+                                if subfunction != _umas_function_code {
+                                    return false;
+                                }
+                            }
+                        } else {
+                            return false;
+                        }
                     }
                     ModbusArg::Unknow => return false,
                 };
@@ -398,6 +453,8 @@ mod tests {
                 dir: Direction::Bi,
                 dst_ip: None,
                 dst_port: None,
+                src_mac: None,
+                dst_mac: None,
                 msg: "Modbus Read Coils(1)".to_string(),
             },
             args: IcsRuleArg::Modbus(ModbusArg::ReadCoils {
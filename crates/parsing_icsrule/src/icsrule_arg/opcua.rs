@@ -1,6 +1,16 @@
-use parsing_parser::{parsers::opcua, ApplicationLayer, L5Packet};
-use serde::{Deserialize, Serialize};
+use std::fmt;
 
+use parsing_parser::{
+    parsers::opcua::{
+        self, MessageTypeEnum, NodeId, NodeIdentifier, OpcuaHeader, ServiceEnum, ServiceNodeidInfo,
+        UserIdentityToken,
+    },
+    ApplicationLayer, L5Packet,
+};
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::rule_utils::{NumVec, Wildcard};
 use crate::{detect::IcsRuleDetector, detect_option_eq};
 
 #[derive(Serialize, Deserialize, Debug, PartialEq)]
@@ -9,6 +19,245 @@ pub struct OpcuaArg {
     message_type: Option<u32>,
     #[serde(rename = "function_code")]
     service_nodeid_numeric: Option<u32>,
+    // Read/Write/Call 的目标节点，任一节点满足即匹配
+    #[serde(skip_serializing_if = "Option::is_none")]
+    node_id: Option<Vec<NodeIdPattern>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    attribute_id: Option<NumVec<u32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    endpoint_url: Option<Wildcard>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    security_policy: Option<Wildcard>,
+    // 仅支持 none，见 OpcuaSecurityMode 的反序列化
+    #[serde(skip_serializing_if = "Option::is_none")]
+    security_mode: Option<OpcuaSecurityMode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    user_token: Option<OpcuaUserToken>,
+}
+
+// 只有模式 None 能在明文的 OpenSecureChannel 请求中看到，Sign/SignAndEncrypt 不可匹配
+#[derive(Serialize, Debug, PartialEq, Clone, Copy)]
+pub enum OpcuaSecurityMode {
+    #[serde(rename = "none")]
+    None,
+}
+
+impl OpcuaSecurityMode {
+    fn from_u32(mode: u32) -> Option<Self> {
+        match mode {
+            1 => Some(Self::None),
+            _ => None,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for OpcuaSecurityMode {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_str(OpcuaSecurityModeVisitor)
+    }
+}
+
+struct OpcuaSecurityModeVisitor;
+
+impl<'de> Visitor<'de> for OpcuaSecurityModeVisitor {
+    type Value = OpcuaSecurityMode;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("security mode \"none\"")
+    }
+
+    // 安全策略非 None 时 OpenSecureChannel 请求已加密，报文中只能看到模式 None，
+    // 其余模式的规则永远无法命中，加载时即拒绝
+    fn visit_str<E>(self, v: &str) -> core::result::Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match v {
+            "none" => Ok(OpcuaSecurityMode::None),
+            "sign" | "sign_and_encrypt" => Err(de::Error::custom(format!(
+                "security mode \"{}\" is not supported: OpenSecureChannel is encrypted under any policy other than None",
+                v
+            ))),
+            _ => Err(de::Error::invalid_value(de::Unexpected::Str(v), &self)),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy)]
+pub enum OpcuaUserToken {
+    #[serde(rename = "anonymous")]
+    Anonymous,
+    #[serde(rename = "username")]
+    UserName,
+    #[serde(rename = "certificate")]
+    Certificate,
+    #[serde(rename = "issued")]
+    Issued,
+}
+
+impl OpcuaUserToken {
+    fn from_token(token: &UserIdentityToken) -> Option<Self> {
+        match token {
+            UserIdentityToken::Anonymous { .. } => Some(Self::Anonymous),
+            UserIdentityToken::UserName { .. } => Some(Self::UserName),
+            UserIdentityToken::X509 { .. } => Some(Self::Certificate),
+            UserIdentityToken::Issued { .. } => Some(Self::Issued),
+            _ => None,
+        }
+    }
+}
+
+// NodeId 匹配模式，格式同 OPC UA 字符串表示: "ns=2;s=Line1.*"、"i=2258"、"ns=*;g=..."
+// 省略 ns 时为命名空间 0，标识符部分支持通配符；b= 按十六进制小写匹配
+#[derive(Debug, PartialEq)]
+pub struct NodeIdPattern {
+    namespace: Option<u16>,
+    kind: char,
+    identifier: Wildcard,
+}
+
+impl NodeIdPattern {
+    pub fn matches(&self, node_id: &NodeId) -> bool {
+        if let Some(namespace) = self.namespace {
+            if namespace != node_id.namespace {
+                return false;
+            }
+        }
+        let identifier = match (self.kind, &node_id.identifier) {
+            ('i', NodeIdentifier::Numeric(v)) => v.to_string(),
+            ('s', NodeIdentifier::String(v)) => v.to_string(),
+            ('g', NodeIdentifier::Guid(v)) => format_guid(v),
+            ('b', NodeIdentifier::Opaque(v)) => v.iter().map(|b| format!("{:02x}", b)).collect(),
+            _ => return false,
+        };
+        self.identifier.matches(identifier.as_bytes())
+    }
+}
+
+// Data1-Data3 为小端序
+fn format_guid(v: &[u8]) -> String {
+    if v.len() != 16 {
+        return String::new();
+    }
+    format!(
+        "{:08x}-{:04x}-{:04x}-{:02x}{:02x}-{}",
+        u32::from_le_bytes([v[0], v[1], v[2], v[3]]),
+        u16::from_le_bytes([v[4], v[5]]),
+        u16::from_le_bytes([v[6], v[7]]),
+        v[8],
+        v[9],
+        v[10..].iter().map(|b| format!("{:02x}", b)).collect::<String>()
+    )
+}
+
+impl fmt::Display for NodeIdPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.namespace {
+            Some(0) => {}
+            Some(namespace) => write!(f, "ns={};", namespace)?,
+            None => write!(f, "ns=*;")?,
+        }
+        write!(f, "{}={}", self.kind, self.identifier.0)
+    }
+}
+
+impl Serialize for NodeIdPattern {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for NodeIdPattern {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_str(NodeIdPatternVisitor)
+    }
+}
+
+struct NodeIdPatternVisitor;
+
+impl<'de> Visitor<'de> for NodeIdPatternVisitor {
+    type Value = NodeIdPattern;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("invalid node id.(e.g. \"ns=2;s=Line1.Setpoint\")")
+    }
+
+    fn visit_str<E>(self, v: &str) -> core::result::Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        let invalid = || de::Error::invalid_value(de::Unexpected::Str(v), &self);
+        let (namespace, node) = match v.trim().split_once(';') {
+            Some((ns, node)) => match ns.strip_prefix("ns=") {
+                Some("*") => (None, node),
+                Some(ns) => (Some(ns.parse::<u16>().map_err(|_| invalid())?), node),
+                None => return Err(invalid()),
+            },
+            None => (Some(0), v.trim()),
+        };
+        let (kind, identifier) = match node.split_once('=') {
+            Some(("i", id)) => ('i', id.to_string()),
+            Some(("s", id)) => ('s', id.to_string()),
+            Some(("g", id)) => ('g', id.to_ascii_lowercase()),
+            Some(("b", id)) => ('b', id.to_ascii_lowercase()),
+            _ => return Err(invalid()),
+        };
+        Ok(NodeIdPattern {
+            namespace,
+            kind,
+            identifier: Wildcard(identifier),
+        })
+    }
+}
+
+fn service_nodeid_info<'a>(opcua: &'a OpcuaHeader) -> Option<&'a ServiceNodeidInfo<'a>> {
+    let msg_variant_info = match &opcua.message_type_enum {
+        MessageTypeEnum::Message { msg_variant_info, .. }
+        | MessageTypeEnum::OpenSecureChannel { msg_variant_info, .. }
+        | MessageTypeEnum::CloseSecureChannel { msg_variant_info, .. } => msg_variant_info,
+        _ => return None,
+    };
+    match msg_variant_info {
+        opcua::MsgVariantInfo::Service { service_nodeid_info, .. } => Some(service_nodeid_info),
+        _ => None,
+    }
+}
+
+fn service_enum<'a>(opcua: &'a OpcuaHeader) -> Option<&'a ServiceEnum<'a>> {
+    match service_nodeid_info(opcua)? {
+        ServiceNodeidInfo::TB { service_enum, .. }
+        | ServiceNodeidInfo::FB { service_enum, .. }
+        | ServiceNodeidInfo::Numeric { service_enum, .. } => Some(service_enum),
+        _ => None,
+    }
+}
+
+// 收集 Read/Write/Call 请求的目标节点及属性，Call 的对象与方法均作为目标，属性为空
+fn node_targets<'a>(service: &'a ServiceEnum) -> Vec<(&'a NodeId<'a>, Option<u32>)> {
+    match service {
+        ServiceEnum::ReadRequest { nodes_to_read, .. } => nodes_to_read
+            .iter()
+            .map(|o| (&o.node_id, Some(o.attribute_id)))
+            .collect(),
+        ServiceEnum::WriteRequest { nodes_to_write, .. } => nodes_to_write
+            .iter()
+            .map(|o| (&o.node_id, Some(o.attribute_id)))
+            .collect(),
+        ServiceEnum::CallRequest { methods_to_call, .. } => methods_to_call
+            .iter()
+            .flat_map(|o| [(&o.object_id, None), (&o.method_id, None)])
+            .collect(),
+        _ => Vec::new(),
+    }
 }
 
 impl IcsRuleDetector for OpcuaArg {
@@ -17,17 +266,9 @@ impl IcsRuleDetector for OpcuaArg {
             detect_option_eq!(self.message_type, opcua.message_type);
 
             if let Some(target_service_nodeid_numeric) = self.service_nodeid_numeric {
-                if let opcua::MessageTypeEnum::Message {
-                    msg_variant_info:
-                        opcua::MsgVariantInfo::Service {
-                            service_nodeid_info,
-                            ..
-                        },
-                    ..
-                } = &opcua.message_type_enum
-                {
+                if let Some(service_nodeid_info) = service_nodeid_info(opcua) {
                     match service_nodeid_info {
-                        opcua::ServiceNodeidInfo::TB {
+                        ServiceNodeidInfo::TB {
                             service_nodeid_numeric,
                             ..
                         } => {
@@ -35,7 +276,7 @@ impl IcsRuleDetector for OpcuaArg {
                                 return false;
                             }
                         }
-                        opcua::ServiceNodeidInfo::FB {
+                        ServiceNodeidInfo::FB {
                             service_nodeid_numeric,
                             ..
                         } => {
@@ -43,7 +284,7 @@ impl IcsRuleDetector for OpcuaArg {
                                 return false;
                             }
                         }
-                        opcua::ServiceNodeidInfo::Numeric {
+                        ServiceNodeidInfo::Numeric {
                             service_nodeid_numeric,
                             ..
                         } => {
@@ -56,6 +297,78 @@ impl IcsRuleDetector for OpcuaArg {
                 }
             }
 
+            let service = service_enum(opcua);
+
+            if self.node_id.is_some() || self.attribute_id.is_some() {
+                // 任一目标同时满足 node_id 与 attribute_id 即匹配
+                let targets = service.map(node_targets).unwrap_or_default();
+                let matched = targets.iter().any(|(node_id, attribute_id)| {
+                    self.node_id
+                        .as_ref()
+                        .map_or(true, |o| o.iter().any(|p| p.matches(node_id)))
+                        && self.attribute_id.as_ref().map_or(true, |o| {
+                            attribute_id.map_or(false, |attribute_id| o.contain(attribute_id))
+                        })
+                });
+                if !matched {
+                    return false;
+                }
+            }
+
+            if let Some(target_endpoint_url) = &self.endpoint_url {
+                let endpoint_url = match (&opcua.message_type_enum, service) {
+                    (MessageTypeEnum::Hello { endpoint_url, .. }, _)
+                    | (_, Some(ServiceEnum::GetEndpointsRequest { endpoint_url, .. }))
+                    | (_, Some(ServiceEnum::CreateSessionRequest { endpoint_url, .. })) => {
+                        endpoint_url
+                    }
+                    _ => return false,
+                };
+                if !target_endpoint_url.matches(endpoint_url.as_bytes()) {
+                    return false;
+                }
+            }
+
+            if let Some(target_security_policy) = &self.security_policy {
+                match &opcua.message_type_enum {
+                    MessageTypeEnum::OpenSecureChannel {
+                        security_policy_uri,
+                        ..
+                    } => {
+                        if !target_security_policy.matches(security_policy_uri.as_bytes()) {
+                            return false;
+                        }
+                    }
+                    _ => return false,
+                }
+            }
+
+            // 安全模式仅能从明文的 OpenSecureChannel 请求中获得
+            if let Some(target_security_mode) = self.security_mode {
+                match service {
+                    Some(ServiceEnum::OpenSecureChannelRequest { security_mode, .. }) => {
+                        if OpcuaSecurityMode::from_u32(*security_mode) != Some(target_security_mode) {
+                            return false;
+                        }
+                    }
+                    _ => return false,
+                }
+            }
+
+            if let Some(target_user_token) = self.user_token {
+                match service {
+                    Some(ServiceEnum::ActivateSessionRequest {
+                        user_identity_token,
+                        ..
+                    }) => {
+                        if OpcuaUserToken::from_token(user_identity_token) != Some(target_user_token) {
+                            return false;
+                        }
+                    }
+                    _ => return false,
+                }
+            }
+
             true
         } else {
             false
@@ -86,11 +399,19 @@ mod tests {
                 dir: Direction::Uni,
                 dst_ip: None,
                 dst_port: Some(NumVec(vec![Num::Single(9600u16)])),
+                src_mac: None,
+                dst_mac: None,
                 msg: "Opcua Read".to_string(),
             },
             args: IcsRuleArg::OPCUA(OpcuaArg {
                 message_type: Some(0x04),
                 service_nodeid_numeric: Some(631),
+                node_id: None,
+                attribute_id: None,
+                endpoint_url: None,
+                security_policy: None,
+                security_mode: None,
+                user_token: None,
             }),
         };
 
@@ -100,6 +421,84 @@ mod tests {
         )
     }
 
+    #[test]
+    fn serialize_opcua_node_icsrule() {
+        let opcua_arg = OpcuaArg {
+            message_type: None,
+            service_nodeid_numeric: Some(673),
+            node_id: Some(vec![
+                NodeIdPattern {
+                    namespace: Some(2),
+                    kind: 's',
+                    identifier: Wildcard("Line1.*".to_string()),
+                },
+                NodeIdPattern {
+                    namespace: Some(0),
+                    kind: 'i',
+                    identifier: Wildcard("2258".to_string()),
+                },
+                NodeIdPattern {
+                    namespace: None,
+                    kind: 'g',
+                    identifier: Wildcard("*".to_string()),
+                },
+            ]),
+            attribute_id: Some(NumVec(vec![Num::Single(13u32)])),
+            endpoint_url: None,
+            security_policy: Some(Wildcard("*#None".to_string())),
+            security_mode: Some(OpcuaSecurityMode::None),
+            user_token: Some(OpcuaUserToken::UserName),
+        };
+
+        let json = r#"{"type":null,"function_code":673,"node_id":["ns=2;s=Line1.*","i=2258","ns=*;g=*"],"attribute_id":[13],"security_policy":"*#None","security_mode":"none","user_token":"username"}"#;
+        assert_eq!(serde_json::to_string(&opcua_arg).unwrap(), json);
+        // 数值范围以字符串形式读取
+        let json = json.replace("[13]", r#"["13"]"#);
+        assert_eq!(serde_json::from_str::<OpcuaArg>(&json).unwrap(), opcua_arg);
+        assert!(serde_json::from_str::<NodeIdPattern>(r#""ns=x;i=1""#).is_err());
+        assert!(serde_json::from_str::<NodeIdPattern>(r#""ns=1;q=1""#).is_err());
+    }
+
+    #[test]
+    fn deserialize_opcua_security_mode() {
+        // 加密的 OpenSecureChannel 中无法取得 Sign/SignAndEncrypt，加载时拒绝
+        for mode in ["sign", "sign_and_encrypt"] {
+            let json = format!(r#"{{"type":null,"function_code":null,"security_mode":"{}"}}"#, mode);
+            let error = serde_json::from_str::<OpcuaArg>(&json).unwrap_err();
+            assert!(error.to_string().contains("OpenSecureChannel is encrypted"));
+        }
+        assert!(serde_json::from_str::<OpcuaSecurityMode>(r#""encrypt""#).is_err());
+        assert_eq!(
+            serde_json::from_str::<OpcuaSecurityMode>(r#""none""#).unwrap(),
+            OpcuaSecurityMode::None
+        );
+    }
+
+    #[test]
+    fn node_id_pattern_match() {
+        let pattern: NodeIdPattern = serde_json::from_str(r#""ns=2;s=Line?.Set*""#).unwrap();
+        assert!(pattern.matches(&NodeId {
+            namespace: 2,
+            identifier: NodeIdentifier::String("Line1.Setpoint"),
+        }));
+        assert!(!pattern.matches(&NodeId {
+            namespace: 3,
+            identifier: NodeIdentifier::String("Line1.Setpoint"),
+        }));
+
+        let pattern: NodeIdPattern = serde_json::from_str(r#""ns=*;i=50??""#).unwrap();
+        assert!(pattern.matches(&NodeId { namespace: 2, identifier: NodeIdentifier::Numeric(5001) }));
+        assert!(!pattern.matches(&NodeId { namespace: 2, identifier: NodeIdentifier::String("5001") }));
+
+        let guid = [
+            0x78, 0x56, 0x34, 0x12, 0x34, 0x12, 0x78, 0x56, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06,
+            0x07, 0x08,
+        ];
+        let pattern: NodeIdPattern =
+            serde_json::from_str(r#""ns=1;g=12345678-1234-5678-0102-030405060708""#).unwrap();
+        assert!(pattern.matches(&NodeId { namespace: 1, identifier: NodeIdentifier::Guid(&guid) }));
+    }
+
     #[test]
     fn deserialize_dnp3_icsrule() {
         let mut fins_rule = HmIcsRules::new();
@@ -0,0 +1,192 @@
+use parsing_parser::parsers::{DcpBlock, DcpBlockValue, DcpHeader, PnioBlockData, PnioCmHeader, ProfinetHeader};
+use parsing_parser::{ApplicationLayer, L5Packet};
+use serde::{Deserialize, Serialize};
+
+use crate::detect::IcsRuleDetector;
+use crate::rule_utils::{NumVec, Wildcard};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct ProfinetArg {
+    // RT FrameID，如 0xfefd DCP Get/Set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    frame_id: Option<NumVec<u16>>,
+    // DCP 服务，3 Get、4 Set、5 Identify
+    #[serde(skip_serializing_if = "Option::is_none")]
+    service_id: Option<NumVec<u8>>,
+    // DCP 服务类型，0 请求、1 响应
+    #[serde(skip_serializing_if = "Option::is_none")]
+    service_type: Option<NumVec<u8>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    option: Option<NumVec<u8>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    suboption: Option<NumVec<u8>>,
+    // DCP NameOfStation 或 PNIO-CM 连接请求中的站名
+    #[serde(skip_serializing_if = "Option::is_none")]
+    station_name: Option<Wildcard>,
+    // PNIO-CM 操作号，0 Connect、2 Read、3 Write
+    #[serde(skip_serializing_if = "Option::is_none")]
+    opnum: Option<NumVec<u16>>,
+    // PNIO-CM 记录索引
+    #[serde(skip_serializing_if = "Option::is_none")]
+    index: Option<NumVec<u16>>,
+}
+
+impl ProfinetArg {
+    fn has_dcp_criteria(&self) -> bool {
+        self.service_id.is_some()
+            || self.service_type.is_some()
+            || self.option.is_some()
+            || self.suboption.is_some()
+    }
+
+    fn detect_block(&self, block: &DcpBlock) -> bool {
+        if let Some(option) = &self.option {
+            if !option.contain(block.option) {
+                return false;
+            }
+        }
+        if let Some(suboption) = &self.suboption {
+            if !suboption.contain(block.suboption) {
+                return false;
+            }
+        }
+        if let Some(station_name) = &self.station_name {
+            match block.value {
+                DcpBlockValue::NameOfStation(name) if station_name.matches(name.as_bytes()) => {}
+                _ => return false,
+            }
+        }
+        true
+    }
+
+    fn detect_dcp(&self, dcp: &DcpHeader) -> bool {
+        if let Some(service_id) = &self.service_id {
+            if !service_id.contain(dcp.service_id) {
+                return false;
+            }
+        }
+        if let Some(service_type) = &self.service_type {
+            if !service_type.contain(dcp.service_type) {
+                return false;
+            }
+        }
+        if self.option.is_none() && self.suboption.is_none() && self.station_name.is_none() {
+            return true;
+        }
+        // 任一块满足全部块条件即匹配
+        dcp.blocks.iter().any(|block| self.detect_block(block))
+    }
+
+    fn detect_profinet(&self, profinet: &ProfinetHeader) -> bool {
+        if self.opnum.is_some() || self.index.is_some() {
+            return false;
+        }
+        if let Some(frame_id) = &self.frame_id {
+            if !frame_id.contain(profinet.frame_id) {
+                return false;
+            }
+        }
+        if !self.has_dcp_criteria() && self.station_name.is_none() {
+            return true;
+        }
+        match profinet.pdu.dcp() {
+            Some(dcp) => self.detect_dcp(dcp),
+            None => false,
+        }
+    }
+
+    fn detect_pnio_cm(&self, pnio_cm: &PnioCmHeader) -> bool {
+        if self.frame_id.is_some() || self.has_dcp_criteria() {
+            return false;
+        }
+        if let Some(opnum) = &self.opnum {
+            if !opnum.contain(pnio_cm.rpc.opnum) {
+                return false;
+            }
+        }
+        if let Some(index) = &self.index {
+            // 多记录写入时任一记录索引匹配即可
+            let hit = pnio_cm.blocks().iter().any(|block| match block.data {
+                PnioBlockData::Record { index: i, .. } => index.contain(i),
+                _ => false,
+            });
+            if !hit {
+                return false;
+            }
+        }
+        if let Some(station_name) = &self.station_name {
+            let hit = pnio_cm.blocks().iter().any(|block| match block.data {
+                PnioBlockData::Ar { station_name: Some(name), .. } => station_name.matches(name.as_bytes()),
+                _ => false,
+            });
+            if !hit {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+impl IcsRuleDetector for ProfinetArg {
+    fn detect(&self, l5: &L5Packet) -> bool {
+        match &l5.application_layer {
+            ApplicationLayer::Profinet(profinet) => self.detect_profinet(profinet),
+            ApplicationLayer::PnioCm(pnio_cm) => self.detect_pnio_cm(pnio_cm),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parsing_rule::{RuleAction, Direction};
+
+    use crate::{
+        icsrule_arg::IcsRuleArg,
+        HmIcsRules, IcsRule, IcsRuleBasis, rule_utils::*,
+    };
+
+    use super::*;
+
+    #[test]
+    fn serialize_profinet_icsrule() {
+        let profinet_rule = IcsRule {
+            basic: IcsRuleBasis {
+                active: true,
+                rid: 1,
+                action: RuleAction::Alert,
+                src_ip: None,
+                src_port: None,
+                dir: Direction::Uni,
+                dst_ip: None,
+                dst_port: None,
+                src_mac: None,
+                dst_mac: None,
+                msg: "DCP Set NameOfStation".to_string(),
+            },
+            args: IcsRuleArg::PROFINET(ProfinetArg {
+                frame_id: None,
+                service_id: Some(NumVec(vec![Num::Single(4u8)])),
+                service_type: Some(NumVec(vec![Num::Single(0u8)])),
+                option: Some(NumVec(vec![Num::Single(2u8)])),
+                suboption: Some(NumVec(vec![Num::Single(2u8)])),
+                station_name: None,
+                opnum: None,
+                index: None,
+            }),
+        };
+
+        assert_eq!(
+            serde_json::to_string(&profinet_rule).unwrap(),
+            r#"{"active":true,"rid":1,"action":"alert","src":null,"sport":null,"dire":"->","dst":null,"dport":null,"msg":"DCP Set NameOfStation","proname":"PROFINET","args":{"service_id":[4],"service_type":[0],"option":[2],"suboption":[2]}}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_profinet_icsrule() {
+        let mut profinet_rule = HmIcsRules::new();
+
+        let file_str = "./tests/unitest_profinet.json";
+        assert!(profinet_rule.load_rules(file_str));
+    }
+}
@@ -1,11 +1,11 @@
 use parsing_parser::{
-    parsers::s7comm::{self, SyntaxIdEnum},
+    parsers::s7comm::{self, SyntaxIdEnum, UserdataData},
     ApplicationLayer, L5Packet,
 };
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    detect::IcsRuleDetector, detect_address, detect_option_eq, detect_utils::bytes_to_u32,
+    detect::IcsRuleDetector, detect_address, detect_option_eq,
 };
 
 #[derive(Serialize, Deserialize, Debug, PartialEq)]
@@ -24,7 +24,14 @@ pub enum S7CommArg {
         param: S7AckDataParm,
     },
     #[serde(rename = "7", alias = "0x07")]
-    Userdata { subfunction: Option<u8> },
+    Userdata {
+        function_group: Option<u8>,
+        subfunction: Option<u8>,
+        szl_id: Option<u16>,
+        szl_index: Option<u16>,
+        block_type: Option<u8>,
+        block_number: Option<u16>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq)]
@@ -43,8 +50,8 @@ pub enum S7JobParm {
         area: Option<u8>,
         start_address: Option<u32>,
         end_address: Option<u32>,
-        min_value: Option<u32>,
-        max_value: Option<u32>,
+        min_value: Option<f64>,
+        max_value: Option<f64>,
     },
     #[serde(rename = "26", alias = "0x1a", alias = "0x1A")]
     RequestDownload {},
@@ -71,8 +78,8 @@ pub enum S7AckDataParm {
     SetupCommunication {},
     #[serde(rename = "4", alias = "0x04")]
     ReadVar {
-        min_value: Option<u32>,
-        max_value: Option<u32>,
+        min_value: Option<f64>,
+        max_value: Option<f64>,
     },
     #[serde(rename = "5", alias = "0x05")]
     WriteVar {},
@@ -143,6 +150,7 @@ impl IcsRuleDetector for S7CommArg {
                                 s7comm::JobParam::WriteVar {
                                     items,
                                     standard_items,
+                                    values,
                                     ..
                                 },
                             ..
@@ -166,15 +174,19 @@ impl IcsRuleDetector for S7CommArg {
                                 }
                             }
 
-                            for standard_item in standard_items {
-                                if standard_item.data.len() <= 4 {
-                                    if let Some(ref value) = bytes_to_u32(standard_item.data) {
-                                        detect_address!(min_value, max_value, value);
+                            if min_value.is_some() || max_value.is_some() {
+                                // 数据项按请求中的 transport size 解码后再比较
+                                if values.len() != standard_items.len() {
+                                    return false;
+                                }
+                                for value in values {
+                                    if let Some(numeric_values) = value.value.numeric_values() {
+                                        for ref numeric_value in numeric_values {
+                                            detect_address!(min_value, max_value, numeric_value);
+                                        }
                                     } else {
                                         return false;
                                     }
-                                } else {
-                                    return false;
                                 }
                             }
                         } else {
@@ -208,15 +220,17 @@ impl IcsRuleDetector for S7CommArg {
                             ..
                         } = &s7.parameter
                         {
-                            for standard_item in standard_items {
-                                if standard_item.data.len() <= 4 {
-                                    if let Some(ref value) = bytes_to_u32(standard_item.data) {
-                                        detect_address!(min_value, max_value, value);
+                            if min_value.is_some() || max_value.is_some() {
+                                for standard_item in standard_items {
+                                    if let Some(numeric_values) =
+                                        standard_item.value.numeric_values()
+                                    {
+                                        for ref numeric_value in numeric_values {
+                                            detect_address!(min_value, max_value, numeric_value);
+                                        }
                                     } else {
                                         return false;
                                     }
-                                } else {
-                                    return false;
                                 }
                             }
                         }
@@ -231,13 +245,76 @@ impl IcsRuleDetector for S7CommArg {
                     S7AckDataParm::PiService {} => {}
                     S7AckDataParm::PlcStop {} => {}
                 },
-                Self::Userdata { subfunction } => {
+                Self::Userdata {
+                    function_group,
+                    subfunction,
+                    szl_id,
+                    szl_index,
+                    block_type,
+                    block_number,
+                } => {
                     if let s7comm::Parameter::Userdata {
+                        function_group: _function_group,
                         subfunction: _subfunction,
+                        userdata_data,
                         ..
                     } = &s7.parameter
                     {
+                        detect_option_eq!(function_group, _function_group);
                         detect_option_eq!(subfunction, _subfunction);
+
+                        if szl_id.is_some() || szl_index.is_some() {
+                            match userdata_data {
+                                UserdataData::ReadSzlRequest {
+                                    szl_id: _szl_id,
+                                    szl_index: _szl_index,
+                                }
+                                | UserdataData::ReadSzlResponse {
+                                    szl_id: _szl_id,
+                                    szl_index: _szl_index,
+                                    ..
+                                } => {
+                                    detect_option_eq!(szl_id, _szl_id);
+                                    detect_option_eq!(szl_index, _szl_index);
+                                }
+                                _ => return false,
+                            }
+                        }
+
+                        if block_type.is_some() || block_number.is_some() {
+                            match userdata_data {
+                                UserdataData::ListBlocksOfTypeRequest {
+                                    block_type: _block_type,
+                                } => {
+                                    if block_number.is_some() {
+                                        return false;
+                                    }
+                                    detect_option_eq!(block_type, _block_type);
+                                }
+                                UserdataData::BlockInfoRequest {
+                                    block_type: _block_type,
+                                    block_number: _block_number,
+                                    ..
+                                } => {
+                                    detect_option_eq!(block_type, _block_type);
+                                    if let Some(block_number) = block_number {
+                                        match _block_number.parse::<u16>() {
+                                            Ok(ref _block_number) if block_number == _block_number => {}
+                                            _ => return false,
+                                        }
+                                    }
+                                }
+                                UserdataData::BlockInfoResponse {
+                                    block_type: _block_type,
+                                    block_number: _block_number,
+                                    ..
+                                } => {
+                                    detect_option_eq!(block_type, _block_type);
+                                    detect_option_eq!(block_number, _block_number);
+                                }
+                                _ => return false,
+                            }
+                        }
                     } else {
                         return false;
                     }
@@ -274,6 +351,8 @@ pub mod tests {
                 dir: Direction::Bi,
                 dst_ip: None,
                 dst_port: None,
+                src_mac: None,
+                dst_mac: None,
                 msg: "Job - Read Var (0x04)".to_string(),
             },
             args: IcsRuleArg::S7COMM(S7CommArg::Job {
@@ -0,0 +1,93 @@
+use parsing_parser::{ApplicationLayer, L5Packet};
+use serde::{Deserialize, Serialize};
+
+use crate::detect::IcsRuleDetector;
+use crate::rule_utils::NumVec;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct TristationArg {
+    // 报文类型，如 1 连接请求、5 执行命令
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message_type: Option<NumVec<u8>>,
+    // 执行命令的命令码
+    #[serde(skip_serializing_if = "Option::is_none")]
+    command: Option<NumVec<u8>>,
+    // 下载、分配程序、运行/停机等修改控制程序的命令
+    #[serde(skip_serializing_if = "Option::is_none")]
+    program_change: Option<bool>,
+}
+
+impl IcsRuleDetector for TristationArg {
+    fn detect(&self, l5: &L5Packet) -> bool {
+        if let ApplicationLayer::Tristation(tristation) = &l5.application_layer {
+            if let Some(message_type) = &self.message_type {
+                if !message_type.contain(tristation.message_type) {
+                    return false;
+                }
+            }
+            if let Some(command) = &self.command {
+                match tristation.command_code() {
+                    Some(code) if command.contain(code) => {}
+                    _ => return false,
+                }
+            }
+            if let Some(program_change) = self.program_change {
+                if tristation.is_program_change() != program_change {
+                    return false;
+                }
+            }
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parsing_rule::{RuleAction, Direction};
+
+    use crate::{
+        icsrule_arg::IcsRuleArg,
+        HmIcsRules, IcsRule, IcsRuleBasis, rule_utils::*,
+    };
+
+    use super::*;
+
+    #[test]
+    fn serialize_tristation_icsrule() {
+        let tristation_rule = IcsRule {
+            basic: IcsRuleBasis {
+                active: true,
+                rid: 1,
+                action: RuleAction::Alert,
+                src_ip: None,
+                src_port: None,
+                dir: Direction::Uni,
+                dst_ip: None,
+                dst_port: Some(NumVec(vec![Num::Single(1502u16)])),
+                src_mac: None,
+                dst_mac: None,
+                msg: "TriStation program change".to_string(),
+            },
+            args: IcsRuleArg::TRISTATION(TristationArg {
+                message_type: None,
+                command: None,
+                program_change: Some(true),
+            }),
+        };
+
+        assert_eq!(
+            serde_json::to_string(&tristation_rule).unwrap(),
+            r#"{"active":true,"rid":1,"action":"alert","src":null,"sport":null,"dire":"->","dst":null,"dport":[1502],"msg":"TriStation program change","proname":"TRISTATION","args":{"program_change":true}}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_tristation_icsrule() {
+        let mut tristation_rule = HmIcsRules::new();
+
+        let file_str = "./tests/unitest_tristation.json";
+        assert!(tristation_rule.load_rules(file_str));
+    }
+}
@@ -3,7 +3,8 @@ use std::net::{Ipv4Addr, IpAddr};
 use std::str::FromStr;
 
 use ipnet::Ipv4Net;
-use serde::{Serialize, Deserialize};
+use parsing_parser::MacAddress;
+use serde::{Serialize, Serializer, Deserialize};
 use serde::de::{self, Visitor};
 
 // IPv4 with range
@@ -118,6 +119,71 @@ impl<'de> Deserialize<'de> for Ipv4Range {
     }
 }
 
+// MAC address (GOOSE/SV 等二层协议)
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct MacAddressVec(pub Vec<RuleMacAddress>);
+
+impl MacAddressVec
+{
+    #[inline]
+    pub fn contain(&self, target: &MacAddress) -> bool {
+        self.0.iter().any(|n| n.0 == target.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RuleMacAddress(pub [u8; 6]);
+
+impl Serialize for RuleMacAddress {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer
+    {
+        let m = self.0;
+        serializer.serialize_str(&format!(
+            "{:02x}:{:02x}:{:02x}:{:02x}:{:02x}:{:02x}",
+            m[0], m[1], m[2], m[3], m[4], m[5]
+        ))
+    }
+}
+
+impl<'de> Deserialize<'de> for RuleMacAddress {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>
+    {
+        struct RuleMacAddressVisitor;
+
+        impl<'de> Visitor<'de> for RuleMacAddressVisitor {
+            type Value = RuleMacAddress;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("invalid mac address.(e.g. \"01:0c:cd:01:00:01\" or \"01-0c-cd-01-00-01\")")
+            }
+
+            fn visit_str<E>(self, v: &str) -> core::result::Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                let mut address = [0u8; 6];
+                let mut parts = v.trim().split(|c| c == ':' || c == '-');
+                for byte in address.iter_mut() {
+                    *byte = match parts.next().map(|part| u8::from_str_radix(part, 16)) {
+                        Some(Ok(o)) => o,
+                        _ => return Err(de::Error::invalid_type(de::Unexpected::Str(v), &self))
+                    };
+                }
+                if parts.next().is_some() {
+                    return Err(de::Error::invalid_type(de::Unexpected::Str(v), &self));
+                }
+                Ok(RuleMacAddress(address))
+            }
+        }
+
+        deserializer.deserialize_str(RuleMacAddressVisitor)
+    }
+}
+
 // num with range
 // ref: https://users.rust-lang.org/t/how-to-write-a-simple-generic-function-with-numeric-types/10943
 #[derive(Serialize, Deserialize, Debug, PartialEq)]
@@ -287,3 +353,33 @@ where
         }
     }
 }
+
+// wildcard pattern, `*` matches any sequence and `?` matches a single byte
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(transparent)]
+pub struct Wildcard(pub String);
+
+impl Wildcard {
+    pub fn matches(&self, target: &[u8]) -> bool {
+        let pattern = self.0.as_bytes();
+        let (mut p, mut t) = (0, 0);
+        // 最近一次`*`的位置及其对应的目标位置，用于回溯
+        let mut star: Option<(usize, usize)> = None;
+        while t < target.len() {
+            if p < pattern.len() && (pattern[p] == b'?' || pattern[p] == target[t]) {
+                p += 1;
+                t += 1;
+            } else if p < pattern.len() && pattern[p] == b'*' {
+                star = Some((p, t));
+                p += 1;
+            } else if let Some((star_p, star_t)) = star {
+                p = star_p + 1;
+                t = star_t + 1;
+                star = Some((star_p, star_t + 1));
+            } else {
+                return false;
+            }
+        }
+        pattern[p..].iter().all(|&c| c == b'*')
+    }
+}
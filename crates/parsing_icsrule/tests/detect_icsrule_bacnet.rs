@@ -0,0 +1,63 @@
+use parsing_icsrule::HmIcsRules;
+use parsing_parser::{QuinPacket, QuinPacketOptions};
+use parsing_rule::{DetectMiss, DetectResultICS, RuleAction, RulesDetectorICS};
+
+// WriteProperty analog-value,1 present-value = 72.5, priority 8
+const BACNET_WRITE_PROPERTY_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x04, 0x05, 0x06, 0x00, 0x0c, 0x29, 0x01, 0x02, 0x03,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x36, 0x00, 0x01, 0x40, 0x00, 0x40, 0x11,
+    0xb7, 0x47, 0xc0, 0xa8, 0x01, 0x0a, 0xc0, 0xa8, 0x01, 0x14, 0xba, 0xc0,
+    0xba, 0xc0, 0x00, 0x22, 0x00, 0x00, 0x81, 0x0a, 0x00, 0x1a, 0x01, 0x04,
+    0x00, 0x05, 0x01, 0x0f, 0x0c, 0x00, 0x80, 0x00, 0x01, 0x19, 0x55, 0x3e,
+    0x44, 0x42, 0x91, 0x00, 0x00, 0x3f, 0x49, 0x08,
+];
+
+// ReadPropertyMultiple analog-input,3 (present-value, status-flags), device,1234 (object-name)
+const BACNET_READ_PROPERTY_MULTIPLE_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x04, 0x05, 0x06, 0x00, 0x0c, 0x29, 0x01, 0x02, 0x03,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x3a, 0x00, 0x01, 0x40, 0x00, 0x40, 0x11,
+    0xb7, 0x43, 0xc0, 0xa8, 0x01, 0x0a, 0xc0, 0xa8, 0x01, 0x14, 0xba, 0xc0,
+    0xba, 0xc0, 0x00, 0x26, 0x00, 0x00, 0x81, 0x0a, 0x00, 0x1e, 0x01, 0x04,
+    0x00, 0x05, 0x02, 0x0e, 0x0c, 0x00, 0x00, 0x00, 0x03, 0x1e, 0x09, 0x55,
+    0x09, 0x6f, 0x1f, 0x0c, 0x02, 0x00, 0x04, 0xd2, 0x1e, 0x09, 0x4d, 0x1f,
+];
+
+// I-Am device,1234 vendor 260
+const BACNET_I_AM_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x04, 0x05, 0x06, 0x00, 0x0c, 0x29, 0x01, 0x02, 0x03,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x31, 0x00, 0x01, 0x40, 0x00, 0x40, 0x11,
+    0xb7, 0x4c, 0xc0, 0xa8, 0x01, 0x0a, 0xc0, 0xa8, 0x01, 0x14, 0xba, 0xc0,
+    0xba, 0xc0, 0x00, 0x1d, 0x00, 0x00, 0x81, 0x0a, 0x00, 0x15, 0x01, 0x00,
+    0x10, 0x00, 0xc4, 0x02, 0x00, 0x04, 0xd2, 0x22, 0x05, 0xc4, 0x91, 0x03,
+    0x22, 0x01, 0x04,
+];
+
+#[test]
+fn detect_bacnet_write_property() {
+    let mut bacnet_rule = HmIcsRules::new();
+    assert!(bacnet_rule.load_rules("./tests/ics_rules_bacnet.json"));
+
+    let packet = QuinPacket::parse_from_stream(BACNET_WRITE_PROPERTY_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(packet, QuinPacket::L5(_)));
+    assert_eq!(bacnet_rule.detect(&packet), DetectResultICS::Hit(1, RuleAction::Drop));
+
+    bacnet_rule.deactive_rule(1);
+    assert_eq!(
+        bacnet_rule.detect(&packet),
+        DetectResultICS::Miss(DetectMiss::Content)
+    );
+}
+
+#[test]
+fn detect_bacnet_object_property() {
+    let mut bacnet_rule = HmIcsRules::new();
+    assert!(bacnet_rule.load_rules("./tests/ics_rules_bacnet.json"));
+
+    let packet =
+        QuinPacket::parse_from_stream(BACNET_READ_PROPERTY_MULTIPLE_FRAME, &QuinPacketOptions::default());
+    assert_eq!(bacnet_rule.detect(&packet), DetectResultICS::Hit(2, RuleAction::Alert));
+
+    // I-Am 只携带设备对象，没有属性标识
+    let packet = QuinPacket::parse_from_stream(BACNET_I_AM_FRAME, &QuinPacketOptions::default());
+    assert_eq!(bacnet_rule.detect(&packet), DetectResultICS::Hit(3, RuleAction::Alert));
+}
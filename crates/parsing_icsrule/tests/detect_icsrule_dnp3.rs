@@ -0,0 +1,115 @@
+use parsing_icsrule::HmIcsRules;
+use parsing_parser::{QuinFlow, QuinPacket, QuinPacketOptions};
+use parsing_rule::{DetectMiss, DetectResultICS, RuleAction, RulesDetectorICS};
+
+// DNP3 Select: 3 -> 4, g12v1 CROB (qualifier 0x28), index 34463, control code 0x03 (latch on)
+const DNP3_SELECT_FRAME: &[u8] = &[
+    0x00, 0x42, 0x15, 0x69, 0x87, 0x25, 0x00, 0x16, 0xcb, 0x8e, 0x3f, 0x0b,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x4b, 0x08, 0x8c, 0x40, 0x00, 0x80, 0x06,
+    0xec, 0x8c, 0xc0, 0xa8, 0x42, 0x21, 0xc0, 0xa8, 0x42, 0x22, 0x04, 0x8f,
+    0x4e, 0x20, 0xde, 0x6e, 0xf3, 0xc0, 0xa2, 0x46, 0xda, 0x01, 0x50, 0x18,
+    0xfe, 0x76, 0x96, 0xa5, 0x00, 0x00, 0x05, 0x64, 0x1a, 0xc4, 0x04, 0x00,
+    0x03, 0x00, 0xc2, 0xe6, 0xd2, 0xc3, 0x03, 0x0c, 0x01, 0x28, 0x01, 0x00,
+    0x9f, 0x86, 0x03, 0x01, 0x64, 0x00, 0x00, 0x00, 0xec, 0x41, 0x64, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x5b,
+];
+
+// DNP3 Direct Operate: 3 -> 4, g12v1 CROB (qualifier 0x17), index 2, control code 0x81 (trip pulse on)
+const DNP3_DIRECT_OPERATE_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x04, 0x05, 0x06, 0x00, 0x0c, 0x29, 0x01, 0x02, 0x03,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x49, 0x00, 0x01, 0x40, 0x00, 0x40, 0x06,
+    0xb7, 0x3f, 0xc0, 0xa8, 0x01, 0x0a, 0xc0, 0xa8, 0x01, 0x14, 0x04, 0x87,
+    0x4e, 0x20, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x50, 0x18,
+    0x20, 0x00, 0x00, 0x00, 0x00, 0x00, 0x05, 0x64, 0x18, 0xc4, 0x04, 0x00,
+    0x03, 0x00, 0x75, 0xc0, 0xc3, 0xc5, 0x05, 0x0c, 0x01, 0x17, 0x01, 0x02,
+    0x81, 0x01, 0xe8, 0x03, 0x00, 0x00, 0x00, 0x00, 0x8d, 0x55, 0x00, 0x00,
+    0x00, 0xff, 0xff,
+];
+
+// 同一 Direct Operate 分片拆成两个传输段，分别位于两个 TCP 报文中
+const DNP3_DIRECT_OPERATE_FIRST_SEGMENT: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x04, 0x05, 0x06, 0x00, 0x0c, 0x29, 0x01, 0x02, 0x03,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x3d, 0x00, 0x01, 0x40, 0x00, 0x40, 0x06,
+    0xb7, 0x4b, 0xc0, 0xa8, 0x01, 0x0a, 0xc0, 0xa8, 0x01, 0x14, 0x04, 0x87,
+    0x4e, 0x20, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x50, 0x18,
+    0x20, 0x00, 0x00, 0x00, 0x00, 0x00, 0x05, 0x64, 0x0e, 0xc4, 0x04, 0x00,
+    0x03, 0x00, 0x6d, 0xd3, 0x43, 0xc5, 0x05, 0x0c, 0x01, 0x17, 0x01, 0x02,
+    0x81, 0xa7, 0xda,
+];
+
+const DNP3_DIRECT_OPERATE_LAST_SEGMENT: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x04, 0x05, 0x06, 0x00, 0x0c, 0x29, 0x01, 0x02, 0x03,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x3f, 0x00, 0x01, 0x40, 0x00, 0x40, 0x06,
+    0xb7, 0x49, 0xc0, 0xa8, 0x01, 0x0a, 0xc0, 0xa8, 0x01, 0x14, 0x04, 0x87,
+    0x4e, 0x20, 0x00, 0x00, 0x00, 0x16, 0x00, 0x00, 0x00, 0x01, 0x50, 0x18,
+    0x20, 0x00, 0x00, 0x00, 0x00, 0x00, 0x05, 0x64, 0x10, 0xc4, 0x04, 0x00,
+    0x03, 0x00, 0xa9, 0x5a, 0x84, 0x01, 0xe8, 0x03, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0xe8, 0xd0,
+];
+
+#[test]
+fn detect_dnp3_select_crob() {
+    let packet = QuinPacket::parse_from_stream(DNP3_SELECT_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(packet, QuinPacket::L5(_)));
+
+    let mut dnp3_rule = HmIcsRules::new();
+    assert!(dnp3_rule.load_rules("./tests/ics_rules_dnp3.json"));
+
+    assert_eq!(
+        dnp3_rule.detect(&packet),
+        DetectResultICS::Hit(2, RuleAction::Alert)
+    );
+}
+
+#[test]
+fn detect_dnp3_select_other_point() {
+    let packet = QuinPacket::parse_from_stream(DNP3_SELECT_FRAME, &QuinPacketOptions::default());
+
+    let mut dnp3_rule = HmIcsRules::new();
+    assert!(dnp3_rule.load_rules("./tests/ics_rules_dnp3.json"));
+    dnp3_rule.deactive_rule(2);
+
+    assert_eq!(
+        dnp3_rule.detect(&packet),
+        DetectResultICS::Miss(DetectMiss::Content)
+    );
+}
+
+#[test]
+fn detect_dnp3_direct_operate_trip() {
+    let packet = QuinPacket::parse_from_stream(DNP3_DIRECT_OPERATE_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(packet, QuinPacket::L5(_)));
+
+    let mut dnp3_rule = HmIcsRules::new();
+    assert!(dnp3_rule.load_rules("./tests/ics_rules_dnp3.json"));
+
+    assert_eq!(
+        dnp3_rule.detect(&packet),
+        DetectResultICS::Hit(3, RuleAction::Alert)
+    );
+}
+
+#[test]
+fn detect_dnp3_direct_operate_across_segments() {
+    let mut dnp3_rule = HmIcsRules::new();
+    assert!(dnp3_rule.load_rules("./tests/ics_rules_dnp3.json"));
+
+    // 无状态解析时最后一个传输段不含完整分片
+    let packet = QuinPacket::parse_from_stream(DNP3_DIRECT_OPERATE_LAST_SEGMENT, &QuinPacketOptions::default());
+    assert_eq!(
+        dnp3_rule.detect(&packet),
+        DetectResultICS::Miss(DetectMiss::Content)
+    );
+
+    let mut flow = QuinFlow::new();
+    let packet = QuinPacket::parse_from_flow(DNP3_DIRECT_OPERATE_FIRST_SEGMENT, &QuinPacketOptions::default(), &mut flow);
+    assert_eq!(
+        dnp3_rule.detect(&packet),
+        DetectResultICS::Miss(DetectMiss::Content)
+    );
+    let packet = QuinPacket::parse_from_flow(DNP3_DIRECT_OPERATE_LAST_SEGMENT, &QuinPacketOptions::default(), &mut flow);
+    assert_eq!(
+        dnp3_rule.detect(&packet),
+        DetectResultICS::Hit(3, RuleAction::Alert)
+    );
+}
@@ -0,0 +1,126 @@
+use parsing_icsrule::HmIcsRules;
+use parsing_parser::{QuinPacket, QuinPacketOptions};
+use parsing_rule::{DetectMiss, DetectResultICS, RuleAction, RulesDetectorICS};
+
+// RegisterSession request
+const REGISTER_SESSION_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x04, 0x05, 0x06, 0x00, 0x0c, 0x29, 0x01, 0x02, 0x03,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x44, 0x00, 0x01, 0x40, 0x00, 0x40, 0x06,
+    0xb7, 0x44, 0xc0, 0xa8, 0x01, 0x0a, 0xc0, 0xa8, 0x01, 0x14, 0xc3, 0x50,
+    0xaf, 0x12, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x50, 0x18,
+    0x20, 0x00, 0x00, 0x00, 0x00, 0x00, 0x65, 0x00, 0x04, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x88, 0x77, 0x66, 0x55, 0x44, 0x33,
+    0x22, 0x11, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+];
+
+// SendRRData Forward Open to Connection Manager
+const FORWARD_OPEN_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x04, 0x05, 0x06, 0x00, 0x0c, 0x29, 0x01, 0x02, 0x03,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x80, 0x00, 0x01, 0x40, 0x00, 0x40, 0x06,
+    0xb7, 0x08, 0xc0, 0xa8, 0x01, 0x0a, 0xc0, 0xa8, 0x01, 0x14, 0xc3, 0x50,
+    0xaf, 0x12, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x50, 0x18,
+    0x20, 0x00, 0x00, 0x00, 0x00, 0x00, 0x6f, 0x00, 0x40, 0x00, 0x78, 0x56,
+    0x34, 0x12, 0x00, 0x00, 0x00, 0x00, 0x88, 0x77, 0x66, 0x55, 0x44, 0x33,
+    0x22, 0x11, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0a, 0x00,
+    0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xb2, 0x00, 0x30, 0x00, 0x54, 0x02,
+    0x20, 0x06, 0x24, 0x01, 0x0a, 0x0e, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00,
+    0x00, 0x80, 0x01, 0x01, 0x01, 0x00, 0xef, 0xbe, 0xad, 0xde, 0x03, 0x00,
+    0x00, 0x00, 0x20, 0xa1, 0x07, 0x00, 0xf4, 0x43, 0x20, 0xa1, 0x07, 0x00,
+    0xf4, 0x43, 0xa3, 0x03, 0x01, 0x00, 0x20, 0x02, 0x24, 0x01,
+];
+
+// SendUnitData Multiple Service Packet: Read Tag Motor.Speed, Write Tag Valve[3]
+const MULTIPLE_SERVICE_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x04, 0x05, 0x06, 0x00, 0x0c, 0x29, 0x01, 0x02, 0x03,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x8a, 0x00, 0x01, 0x40, 0x00, 0x40, 0x06,
+    0xb6, 0xfe, 0xc0, 0xa8, 0x01, 0x0a, 0xc0, 0xa8, 0x01, 0x14, 0xc3, 0x50,
+    0xaf, 0x12, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x50, 0x18,
+    0x20, 0x00, 0x00, 0x00, 0x00, 0x00, 0x70, 0x00, 0x4a, 0x00, 0x78, 0x56,
+    0x34, 0x12, 0x00, 0x00, 0x00, 0x00, 0x88, 0x77, 0x66, 0x55, 0x44, 0x33,
+    0x22, 0x11, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0a, 0x00,
+    0x02, 0x00, 0xa1, 0x00, 0x04, 0x00, 0x44, 0x33, 0x22, 0x11, 0xb1, 0x00,
+    0x36, 0x00, 0x01, 0x00, 0x0a, 0x02, 0x20, 0x02, 0x24, 0x01, 0x02, 0x00,
+    0x06, 0x00, 0x1a, 0x00, 0x4c, 0x08, 0x91, 0x05, 0x4d, 0x6f, 0x74, 0x6f,
+    0x72, 0x00, 0x91, 0x05, 0x53, 0x70, 0x65, 0x65, 0x64, 0x00, 0x01, 0x00,
+    0x4d, 0x05, 0x91, 0x05, 0x56, 0x61, 0x6c, 0x76, 0x65, 0x00, 0x28, 0x03,
+    0xc4, 0x00, 0x01, 0x00, 0x2a, 0x00, 0x00, 0x00,
+];
+
+// Unconnected Send wrapping Read Tag Program:Main.Counter
+const UNCONNECTED_SEND_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x04, 0x05, 0x06, 0x00, 0x0c, 0x29, 0x01, 0x02, 0x03,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x7a, 0x00, 0x01, 0x40, 0x00, 0x40, 0x06,
+    0xb7, 0x0e, 0xc0, 0xa8, 0x01, 0x0a, 0xc0, 0xa8, 0x01, 0x14, 0xc3, 0x50,
+    0xaf, 0x12, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x50, 0x18,
+    0x20, 0x00, 0x00, 0x00, 0x00, 0x00, 0x6f, 0x00, 0x3a, 0x00, 0x78, 0x56,
+    0x34, 0x12, 0x00, 0x00, 0x00, 0x00, 0x88, 0x77, 0x66, 0x55, 0x44, 0x33,
+    0x22, 0x11, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0a, 0x00,
+    0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xb2, 0x00, 0x2a, 0x00, 0x52, 0x02,
+    0x20, 0x06, 0x24, 0x01, 0x0a, 0x0e, 0x1c, 0x00, 0x4c, 0x0c, 0x91, 0x0c,
+    0x50, 0x72, 0x6f, 0x67, 0x72, 0x61, 0x6d, 0x3a, 0x4d, 0x61, 0x69, 0x6e,
+    0x91, 0x07, 0x43, 0x6f, 0x75, 0x6e, 0x74, 0x65, 0x72, 0x00, 0x01, 0x00,
+    0x01, 0x00, 0x01, 0x00,
+];
+
+// Read Tag response, general status 0x04 (path segment error)
+const READ_TAG_ERROR_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x01, 0x02, 0x03, 0x00, 0x0c, 0x29, 0x04, 0x05, 0x06,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x54, 0x00, 0x01, 0x40, 0x00, 0x40, 0x06,
+    0xb7, 0x34, 0xc0, 0xa8, 0x01, 0x14, 0xc0, 0xa8, 0x01, 0x0a, 0xaf, 0x12,
+    0xc3, 0x50, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x50, 0x18,
+    0x20, 0x00, 0x00, 0x00, 0x00, 0x00, 0x6f, 0x00, 0x14, 0x00, 0x78, 0x56,
+    0x34, 0x12, 0x00, 0x00, 0x00, 0x00, 0x88, 0x77, 0x66, 0x55, 0x44, 0x33,
+    0x22, 0x11, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0a, 0x00,
+    0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xb2, 0x00, 0x04, 0x00, 0xcc, 0x00,
+    0x04, 0x00,
+];
+
+// UDP ListIdentity request
+const LIST_IDENTITY_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x04, 0x05, 0x06, 0x00, 0x0c, 0x29, 0x01, 0x02, 0x03,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x34, 0x00, 0x01, 0x40, 0x00, 0x40, 0x11,
+    0xb6, 0x5e, 0xc0, 0xa8, 0x01, 0x0a, 0xc0, 0xa8, 0x01, 0xff, 0xc3, 0x51,
+    0xaf, 0x12, 0x00, 0x20, 0x00, 0x00, 0x63, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x88, 0x77, 0x66, 0x55, 0x44, 0x33,
+    0x22, 0x11, 0x00, 0x00, 0x00, 0x00,
+];
+
+#[test]
+fn detect_enip_command_and_service() {
+    let mut enip_rule = HmIcsRules::new();
+    assert!(enip_rule.load_rules("./tests/ics_rules_enip.json"));
+
+    let packet = QuinPacket::parse_from_stream(REGISTER_SESSION_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(packet, QuinPacket::L5(_)));
+    assert_eq!(enip_rule.detect(&packet), DetectResultICS::Hit(4, RuleAction::Pass));
+
+    let packet = QuinPacket::parse_from_stream(FORWARD_OPEN_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(packet, QuinPacket::L5(_)));
+    assert_eq!(enip_rule.detect(&packet), DetectResultICS::Hit(1, RuleAction::Alert));
+
+    let packet = QuinPacket::parse_from_stream(READ_TAG_ERROR_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(packet, QuinPacket::L5(_)));
+    assert_eq!(enip_rule.detect(&packet), DetectResultICS::Hit(3, RuleAction::Alert));
+
+    let packet = QuinPacket::parse_from_stream(LIST_IDENTITY_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(packet, QuinPacket::L5(_)));
+    assert_eq!(enip_rule.detect(&packet), DetectResultICS::Miss(DetectMiss::Content));
+}
+
+#[test]
+fn detect_enip_embedded_tag() {
+    let mut enip_rule = HmIcsRules::new();
+    assert!(enip_rule.load_rules("./tests/ics_rules_enip.json"));
+
+    // Multiple Service Packet、Unconnected Send 中内嵌的标签服务
+    let multiple_service = QuinPacket::parse_from_stream(MULTIPLE_SERVICE_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(multiple_service, QuinPacket::L5(_)));
+    assert_eq!(enip_rule.detect(&multiple_service), DetectResultICS::Hit(2, RuleAction::Drop));
+
+    let packet = QuinPacket::parse_from_stream(UNCONNECTED_SEND_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(packet, QuinPacket::L5(_)));
+    assert_eq!(enip_rule.detect(&packet), DetectResultICS::Hit(5, RuleAction::Alert));
+
+    enip_rule.deactive_rule(2);
+    assert_eq!(enip_rule.detect(&multiple_service), DetectResultICS::Miss(DetectMiss::Content));
+}
@@ -0,0 +1,129 @@
+use parsing_icsrule::HmIcsRules;
+use parsing_parser::{QuinPacket, QuinPacketOptions};
+use parsing_rule::{DetectMiss, DetectResultICS, RuleAction, RulesDetectorICS};
+
+// FINS/TCP Memory Area Read DM(0x82) 100, 2 words, SID 0x11
+const FINS_TCP_READ_COMMAND_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x04, 0x05, 0x06, 0x00, 0x0c, 0x29, 0x01, 0x02, 0x03,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x4a, 0x00, 0x01, 0x40, 0x00, 0x40, 0x06,
+    0xb7, 0x3e, 0xc0, 0xa8, 0x01, 0x0a, 0xc0, 0xa8, 0x01, 0x14, 0xc3, 0x50,
+    0x25, 0x80, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x50, 0x18,
+    0x20, 0x00, 0x00, 0x00, 0x00, 0x00, 0x46, 0x49, 0x4e, 0x53, 0x00, 0x00,
+    0x00, 0x1a, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x80, 0x00,
+    0x02, 0x00, 0x14, 0x00, 0x00, 0x0a, 0x00, 0x11, 0x01, 0x01, 0x82, 0x00,
+    0x64, 0x00, 0x00, 0x02,
+];
+
+// FINS/TCP Memory Area Read response, normal completion
+const FINS_TCP_READ_RESPONSE_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x01, 0x02, 0x03, 0x00, 0x0c, 0x29, 0x04, 0x05, 0x06,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x4a, 0x00, 0x01, 0x40, 0x00, 0x40, 0x06,
+    0xb7, 0x3e, 0xc0, 0xa8, 0x01, 0x14, 0xc0, 0xa8, 0x01, 0x0a, 0x25, 0x80,
+    0xc3, 0x50, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x50, 0x18,
+    0x20, 0x00, 0x00, 0x00, 0x00, 0x00, 0x46, 0x49, 0x4e, 0x53, 0x00, 0x00,
+    0x00, 0x1a, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0xc0, 0x00,
+    0x02, 0x00, 0x0a, 0x00, 0x00, 0x14, 0x00, 0x11, 0x01, 0x01, 0x00, 0x00,
+    0x12, 0x34, 0x56, 0x78,
+];
+
+// FINS/UDP Memory Area Write DM(0x82) 200, 1 word, SID 0x12
+const FINS_UDP_WRITE_COMMAND_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x04, 0x05, 0x06, 0x00, 0x0c, 0x29, 0x01, 0x02, 0x03,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x30, 0x00, 0x01, 0x40, 0x00, 0x40, 0x11,
+    0xb7, 0x4d, 0xc0, 0xa8, 0x01, 0x0a, 0xc0, 0xa8, 0x01, 0x14, 0xc3, 0x51,
+    0x25, 0x80, 0x00, 0x1c, 0x00, 0x00, 0x80, 0x00, 0x02, 0x00, 0x14, 0x00,
+    0x00, 0x0a, 0x00, 0x12, 0x01, 0x02, 0x82, 0x00, 0xc8, 0x00, 0x00, 0x01,
+    0x00, 0x2a,
+];
+
+// FINS/UDP Memory Area Write response, end code 0x2108 (write protected)
+const FINS_UDP_WRITE_RESPONSE_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x01, 0x02, 0x03, 0x00, 0x0c, 0x29, 0x04, 0x05, 0x06,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x2a, 0x00, 0x01, 0x40, 0x00, 0x40, 0x11,
+    0xb7, 0x53, 0xc0, 0xa8, 0x01, 0x14, 0xc0, 0xa8, 0x01, 0x0a, 0x25, 0x80,
+    0xc3, 0x51, 0x00, 0x16, 0x00, 0x00, 0xc0, 0x00, 0x02, 0x00, 0x0a, 0x00,
+    0x00, 0x14, 0x00, 0x12, 0x01, 0x02, 0x21, 0x08,
+];
+
+// FINS/UDP Parameter Area Read 0x8010, SID 0x13
+const FINS_UDP_PARAMETER_READ_COMMAND_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x04, 0x05, 0x06, 0x00, 0x0c, 0x29, 0x01, 0x02, 0x03,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x2e, 0x00, 0x01, 0x40, 0x00, 0x40, 0x11,
+    0xb7, 0x4f, 0xc0, 0xa8, 0x01, 0x0a, 0xc0, 0xa8, 0x01, 0x14, 0xc3, 0x51,
+    0x25, 0x80, 0x00, 0x1a, 0x00, 0x00, 0x80, 0x00, 0x02, 0x00, 0x14, 0x00,
+    0x00, 0x0a, 0x00, 0x13, 0x02, 0x01, 0x80, 0x10, 0x00, 0x00, 0x00, 0x10,
+];
+
+// FINS/UDP Parameter Area Read response, end code 0x1101 without data
+const FINS_UDP_PARAMETER_READ_RESPONSE_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x01, 0x02, 0x03, 0x00, 0x0c, 0x29, 0x04, 0x05, 0x06,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x2a, 0x00, 0x01, 0x40, 0x00, 0x40, 0x11,
+    0xb7, 0x53, 0xc0, 0xa8, 0x01, 0x14, 0xc0, 0xa8, 0x01, 0x0a, 0x25, 0x80,
+    0xc3, 0x51, 0x00, 0x16, 0x00, 0x00, 0xc0, 0x00, 0x02, 0x00, 0x0a, 0x00,
+    0x00, 0x14, 0x00, 0x13, 0x02, 0x01, 0x11, 0x01,
+];
+
+#[test]
+fn detect_fins_tcp_command() {
+    let mut fins_rule = HmIcsRules::new();
+    assert!(fins_rule.load_rules("./tests/ics_rules_fins.json"));
+
+    let packet = QuinPacket::parse_from_stream(FINS_TCP_READ_COMMAND_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(packet, QuinPacket::L5(_)));
+    assert_eq!(fins_rule.detect(&packet), DetectResultICS::Hit(4, RuleAction::Alert));
+
+    let packet = QuinPacket::parse_from_stream(FINS_TCP_READ_RESPONSE_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(packet, QuinPacket::L5(_)));
+    assert_eq!(fins_rule.detect(&packet), DetectResultICS::Hit(5, RuleAction::Pass));
+}
+
+#[test]
+fn detect_fins_udp_write_and_end_code() {
+    let mut fins_rule = HmIcsRules::new();
+    assert!(fins_rule.load_rules("./tests/ics_rules_fins.json"));
+
+    let command = QuinPacket::parse_from_stream(FINS_UDP_WRITE_COMMAND_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(command, QuinPacket::L5(_)));
+    assert_eq!(fins_rule.detect(&command), DetectResultICS::Hit(1, RuleAction::Drop));
+    // 响应的区域条件取自 SID 关联到的写请求
+    let packet = QuinPacket::parse_from_stream(FINS_UDP_WRITE_RESPONSE_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(packet, QuinPacket::L5(_)));
+    assert_eq!(fins_rule.detect(&packet), DetectResultICS::Hit(2, RuleAction::Alert));
+
+    fins_rule.deactive_rule(1);
+    assert_eq!(fins_rule.detect(&command), DetectResultICS::Miss(DetectMiss::Content));
+}
+
+#[test]
+fn detect_fins_response_without_request() {
+    let mut fins_rule = HmIcsRules::new();
+    assert!(fins_rule.load_rules("./tests/ics_rules_fins.json"));
+
+    // 尚未见到请求时无法匹配区域条件
+    let response = QuinPacket::parse_from_stream(FINS_UDP_PARAMETER_READ_RESPONSE_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(response, QuinPacket::L5(_)));
+    assert_eq!(fins_rule.detect(&response), DetectResultICS::Miss(DetectMiss::Content));
+
+    let packet = QuinPacket::parse_from_stream(FINS_UDP_PARAMETER_READ_COMMAND_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(packet, QuinPacket::L5(_)));
+    assert_eq!(fins_rule.detect(&packet), DetectResultICS::Miss(DetectMiss::Content));
+    assert_eq!(fins_rule.detect(&response), DetectResultICS::Hit(3, RuleAction::Alert));
+}
+
+#[test]
+fn detect_fins_transactions_per_rule_set() {
+    let mut fins_rule = HmIcsRules::new();
+    assert!(fins_rule.load_rules("./tests/ics_rules_fins.json"));
+    let mut other_rule = HmIcsRules::new();
+    assert!(other_rule.load_rules("./tests/ics_rules_fins.json"));
+
+    // 请求与响应的关联只在同一规则集合内有效
+    let command = QuinPacket::parse_from_stream(FINS_UDP_PARAMETER_READ_COMMAND_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(command, QuinPacket::L5(_)));
+    assert_eq!(fins_rule.detect(&command), DetectResultICS::Miss(DetectMiss::Content));
+
+    let response = QuinPacket::parse_from_stream(FINS_UDP_PARAMETER_READ_RESPONSE_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(response, QuinPacket::L5(_)));
+    assert_eq!(other_rule.detect(&response), DetectResultICS::Miss(DetectMiss::Content));
+    assert_eq!(fins_rule.detect(&response), DetectResultICS::Hit(3, RuleAction::Alert));
+}
@@ -0,0 +1,50 @@
+use parsing_icsrule::HmIcsRules;
+use parsing_parser::{QuinPacket, QuinPacketOptions};
+use parsing_rule::{DetectMiss, DetectResultICS, RuleAction, RulesDetectorICS};
+
+// GOOSE: 00:a0:f4:08:2f:77 -> 01:a0:f4:08:2f:77, APPID 1
+const GOOSE_FRAME: &[u8] = &[
+    0x01, 0xa0, 0xf4, 0x08, 0x2f, 0x77, 0x00, 0xa0, 0xf4, 0x08, 0x2f, 0x77,
+    0x88, 0xb8, 0x00, 0x01, 0x00, 0x91, 0x00, 0x00, 0x00, 0x00, 0x61, 0x81,
+    0x86, 0x80, 0x1a, 0x47, 0x45, 0x44, 0x65, 0x76, 0x69, 0x63, 0x65, 0x46,
+    0x36, 0x35, 0x30, 0x2f, 0x4c, 0x4c, 0x4e, 0x30, 0x24, 0x47, 0x4f, 0x24,
+    0x67, 0x63, 0x62, 0x30, 0x31, 0x81, 0x03, 0x00, 0x9c, 0x40, 0x82, 0x18,
+    0x47, 0x45, 0x44, 0x65, 0x76, 0x69, 0x63, 0x65, 0x46, 0x36, 0x35, 0x30,
+    0x2f, 0x4c, 0x4c, 0x4e, 0x30, 0x24, 0x47, 0x4f, 0x4f, 0x53, 0x45, 0x31,
+    0x83, 0x0b, 0x46, 0x36, 0x35, 0x30, 0x5f, 0x47, 0x4f, 0x4f, 0x53, 0x45,
+    0x31, 0x84, 0x08, 0x38, 0x6e, 0xbb, 0xf3, 0x42, 0x17, 0x28, 0x0a, 0x85,
+    0x01, 0x01, 0x86, 0x01, 0x0a, 0x87, 0x01, 0x00, 0x88, 0x01, 0x01, 0x89,
+    0x01, 0x00, 0x8a, 0x01, 0x08, 0xab, 0x20, 0x83, 0x01, 0x00, 0x84, 0x03,
+    0x03, 0x00, 0x00, 0x83, 0x01, 0x00, 0x84, 0x03, 0x03, 0x00, 0x00, 0x83,
+    0x01, 0x00, 0x84, 0x03, 0x03, 0x00, 0x00, 0x83, 0x01, 0x00, 0x84, 0x03,
+    0x03, 0x00, 0x00,
+];
+
+#[test]
+fn detect_goose_with_mac() {
+    let packet = QuinPacket::parse_from_stream(GOOSE_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(packet, QuinPacket::L5(_)));
+
+    let mut goose_rule = HmIcsRules::new();
+    assert!(goose_rule.load_rules("./tests/ics_rules_goose.json"));
+
+    assert_eq!(
+        goose_rule.detect(&packet),
+        DetectResultICS::Hit(1, RuleAction::Alert)
+    );
+}
+
+#[test]
+fn detect_goose_with_mismatched_mac() {
+    let mut frame = GOOSE_FRAME.to_vec();
+    frame[11] = 0x78;
+    let packet = QuinPacket::parse_from_stream(&frame, &QuinPacketOptions::default());
+
+    let mut goose_rule = HmIcsRules::new();
+    assert!(goose_rule.load_rules("./tests/ics_rules_goose.json"));
+
+    assert_eq!(
+        goose_rule.detect(&packet),
+        DetectResultICS::Miss(DetectMiss::Behavior)
+    );
+}
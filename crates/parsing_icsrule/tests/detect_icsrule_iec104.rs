@@ -0,0 +1,66 @@
+use parsing_icsrule::HmIcsRules;
+use parsing_parser::{QuinPacket, QuinPacketOptions};
+use parsing_rule::{DetectMiss, DetectResultICS, RuleAction, RulesDetectorICS};
+
+// IEC104 C_SE_NC_1 (50): COT 6, CA 1, IOA 4097, value 42.5
+const IEC104_SETPOINT_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x04, 0x05, 0x06, 0x00, 0x0c, 0x29, 0x01, 0x02, 0x03,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x3c, 0x00, 0x01, 0x40, 0x00, 0x40, 0x06,
+    0xb7, 0x4c, 0xc0, 0xa8, 0x01, 0x0a, 0xc0, 0xa8, 0x01, 0x14, 0x9c, 0x41,
+    0x09, 0x64, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x50, 0x18,
+    0x20, 0x00, 0x00, 0x00, 0x00, 0x00, 0x68, 0x12, 0x02, 0x00, 0x00, 0x00,
+    0x32, 0x01, 0x06, 0x00, 0x01, 0x00, 0x01, 0x10, 0x00, 0x00, 0x00, 0x2a,
+    0x42, 0x00,
+];
+
+// IEC104 C_SC_NA_1 (45): COT 6, CA 1, IOA 11, SCO 0x81 (select, on)
+const IEC104_SINGLE_COMMAND_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x04, 0x05, 0x06, 0x00, 0x0c, 0x29, 0x01, 0x02, 0x03,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x38, 0x00, 0x01, 0x40, 0x00, 0x40, 0x06,
+    0xb7, 0x50, 0xc0, 0xa8, 0x01, 0x0a, 0xc0, 0xa8, 0x01, 0x14, 0x9c, 0x41,
+    0x09, 0x64, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x50, 0x18,
+    0x20, 0x00, 0x00, 0x00, 0x00, 0x00, 0x68, 0x0e, 0x02, 0x00, 0x00, 0x00,
+    0x2d, 0x01, 0x06, 0x00, 0x01, 0x00, 0x0b, 0x00, 0x00, 0x81,
+];
+
+#[test]
+fn detect_iec104_setpoint_in_range() {
+    let packet = QuinPacket::parse_from_stream(IEC104_SETPOINT_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(packet, QuinPacket::L5(_)));
+
+    let mut iec104_rule = HmIcsRules::new();
+    assert!(iec104_rule.load_rules("./tests/ics_rules_iec104.json"));
+
+    assert_eq!(
+        iec104_rule.detect(&packet),
+        DetectResultICS::Hit(1, RuleAction::Pass)
+    );
+}
+
+#[test]
+fn detect_iec104_setpoint_out_of_range() {
+    let packet = QuinPacket::parse_from_stream(IEC104_SETPOINT_FRAME, &QuinPacketOptions::default());
+
+    let mut iec104_rule = HmIcsRules::new();
+    assert!(iec104_rule.load_rules("./tests/ics_rules_iec104.json"));
+    iec104_rule.deactive_rule(1);
+
+    assert_eq!(
+        iec104_rule.detect(&packet),
+        DetectResultICS::Miss(DetectMiss::Content)
+    );
+}
+
+#[test]
+fn detect_iec104_single_command() {
+    let packet = QuinPacket::parse_from_stream(IEC104_SINGLE_COMMAND_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(packet, QuinPacket::L5(_)));
+
+    let mut iec104_rule = HmIcsRules::new();
+    assert!(iec104_rule.load_rules("./tests/ics_rules_iec104.json"));
+
+    assert_eq!(
+        iec104_rule.detect(&packet),
+        DetectResultICS::Hit(2, RuleAction::Alert)
+    );
+}
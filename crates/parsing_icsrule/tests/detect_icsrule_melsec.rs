@@ -0,0 +1,136 @@
+use parsing_icsrule::HmIcsRules;
+use parsing_parser::{QuinPacket, QuinPacketOptions};
+use parsing_rule::{DetectMiss, DetectResultICS, RuleAction, RulesDetectorICS};
+
+// 3E batch read D100, 10 words
+const BATCH_READ_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x04, 0x05, 0x06, 0x00, 0x0c, 0x29, 0x01, 0x02, 0x03,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x3d, 0x00, 0x01, 0x40, 0x00, 0x40, 0x06,
+    0xb3, 0x38, 0xc0, 0xa8, 0x03, 0x0a, 0xc0, 0xa8, 0x03, 0x27, 0xc3, 0x5a,
+    0x13, 0x8f, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x50, 0x18,
+    0x20, 0x00, 0x00, 0x00, 0x00, 0x00, 0x50, 0x00, 0x00, 0xff, 0xff, 0x03,
+    0x00, 0x0c, 0x00, 0x10, 0x00, 0x01, 0x04, 0x00, 0x00, 0x64, 0x00, 0x00,
+    0xa8, 0x0a, 0x00,
+];
+
+// 3E batch write D250-D251
+const BATCH_WRITE_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x04, 0x05, 0x06, 0x00, 0x0c, 0x29, 0x01, 0x02, 0x03,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x41, 0x00, 0x01, 0x40, 0x00, 0x40, 0x06,
+    0xb3, 0x34, 0xc0, 0xa8, 0x03, 0x0a, 0xc0, 0xa8, 0x03, 0x27, 0xc3, 0x5a,
+    0x13, 0x8f, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x50, 0x18,
+    0x20, 0x00, 0x00, 0x00, 0x00, 0x00, 0x50, 0x00, 0x00, 0xff, 0xff, 0x03,
+    0x00, 0x10, 0x00, 0x10, 0x00, 0x01, 0x14, 0x00, 0x00, 0xfa, 0x00, 0x00,
+    0xa8, 0x02, 0x00, 0x10, 0x00, 0x20, 0x00,
+];
+
+// 4E random write D200, D201 and dword D300 over UDP
+const RANDOM_WRITE_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x04, 0x05, 0x06, 0x00, 0x0c, 0x29, 0x01, 0x02, 0x03,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x45, 0x00, 0x01, 0x40, 0x00, 0x40, 0x11,
+    0xb3, 0x25, 0xc0, 0xa8, 0x03, 0x0a, 0xc0, 0xa8, 0x03, 0x27, 0xc3, 0x5b,
+    0x13, 0x8e, 0x00, 0x31, 0x00, 0x00, 0x54, 0x00, 0x34, 0x12, 0x00, 0x00,
+    0x00, 0xff, 0xff, 0x03, 0x00, 0x1c, 0x00, 0x10, 0x00, 0x02, 0x14, 0x00,
+    0x00, 0x02, 0x01, 0xc8, 0x00, 0x00, 0xa8, 0x01, 0x00, 0xc9, 0x00, 0x00,
+    0xa8, 0x02, 0x00, 0x2c, 0x01, 0x00, 0xa8, 0x03, 0x00, 0x04, 0x00,
+];
+
+// 4E response, end code 0xC059
+const ERROR_RESPONSE_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x01, 0x02, 0x03, 0x00, 0x0c, 0x29, 0x04, 0x05, 0x06,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x34, 0x00, 0x01, 0x40, 0x00, 0x40, 0x11,
+    0xb3, 0x36, 0xc0, 0xa8, 0x03, 0x27, 0xc0, 0xa8, 0x03, 0x0a, 0x13, 0x8e,
+    0xc3, 0x5b, 0x00, 0x20, 0x00, 0x00, 0xd4, 0x00, 0x34, 0x12, 0x00, 0x00,
+    0x00, 0xff, 0xff, 0x03, 0x00, 0x0b, 0x00, 0x59, 0xc0, 0x00, 0xff, 0xff,
+    0x03, 0x00, 0x02, 0x14, 0x00, 0x00,
+];
+
+// 3E remote RUN (forced)
+const REMOTE_RUN_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x04, 0x05, 0x06, 0x00, 0x0c, 0x29, 0x01, 0x02, 0x03,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x3b, 0x00, 0x01, 0x40, 0x00, 0x40, 0x06,
+    0xb3, 0x3a, 0xc0, 0xa8, 0x03, 0x0a, 0xc0, 0xa8, 0x03, 0x27, 0xc3, 0x5a,
+    0x13, 0x8f, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x50, 0x18,
+    0x20, 0x00, 0x00, 0x00, 0x00, 0x00, 0x50, 0x00, 0x00, 0xff, 0xff, 0x03,
+    0x00, 0x0a, 0x00, 0x10, 0x00, 0x01, 0x10, 0x00, 0x00, 0x03, 0x00, 0x00,
+    0x00,
+];
+
+// 3E remote STOP
+const REMOTE_STOP_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x04, 0x05, 0x06, 0x00, 0x0c, 0x29, 0x01, 0x02, 0x03,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x39, 0x00, 0x01, 0x40, 0x00, 0x40, 0x06,
+    0xb3, 0x3c, 0xc0, 0xa8, 0x03, 0x0a, 0xc0, 0xa8, 0x03, 0x27, 0xc3, 0x5a,
+    0x13, 0x8f, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x50, 0x18,
+    0x20, 0x00, 0x00, 0x00, 0x00, 0x00, 0x50, 0x00, 0x00, 0xff, 0xff, 0x03,
+    0x00, 0x08, 0x00, 0x10, 0x00, 0x02, 0x10, 0x00, 0x00, 0x01, 0x00,
+];
+
+// 3E remote RESET
+const REMOTE_RESET_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x04, 0x05, 0x06, 0x00, 0x0c, 0x29, 0x01, 0x02, 0x03,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x39, 0x00, 0x01, 0x40, 0x00, 0x40, 0x06,
+    0xb3, 0x3c, 0xc0, 0xa8, 0x03, 0x0a, 0xc0, 0xa8, 0x03, 0x27, 0xc3, 0x5a,
+    0x13, 0x8f, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x50, 0x18,
+    0x20, 0x00, 0x00, 0x00, 0x00, 0x00, 0x50, 0x00, 0x00, 0xff, 0xff, 0x03,
+    0x00, 0x08, 0x00, 0x10, 0x00, 0x06, 0x10, 0x00, 0x00, 0x01, 0x00,
+];
+
+// 3E multiple block batch write D500-D502 and M16
+const BLOCK_WRITE_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x04, 0x05, 0x06, 0x00, 0x0c, 0x29, 0x01, 0x02, 0x03,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x4d, 0x00, 0x01, 0x40, 0x00, 0x40, 0x06,
+    0xb3, 0x28, 0xc0, 0xa8, 0x03, 0x0a, 0xc0, 0xa8, 0x03, 0x27, 0xc3, 0x5a,
+    0x13, 0x8f, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x50, 0x18,
+    0x20, 0x00, 0x00, 0x00, 0x00, 0x00, 0x50, 0x00, 0x00, 0xff, 0xff, 0x03,
+    0x00, 0x1c, 0x00, 0x10, 0x00, 0x06, 0x14, 0x00, 0x00, 0x01, 0x01, 0xf4,
+    0x01, 0x00, 0xa8, 0x03, 0x00, 0x01, 0x00, 0x02, 0x00, 0x03, 0x00, 0x10,
+    0x00, 0x00, 0x90, 0x01, 0x00, 0x05, 0x00,
+];
+
+#[test]
+fn detect_melsec_command() {
+    let mut melsec_rule = HmIcsRules::new();
+    assert!(melsec_rule.load_rules("./tests/ics_rules_melsec.json"));
+
+    let remote_stop = QuinPacket::parse_from_stream(REMOTE_STOP_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(remote_stop, QuinPacket::L5(_)));
+    assert_eq!(melsec_rule.detect(&remote_stop), DetectResultICS::Hit(1, RuleAction::Drop));
+
+    let packet = QuinPacket::parse_from_stream(REMOTE_RESET_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(packet, QuinPacket::L5(_)));
+    assert_eq!(melsec_rule.detect(&packet), DetectResultICS::Hit(1, RuleAction::Drop));
+
+    let packet = QuinPacket::parse_from_stream(REMOTE_RUN_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(packet, QuinPacket::L5(_)));
+    assert_eq!(melsec_rule.detect(&packet), DetectResultICS::Miss(DetectMiss::Content));
+
+    let packet = QuinPacket::parse_from_stream(BATCH_READ_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(packet, QuinPacket::L5(_)));
+    assert_eq!(melsec_rule.detect(&packet), DetectResultICS::Hit(4, RuleAction::Pass));
+
+    let packet = QuinPacket::parse_from_stream(ERROR_RESPONSE_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(packet, QuinPacket::L5(_)));
+    assert_eq!(melsec_rule.detect(&packet), DetectResultICS::Hit(3, RuleAction::Alert));
+
+    melsec_rule.deactive_rule(1);
+    assert_eq!(melsec_rule.detect(&remote_stop), DetectResultICS::Miss(DetectMiss::Content));
+}
+
+#[test]
+fn detect_melsec_device_range() {
+    let mut melsec_rule = HmIcsRules::new();
+    assert!(melsec_rule.load_rules("./tests/ics_rules_melsec.json"));
+
+    let packet = QuinPacket::parse_from_stream(BATCH_WRITE_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(packet, QuinPacket::L5(_)));
+    assert_eq!(melsec_rule.detect(&packet), DetectResultICS::Hit(2, RuleAction::Alert));
+    // D300 超出规则范围
+    let packet = QuinPacket::parse_from_stream(RANDOM_WRITE_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(packet, QuinPacket::L5(_)));
+    assert_eq!(melsec_rule.detect(&packet), DetectResultICS::Miss(DetectMiss::Content));
+
+    let packet = QuinPacket::parse_from_stream(BLOCK_WRITE_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(packet, QuinPacket::L5(_)));
+    assert_eq!(melsec_rule.detect(&packet), DetectResultICS::Miss(DetectMiss::Content));
+}
@@ -0,0 +1,73 @@
+use parsing_icsrule::HmIcsRules;
+use parsing_parser::{QuinPacket, QuinPacketOptions};
+use parsing_rule::{DetectMiss, DetectResultICS, RuleAction, RulesDetectorICS};
+
+// MMS Write: IED1LD0/CSWI1$CO$Pos$Oper = true
+const MMS_OPERATE_WRITE_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x04, 0x05, 0x06, 0x00, 0x0c, 0x29, 0x01, 0x02, 0x03,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x6d, 0x00, 0x01, 0x40, 0x00, 0x40, 0x06,
+    0xb7, 0x1b, 0xc0, 0xa8, 0x01, 0x0a, 0xc0, 0xa8, 0x01, 0x14, 0xc0, 0xa8,
+    0x00, 0x66, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x50, 0x18,
+    0x20, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x45, 0x02, 0xf0,
+    0x80, 0x01, 0x00, 0x01, 0x00, 0x61, 0x38, 0x30, 0x36, 0x02, 0x01, 0x03,
+    0xa0, 0x31, 0xa0, 0x2f, 0x02, 0x02, 0x00, 0x2a, 0xa5, 0x29, 0xa0, 0x22,
+    0x30, 0x20, 0xa0, 0x1e, 0xa1, 0x1c, 0x1a, 0x07, 0x49, 0x45, 0x44, 0x31,
+    0x4c, 0x44, 0x30, 0x1a, 0x11, 0x43, 0x53, 0x57, 0x49, 0x31, 0x24, 0x43,
+    0x4f, 0x24, 0x50, 0x6f, 0x73, 0x24, 0x4f, 0x70, 0x65, 0x72, 0xa0, 0x03,
+    0x83, 0x01, 0x01,
+];
+
+// MMS FileDelete: COMTRADE/FLT001.cfg
+const MMS_FILE_DELETE_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x04, 0x05, 0x06, 0x00, 0x0c, 0x29, 0x01, 0x02, 0x03,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x5b, 0x00, 0x01, 0x40, 0x00, 0x40, 0x06,
+    0xb7, 0x2d, 0xc0, 0xa8, 0x01, 0x0a, 0xc0, 0xa8, 0x01, 0x14, 0xc0, 0xa8,
+    0x00, 0x66, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x50, 0x18,
+    0x20, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x33, 0x02, 0xf0,
+    0x80, 0x01, 0x00, 0x01, 0x00, 0x61, 0x26, 0x30, 0x24, 0x02, 0x01, 0x03,
+    0xa0, 0x1f, 0xa0, 0x1d, 0x02, 0x02, 0x00, 0x2a, 0xbf, 0x4c, 0x16, 0x19,
+    0x08, 0x43, 0x4f, 0x4d, 0x54, 0x52, 0x41, 0x44, 0x45, 0x19, 0x0a, 0x46,
+    0x4c, 0x54, 0x30, 0x30, 0x31, 0x2e, 0x63, 0x66, 0x67,
+];
+
+#[test]
+fn detect_mms_operate_write() {
+    let packet = QuinPacket::parse_from_stream(MMS_OPERATE_WRITE_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(packet, QuinPacket::L5(_)));
+
+    let mut mms_rule = HmIcsRules::new();
+    assert!(mms_rule.load_rules("./tests/ics_rules_mms.json"));
+
+    assert_eq!(
+        mms_rule.detect(&packet),
+        DetectResultICS::Hit(1, RuleAction::Alert)
+    );
+}
+
+#[test]
+fn detect_mms_item_mismatch() {
+    let packet = QuinPacket::parse_from_stream(MMS_OPERATE_WRITE_FRAME, &QuinPacketOptions::default());
+
+    let mut mms_rule = HmIcsRules::new();
+    assert!(mms_rule.load_rules("./tests/ics_rules_mms.json"));
+    mms_rule.deactive_rule(1);
+
+    assert_eq!(
+        mms_rule.detect(&packet),
+        DetectResultICS::Miss(DetectMiss::Content)
+    );
+}
+
+#[test]
+fn detect_mms_file_delete() {
+    let packet = QuinPacket::parse_from_stream(MMS_FILE_DELETE_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(packet, QuinPacket::L5(_)));
+
+    let mut mms_rule = HmIcsRules::new();
+    assert!(mms_rule.load_rules("./tests/ics_rules_mms.json"));
+
+    assert_eq!(
+        mms_rule.detect(&packet),
+        DetectResultICS::Hit(2, RuleAction::Drop)
+    );
+}
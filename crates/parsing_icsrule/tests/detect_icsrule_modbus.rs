@@ -5,7 +5,7 @@ use parsing_parser::{
     parsers::{EthernetHeader, Ipv4Header, ModbusReqHeader, TcpHeader},
     ApplicationLayer, L5Packet, LinkLayer, MacAddress, NetworkLayer, QuinPacket, TransportLayer,
 };
-use parsing_rule::{RuleAction, RulesDetectorICS, DetectResultICS};
+use parsing_rule::{DetectMiss, DetectResultICS, RuleAction, RulesDetectorICS};
 
 fn make_modbus_req_packet(req_pdu: parsing_parser::parsers::modbus_req::PDU) -> QuinPacket {
     let app_layer = ApplicationLayer::ModbusReq(ModbusReqHeader {
@@ -398,3 +398,66 @@ fn detect_modbus_read_fifo_queue() {
         DetectResultICS::Hit(24, RuleAction::Alert)
     );
 }
+
+#[test]
+fn detect_modbus_umas() {
+    let req_pdu = parsing_parser::parsers::modbus_req::PDU {
+        function_code: 90,
+        data: parsing_parser::parsers::modbus_req::Data::Umas {
+            session_key: 0x12,
+            umas_function_code: 0x41,
+            umas_data: parsing_parser::parsers::modbus_req::UmasData::StopPlc {
+                data: &[0xff, 0x00],
+            },
+        },
+    };
+    let packet_req = make_modbus_req_packet(req_pdu);
+
+    let mut modbus_rule = HmIcsRules::new();
+    assert!(modbus_rule.load_rules("./tests/ics_rules_modbus.json"));
+
+    assert_eq!(
+        modbus_rule.detect(&packet_req),
+        DetectResultICS::Hit(90, RuleAction::Alert)
+    );
+
+    let req_pdu = parsing_parser::parsers::modbus_req::PDU {
+        function_code: 90,
+        data: parsing_parser::parsers::modbus_req::Data::Umas {
+            session_key: 0x12,
+            umas_function_code: 0x40,
+            umas_data: parsing_parser::parsers::modbus_req::UmasData::StartPlc {
+                data: &[0xff, 0x00],
+            },
+        },
+    };
+    let packet_req = make_modbus_req_packet(req_pdu);
+
+    assert_eq!(
+        modbus_rule.detect(&packet_req),
+        DetectResultICS::Miss(DetectMiss::Content)
+    );
+}
+
+#[test]
+fn detect_modbus_read_device_identification() {
+    let req_pdu = parsing_parser::parsers::modbus_req::PDU {
+        function_code: 43,
+        data: parsing_parser::parsers::modbus_req::Data::EncapsulatedInterfaceTransport {
+            mei_type: 0x0e,
+            mei_data: parsing_parser::parsers::modbus_req::MeiReqData::ReadDeviceIdentification {
+                read_device_id_code: 1,
+                object_id: 0,
+            },
+        },
+    };
+    let packet_req = make_modbus_req_packet(req_pdu);
+
+    let mut modbus_rule = HmIcsRules::new();
+    assert!(modbus_rule.load_rules("./tests/ics_rules_modbus.json"));
+
+    assert_eq!(
+        modbus_rule.detect(&packet_req),
+        DetectResultICS::Hit(43, RuleAction::Alert)
+    );
+}
@@ -0,0 +1,111 @@
+use parsing_icsrule::HmIcsRules;
+use parsing_parser::{QuinPacket, QuinPacketOptions};
+use parsing_rule::{DetectMiss, DetectResultICS, RuleAction, RulesDetectorICS};
+
+// Write ns=2;s=Line1.Setpoint Value = 42.5, 192.168.1.50 -> 192.168.1.20
+const OPCUA_WRITE_FROM_HMI_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x04, 0x05, 0x06, 0x00, 0x0c, 0x29, 0x01, 0x02, 0x03,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x8c, 0x00, 0x01, 0x40, 0x00, 0x40, 0x06,
+    0xb6, 0xd4, 0xc0, 0xa8, 0x01, 0x32, 0xc0, 0xa8, 0x01, 0x14, 0xc3, 0x51,
+    0xbd, 0x10, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x50, 0x18,
+    0x20, 0x00, 0x00, 0x00, 0x00, 0x00, 0x4d, 0x53, 0x47, 0x46, 0x64, 0x00,
+    0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x33, 0x00,
+    0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0xa1, 0x02, 0x00, 0x00,
+    0x12, 0x45, 0x93, 0x25, 0xc8, 0xc7, 0xd6, 0x01, 0x01, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0x10, 0x27, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x03, 0x02, 0x00, 0x0e, 0x00,
+    0x00, 0x00, 0x4c, 0x69, 0x6e, 0x65, 0x31, 0x2e, 0x53, 0x65, 0x74, 0x70,
+    0x6f, 0x69, 0x6e, 0x74, 0x0d, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff,
+    0x01, 0x0b, 0x00, 0x00, 0x00, 0x00, 0x00, 0x40, 0x45, 0x40,
+];
+
+// Write ns=2;s=Line1.Setpoint Value = 42.5, 192.168.1.10 -> 192.168.1.20
+const OPCUA_WRITE_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x04, 0x05, 0x06, 0x00, 0x0c, 0x29, 0x01, 0x02, 0x03,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x8c, 0x00, 0x01, 0x40, 0x00, 0x40, 0x06,
+    0xb6, 0xfc, 0xc0, 0xa8, 0x01, 0x0a, 0xc0, 0xa8, 0x01, 0x14, 0xc3, 0x51,
+    0xbd, 0x10, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x50, 0x18,
+    0x20, 0x00, 0x00, 0x00, 0x00, 0x00, 0x4d, 0x53, 0x47, 0x46, 0x64, 0x00,
+    0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x33, 0x00,
+    0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0xa1, 0x02, 0x00, 0x00,
+    0x12, 0x45, 0x93, 0x25, 0xc8, 0xc7, 0xd6, 0x01, 0x01, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0x10, 0x27, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x03, 0x02, 0x00, 0x0e, 0x00,
+    0x00, 0x00, 0x4c, 0x69, 0x6e, 0x65, 0x31, 0x2e, 0x53, 0x65, 0x74, 0x70,
+    0x6f, 0x69, 0x6e, 0x74, 0x0d, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff,
+    0x01, 0x0b, 0x00, 0x00, 0x00, 0x00, 0x00, 0x40, 0x45, 0x40,
+];
+
+// Call ns=2;i=5001 / ns=2;i=5002 (3)
+const OPCUA_CALL_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x04, 0x05, 0x06, 0x00, 0x0c, 0x29, 0x01, 0x02, 0x03,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x76, 0x00, 0x01, 0x40, 0x00, 0x40, 0x06,
+    0xb7, 0x12, 0xc0, 0xa8, 0x01, 0x0a, 0xc0, 0xa8, 0x01, 0x14, 0xc3, 0x51,
+    0xbd, 0x10, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x50, 0x18,
+    0x20, 0x00, 0x00, 0x00, 0x00, 0x00, 0x4d, 0x53, 0x47, 0x46, 0x4e, 0x00,
+    0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x33, 0x00,
+    0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0xc8, 0x02, 0x00, 0x00,
+    0x12, 0x45, 0x93, 0x25, 0xc8, 0xc7, 0xd6, 0x01, 0x02, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0x10, 0x27, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x02, 0x89, 0x13, 0x01,
+    0x02, 0x8a, 0x13, 0x01, 0x00, 0x00, 0x00, 0x06, 0x03, 0x00, 0x00, 0x00,
+];
+
+// OpenSecureChannel SecurityPolicy#None, SecurityMode None
+const OPCUA_OPEN_SECURE_CHANNEL_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x04, 0x05, 0x06, 0x00, 0x0c, 0x29, 0x01, 0x02, 0x03,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0xac, 0x00, 0x01, 0x40, 0x00, 0x40, 0x06,
+    0xb6, 0xdc, 0xc0, 0xa8, 0x01, 0x0a, 0xc0, 0xa8, 0x01, 0x14, 0xc3, 0x51,
+    0xbd, 0x10, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x50, 0x18,
+    0x20, 0x00, 0x00, 0x00, 0x00, 0x00, 0x4f, 0x50, 0x4e, 0x46, 0x84, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x2f, 0x00, 0x00, 0x00, 0x68, 0x74,
+    0x74, 0x70, 0x3a, 0x2f, 0x2f, 0x6f, 0x70, 0x63, 0x66, 0x6f, 0x75, 0x6e,
+    0x64, 0x61, 0x74, 0x69, 0x6f, 0x6e, 0x2e, 0x6f, 0x72, 0x67, 0x2f, 0x55,
+    0x41, 0x2f, 0x53, 0x65, 0x63, 0x75, 0x72, 0x69, 0x74, 0x79, 0x50, 0x6f,
+    0x6c, 0x69, 0x63, 0x79, 0x23, 0x4e, 0x6f, 0x6e, 0x65, 0xff, 0xff, 0xff,
+    0xff, 0xff, 0xff, 0xff, 0xff, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00,
+    0x00, 0x01, 0x00, 0xbe, 0x01, 0x00, 0x00, 0x12, 0x45, 0x93, 0x25, 0xc8,
+    0xc7, 0xd6, 0x01, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff,
+    0xff, 0xff, 0xff, 0x10, 0x27, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x80, 0xee, 0x36, 0x00,
+];
+
+#[test]
+fn detect_opcua_write_whitelist() {
+    let mut opcua_rule = HmIcsRules::new();
+    assert!(opcua_rule.load_rules("./tests/ics_rules_opcua.json"));
+
+    // 仅允许 HMI 写入 ns=2;s=Line1.Setpoint
+    let packet = QuinPacket::parse_from_stream(OPCUA_WRITE_FROM_HMI_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(packet, QuinPacket::L5(_)));
+    assert_eq!(opcua_rule.detect(&packet), DetectResultICS::Hit(1, RuleAction::Pass));
+
+    let packet = QuinPacket::parse_from_stream(OPCUA_WRITE_FRAME, &QuinPacketOptions::default());
+    assert_eq!(opcua_rule.detect(&packet), DetectResultICS::Hit(2, RuleAction::Drop));
+}
+
+#[test]
+fn detect_opcua_call_node() {
+    let mut opcua_rule = HmIcsRules::new();
+    assert!(opcua_rule.load_rules("./tests/ics_rules_opcua.json"));
+
+    let packet = QuinPacket::parse_from_stream(OPCUA_CALL_FRAME, &QuinPacketOptions::default());
+    assert_eq!(opcua_rule.detect(&packet), DetectResultICS::Hit(3, RuleAction::Alert));
+}
+
+#[test]
+fn detect_opcua_security_policy() {
+    let mut opcua_rule = HmIcsRules::new();
+    assert!(opcua_rule.load_rules("./tests/ics_rules_opcua.json"));
+
+    let packet =
+        QuinPacket::parse_from_stream(OPCUA_OPEN_SECURE_CHANNEL_FRAME, &QuinPacketOptions::default());
+    assert_eq!(opcua_rule.detect(&packet), DetectResultICS::Hit(4, RuleAction::Alert));
+
+    opcua_rule.deactive_rule(4);
+    assert_eq!(
+        opcua_rule.detect(&packet),
+        DetectResultICS::Miss(DetectMiss::Content)
+    );
+}
@@ -0,0 +1,170 @@
+use parsing_icsrule::HmIcsRules;
+use parsing_parser::{QuinPacket, QuinPacketOptions};
+use parsing_rule::{DetectMiss, DetectResultICS, RuleAction, RulesDetectorICS};
+
+// DCP Identify All request
+const IDENTIFY_REQUEST_FRAME: &[u8] = &[
+    0x01, 0x0e, 0xcf, 0x00, 0x00, 0x00, 0x00, 0x0e, 0x8c, 0x01, 0x02, 0x03,
+    0x88, 0x92, 0xfe, 0xfe, 0x05, 0x00, 0x00, 0x00, 0x10, 0x01, 0x00, 0x01,
+    0x00, 0x04, 0xff, 0xff, 0x00, 0x00,
+];
+
+// DCP Identify response from plc-cell1
+const IDENTIFY_RESPONSE_FRAME: &[u8] = &[
+    0x00, 0x0e, 0x8c, 0x01, 0x02, 0x03, 0x00, 0x1b, 0x1b, 0x0a, 0x0b, 0x0c,
+    0x88, 0x92, 0xfe, 0xff, 0x05, 0x01, 0x00, 0x00, 0x10, 0x01, 0x00, 0x00,
+    0x00, 0x2c, 0x02, 0x02, 0x00, 0x0b, 0x00, 0x00, 0x70, 0x6c, 0x63, 0x2d,
+    0x63, 0x65, 0x6c, 0x6c, 0x31, 0x00, 0x02, 0x03, 0x00, 0x06, 0x00, 0x00,
+    0x00, 0x2a, 0x01, 0x04, 0x01, 0x02, 0x00, 0x0e, 0x00, 0x01, 0xc0, 0xa8,
+    0x00, 0x14, 0xff, 0xff, 0xff, 0x00, 0xc0, 0xa8, 0x00, 0x01,
+];
+
+// DCP Set NameOfStation evil-station
+const SET_NAME_FRAME: &[u8] = &[
+    0x00, 0x1b, 0x1b, 0x0a, 0x0b, 0x0c, 0x00, 0x0e, 0x8c, 0x01, 0x02, 0x03,
+    0x88, 0x92, 0xfe, 0xfd, 0x04, 0x00, 0x00, 0x00, 0x20, 0x02, 0x00, 0x00,
+    0x00, 0x12, 0x02, 0x02, 0x00, 0x0e, 0x00, 0x01, 0x65, 0x76, 0x69, 0x6c,
+    0x2d, 0x73, 0x74, 0x61, 0x74, 0x69, 0x6f, 0x6e,
+];
+
+// DCP Set IP 10.6.6.6
+const SET_IP_FRAME: &[u8] = &[
+    0x00, 0x1b, 0x1b, 0x0a, 0x0b, 0x0c, 0x00, 0x0e, 0x8c, 0x01, 0x02, 0x03,
+    0x88, 0x92, 0xfe, 0xfd, 0x04, 0x00, 0x00, 0x00, 0x20, 0x03, 0x00, 0x00,
+    0x00, 0x12, 0x01, 0x02, 0x00, 0x0e, 0x00, 0x01, 0x0a, 0x06, 0x06, 0x06,
+    0xff, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+
+// VLAN tagged RT cyclic frame
+const CYCLIC_FRAME: &[u8] = &[
+    0x00, 0x0e, 0x8c, 0x01, 0x02, 0x03, 0x00, 0x1b, 0x1b, 0x0a, 0x0b, 0x0c,
+    0x81, 0x00, 0xc0, 0x00, 0x88, 0x92, 0x80, 0x01, 0x00, 0x01, 0x02, 0x03,
+    0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+    0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1a, 0x1b,
+    0x1c, 0x1d, 0x1e, 0x1f, 0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27,
+    0x12, 0x34, 0x35, 0x00,
+];
+
+// PNIO-CM Connect request to plc-controller
+const CONNECT_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x04, 0x05, 0x06, 0x00, 0x0c, 0x29, 0x01, 0x02, 0x03,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0xc8, 0x00, 0x01, 0x40, 0x00, 0x40, 0x11,
+    0xb8, 0xb5, 0xc0, 0xa8, 0x00, 0x0a, 0xc0, 0xa8, 0x00, 0x14, 0xc0, 0x00,
+    0x88, 0x94, 0x00, 0xb4, 0x00, 0x00, 0x04, 0x00, 0x20, 0x00, 0x10, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0xa0, 0xde, 0x97, 0x6c,
+    0xd1, 0x11, 0x82, 0x71, 0x00, 0xa0, 0x24, 0x42, 0xdf, 0x7d, 0xaa, 0xaa,
+    0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa,
+    0xaa, 0xaa, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0x5c, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x10, 0x00, 0x00, 0x48, 0x00, 0x00, 0x00, 0x00, 0x10,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x48, 0x00, 0x00, 0x00, 0x01, 0x01,
+    0x00, 0x44, 0x01, 0x00, 0x00, 0x01, 0x00, 0x01, 0x02, 0x03, 0x04, 0x05,
+    0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0x00, 0x01,
+    0x00, 0x0e, 0x8c, 0x01, 0x02, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x01, 0x31, 0x00, 0x64, 0x88, 0x92, 0x00, 0x0e, 0x70, 0x6c, 0x63, 0x2d,
+    0x63, 0x6f, 0x6e, 0x74, 0x72, 0x6f, 0x6c, 0x6c, 0x65, 0x72,
+];
+
+// PNIO-CM Write request, index 0x8030
+const WRITE_RECORD_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x04, 0x05, 0x06, 0x00, 0x0c, 0x29, 0x01, 0x02, 0x03,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0xc4, 0x00, 0x01, 0x40, 0x00, 0x40, 0x11,
+    0xb8, 0xb9, 0xc0, 0xa8, 0x00, 0x0a, 0xc0, 0xa8, 0x00, 0x14, 0xc0, 0x00,
+    0x88, 0x94, 0x00, 0xb0, 0x00, 0x00, 0x04, 0x00, 0x20, 0x00, 0x10, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0xa0, 0xde, 0x97, 0x6c,
+    0xd1, 0x11, 0x82, 0x71, 0x00, 0xa0, 0x24, 0x42, 0xdf, 0x7d, 0xaa, 0xaa,
+    0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa,
+    0xaa, 0xaa, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00,
+    0x00, 0x00, 0x03, 0x00, 0xff, 0xff, 0xff, 0xff, 0x58, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x10, 0x00, 0x00, 0x44, 0x00, 0x00, 0x00, 0x00, 0x10,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x44, 0x00, 0x00, 0x00, 0x00, 0x08,
+    0x00, 0x3c, 0x01, 0x00, 0x00, 0x02, 0x00, 0x01, 0x02, 0x03, 0x04, 0x05,
+    0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x80, 0x30, 0x00, 0x00,
+    0x00, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x01, 0x02, 0x03, 0x04,
+];
+
+// PNIO-CM Write Multiple request, index 0x8030 and 0x0100
+const WRITE_MULTIPLE_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x04, 0x05, 0x06, 0x00, 0x0c, 0x29, 0x01, 0x02, 0x03,
+    0x08, 0x00, 0x45, 0x00, 0x01, 0x46, 0x00, 0x01, 0x40, 0x00, 0x40, 0x11,
+    0xb8, 0x37, 0xc0, 0xa8, 0x00, 0x0a, 0xc0, 0xa8, 0x00, 0x14, 0xc0, 0x00,
+    0x88, 0x94, 0x01, 0x32, 0x00, 0x00, 0x04, 0x00, 0x20, 0x00, 0x10, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0xa0, 0xde, 0x97, 0x6c,
+    0xd1, 0x11, 0x82, 0x71, 0x00, 0xa0, 0x24, 0x42, 0xdf, 0x7d, 0xaa, 0xaa,
+    0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa,
+    0xaa, 0xaa, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x02, 0x00,
+    0x00, 0x00, 0x03, 0x00, 0xff, 0xff, 0xff, 0xff, 0xda, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x10, 0x00, 0x00, 0xc6, 0x00, 0x00, 0x00, 0x00, 0x10,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xc6, 0x00, 0x00, 0x00, 0x00, 0x08,
+    0x00, 0x3c, 0x01, 0x00, 0x00, 0x04, 0x00, 0x01, 0x02, 0x03, 0x04, 0x05,
+    0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xe0, 0x40, 0x00, 0x00,
+    0x00, 0x86, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x08, 0x00, 0x3c, 0x01, 0x00, 0x00, 0x02, 0x00, 0x01,
+    0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d,
+    0x0e, 0x0f, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00,
+    0x80, 0x30, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x02, 0x03, 0x04, 0x00, 0x08,
+    0x00, 0x3c, 0x01, 0x00, 0x00, 0x03, 0x00, 0x01, 0x02, 0x03, 0x04, 0x05,
+    0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x01, 0x00, 0x01, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+    0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0xaa, 0xbb,
+];
+
+#[test]
+fn detect_profinet_dcp() {
+    let mut profinet_rule = HmIcsRules::new();
+    assert!(profinet_rule.load_rules("./tests/ics_rules_profinet.json"));
+
+    let set_ip = QuinPacket::parse_from_stream(SET_IP_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(set_ip, QuinPacket::L5(_)));
+    assert_eq!(profinet_rule.detect(&set_ip), DetectResultICS::Hit(1, RuleAction::Drop));
+
+    let packet = QuinPacket::parse_from_stream(SET_NAME_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(packet, QuinPacket::L5(_)));
+    assert_eq!(profinet_rule.detect(&packet), DetectResultICS::Hit(2, RuleAction::Alert));
+
+    let packet = QuinPacket::parse_from_stream(IDENTIFY_RESPONSE_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(packet, QuinPacket::L5(_)));
+    assert_eq!(profinet_rule.detect(&packet), DetectResultICS::Hit(3, RuleAction::Pass));
+
+    let packet = QuinPacket::parse_from_stream(IDENTIFY_REQUEST_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(packet, QuinPacket::L5(_)));
+    assert_eq!(profinet_rule.detect(&packet), DetectResultICS::Miss(DetectMiss::Content));
+
+    let packet = QuinPacket::parse_from_stream(CYCLIC_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(packet, QuinPacket::L5(_)));
+    assert_eq!(profinet_rule.detect(&packet), DetectResultICS::Miss(DetectMiss::Content));
+
+    profinet_rule.deactive_rule(1);
+    assert_eq!(profinet_rule.detect(&set_ip), DetectResultICS::Miss(DetectMiss::Content));
+}
+
+#[test]
+fn detect_profinet_pnio_cm() {
+    let mut profinet_rule = HmIcsRules::new();
+    assert!(profinet_rule.load_rules("./tests/ics_rules_profinet.json"));
+
+    let packet = QuinPacket::parse_from_stream(CONNECT_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(packet, QuinPacket::L5(_)));
+    assert_eq!(profinet_rule.detect(&packet), DetectResultICS::Hit(5, RuleAction::Alert));
+
+    let packet = QuinPacket::parse_from_stream(WRITE_RECORD_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(packet, QuinPacket::L5(_)));
+    assert_eq!(profinet_rule.detect(&packet), DetectResultICS::Miss(DetectMiss::Content));
+    // 多记录写入内的记录索引
+    let packet = QuinPacket::parse_from_stream(WRITE_MULTIPLE_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(packet, QuinPacket::L5(_)));
+    assert_eq!(profinet_rule.detect(&packet), DetectResultICS::Hit(4, RuleAction::Alert));
+}
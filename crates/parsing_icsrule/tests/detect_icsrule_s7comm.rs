@@ -0,0 +1,91 @@
+use std::{net::Ipv4Addr, str::FromStr};
+
+use parsing_icsrule::HmIcsRules;
+use parsing_parser::{
+    parsers::{s7comm::parse_s7comm_header, EthernetHeader, Ipv4Header, TcpHeader},
+    ApplicationLayer, L5Packet, LinkLayer, MacAddress, NetworkLayer, QuinPacket, TransportLayer,
+};
+use parsing_rule::{DetectMiss, DetectResultICS, RuleAction, RulesDetectorICS};
+
+fn make_s7comm_packet(s7comm_payload: &[u8]) -> QuinPacket {
+    let (_, s7comm_header) = parse_s7comm_header(s7comm_payload).unwrap();
+    let app_layer = ApplicationLayer::S7comm(s7comm_header);
+
+    let l5 = L5Packet {
+        link_layer: LinkLayer::Ethernet(EthernetHeader {
+            dst_mac: MacAddress([32, 16, 21, 233, 21, 2]),
+            src_mac: MacAddress([32, 16, 21, 233, 21, 1]),
+            link_type: 2048,
+        }),
+        network_layer: NetworkLayer::Ipv4(Ipv4Header {
+            src_ip: Ipv4Addr::from_str("192.168.3.189").unwrap(),
+            dst_ip: Ipv4Addr::from_str("192.168.3.190").unwrap(),
+            version: 4,
+            header_length: 5,
+            diff_service: 0,
+            ecn: 0,
+            total_length: 131,
+            id: 52555,
+            flags: 2,
+            fragment_offset: 0,
+            ttl: 64,
+            protocol: 6,
+            checksum: 38996,
+            options: None,
+        }),
+        transport_layer: TransportLayer::Tcp(TcpHeader {
+            src_port: 53211,
+            dst_port: 102,
+            seq: 1175987464,
+            ack: 3947317609,
+            header_length: 5,
+            reserved: 0,
+            flags: 24,
+            window_size: 256,
+            checksum: 45344,
+            urgent_pointer: 0,
+            options: None,
+            padding: None,
+            payload: &[],
+        }),
+        application_layer: app_layer,
+        remain: &[],
+        error: None,
+    };
+
+    return QuinPacket::L5(l5);
+}
+
+// Job - Write Var: DB1.DBD0 (REAL)
+fn write_var_real(value: f32) -> Vec<u8> {
+    let mut payload = vec![
+        0x32, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0x0e, 0x00, 0x08, // header
+        0x05, 0x01, // function code + item count
+        0x12, 0x0a, 0x10, 0x08, 0x00, 0x01, 0x00, 0x01, 0x84, 0x00, 0x00, 0x00, // item
+        0x00, 0x07, 0x00, 0x04, // data item: transport size REAL, 4 bytes
+    ];
+    payload.extend_from_slice(&value.to_be_bytes());
+    payload
+}
+
+#[test]
+fn detect_s7comm_write_var_real() {
+    let payload = write_var_real(25.5);
+    let packet = make_s7comm_packet(&payload);
+
+    let mut s7comm_rule = HmIcsRules::new();
+    assert!(s7comm_rule.load_rules("./tests/ics_rules_s7comm.json"));
+
+    assert_eq!(
+        s7comm_rule.detect(&packet),
+        DetectResultICS::Hit(1, RuleAction::Alert)
+    );
+
+    let payload = write_var_real(35.5);
+    let packet = make_s7comm_packet(&payload);
+
+    assert_eq!(
+        s7comm_rule.detect(&packet),
+        DetectResultICS::Miss(DetectMiss::Content)
+    );
+}
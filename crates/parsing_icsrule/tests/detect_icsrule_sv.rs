@@ -0,0 +1,51 @@
+use parsing_icsrule::HmIcsRules;
+use parsing_parser::{QuinPacket, QuinPacketOptions};
+use parsing_rule::{DetectMiss, DetectResultICS, RuleAction, RulesDetectorICS};
+
+// SV: 00:1e:c9:08:4e:6c -> 01:0c:cd:04:00:01, APPID 0x4000, svID "MU01"
+const SV_FRAME: &[u8] = &[
+    0x01, 0x0c, 0xcd, 0x04, 0x00, 0x01, 0x00, 0x1e, 0xc9, 0x08, 0x4e, 0x6c,
+    0x88, 0xba, 0x40, 0x00, 0x00, 0x36, 0x00, 0x00, 0x00, 0x00, 0x60, 0x2c,
+    0x80, 0x01, 0x01, 0xa2, 0x27, 0x30, 0x25, 0x80, 0x04, 0x4d, 0x55, 0x30,
+    0x31, 0x82, 0x02, 0x00, 0x0a, 0x83, 0x04, 0x00, 0x00, 0x00, 0x01, 0x85,
+    0x01, 0x02, 0x87, 0x10, 0x00, 0x00, 0x03, 0xe8, 0x00, 0x00, 0x00, 0x00,
+    0xff, 0xff, 0xfc, 0x18, 0x00, 0x00, 0x08, 0x03,
+];
+
+#[test]
+fn detect_sv_with_mac() {
+    let packet = QuinPacket::parse_from_stream(SV_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(packet, QuinPacket::L5(_)));
+
+    let mut sv_rule = HmIcsRules::new();
+    assert!(sv_rule.load_rules("./tests/ics_rules_sv.json"));
+
+    assert_eq!(
+        sv_rule.detect(&packet),
+        DetectResultICS::Hit(1, RuleAction::Alert)
+    );
+}
+
+#[test]
+fn detect_sv_with_mismatched_mac() {
+    let mut sv_rule = HmIcsRules::new();
+    assert!(sv_rule.load_rules("./tests/ics_rules_sv.json"));
+
+    // 源 MAC 不符
+    let mut frame = SV_FRAME.to_vec();
+    frame[11] = 0x6d;
+    let packet = QuinPacket::parse_from_stream(&frame, &QuinPacketOptions::default());
+    assert_eq!(
+        sv_rule.detect(&packet),
+        DetectResultICS::Miss(DetectMiss::Behavior)
+    );
+
+    // 目的 MAC 不符
+    let mut frame = SV_FRAME.to_vec();
+    frame[5] = 0x02;
+    let packet = QuinPacket::parse_from_stream(&frame, &QuinPacketOptions::default());
+    assert_eq!(
+        sv_rule.detect(&packet),
+        DetectResultICS::Miss(DetectMiss::Behavior)
+    );
+}
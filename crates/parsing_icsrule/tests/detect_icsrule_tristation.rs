@@ -0,0 +1,124 @@
+use parsing_icsrule::HmIcsRules;
+use parsing_parser::{QuinPacket, QuinPacketOptions};
+use parsing_rule::{DetectMiss, DetectResultICS, RuleAction, RulesDetectorICS};
+
+// Connect request from engineering workstation
+const CONNECT_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x04, 0x05, 0x06, 0x00, 0x0c, 0x29, 0x01, 0x02, 0x03,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x26, 0x00, 0x01, 0x40, 0x00, 0x40, 0x11,
+    0xa5, 0x3e, 0xc0, 0xa8, 0x0a, 0x05, 0xc0, 0xa8, 0x0a, 0x32, 0xc3, 0x64,
+    0x05, 0xde, 0x00, 0x12, 0x00, 0x00, 0x01, 0x00, 0x04, 0x00, 0x01, 0x00,
+    0x00, 0x00, 0xc1, 0xb4,
+];
+
+// Get CP status reply to engineering workstation
+const CP_STATUS_REPLY_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x01, 0x02, 0x03, 0x00, 0x0c, 0x29, 0x04, 0x05, 0x06,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x36, 0x00, 0x01, 0x40, 0x00, 0x40, 0x11,
+    0xa5, 0x2e, 0xc0, 0xa8, 0x0a, 0x32, 0xc0, 0xa8, 0x0a, 0x05, 0x05, 0xde,
+    0xc3, 0x64, 0x00, 0x22, 0x00, 0x00, 0x05, 0x00, 0x14, 0x00, 0x6c, 0x01,
+    0x01, 0x00, 0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09,
+    0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0x84, 0xcc,
+];
+
+// Get CP status from engineering workstation
+const GET_CP_STATUS_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x04, 0x05, 0x06, 0x00, 0x0c, 0x29, 0x01, 0x02, 0x03,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x26, 0x00, 0x01, 0x40, 0x00, 0x40, 0x11,
+    0xa5, 0x3e, 0xc0, 0xa8, 0x0a, 0x05, 0xc0, 0xa8, 0x0a, 0x32, 0xc3, 0x64,
+    0x05, 0xde, 0x00, 0x12, 0x00, 0x00, 0x05, 0x00, 0x04, 0x00, 0x14, 0x01,
+    0x01, 0x00, 0x94, 0x1b,
+];
+
+// Allocate program from engineering workstation
+const ALLOCATE_PROGRAM_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x04, 0x05, 0x06, 0x00, 0x0c, 0x29, 0x01, 0x02, 0x03,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x36, 0x00, 0x01, 0x40, 0x00, 0x40, 0x11,
+    0xa5, 0x2e, 0xc0, 0xa8, 0x0a, 0x05, 0xc0, 0xa8, 0x0a, 0x32, 0xc3, 0x64,
+    0x05, 0xde, 0x00, 0x22, 0x00, 0x00, 0x05, 0x00, 0x14, 0x00, 0x30, 0x01,
+    0x02, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x90, 0x90,
+    0x90, 0x90, 0x90, 0x90, 0x90, 0x90, 0x73, 0xc7,
+];
+
+// Halt program from engineering workstation
+const HALT_PROGRAM_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x04, 0x05, 0x06, 0x00, 0x0c, 0x29, 0x01, 0x02, 0x03,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x26, 0x00, 0x01, 0x40, 0x00, 0x40, 0x11,
+    0xa5, 0x3e, 0xc0, 0xa8, 0x0a, 0x05, 0xc0, 0xa8, 0x0a, 0x32, 0xc3, 0x64,
+    0x05, 0xde, 0x00, 0x12, 0x00, 0x00, 0x05, 0x00, 0x04, 0x00, 0x16, 0x01,
+    0x04, 0x00, 0x96, 0xf3,
+];
+
+// Get point values from 192.168.10.66
+const ROGUE_READ_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x04, 0x05, 0x06, 0x00, 0x0c, 0x29, 0x01, 0x02, 0x03,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x2a, 0x00, 0x01, 0x40, 0x00, 0x40, 0x11,
+    0xa4, 0xfd, 0xc0, 0xa8, 0x0a, 0x42, 0xc0, 0xa8, 0x0a, 0x32, 0xc3, 0x64,
+    0x05, 0xde, 0x00, 0x16, 0x00, 0x00, 0x05, 0x00, 0x08, 0x00, 0x1d, 0x01,
+    0x05, 0x00, 0x00, 0x01, 0x04, 0x00, 0x0e, 0xe6,
+];
+
+// Set point values from 192.168.10.66
+const ROGUE_WRITE_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x04, 0x05, 0x06, 0x00, 0x0c, 0x29, 0x01, 0x02, 0x03,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x2c, 0x00, 0x01, 0x40, 0x00, 0x40, 0x11,
+    0xa4, 0xfb, 0xc0, 0xa8, 0x0a, 0x42, 0xc0, 0xa8, 0x0a, 0x32, 0xc3, 0x64,
+    0x05, 0xde, 0x00, 0x18, 0x00, 0x00, 0x05, 0x00, 0x0a, 0x00, 0x1c, 0x01,
+    0x06, 0x00, 0x00, 0x01, 0x01, 0x00, 0x01, 0x00, 0x6c, 0xa1,
+];
+
+// Ping from 192.168.10.66
+const ROGUE_PING_FRAME: &[u8] = &[
+    0x00, 0x0c, 0x29, 0x04, 0x05, 0x06, 0x00, 0x0c, 0x29, 0x01, 0x02, 0x03,
+    0x08, 0x00, 0x45, 0x00, 0x00, 0x22, 0x00, 0x01, 0x40, 0x00, 0x40, 0x11,
+    0xa5, 0x05, 0xc0, 0xa8, 0x0a, 0x42, 0xc0, 0xa8, 0x0a, 0x32, 0xc3, 0x64,
+    0x05, 0xde, 0x00, 0x0e, 0x00, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, 0x88,
+];
+
+#[test]
+fn detect_tristation_program_change() {
+    let mut tristation_rule = HmIcsRules::new();
+    assert!(tristation_rule.load_rules("./tests/ics_rules_tristation.json"));
+
+    // 即使来自工程师站，程序变更也告警
+    let packet = QuinPacket::parse_from_stream(ALLOCATE_PROGRAM_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(packet, QuinPacket::L5(_)));
+    assert_eq!(tristation_rule.detect(&packet), DetectResultICS::Hit(1, RuleAction::Alert));
+
+    let packet = QuinPacket::parse_from_stream(HALT_PROGRAM_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(packet, QuinPacket::L5(_)));
+    assert_eq!(tristation_rule.detect(&packet), DetectResultICS::Hit(1, RuleAction::Alert));
+
+    let packet = QuinPacket::parse_from_stream(ROGUE_WRITE_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(packet, QuinPacket::L5(_)));
+    assert_eq!(tristation_rule.detect(&packet), DetectResultICS::Hit(2, RuleAction::Alert));
+}
+
+#[test]
+fn detect_tristation_source() {
+    let mut tristation_rule = HmIcsRules::new();
+    assert!(tristation_rule.load_rules("./tests/ics_rules_tristation.json"));
+
+    let packet = QuinPacket::parse_from_stream(CONNECT_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(packet, QuinPacket::L5(_)));
+    assert_eq!(tristation_rule.detect(&packet), DetectResultICS::Hit(3, RuleAction::Pass));
+
+    let packet = QuinPacket::parse_from_stream(GET_CP_STATUS_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(packet, QuinPacket::L5(_)));
+    assert_eq!(tristation_rule.detect(&packet), DetectResultICS::Hit(3, RuleAction::Pass));
+
+    let packet = QuinPacket::parse_from_stream(CP_STATUS_REPLY_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(packet, QuinPacket::L5(_)));
+    assert_eq!(tristation_rule.detect(&packet), DetectResultICS::Hit(4, RuleAction::Pass));
+    // 非工程师站的任意 TriStation 报文
+    let rogue_read = QuinPacket::parse_from_stream(ROGUE_READ_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(rogue_read, QuinPacket::L5(_)));
+    assert_eq!(tristation_rule.detect(&rogue_read), DetectResultICS::Hit(5, RuleAction::Alert));
+
+    let packet = QuinPacket::parse_from_stream(ROGUE_PING_FRAME, &QuinPacketOptions::default());
+    assert!(matches!(packet, QuinPacket::L5(_)));
+    assert_eq!(tristation_rule.detect(&packet), DetectResultICS::Hit(5, RuleAction::Alert));
+
+    tristation_rule.deactive_rule(5);
+    assert_eq!(tristation_rule.detect(&rogue_read), DetectResultICS::Miss(DetectMiss::Content));
+}
@@ -5,6 +5,7 @@ use crate::ProtocolType;
 
 /// 表示各类解析错误的结构。
 /// * `ParsingHeader`: 表示解析当前层协议过程出错。
+/// * `ChecksumMismatch`: 表示当前层协议的校验(如 CRC)未通过。
 /// * `UnknowPayload`: 表示无法判断上层协议。
 /// * `NotEndPayload`: 表示解析流程已经走完但是依旧还剩余未解析的比特。
 /// * `Adaptor`: 在 FFI 适配胶水层发生错误。
@@ -15,6 +16,11 @@ pub enum ParseError {
         protocol: ProtocolType,
         offset: usize
     },
+    #[error("Checksum mismatch when parsing {protocol:?} at offset {offset}")]
+    ChecksumMismatch{
+        protocol: ProtocolType,
+        offset: usize
+    },
     #[error("Can't choose next level protocol.")]
     UnknownPayload,
     #[error("Remain some bytes when complete parsing.")]
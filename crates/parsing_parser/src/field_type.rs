@@ -239,6 +239,146 @@ pub fn ber_tl_v(input_raw: &[u8]) -> nom::IResult<&[u8], &[u8]> {
     return Ok(take(_ber_tl.length as usize)(input)?);
 }
 
+/// 将 BER INTEGER 的内容字节按补码解析为 i64，超过 8 字节时返回 None。
+#[inline]
+pub fn ber_integer(value: &[u8]) -> Option<i64> {
+    if value.is_empty() || value.len() > 8 {
+        return None;
+    }
+    let mut out: i64 = if value[0] & 0x80 != 0 { -1 } else { 0 };
+    for byte in value {
+        out = (out << 8) | *byte as i64;
+    }
+    Some(out)
+}
+
+/// 将 BER Unsigned 的内容字节解析为 u64，允许一个前导 0x00。
+#[inline]
+pub fn ber_unsigned(value: &[u8]) -> Option<u64> {
+    let value = match value {
+        [0x00, rest @ ..] if !rest.is_empty() => rest,
+        _ => value,
+    };
+    if value.is_empty() || value.len() > 8 {
+        return None;
+    }
+    Some(value.iter().fold(0u64, |out, byte| (out << 8) | *byte as u64))
+}
+
+/// UtcTime是 IEC 61850 的 UTC 时间：4 字节秒数、3 字节秒小数部分与 1 字节时间品质。
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct UtcTime {
+    pub seconds: u32,
+    pub fraction: u32,
+    pub quality: u8,
+}
+
+impl UtcTime {
+    pub fn from_bytes(value: &[u8]) -> Option<Self> {
+        match value {
+            [s1, s2, s3, s4, f1, f2, f3, quality] => Some(UtcTime {
+                seconds: u32::from_be_bytes([*s1, *s2, *s3, *s4]),
+                fraction: u32::from_be_bytes([0, *f1, *f2, *f3]),
+                quality: *quality,
+            }),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn nanoseconds(&self) -> u32 {
+        ((self.fraction as u64 * 1_000_000_000) >> 24) as u32
+    }
+}
+
+/// 解析 S7comm-plus 使用的无符号变长整数(VLQ)，每字节低 7 位有效，最高位为延续标志。
+#[inline]
+pub fn vlq_u32(input: &[u8]) -> nom::IResult<&[u8], u32> {
+    let mut value: u32 = 0;
+    let mut input = input;
+    let mut octet;
+    for _ in 0..5 {
+        (input, octet) = u8(input)?;
+        value = (value << 7) | (octet & 0x7f) as u32;
+        if octet & 0x80 == 0 {
+            return Ok((input, value));
+        }
+    }
+    Err(nom::Err::Error(nom::error::Error::new(
+        input,
+        nom::error::ErrorKind::TooLarge,
+    )))
+}
+
+/// 解析 S7comm-plus 使用的有符号变长整数(VLQ)，首字节第 7 位为符号位。
+#[inline]
+pub fn vlq_i32(input: &[u8]) -> nom::IResult<&[u8], i32> {
+    let mut value: i32 = 0;
+    let mut input = input;
+    let mut octet;
+    for i in 0..5 {
+        (input, octet) = u8(input)?;
+        if i == 0 && octet & 0x40 != 0 {
+            value = -64;
+            octet &= 0xbf;
+        } else {
+            value = value.wrapping_shl(7);
+        }
+        value = value.wrapping_add((octet & 0x7f) as i32);
+        if octet & 0x80 == 0 {
+            return Ok((input, value));
+        }
+    }
+    Err(nom::Err::Error(nom::error::Error::new(
+        input,
+        nom::error::ErrorKind::TooLarge,
+    )))
+}
+
+/// 64 位无符号 VLQ，第 9 个字节使用全部 8 位。
+#[inline]
+pub fn vlq_u64(input: &[u8]) -> nom::IResult<&[u8], u64> {
+    let mut value: u64 = 0;
+    let mut input = input;
+    let mut octet;
+    for i in 0..9 {
+        (input, octet) = u8(input)?;
+        if i == 8 {
+            return Ok((input, (value << 8) | octet as u64));
+        }
+        value = (value << 7) | (octet & 0x7f) as u64;
+        if octet & 0x80 == 0 {
+            return Ok((input, value));
+        }
+    }
+    unreachable!()
+}
+
+/// 64 位有符号 VLQ，第 9 个字节使用全部 8 位。
+#[inline]
+pub fn vlq_i64(input: &[u8]) -> nom::IResult<&[u8], i64> {
+    let mut value: i64 = 0;
+    let mut input = input;
+    let mut octet;
+    for i in 0..9 {
+        (input, octet) = u8(input)?;
+        if i == 8 {
+            return Ok((input, value.wrapping_shl(8).wrapping_add(octet as i64)));
+        }
+        if i == 0 && octet & 0x40 != 0 {
+            value = -64;
+            octet &= 0xbf;
+        } else {
+            value = value.wrapping_shl(7);
+        }
+        value = value.wrapping_add((octet & 0x7f) as i64);
+        if octet & 0x80 == 0 {
+            return Ok((input, value));
+        }
+    }
+    unreachable!()
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
@@ -347,4 +487,28 @@ mod tests {
             }))
         );
     }
+
+    #[test]
+    fn ber_number_parser() {
+        assert_eq!(ber_integer(&[0xff]), Some(-1));
+        assert_eq!(ber_integer(&[0x00, 0x80]), Some(128));
+        assert_eq!(ber_integer(&[0xfe, 0x0c]), Some(-500));
+        assert_eq!(ber_unsigned(&[0x00, 0xff, 0xff, 0xff, 0xff]), Some(0xffff_ffff));
+        assert_eq!(ber_unsigned(&[]), None);
+    }
+
+    #[test]
+    fn vlq_parser() {
+        assert_eq!(vlq_u32(&[0x05, 0xcc]), Ok((&[0xcc][..], 5)));
+        assert_eq!(vlq_u32(&[0x89, 0x0a, 0xcc]), Ok((&[0xcc][..], 0x48a)));
+        assert_eq!(vlq_u32(&[0x8f, 0xff, 0xff, 0xff, 0x7f]), Ok((&[][..], u32::MAX)));
+        assert!(vlq_u32(&[0xff, 0xff, 0xff, 0xff, 0xff, 0x7f]).is_err());
+
+        assert_eq!(vlq_i32(&[0x05]), Ok((&[][..], 5)));
+        assert_eq!(vlq_i32(&[0x7f]), Ok((&[][..], -1)));
+        assert_eq!(vlq_i32(&[0xfe, 0x7f]), Ok((&[][..], -129)));
+
+        assert_eq!(vlq_u64(&[0x81, 0x00]), Ok((&[][..], 128)));
+        assert_eq!(vlq_i64(&[0x40]), Ok((&[][..], -64)));
+    }
 }
@@ -30,8 +30,9 @@ impl LinkLayer {
 pub enum NetworkLayer<'a> {
     Ipv4(Ipv4Header<'a>),
     Ipv6(Ipv6Header<'a>),
-    Goose(GooseHeader<'a>),
     Vlan(VlanHeader),
+    /// 二层直接承载的协议 (GOOSE/SV) 没有网络层
+    Empty,
 }
 
 impl<'a> NetworkLayer<'a> {
@@ -40,8 +41,8 @@ impl<'a> NetworkLayer<'a> {
         match self {
             NetworkLayer::Ipv4(ipv4) => Some(IpAddr::V4(ipv4.dst_ip)),
             NetworkLayer::Ipv6(ipv6) => Some(IpAddr::V6(ipv6.dst_ip)),
-            NetworkLayer::Goose(_) => None,
-            NetworkLayer::Vlan(_) => None
+            NetworkLayer::Vlan(_) => None,
+            NetworkLayer::Empty => None
         }
     }
 
@@ -50,8 +51,8 @@ impl<'a> NetworkLayer<'a> {
         match self {
             NetworkLayer::Ipv4(ipv4) => Some(IpAddr::V4(ipv4.src_ip)),
             NetworkLayer::Ipv6(ipv6) => Some(IpAddr::V6(ipv6.src_ip)),
-            NetworkLayer::Goose(_) => None,
-            NetworkLayer::Vlan(_) => None
+            NetworkLayer::Vlan(_) => None,
+            NetworkLayer::Empty => None
         }
     }
 }
@@ -61,7 +62,8 @@ impl<'a> NetworkLayer<'a> {
 pub enum TransportLayer<'a> {
     Tcp(TcpHeader<'a>),
     Udp(UdpHeader<'a>),
-    Sv(SvHeader<'a>),
+    /// 二层直接承载的协议 (GOOSE/SV) 没有传输层
+    Empty,
 }
 
 impl<'a> TransportLayer<'a> {
@@ -70,7 +72,7 @@ impl<'a> TransportLayer<'a> {
         match self {
             TransportLayer::Tcp(tcp) => Some(tcp.dst_port),
             TransportLayer::Udp(udp) => Some(udp.dst_port),
-            TransportLayer::Sv(_) => None
+            TransportLayer::Empty => None
         }
     }
 
@@ -79,7 +81,7 @@ impl<'a> TransportLayer<'a> {
         match self {
             TransportLayer::Tcp(tcp) => Some(tcp.src_port),
             TransportLayer::Udp(udp) => Some(udp.src_port),
-            TransportLayer::Sv(_) => None
+            TransportLayer::Empty => None
         }
     }
 }
@@ -95,12 +97,19 @@ pub enum ApplicationLayer<'a> {
     FinsUdpRsp(FinsUdpRspHeader<'a>),
     Mms(MmsHeader<'a>),
     S7comm(S7commHeader<'a>),
+    S7commPlus(S7commPlusHeader<'a>),
+    Enip(EnipHeader<'a>),
     Bacnet(BacnetHeader<'a>),
     Dnp3(Dnp3Header),
+    Iec101(Iec101Header),
     Iec104(Iec104Header),
     Opcua(OpcuaHeader<'a>),
     Http(HttpHeader<'a>),
     IsoOnTcp(IsoOnTcpHeader),
     Goose(GooseHeader<'a>),
-    Sv(SvHeader<'a>)
+    Sv(SvHeader<'a>),
+    Profinet(ProfinetHeader<'a>),
+    PnioCm(PnioCmHeader<'a>),
+    Melsec(MelsecHeader<'a>),
+    Tristation(TristationHeader<'a>)
 }
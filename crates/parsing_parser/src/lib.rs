@@ -12,7 +12,7 @@ pub mod parsers;
 
 pub use errors::ParseError;
 // field -> protocol -> layer -> packet => parser
-pub use field_type::{BerTL, MacAddress};
+pub use field_type::{BerTL, MacAddress, UtcTime};
 pub use layer::*;
 pub use packet::*;
 pub use protocol::*;
@@ -1,7 +1,12 @@
 mod level;
 mod level_packet;
+mod quin_flow;
 mod quin_packet;
 
 pub use level::{AppLevel, LinkLevel, NetLevel, TransLevel};
 pub use level_packet::{L1Packet, L2Packet, L3Packet, L4Packet, L5Packet};
-pub use quin_packet::{QuinPacket, QuinPacketOptions};
+pub use quin_flow::QuinFlow;
+pub use quin_packet::{
+    HttpOptions, Iec101Options, IecAsduOptions, MelsecOptions, PortList, QuinPacket,
+    QuinPacketOptions, PORT_LIST_CAPACITY,
+};
@@ -0,0 +1,72 @@
+use super::quin_packet::{QuinPacket, QuinPacketOptions};
+use crate::layer::{ApplicationLayer, TransportLayer};
+use crate::parsers::{parse_ethernet_layer, Dnp3Reassembler, HttpDirection, HttpFlow, OpcuaSecureChannels};
+
+// TCP 标志位 FIN
+const TCP_FLAG_FIN: u16 = 0x001;
+
+/// QuinFlow保存一条连接上跨数据包的解析状态，由调用方按连接持有，连接结束时释放。
+/// 使用示例如下：
+/// ```
+/// use parsing_parser::*;
+///
+/// let mut flow = QuinFlow::new();
+/// for input in [&[1u8, 2, 3][..], &[4, 5, 6][..]] {
+///     let packet = QuinPacket::parse_from_flow(input, &QuinPacketOptions::default(), &mut flow);
+///     println!("packet: {:?}", packet);
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct QuinFlow {
+    /// DNP3 跨 TCP 报文的分片重组
+    pub dnp3: Dnp3Reassembler,
+    /// OPC UA 安全通道状态与分块消息重组
+    pub opcua: OpcuaSecureChannels,
+    /// HTTP 跨 TCP 报文的消息重组与请求响应配对
+    pub http: HttpFlow,
+}
+
+impl QuinFlow {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<'a> QuinPacket<'a> {
+    /// 在连接上下文中解析u8字节流，解析结果中包含跨数据包重组得到的应用层数据。
+    ///
+    /// Warning: 同 [`QuinPacket::parse_from_stream`]，默认第一层是link-Ethernet。
+    pub fn parse_from_flow<'b>(
+        input: &'b [u8],
+        options: &QuinPacketOptions,
+        flow: &'b mut QuinFlow,
+    ) -> QuinPacket<'b> {
+        let mut packet = parse_ethernet_layer(input, options);
+        if let QuinPacket::L5(l5) = &mut packet {
+            match &mut l5.application_layer {
+                ApplicationLayer::Dnp3(dnp3) => flow.dnp3.update(dnp3),
+                ApplicationLayer::Opcua(opcua) => flow.opcua.update(opcua),
+                _ => {}
+            }
+        }
+        // 消息体可能独占报文而无法识别为 HTTP，按端口推入全部 TCP 负载
+        let transport_layer = match &packet {
+            QuinPacket::L4(l4) => Some(&l4.transport_layer),
+            QuinPacket::L5(l5) => Some(&l5.transport_layer),
+            _ => None,
+        };
+        if let Some(TransportLayer::Tcp(tcp)) = transport_layer {
+            let direction = if options.http.ports.contains(tcp.dst_port) {
+                Some(HttpDirection::ToServer)
+            } else if options.http.ports.contains(tcp.src_port) {
+                Some(HttpDirection::ToClient)
+            } else {
+                None
+            };
+            if let Some(direction) = direction {
+                flow.http.update(direction, tcp.payload, tcp.flags & TCP_FLAG_FIN != 0);
+            }
+        }
+        packet
+    }
+}
@@ -83,10 +83,151 @@ impl<'a> QuinPacket<'a> {
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub struct QuinPacketOptions {
     pub stop: Option<ProtocolType>,
+    /// IEC 104 ASDU字段长度
+    pub iec104: IecAsduOptions,
+    /// IEC 101 (FT1.2) 链路层及ASDU字段长度
+    pub iec101: Iec101Options,
+    /// HTTP 识别端口
+    pub http: HttpOptions,
+    /// MELSEC MC 协议识别端口
+    pub melsec: MelsecOptions,
 }
 
 impl Default for QuinPacketOptions {
     fn default() -> Self {
-        Self { stop: None }
+        Self {
+            stop: None,
+            iec104: IecAsduOptions::default(),
+            iec101: Iec101Options::default(),
+            http: HttpOptions::default(),
+            melsec: MelsecOptions::default(),
+        }
+    }
+}
+
+/// IEC 60870-5 ASDU中各地址字段所占字节数。
+/// 传送原因长度为2时，第2个字节为源发站地址。
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct IecAsduOptions {
+    /// 传送原因长度，取值1~2
+    pub cot_size: u8,
+    /// 公共地址长度，取值1~2
+    pub ca_size: u8,
+    /// 信息对象地址长度，取值1~3
+    pub ioa_size: u8,
+}
+
+impl Default for IecAsduOptions {
+    fn default() -> Self {
+        Self {
+            cot_size: 2,
+            ca_size: 2,
+            ioa_size: 3,
+        }
+    }
+}
+
+/// PortList 可容纳的端口数
+pub const PORT_LIST_CAPACITY: usize = 8;
+
+/// PortList是解析选项中按端口识别协议时使用的端口列表。
+/// 以定长数组存储，保持`QuinPacketOptions`可复制：
+/// ```
+/// use parsing_parser::PortList;
+///
+/// let mut ports = PortList::from_slice(&[80, 8080]);
+/// assert!(ports.push(8081));
+/// assert!(ports.contains(8081));
+/// assert_eq!(ports.as_slice(), &[80, 8080, 8081]);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct PortList {
+    ports: [u16; PORT_LIST_CAPACITY],
+    len: u8,
+}
+
+impl PortList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 超出容量的端口被忽略
+    pub fn from_slice(ports: &[u16]) -> Self {
+        let mut list = Self::new();
+        for &port in ports {
+            list.push(port);
+        }
+        list
+    }
+
+    /// 追加端口，列表已满时返回 false
+    pub fn push(&mut self, port: u16) -> bool {
+        if self.len as usize >= PORT_LIST_CAPACITY {
+            return false;
+        }
+        self.ports[self.len as usize] = port;
+        self.len += 1;
+        true
+    }
+
+    pub fn contains(&self, port: u16) -> bool {
+        self.as_slice().contains(&port)
+    }
+
+    pub fn as_slice(&self) -> &[u16] {
+        &self.ports[..self.len as usize]
+    }
+}
+
+/// IEC 101 (FT1.2) 解析选项。
+/// 串口数据经串口服务器转为TCP后，通过`ports`指定承载IEC 101的端口。
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Iec101Options {
+    /// 承载IEC 101的TCP端口，为空时不识别
+    pub ports: PortList,
+    /// 链路地址长度，取值0~2
+    pub link_addr_size: u8,
+    pub asdu: IecAsduOptions,
+}
+
+impl Default for Iec101Options {
+    fn default() -> Self {
+        Self {
+            ports: PortList::new(),
+            link_addr_size: 1,
+            asdu: IecAsduOptions {
+                cot_size: 1,
+                ca_size: 1,
+                ioa_size: 2,
+            },
+        }
+    }
+}
+
+/// HTTP 解析选项。
+/// PLC、HMI 的 Web 管理界面常使用非标准端口，可通过`ports`追加。
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct HttpOptions {
+    /// 承载HTTP的TCP端口
+    pub ports: PortList,
+}
+
+impl Default for HttpOptions {
+    fn default() -> Self {
+        Self { ports: PortList::from_slice(&[80, 8080]) }
+    }
+}
+
+/// MELSEC MC 协议解析选项。
+/// 以太网模块的 MC 协议端口由参数设置决定，TCP、UDP 共用`ports`。
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct MelsecOptions {
+    /// 承载MC协议的TCP/UDP端口
+    pub ports: PortList,
+}
+
+impl Default for MelsecOptions {
+    fn default() -> Self {
+        Self { ports: PortList::from_slice(&[5006, 5007]) }
     }
 }
@@ -42,7 +42,7 @@ use std::ops::BitXor;
 
 use super::parse_l5_eof_layer;
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct BacnetHeader<'a> {
     pub bvlc: Bvlc,
     pub npdu: Npdu<'a>,
@@ -359,8 +359,153 @@ pub struct BacnetObjectPropertyReferenceItem {
     pub bacnet_object_property_reference_info: BacnetObjectPropertyReferenceInfo,
 }
 
+/// BACnet 标签: 应用标签、上下文标签及构造类型的开/闭标签
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BacnetTagKind {
+    Application,
+    Context,
+    Opening,
+    Closing,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct BacnetTag {
+    pub tag_number: u8,
+    pub kind: BacnetTagKind,
+    // 应用标签 Boolean 的取值也在此字段中
+    pub length: u32,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct BacnetObjectIdentifier {
+    pub object_type: u16,
+    pub instance_number: u32,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum BacnetValue<'a> {
+    Null,
+    Boolean(bool),
+    Unsigned(u64),
+    Signed(i64),
+    Real(f32),
+    Double(f64),
+    OctetString(&'a [u8]),
+    CharacterString {
+        charset: u8,
+        value: &'a [u8],
+    },
+    BitString {
+        unused_bits: u8,
+        bits: &'a [u8],
+    },
+    Enumerated(u64),
+    Date {
+        year: u8,
+        month: u8,
+        day: u8,
+        weekday: u8,
+    },
+    Time {
+        hour: u8,
+        minute: u8,
+        second: u8,
+        hundredths: u8,
+    },
+    ObjectIdentifier(BacnetObjectIdentifier),
+    // 上下文标签的基本类型无法脱离服务定义解码，保留原始数据
+    Context {
+        tag_number: u8,
+        data: &'a [u8],
+    },
+    Constructed {
+        tag_number: u8,
+        values: Vec<BacnetValue<'a>>,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct BacnetPropertyReference {
+    pub property_identifier: u32,
+    pub property_array_index: Option<u32>,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
-pub enum ConfirmedServiceRequest {
+pub struct ReadAccessSpecification {
+    pub object_identifier: BacnetObjectIdentifier,
+    pub property_references: Vec<BacnetPropertyReference>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct BacnetPropertyValue<'a> {
+    pub property_identifier: u32,
+    pub property_array_index: Option<u32>,
+    pub value: Vec<BacnetValue<'a>>,
+    pub priority: Option<u32>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct WriteAccessSpecification<'a> {
+    pub object_identifier: BacnetObjectIdentifier,
+    pub properties: Vec<BacnetPropertyValue<'a>>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum AtomicWriteFileAccess<'a> {
+    Stream {
+        file_start_position: i64,
+        file_data: &'a [u8],
+    },
+    Record {
+        file_start_record: i64,
+        record_count: u64,
+        file_record_data: Vec<&'a [u8]>,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum WhoHasObject<'a> {
+    Identifier(BacnetObjectIdentifier),
+    Name(&'a [u8]),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum UnconfirmedServiceRequest<'a> {
+    IAm {
+        device_identifier: BacnetObjectIdentifier,
+        max_apdu_length_accepted: u64,
+        segmentation_supported: u64,
+        vendor_id: u64,
+    },
+    IHave {
+        device_identifier: BacnetObjectIdentifier,
+        object_identifier: BacnetObjectIdentifier,
+        object_name: &'a [u8],
+    },
+    UnconfirmedCovNotification {},
+    UnconfirmedEventNotification {},
+    UnconfirmedPrivateTransfer {},
+    UnconfirmedTextMessage {},
+    TimeSynchronization {},
+    WhoHas {
+        device_instance_range_low_limit: Option<u32>,
+        device_instance_range_high_limit: Option<u32>,
+        object: WhoHasObject<'a>,
+    },
+    WhoIs {
+        device_instance_range_low_limit: Option<u32>,
+        device_instance_range_high_limit: Option<u32>,
+    },
+    UtcTimeSynchronization {},
+    WriteGroup {},
+    UnconfirmedCovNotificationMultiple {},
+    UnconfirmedAuditNotification {},
+    WhoAmI {},
+    YouAre {},
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ConfirmedServiceRequest<'a> {
     AcknowledgeAlarm {},
     ConfirmedCovNotification {},
     ConfirmedEventNotification {},
@@ -368,7 +513,10 @@ pub enum ConfirmedServiceRequest {
     GetEnrollmentSummary {},
     SubscribeCov {},
     AtomicReadFile {},
-    AtomicWriteFile {},
+    AtomicWriteFile {
+        file_identifier: BacnetObjectIdentifier,
+        access: AtomicWriteFileAccess<'a>,
+    },
     AddListElement {},
     RemoveListElement {},
     CreateObject {},
@@ -377,13 +525,27 @@ pub enum ConfirmedServiceRequest {
         property_items: Vec<BacnetObjectPropertyReferenceItem>,
     },
     ReadPropertyConditional {},
-    ReadPropertyMultiple {},
-    WriteProperty {},
-    WritePropertyMultiple {},
-    DeviceCommunicationControl {},
+    ReadPropertyMultiple {
+        read_access_specs: Vec<ReadAccessSpecification>,
+    },
+    WriteProperty {
+        object_identifier: BacnetObjectIdentifier,
+        property: BacnetPropertyValue<'a>,
+    },
+    WritePropertyMultiple {
+        write_access_specs: Vec<WriteAccessSpecification<'a>>,
+    },
+    DeviceCommunicationControl {
+        time_duration: Option<u32>,
+        enable_disable: u32,
+        password: Option<&'a [u8]>,
+    },
     ConfirmedPrivateTransfer {},
     ConfirmedTextMessage {},
-    ReinitializeDevice {},
+    ReinitializeDevice {
+        reinitialized_state: u32,
+        password: Option<&'a [u8]>,
+    },
     VtOpen {},
     VtClose {},
     VtData {},
@@ -450,8 +612,8 @@ pub enum ConfirmedServiceAck {
     AuditLogQueryAck {},
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
-pub enum ApduInfo {
+#[derive(Debug, PartialEq, Clone)]
+pub enum ApduInfo<'a> {
     ComfirmedServiceRequest {
         unknow_bit: u8,
         response_segments: u8,
@@ -459,7 +621,15 @@ pub enum ApduInfo {
         invoke_id: u8,
         segmented_req_info: SegmentedReqInfo,
         service_choice: u8,
-        confirmed_service_request: ConfirmedServiceRequest,
+        confirmed_service_request: ConfirmedServiceRequest<'a>,
+    },
+    UnconfirmedServiceRequest {
+        service_choice: u8,
+        unconfirmed_service_request: UnconfirmedServiceRequest<'a>,
+    },
+    SimpleAckPdu {
+        invoke_id: u8,
+        service_choice: u8,
     },
     ComplexAckPdu {
         invoke_id: u8,
@@ -469,7 +639,7 @@ pub enum ApduInfo {
     },
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum ApduOption<'a> {
     UnknowApdu {
         unknow_data: &'a [u8],
@@ -477,7 +647,7 @@ pub enum ApduOption<'a> {
     Apdu {
         apdu_type: u8,
         pdu_flags: u8,
-        apdu_info: ApduInfo,
+        apdu_info: ApduInfo<'a>,
     },
 }
 
@@ -1170,6 +1340,358 @@ pub fn parse_bacnet_object_property_reference_item(
     ))
 }
 
+// 构造类型嵌套层数上限
+const BACNET_MAX_VALUE_DEPTH: usize = 8;
+
+fn bacnet_error<T>(input: &[u8]) -> IResult<&[u8], T> {
+    Err(nom::Err::Error(nom::error::Error::new(
+        input,
+        nom::error::ErrorKind::Verify,
+    )))
+}
+
+pub fn parse_bacnet_tag(input: &[u8]) -> IResult<&[u8], BacnetTag> {
+    let (input, octet) = u8(input)?;
+    let (input, tag_number) = match octet >> 4 {
+        0x0f => u8(input)?,
+        tag_number => (input, tag_number),
+    };
+    let class = (octet >> 3) & 0x01;
+    let length_value_type = octet & 0x07;
+    let kind = match (class, length_value_type) {
+        (1, 6) => return Ok((input, BacnetTag { tag_number, kind: BacnetTagKind::Opening, length: 0 })),
+        (1, 7) => return Ok((input, BacnetTag { tag_number, kind: BacnetTagKind::Closing, length: 0 })),
+        (1, _) => BacnetTagKind::Context,
+        _ => BacnetTagKind::Application,
+    };
+    // 扩展长度: 254 后跟 2 字节，255 后跟 4 字节
+    let (input, length) = match length_value_type {
+        5 => match u8(input)? {
+            (input, 254) => map(be_u16, |v| v as u32)(input)?,
+            (input, 255) => be_u32(input)?,
+            (input, length) => (input, length as u32),
+        },
+        length => (input, length as u32),
+    };
+    Ok((input, BacnetTag { tag_number, kind, length }))
+}
+
+fn bacnet_unsigned(data: &[u8]) -> Option<u64> {
+    match data.len() {
+        1..=8 => Some(data.iter().fold(0u64, |acc, b| (acc << 8) | *b as u64)),
+        _ => None,
+    }
+}
+
+fn bacnet_signed(data: &[u8]) -> Option<i64> {
+    let value = bacnet_unsigned(data)?;
+    let shift = 64 - data.len() * 8;
+    Some(((value << shift) as i64) >> shift)
+}
+
+fn bacnet_object_identifier(data: &[u8]) -> Option<BacnetObjectIdentifier> {
+    match data {
+        [a, b, c, d] => {
+            let value = u32::from_be_bytes([*a, *b, *c, *d]);
+            Some(BacnetObjectIdentifier {
+                object_type: (value >> 22) as u16,
+                instance_number: value & 0x3fffff,
+            })
+        }
+        _ => None,
+    }
+}
+
+fn parse_bacnet_application_data<'a>(
+    input: &'a [u8],
+    tag: &BacnetTag,
+) -> IResult<&'a [u8], BacnetValue<'a>> {
+    // Boolean 的取值在标签中，无内容
+    if tag.tag_number == 1 {
+        return Ok((input, BacnetValue::Boolean(tag.length != 0)));
+    }
+    let (input, data) = take(tag.length as usize)(input)?;
+    let value = match (tag.tag_number, data) {
+        (0, _) => Some(BacnetValue::Null),
+        (2, _) => bacnet_unsigned(data).map(BacnetValue::Unsigned),
+        (3, _) => bacnet_signed(data).map(BacnetValue::Signed),
+        (4, [a, b, c, d]) => Some(BacnetValue::Real(f32::from_be_bytes([*a, *b, *c, *d]))),
+        (5, _) => <[u8; 8]>::try_from(data).ok().map(|v| BacnetValue::Double(f64::from_be_bytes(v))),
+        (6, _) => Some(BacnetValue::OctetString(data)),
+        (7, [charset, value @ ..]) => Some(BacnetValue::CharacterString { charset: *charset, value }),
+        (8, [unused_bits, bits @ ..]) => Some(BacnetValue::BitString { unused_bits: *unused_bits, bits }),
+        (9, _) => bacnet_unsigned(data).map(BacnetValue::Enumerated),
+        (10, [year, month, day, weekday]) => Some(BacnetValue::Date {
+            year: *year,
+            month: *month,
+            day: *day,
+            weekday: *weekday,
+        }),
+        (11, [hour, minute, second, hundredths]) => Some(BacnetValue::Time {
+            hour: *hour,
+            minute: *minute,
+            second: *second,
+            hundredths: *hundredths,
+        }),
+        (12, _) => bacnet_object_identifier(data).map(BacnetValue::ObjectIdentifier),
+        _ => None,
+    };
+    match value {
+        Some(value) => Ok((input, value)),
+        None => bacnet_error(data),
+    }
+}
+
+fn parse_bacnet_value_with_depth(input: &[u8], depth: usize) -> IResult<&[u8], BacnetValue> {
+    let (remain, tag) = parse_bacnet_tag(input)?;
+    match tag.kind {
+        BacnetTagKind::Application => parse_bacnet_application_data(remain, &tag),
+        BacnetTagKind::Context => {
+            let (remain, data) = take(tag.length as usize)(remain)?;
+            Ok((remain, BacnetValue::Context { tag_number: tag.tag_number, data }))
+        }
+        BacnetTagKind::Opening if depth < BACNET_MAX_VALUE_DEPTH => {
+            let (remain, values) =
+                parse_bacnet_constructed_values(remain, tag.tag_number, depth + 1)?;
+            Ok((remain, BacnetValue::Constructed { tag_number: tag.tag_number, values }))
+        }
+        _ => bacnet_error(input),
+    }
+}
+
+pub fn parse_bacnet_value(input: &[u8]) -> IResult<&[u8], BacnetValue> {
+    parse_bacnet_value_with_depth(input, 0)
+}
+
+// 开标签之后的值序列，直到同编号的闭标签
+fn parse_bacnet_constructed_values(
+    input: &[u8],
+    tag_number: u8,
+    depth: usize,
+) -> IResult<&[u8], Vec<BacnetValue>> {
+    let mut values = Vec::new();
+    let mut input = input;
+    loop {
+        let (remain, tag) = parse_bacnet_tag(input)?;
+        if tag.kind == BacnetTagKind::Closing {
+            if tag.tag_number != tag_number {
+                return bacnet_error(input);
+            }
+            return Ok((remain, values));
+        }
+        let (remain, value) = parse_bacnet_value_with_depth(input, depth)?;
+        values.push(value);
+        input = remain;
+    }
+}
+
+fn parse_bacnet_expected_tag(
+    input: &[u8],
+    tag_number: u8,
+    kind: BacnetTagKind,
+) -> IResult<&[u8], BacnetTag> {
+    let (remain, tag) = parse_bacnet_tag(input)?;
+    if tag.tag_number != tag_number || tag.kind != kind {
+        return bacnet_error(input);
+    }
+    Ok((remain, tag))
+}
+
+fn is_bacnet_tag(input: &[u8], tag_number: u8, kind: BacnetTagKind) -> bool {
+    matches!(parse_bacnet_tag(input), Ok((_, tag)) if tag.tag_number == tag_number && tag.kind == kind)
+}
+
+fn parse_bacnet_context_data(input: &[u8], tag_number: u8) -> IResult<&[u8], &[u8]> {
+    let (input, tag) = parse_bacnet_expected_tag(input, tag_number, BacnetTagKind::Context)?;
+    take(tag.length as usize)(input)
+}
+
+fn parse_bacnet_context_unsigned(input: &[u8], tag_number: u8) -> IResult<&[u8], u32> {
+    let (remain, data) = parse_bacnet_context_data(input, tag_number)?;
+    match bacnet_unsigned(data) {
+        Some(value) if data.len() <= 4 => Ok((remain, value as u32)),
+        _ => bacnet_error(input),
+    }
+}
+
+fn parse_bacnet_optional_context_unsigned(
+    input: &[u8],
+    tag_number: u8,
+) -> IResult<&[u8], Option<u32>> {
+    match is_bacnet_tag(input, tag_number, BacnetTagKind::Context) {
+        true => map(|i| parse_bacnet_context_unsigned(i, tag_number), Some)(input),
+        false => Ok((input, None)),
+    }
+}
+
+fn parse_bacnet_context_object_identifier(
+    input: &[u8],
+    tag_number: u8,
+) -> IResult<&[u8], BacnetObjectIdentifier> {
+    let (remain, data) = parse_bacnet_context_data(input, tag_number)?;
+    match bacnet_object_identifier(data) {
+        Some(object_identifier) => Ok((remain, object_identifier)),
+        None => bacnet_error(input),
+    }
+}
+
+// 上下文标签的 CharacterString，返回去掉字符集后的内容
+fn parse_bacnet_optional_context_character_string(
+    input: &[u8],
+    tag_number: u8,
+) -> IResult<&[u8], Option<&[u8]>> {
+    if !is_bacnet_tag(input, tag_number, BacnetTagKind::Context) {
+        return Ok((input, None));
+    }
+    let (remain, data) = parse_bacnet_context_data(input, tag_number)?;
+    match data {
+        [_charset, value @ ..] => Ok((remain, Some(value))),
+        _ => bacnet_error(input),
+    }
+}
+
+fn parse_bacnet_application_value(input: &[u8], tag_number: u8) -> IResult<&[u8], BacnetValue> {
+    let (remain, tag) = parse_bacnet_expected_tag(input, tag_number, BacnetTagKind::Application)?;
+    parse_bacnet_application_data(remain, &tag)
+}
+
+fn parse_bacnet_application_unsigned(input: &[u8]) -> IResult<&[u8], u64> {
+    match parse_bacnet_application_value(input, 2)? {
+        (remain, BacnetValue::Unsigned(value)) => Ok((remain, value)),
+        _ => bacnet_error(input),
+    }
+}
+
+fn parse_bacnet_application_enumerated(input: &[u8]) -> IResult<&[u8], u64> {
+    match parse_bacnet_application_value(input, 9)? {
+        (remain, BacnetValue::Enumerated(value)) => Ok((remain, value)),
+        _ => bacnet_error(input),
+    }
+}
+
+fn parse_bacnet_application_signed(input: &[u8]) -> IResult<&[u8], i64> {
+    match parse_bacnet_application_value(input, 3)? {
+        (remain, BacnetValue::Signed(value)) => Ok((remain, value)),
+        _ => bacnet_error(input),
+    }
+}
+
+fn parse_bacnet_application_object_identifier(
+    input: &[u8],
+) -> IResult<&[u8], BacnetObjectIdentifier> {
+    match parse_bacnet_application_value(input, 12)? {
+        (remain, BacnetValue::ObjectIdentifier(value)) => Ok((remain, value)),
+        _ => bacnet_error(input),
+    }
+}
+
+fn parse_bacnet_application_octet_string(input: &[u8]) -> IResult<&[u8], &[u8]> {
+    match parse_bacnet_application_value(input, 6)? {
+        (remain, BacnetValue::OctetString(value)) => Ok((remain, value)),
+        _ => bacnet_error(input),
+    }
+}
+
+fn parse_bacnet_application_character_string(input: &[u8]) -> IResult<&[u8], &[u8]> {
+    match parse_bacnet_application_value(input, 7)? {
+        (remain, BacnetValue::CharacterString { value, .. }) => Ok((remain, value)),
+        _ => bacnet_error(input),
+    }
+}
+
+// propertyValue: [n] 开标签、值序列、闭标签
+fn parse_bacnet_property_value_data(input: &[u8], tag_number: u8) -> IResult<&[u8], Vec<BacnetValue>> {
+    let (input, _) = parse_bacnet_expected_tag(input, tag_number, BacnetTagKind::Opening)?;
+    parse_bacnet_constructed_values(input, tag_number, 1)
+}
+
+// BACnetPropertyValue: [0] 属性标识 [1] 数组下标(可选) [2] 值 [3] 优先级(可选)
+fn parse_bacnet_property_value(input: &[u8]) -> IResult<&[u8], BacnetPropertyValue> {
+    let (input, property_identifier) = parse_bacnet_context_unsigned(input, 0)?;
+    let (input, property_array_index) = parse_bacnet_optional_context_unsigned(input, 1)?;
+    let (input, value) = parse_bacnet_property_value_data(input, 2)?;
+    let (input, priority) = parse_bacnet_optional_context_unsigned(input, 3)?;
+    Ok((
+        input,
+        BacnetPropertyValue {
+            property_identifier,
+            property_array_index,
+            value,
+            priority,
+        },
+    ))
+}
+
+fn parse_bacnet_property_reference(input: &[u8]) -> IResult<&[u8], BacnetPropertyReference> {
+    let (input, property_identifier) = parse_bacnet_context_unsigned(input, 0)?;
+    let (input, property_array_index) = parse_bacnet_optional_context_unsigned(input, 1)?;
+    Ok((
+        input,
+        BacnetPropertyReference {
+            property_identifier,
+            property_array_index,
+        },
+    ))
+}
+
+// [n] 开标签内的列表，直到同编号闭标签
+fn parse_bacnet_list<'a, T, F>(
+    input: &'a [u8],
+    tag_number: u8,
+    mut parser: F,
+) -> IResult<&'a [u8], Vec<T>>
+where
+    F: FnMut(&'a [u8]) -> IResult<&'a [u8], T>,
+{
+    let (mut input, _) = parse_bacnet_expected_tag(input, tag_number, BacnetTagKind::Opening)?;
+    let mut items = Vec::new();
+    while !is_bacnet_tag(input, tag_number, BacnetTagKind::Closing) {
+        let (remain, item) = parser(input)?;
+        items.push(item);
+        input = remain;
+    }
+    let (input, _) = parse_bacnet_expected_tag(input, tag_number, BacnetTagKind::Closing)?;
+    Ok((input, items))
+}
+
+fn parse_read_access_specification(input: &[u8]) -> IResult<&[u8], ReadAccessSpecification> {
+    let (input, object_identifier) = parse_bacnet_context_object_identifier(input, 0)?;
+    let (input, property_references) = parse_bacnet_list(input, 1, parse_bacnet_property_reference)?;
+    Ok((
+        input,
+        ReadAccessSpecification {
+            object_identifier,
+            property_references,
+        },
+    ))
+}
+
+fn parse_write_access_specification(input: &[u8]) -> IResult<&[u8], WriteAccessSpecification> {
+    let (input, object_identifier) = parse_bacnet_context_object_identifier(input, 0)?;
+    let (input, properties) = parse_bacnet_list(input, 1, parse_bacnet_property_value)?;
+    Ok((
+        input,
+        WriteAccessSpecification {
+            object_identifier,
+            properties,
+        },
+    ))
+}
+
+fn many_until_eof<'a, T, F>(input: &'a [u8], mut parser: F) -> IResult<&'a [u8], Vec<T>>
+where
+    F: FnMut(&'a [u8]) -> IResult<&'a [u8], T>,
+{
+    let mut items = Vec::new();
+    let mut input = input;
+    while !input.is_empty() {
+        let (remain, item) = parser(input)?;
+        items.push(item);
+        input = remain;
+    }
+    Ok((input, items))
+}
+
 fn parse_confirmed_service_request_acknowledge_alarm(
     input: &[u8],
 ) -> IResult<&[u8], ConfirmedServiceRequest> {
@@ -1218,7 +1740,46 @@ fn parse_confirmed_service_request_atomic_read_file(
 fn parse_confirmed_service_request_atomic_write_file(
     input: &[u8],
 ) -> IResult<&[u8], ConfirmedServiceRequest> {
-    Ok((input, ConfirmedServiceRequest::AtomicWriteFile {}))
+    let (input, file_identifier) = parse_bacnet_application_object_identifier(input)?;
+    let (input, access) = if is_bacnet_tag(input, 0, BacnetTagKind::Opening) {
+        let (input, _) = parse_bacnet_expected_tag(input, 0, BacnetTagKind::Opening)?;
+        let (input, file_start_position) = parse_bacnet_application_signed(input)?;
+        let (input, file_data) = parse_bacnet_application_octet_string(input)?;
+        let (input, _) = parse_bacnet_expected_tag(input, 0, BacnetTagKind::Closing)?;
+        (
+            input,
+            AtomicWriteFileAccess::Stream {
+                file_start_position,
+                file_data,
+            },
+        )
+    } else {
+        let (input, _) = parse_bacnet_expected_tag(input, 1, BacnetTagKind::Opening)?;
+        let (input, file_start_record) = parse_bacnet_application_signed(input)?;
+        let (mut input, record_count) = parse_bacnet_application_unsigned(input)?;
+        let mut file_record_data = Vec::new();
+        while !is_bacnet_tag(input, 1, BacnetTagKind::Closing) {
+            let (remain, data) = parse_bacnet_application_octet_string(input)?;
+            file_record_data.push(data);
+            input = remain;
+        }
+        let (input, _) = parse_bacnet_expected_tag(input, 1, BacnetTagKind::Closing)?;
+        (
+            input,
+            AtomicWriteFileAccess::Record {
+                file_start_record,
+                record_count,
+                file_record_data,
+            },
+        )
+    };
+    Ok((
+        input,
+        ConfirmedServiceRequest::AtomicWriteFile {
+            file_identifier,
+            access,
+        },
+    ))
 }
 
 fn parse_confirmed_service_request_add_list_element(
@@ -1280,27 +1841,59 @@ fn parse_confirmed_service_request_read_property_conditional(
 fn parse_confirmed_service_request_read_property_multiple(
     input: &[u8],
 ) -> IResult<&[u8], ConfirmedServiceRequest> {
-    Ok((input, ConfirmedServiceRequest::ReadPropertyMultiple {}))
+    let (input, read_access_specs) = many_until_eof(input, parse_read_access_specification)?;
+    Ok((
+        input,
+        ConfirmedServiceRequest::ReadPropertyMultiple { read_access_specs },
+    ))
 }
 
 fn parse_confirmed_service_request_write_property(
     input: &[u8],
 ) -> IResult<&[u8], ConfirmedServiceRequest> {
-    Ok((input, ConfirmedServiceRequest::WriteProperty {}))
+    // [0] 对象标识 [1] 属性标识 [2] 数组下标(可选) [3] 值 [4] 优先级(可选)
+    let (input, object_identifier) = parse_bacnet_context_object_identifier(input, 0)?;
+    let (input, property_identifier) = parse_bacnet_context_unsigned(input, 1)?;
+    let (input, property_array_index) = parse_bacnet_optional_context_unsigned(input, 2)?;
+    let (input, value) = parse_bacnet_property_value_data(input, 3)?;
+    let (input, priority) = parse_bacnet_optional_context_unsigned(input, 4)?;
+    Ok((
+        input,
+        ConfirmedServiceRequest::WriteProperty {
+            object_identifier,
+            property: BacnetPropertyValue {
+                property_identifier,
+                property_array_index,
+                value,
+                priority,
+            },
+        },
+    ))
 }
 
 fn parse_confirmed_service_request_write_property_multiple(
     input: &[u8],
 ) -> IResult<&[u8], ConfirmedServiceRequest> {
-    Ok((input, ConfirmedServiceRequest::WritePropertyMultiple {}))
+    let (input, write_access_specs) = many_until_eof(input, parse_write_access_specification)?;
+    Ok((
+        input,
+        ConfirmedServiceRequest::WritePropertyMultiple { write_access_specs },
+    ))
 }
 
 fn parse_confirmed_service_request_device_communication_control(
     input: &[u8],
 ) -> IResult<&[u8], ConfirmedServiceRequest> {
+    let (input, time_duration) = parse_bacnet_optional_context_unsigned(input, 0)?;
+    let (input, enable_disable) = parse_bacnet_context_unsigned(input, 1)?;
+    let (input, password) = parse_bacnet_optional_context_character_string(input, 2)?;
     Ok((
         input,
-        ConfirmedServiceRequest::DeviceCommunicationControl {},
+        ConfirmedServiceRequest::DeviceCommunicationControl {
+            time_duration,
+            enable_disable,
+            password,
+        },
     ))
 }
 
@@ -1319,7 +1912,15 @@ fn parse_confirmed_service_request_confirmed_text_message(
 fn parse_confirmed_service_request_reinitialize_device(
     input: &[u8],
 ) -> IResult<&[u8], ConfirmedServiceRequest> {
-    Ok((input, ConfirmedServiceRequest::ReinitializeDevice {}))
+    let (input, reinitialized_state) = parse_bacnet_context_unsigned(input, 0)?;
+    let (input, password) = parse_bacnet_optional_context_character_string(input, 1)?;
+    Ok((
+        input,
+        ConfirmedServiceRequest::ReinitializeDevice {
+            reinitialized_state,
+            password,
+        },
+    ))
 }
 
 fn parse_confirmed_service_request_vt_open(
@@ -1731,6 +2332,109 @@ pub fn parse_confirmed_service_ack(
     Ok((input, confirmed_service_ack))
 }
 
+fn parse_unconfirmed_service_request_i_am(
+    input: &[u8],
+) -> IResult<&[u8], UnconfirmedServiceRequest> {
+    let (input, device_identifier) = parse_bacnet_application_object_identifier(input)?;
+    let (input, max_apdu_length_accepted) = parse_bacnet_application_unsigned(input)?;
+    let (input, segmentation_supported) = parse_bacnet_application_enumerated(input)?;
+    let (input, vendor_id) = parse_bacnet_application_unsigned(input)?;
+    Ok((
+        input,
+        UnconfirmedServiceRequest::IAm {
+            device_identifier,
+            max_apdu_length_accepted,
+            segmentation_supported,
+            vendor_id,
+        },
+    ))
+}
+
+fn parse_unconfirmed_service_request_i_have(
+    input: &[u8],
+) -> IResult<&[u8], UnconfirmedServiceRequest> {
+    let (input, device_identifier) = parse_bacnet_application_object_identifier(input)?;
+    let (input, object_identifier) = parse_bacnet_application_object_identifier(input)?;
+    let (input, object_name) = parse_bacnet_application_character_string(input)?;
+    Ok((
+        input,
+        UnconfirmedServiceRequest::IHave {
+            device_identifier,
+            object_identifier,
+            object_name,
+        },
+    ))
+}
+
+// Who-Has: [0] [1] 设备实例范围(可选)，[2] 对象标识或 [3] 对象名
+fn parse_unconfirmed_service_request_who_has(
+    input: &[u8],
+) -> IResult<&[u8], UnconfirmedServiceRequest> {
+    let (input, device_instance_range_low_limit) = parse_bacnet_optional_context_unsigned(input, 0)?;
+    let (input, device_instance_range_high_limit) =
+        parse_bacnet_optional_context_unsigned(input, 1)?;
+    let (input, object) = if is_bacnet_tag(input, 2, BacnetTagKind::Context) {
+        map(|i| parse_bacnet_context_object_identifier(i, 2), WhoHasObject::Identifier)(input)?
+    } else {
+        match parse_bacnet_optional_context_character_string(input, 3)? {
+            (input, Some(name)) => (input, WhoHasObject::Name(name)),
+            (_, None) => return bacnet_error(input),
+        }
+    };
+    Ok((
+        input,
+        UnconfirmedServiceRequest::WhoHas {
+            device_instance_range_low_limit,
+            device_instance_range_high_limit,
+            object,
+        },
+    ))
+}
+
+fn parse_unconfirmed_service_request_who_is(
+    input: &[u8],
+) -> IResult<&[u8], UnconfirmedServiceRequest> {
+    let (input, device_instance_range_low_limit) = parse_bacnet_optional_context_unsigned(input, 0)?;
+    let (input, device_instance_range_high_limit) =
+        parse_bacnet_optional_context_unsigned(input, 1)?;
+    Ok((
+        input,
+        UnconfirmedServiceRequest::WhoIs {
+            device_instance_range_low_limit,
+            device_instance_range_high_limit,
+        },
+    ))
+}
+
+// 未解码的服务保留剩余数据，由上层按负载处理
+pub fn parse_unconfirmed_service_request(
+    input: &[u8],
+    service_choice: u8,
+) -> IResult<&[u8], UnconfirmedServiceRequest> {
+    let (input, unconfirmed_service_request) = match service_choice {
+        0x0 => parse_unconfirmed_service_request_i_am(input),
+        0x01 => parse_unconfirmed_service_request_i_have(input),
+        0x02 => Ok((input, UnconfirmedServiceRequest::UnconfirmedCovNotification {})),
+        0x03 => Ok((input, UnconfirmedServiceRequest::UnconfirmedEventNotification {})),
+        0x04 => Ok((input, UnconfirmedServiceRequest::UnconfirmedPrivateTransfer {})),
+        0x05 => Ok((input, UnconfirmedServiceRequest::UnconfirmedTextMessage {})),
+        0x06 => Ok((input, UnconfirmedServiceRequest::TimeSynchronization {})),
+        0x07 => parse_unconfirmed_service_request_who_has(input),
+        0x08 => parse_unconfirmed_service_request_who_is(input),
+        0x09 => Ok((input, UnconfirmedServiceRequest::UtcTimeSynchronization {})),
+        0x0a => Ok((input, UnconfirmedServiceRequest::WriteGroup {})),
+        0x0b => Ok((input, UnconfirmedServiceRequest::UnconfirmedCovNotificationMultiple {})),
+        0x0c => Ok((input, UnconfirmedServiceRequest::UnconfirmedAuditNotification {})),
+        0x0d => Ok((input, UnconfirmedServiceRequest::WhoAmI {})),
+        0x0e => Ok((input, UnconfirmedServiceRequest::YouAre {})),
+        _ => Err(nom::Err::Error(nom::error::Error::new(
+            input,
+            nom::error::ErrorKind::Verify,
+        ))),
+    }?;
+    Ok((input, unconfirmed_service_request))
+}
+
 pub fn parse_apdu_info(input: &[u8], apdu_type: u8, pdu_flags: u8) -> IResult<&[u8], ApduInfo> {
     if apdu_type == 0 {
         let (input, (unknow_bit, response_segments, max_adpu_size)) =
@@ -1756,6 +2460,27 @@ pub fn parse_apdu_info(input: &[u8], apdu_type: u8, pdu_flags: u8) -> IResult<&[
                 confirmed_service_request,
             },
         ))
+    } else if apdu_type == 1 {
+        let (input, service_choice) = u8(input)?;
+        let (input, unconfirmed_service_request) =
+            parse_unconfirmed_service_request(input, service_choice)?;
+        Ok((
+            input,
+            ApduInfo::UnconfirmedServiceRequest {
+                service_choice,
+                unconfirmed_service_request,
+            },
+        ))
+    } else if apdu_type == 2 {
+        let (input, invoke_id) = u8(input)?;
+        let (input, service_choice) = u8(input)?;
+        Ok((
+            input,
+            ApduInfo::SimpleAckPdu {
+                invoke_id,
+                service_choice,
+            },
+        ))
     } else if apdu_type == 3 {
         let (input, invoke_id) = u8(input)?;
         let (input, segmented_req_info) = parse_segmented_req_info(input, pdu_flags)?;
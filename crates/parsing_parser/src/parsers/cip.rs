@@ -0,0 +1,670 @@
+use nom::bytes::complete::take;
+use nom::combinator::rest;
+use nom::multi::count;
+use nom::number::complete::{le_u16, le_u32, u8};
+use nom::IResult;
+
+// CIP (Common Industrial Protocol) 消息格式
+//
+// 请求:  | Service | Path Size(字) | Path (EPATH) | Request Data |
+// 响应:  | Service|0x80 | Reserved | General Status | Additional Status Size(字) | Additional Status | Response Data |
+
+pub const CIP_REPLY_FLAG: u8 = 0x80;
+
+// 通用服务
+pub const CIP_GET_ATTRIBUTES_ALL: u8 = 0x01;
+pub const CIP_SET_ATTRIBUTES_ALL: u8 = 0x02;
+pub const CIP_RESET: u8 = 0x05;
+pub const CIP_START: u8 = 0x06;
+pub const CIP_STOP: u8 = 0x07;
+pub const CIP_MULTIPLE_SERVICE_PACKET: u8 = 0x0a;
+pub const CIP_GET_ATTRIBUTE_SINGLE: u8 = 0x0e;
+pub const CIP_SET_ATTRIBUTE_SINGLE: u8 = 0x10;
+
+// Logix 标签服务
+pub const CIP_READ_TAG: u8 = 0x4c;
+pub const CIP_WRITE_TAG: u8 = 0x4d;
+pub const CIP_READ_TAG_FRAGMENTED: u8 = 0x52;
+pub const CIP_WRITE_TAG_FRAGMENTED: u8 = 0x53;
+
+// Connection Manager 服务
+pub const CIP_FORWARD_CLOSE: u8 = 0x4e;
+pub const CIP_UNCONNECTED_SEND: u8 = 0x52;
+pub const CIP_FORWARD_OPEN: u8 = 0x54;
+pub const CIP_LARGE_FORWARD_OPEN: u8 = 0x5b;
+
+pub const CIP_CLASS_CONNECTION_MANAGER: u32 = 0x06;
+
+// 结构体标签的数据类型后跟 2 字节结构句柄
+const CIP_STRUCT_TYPE: u16 = 0x02a0;
+
+// 部分传输 (Read/Write Tag Fragmented 未完)
+pub const CIP_STATUS_PARTIAL_TRANSFER: u8 = 0x06;
+
+// Multiple Service Packet、Unconnected Send 的嵌套层数上限，更深的内嵌消息保留原始数据
+const CIP_MAX_NESTING_DEPTH: usize = 4;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum CipSegment<'a> {
+    Port { port: u16, link_address: &'a [u8] },
+    Class(u32),
+    Instance(u32),
+    Member(u32),
+    ConnectionPoint(u32),
+    Attribute(u32),
+    Symbol(&'a str),
+    // 电子键、数据段等
+    Other { segment_type: u8, data: &'a [u8] },
+}
+
+/// CipPath是请求的目标路径(padded EPATH)。
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct CipPath<'a> {
+    pub segments: Vec<CipSegment<'a>>,
+}
+
+impl<'a> CipPath<'a> {
+    pub fn class(&self) -> Option<u32> {
+        self.segments.iter().find_map(|segment| match segment {
+            CipSegment::Class(class) => Some(*class),
+            _ => None,
+        })
+    }
+
+    pub fn instance(&self) -> Option<u32> {
+        self.segments.iter().find_map(|segment| match segment {
+            CipSegment::Instance(instance) => Some(*instance),
+            _ => None,
+        })
+    }
+
+    pub fn attribute(&self) -> Option<u32> {
+        self.segments.iter().find_map(|segment| match segment {
+            CipSegment::Attribute(attribute) => Some(*attribute),
+            _ => None,
+        })
+    }
+
+    /// 符号段组成的 Logix 标签名，如 `Program:Main.Motor[3]`
+    pub fn tag_name(&self) -> Option<String> {
+        let mut name = String::new();
+        let mut in_index = false;
+        for segment in &self.segments {
+            match segment {
+                CipSegment::Symbol(symbol) => {
+                    if in_index {
+                        name.push(']');
+                        in_index = false;
+                    }
+                    if !name.is_empty() {
+                        name.push('.');
+                    }
+                    name.push_str(symbol);
+                }
+                CipSegment::Member(index) if !name.is_empty() => {
+                    name.push(if in_index { ',' } else { '[' });
+                    name.push_str(&index.to_string());
+                    in_index = true;
+                }
+                _ => {}
+            }
+        }
+        if name.is_empty() {
+            return None;
+        }
+        if in_index {
+            name.push(']');
+        }
+        Some(name)
+    }
+}
+
+fn verify_error<T>(input: &[u8]) -> IResult<&[u8], T> {
+    Err(nom::Err::Error(nom::error::Error::new(
+        input,
+        nom::error::ErrorKind::Verify,
+    )))
+}
+
+fn parse_pad(input: &[u8], padded: bool) -> IResult<&[u8], ()> {
+    if padded {
+        let (input, _) = u8(input)?;
+        return Ok((input, ()));
+    }
+    Ok((input, ()))
+}
+
+fn parse_cip_segment(input: &[u8]) -> IResult<&[u8], CipSegment> {
+    let (input, segment_type) = u8(input)?;
+    match segment_type >> 5 {
+        // 端口段
+        0 => {
+            let extended_link = segment_type & 0x10 != 0;
+            let (input, link_size) = if extended_link { u8(input)? } else { (input, 1) };
+            let (input, port) = if segment_type & 0x0f == 0x0f {
+                le_u16(input)?
+            } else {
+                (input, (segment_type & 0x0f) as u16)
+            };
+            let (input, link_address) = take(link_size as usize)(input)?;
+            let length = 1 + extended_link as usize + link_address.len()
+                + if segment_type & 0x0f == 0x0f { 2 } else { 0 };
+            let (input, _) = parse_pad(input, length % 2 == 1)?;
+            Ok((input, CipSegment::Port { port, link_address }))
+        }
+        // 逻辑段
+        1 => {
+            let logical_type = (segment_type >> 2) & 0x07;
+            // 电子键
+            if logical_type == 5 {
+                let (input, data) = take(9usize)(input)?;
+                return Ok((input, CipSegment::Other { segment_type, data }));
+            }
+            let (input, value) = match segment_type & 0x03 {
+                0 => {
+                    let (input, value) = u8(input)?;
+                    (input, value as u32)
+                }
+                1 => {
+                    let (input, _) = u8(input)?;
+                    let (input, value) = le_u16(input)?;
+                    (input, value as u32)
+                }
+                2 => {
+                    let (input, _) = u8(input)?;
+                    le_u32(input)?
+                }
+                _ => return verify_error(input),
+            };
+            let segment = match logical_type {
+                0 => CipSegment::Class(value),
+                1 => CipSegment::Instance(value),
+                2 => CipSegment::Member(value),
+                3 => CipSegment::ConnectionPoint(value),
+                4 => CipSegment::Attribute(value),
+                _ => CipSegment::Other {
+                    segment_type,
+                    data: &[],
+                },
+            };
+            Ok((input, segment))
+        }
+        // 数据段
+        4 => {
+            let (input, size) = u8(input)?;
+            if segment_type == 0x91 {
+                // ANSI 扩展符号段
+                let (input, symbol) = take(size as usize)(input)?;
+                let (input, _) = parse_pad(input, size % 2 == 1)?;
+                let symbol = match std::str::from_utf8(symbol) {
+                    Ok(symbol) => symbol,
+                    Err(_) => return verify_error(input),
+                };
+                Ok((input, CipSegment::Symbol(symbol)))
+            } else {
+                // 简单数据段，长度以字计
+                let (input, data) = take(size as usize * 2)(input)?;
+                Ok((input, CipSegment::Other { segment_type, data }))
+            }
+        }
+        _ => verify_error(input),
+    }
+}
+
+pub fn parse_cip_path(input: &[u8]) -> IResult<&[u8], CipPath> {
+    let mut segments = Vec::new();
+    let mut input = input;
+    while !input.is_empty() {
+        let (remain, segment) = parse_cip_segment(input)?;
+        segments.push(segment);
+        input = remain;
+    }
+    Ok((input, CipPath { segments }))
+}
+
+// 路径长度以字计
+fn parse_sized_path(input: &[u8], path_size: u8) -> IResult<&[u8], CipPath> {
+    let (input, path) = take(path_size as usize * 2)(input)?;
+    let (_, path) = parse_cip_path(path)?;
+    Ok((input, path))
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ForwardOpenRequest<'a> {
+    pub priority_time_tick: u8,
+    pub timeout_ticks: u8,
+    pub o_t_connection_id: u32,
+    pub t_o_connection_id: u32,
+    pub connection_serial_number: u16,
+    pub originator_vendor_id: u16,
+    pub originator_serial_number: u32,
+    pub connection_timeout_multiplier: u8,
+    pub o_t_rpi: u32,
+    /// Large Forward Open 中为 32 位
+    pub o_t_network_connection_parameters: u32,
+    pub t_o_rpi: u32,
+    pub t_o_network_connection_parameters: u32,
+    pub transport_type_trigger: u8,
+    pub connection_path: CipPath<'a>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum CipRequestData<'a> {
+    MultipleServicePacket {
+        services: Vec<CipMessage<'a>>,
+    },
+    ReadTag {
+        elements: u16,
+    },
+    ReadTagFragmented {
+        elements: u16,
+        offset: u32,
+    },
+    WriteTag {
+        data_type: u16,
+        elements: u16,
+        data: &'a [u8],
+    },
+    WriteTagFragmented {
+        data_type: u16,
+        elements: u16,
+        offset: u32,
+        data: &'a [u8],
+    },
+    ForwardOpen(ForwardOpenRequest<'a>),
+    ForwardClose {
+        priority_time_tick: u8,
+        timeout_ticks: u8,
+        connection_serial_number: u16,
+        originator_vendor_id: u16,
+        originator_serial_number: u32,
+        connection_path: CipPath<'a>,
+    },
+    UnconnectedSend {
+        priority_time_tick: u8,
+        timeout_ticks: u8,
+        request: Box<CipMessage<'a>>,
+        route_path: CipPath<'a>,
+    },
+    Raw(&'a [u8]),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum CipResponseData<'a> {
+    MultipleServicePacket {
+        services: Vec<CipMessage<'a>>,
+    },
+    ReadTag {
+        data_type: u16,
+        data: &'a [u8],
+    },
+    ForwardOpen {
+        o_t_connection_id: u32,
+        t_o_connection_id: u32,
+        connection_serial_number: u16,
+        originator_vendor_id: u16,
+        originator_serial_number: u32,
+        o_t_api: u32,
+        t_o_api: u32,
+    },
+    ForwardClose {
+        connection_serial_number: u16,
+        originator_vendor_id: u16,
+        originator_serial_number: u32,
+    },
+    Raw(&'a [u8]),
+}
+
+/// CipMessage是一条 CIP 显式消息，响应的 service 已去除应答标志位。
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum CipMessage<'a> {
+    Request {
+        service: u8,
+        path: CipPath<'a>,
+        data: CipRequestData<'a>,
+    },
+    Response {
+        service: u8,
+        general_status: u8,
+        additional_status: Vec<u16>,
+        data: CipResponseData<'a>,
+    },
+}
+
+impl<'a> CipMessage<'a> {
+    pub fn service(&self) -> u8 {
+        match self {
+            CipMessage::Request { service, .. } => *service,
+            CipMessage::Response { service, .. } => *service,
+        }
+    }
+
+    pub fn general_status(&self) -> Option<u8> {
+        match self {
+            CipMessage::Request { .. } => None,
+            CipMessage::Response { general_status, .. } => Some(*general_status),
+        }
+    }
+
+    pub fn path(&self) -> Option<&CipPath<'a>> {
+        match self {
+            CipMessage::Request { path, .. } => Some(path),
+            CipMessage::Response { .. } => None,
+        }
+    }
+
+    /// 本消息及 Multiple Service Packet、Unconnected Send 中内嵌的全部消息
+    pub fn flatten(&self) -> Vec<&CipMessage<'a>> {
+        let mut messages = vec![self];
+        match self {
+            CipMessage::Request {
+                data: CipRequestData::MultipleServicePacket { services },
+                ..
+            }
+            | CipMessage::Response {
+                data: CipResponseData::MultipleServicePacket { services },
+                ..
+            } => {
+                for service in services {
+                    messages.extend(service.flatten());
+                }
+            }
+            CipMessage::Request {
+                data: CipRequestData::UnconnectedSend { request, .. },
+                ..
+            } => messages.extend(request.flatten()),
+            _ => {}
+        }
+        messages
+    }
+}
+
+/// 解析一条完整的 CIP 消息，消息长度由外层(CPF 数据项等)决定
+pub fn parse_cip_message(input: &[u8]) -> IResult<&[u8], CipMessage> {
+    parse_cip_message_with_depth(input, 0)
+}
+
+fn parse_cip_message_with_depth(input: &[u8], depth: usize) -> IResult<&[u8], CipMessage> {
+    let (input, service) = u8(input)?;
+    if service & CIP_REPLY_FLAG != 0 {
+        let service = service & !CIP_REPLY_FLAG;
+        let (input, _reserved) = u8(input)?;
+        let (input, general_status) = u8(input)?;
+        let (input, additional_status_size) = u8(input)?;
+        let (input, additional_status) = count(le_u16, additional_status_size as usize)(input)?;
+        let (input, data) = rest(input)?;
+        let data = parse_response_data(service, general_status, data, depth);
+        Ok((
+            input,
+            CipMessage::Response {
+                service,
+                general_status,
+                additional_status,
+                data,
+            },
+        ))
+    } else {
+        let (input, path_size) = u8(input)?;
+        let (input, path) = parse_sized_path(input, path_size)?;
+        let (input, data) = rest(input)?;
+        let data = parse_request_data(service, &path, data, depth);
+        Ok((input, CipMessage::Request { service, path, data }))
+    }
+}
+
+// 服务数据无法解析时保留原始数据
+fn parse_request_data<'a>(
+    service: u8,
+    path: &CipPath,
+    input: &'a [u8],
+    depth: usize,
+) -> CipRequestData<'a> {
+    let nested = depth < CIP_MAX_NESTING_DEPTH;
+    let result = if path.class() == Some(CIP_CLASS_CONNECTION_MANAGER) {
+        match service {
+            CIP_FORWARD_OPEN => parse_forward_open(input, false),
+            CIP_LARGE_FORWARD_OPEN => parse_forward_open(input, true),
+            CIP_FORWARD_CLOSE => parse_forward_close(input),
+            CIP_UNCONNECTED_SEND if nested => parse_unconnected_send(input, depth + 1),
+            _ => return CipRequestData::Raw(input),
+        }
+    } else {
+        match service {
+            CIP_MULTIPLE_SERVICE_PACKET if nested => parse_multiple_service_packet(input, depth + 1)
+                .map(|(input, services)| (input, CipRequestData::MultipleServicePacket { services })),
+            CIP_READ_TAG => parse_read_tag(input),
+            CIP_READ_TAG_FRAGMENTED => parse_read_tag_fragmented(input),
+            CIP_WRITE_TAG => parse_write_tag(input, false),
+            CIP_WRITE_TAG_FRAGMENTED => parse_write_tag(input, true),
+            _ => return CipRequestData::Raw(input),
+        }
+    };
+    match result {
+        Ok((_, data)) => data,
+        Err(_) => CipRequestData::Raw(input),
+    }
+}
+
+fn parse_response_data(
+    service: u8,
+    general_status: u8,
+    input: &[u8],
+    depth: usize,
+) -> CipResponseData {
+    if general_status != 0 && general_status != CIP_STATUS_PARTIAL_TRANSFER {
+        // Multiple Service Packet 中任一服务失败时仍携带各服务的应答
+        if service != CIP_MULTIPLE_SERVICE_PACKET {
+            return CipResponseData::Raw(input);
+        }
+    }
+    let result = match service {
+        CIP_MULTIPLE_SERVICE_PACKET if depth < CIP_MAX_NESTING_DEPTH => {
+            parse_multiple_service_packet(input, depth + 1).map(|(input, services)| {
+                (input, CipResponseData::MultipleServicePacket { services })
+            })
+        }
+        CIP_READ_TAG | CIP_READ_TAG_FRAGMENTED if !input.is_empty() => parse_read_tag_response(input),
+        CIP_FORWARD_OPEN | CIP_LARGE_FORWARD_OPEN => parse_forward_open_response(input),
+        CIP_FORWARD_CLOSE => parse_forward_close_response(input),
+        _ => return CipResponseData::Raw(input),
+    };
+    match result {
+        Ok((_, data)) => data,
+        Err(_) => CipResponseData::Raw(input),
+    }
+}
+
+// 服务数 + 各服务相对服务数字段的偏移
+fn parse_multiple_service_packet(input: &[u8], depth: usize) -> IResult<&[u8], Vec<CipMessage>> {
+    let base = input;
+    let (input, number) = le_u16(input)?;
+    let (input, offsets) = count(le_u16, number as usize)(input)?;
+    let mut services = Vec::with_capacity(offsets.len());
+    for (i, &offset) in offsets.iter().enumerate() {
+        let end = offsets.get(i + 1).map(|&end| end as usize).unwrap_or(base.len());
+        if offset as usize > end || end > base.len() {
+            return verify_error(input);
+        }
+        let (_, service) = parse_cip_message_with_depth(&base[offset as usize..end], depth)?;
+        services.push(service);
+    }
+    Ok((&base[base.len()..], services))
+}
+
+fn parse_data_type(input: &[u8]) -> IResult<&[u8], u16> {
+    let (input, data_type) = le_u16(input)?;
+    if data_type == CIP_STRUCT_TYPE {
+        let (input, _handle) = le_u16(input)?;
+        return Ok((input, data_type));
+    }
+    Ok((input, data_type))
+}
+
+fn parse_read_tag(input: &[u8]) -> IResult<&[u8], CipRequestData> {
+    let (input, elements) = le_u16(input)?;
+    Ok((input, CipRequestData::ReadTag { elements }))
+}
+
+fn parse_read_tag_fragmented(input: &[u8]) -> IResult<&[u8], CipRequestData> {
+    let (input, elements) = le_u16(input)?;
+    let (input, offset) = le_u32(input)?;
+    Ok((input, CipRequestData::ReadTagFragmented { elements, offset }))
+}
+
+fn parse_write_tag(input: &[u8], fragmented: bool) -> IResult<&[u8], CipRequestData> {
+    let (input, data_type) = parse_data_type(input)?;
+    let (input, elements) = le_u16(input)?;
+    if fragmented {
+        let (input, offset) = le_u32(input)?;
+        let (input, data) = rest(input)?;
+        return Ok((
+            input,
+            CipRequestData::WriteTagFragmented {
+                data_type,
+                elements,
+                offset,
+                data,
+            },
+        ));
+    }
+    let (input, data) = rest(input)?;
+    Ok((
+        input,
+        CipRequestData::WriteTag {
+            data_type,
+            elements,
+            data,
+        },
+    ))
+}
+
+fn parse_read_tag_response(input: &[u8]) -> IResult<&[u8], CipResponseData> {
+    let (input, data_type) = parse_data_type(input)?;
+    let (input, data) = rest(input)?;
+    Ok((input, CipResponseData::ReadTag { data_type, data }))
+}
+
+fn parse_forward_open(input: &[u8], large: bool) -> IResult<&[u8], CipRequestData> {
+    let parse_parameters = |input| -> IResult<&[u8], u32> {
+        if large {
+            le_u32(input)
+        } else {
+            let (input, parameters) = le_u16(input)?;
+            Ok((input, parameters as u32))
+        }
+    };
+    let (input, priority_time_tick) = u8(input)?;
+    let (input, timeout_ticks) = u8(input)?;
+    let (input, o_t_connection_id) = le_u32(input)?;
+    let (input, t_o_connection_id) = le_u32(input)?;
+    let (input, connection_serial_number) = le_u16(input)?;
+    let (input, originator_vendor_id) = le_u16(input)?;
+    let (input, originator_serial_number) = le_u32(input)?;
+    let (input, connection_timeout_multiplier) = u8(input)?;
+    let (input, _reserved) = take(3usize)(input)?;
+    let (input, o_t_rpi) = le_u32(input)?;
+    let (input, o_t_network_connection_parameters) = parse_parameters(input)?;
+    let (input, t_o_rpi) = le_u32(input)?;
+    let (input, t_o_network_connection_parameters) = parse_parameters(input)?;
+    let (input, transport_type_trigger) = u8(input)?;
+    let (input, path_size) = u8(input)?;
+    let (input, connection_path) = parse_sized_path(input, path_size)?;
+    Ok((
+        input,
+        CipRequestData::ForwardOpen(ForwardOpenRequest {
+            priority_time_tick,
+            timeout_ticks,
+            o_t_connection_id,
+            t_o_connection_id,
+            connection_serial_number,
+            originator_vendor_id,
+            originator_serial_number,
+            connection_timeout_multiplier,
+            o_t_rpi,
+            o_t_network_connection_parameters,
+            t_o_rpi,
+            t_o_network_connection_parameters,
+            transport_type_trigger,
+            connection_path,
+        }),
+    ))
+}
+
+fn parse_forward_close(input: &[u8]) -> IResult<&[u8], CipRequestData> {
+    let (input, priority_time_tick) = u8(input)?;
+    let (input, timeout_ticks) = u8(input)?;
+    let (input, connection_serial_number) = le_u16(input)?;
+    let (input, originator_vendor_id) = le_u16(input)?;
+    let (input, originator_serial_number) = le_u32(input)?;
+    let (input, path_size) = u8(input)?;
+    let (input, _reserved) = u8(input)?;
+    let (input, connection_path) = parse_sized_path(input, path_size)?;
+    Ok((
+        input,
+        CipRequestData::ForwardClose {
+            priority_time_tick,
+            timeout_ticks,
+            connection_serial_number,
+            originator_vendor_id,
+            originator_serial_number,
+            connection_path,
+        },
+    ))
+}
+
+fn parse_unconnected_send(input: &[u8], depth: usize) -> IResult<&[u8], CipRequestData> {
+    let (input, priority_time_tick) = u8(input)?;
+    let (input, timeout_ticks) = u8(input)?;
+    let (input, message_size) = le_u16(input)?;
+    let (input, message) = take(message_size as usize)(input)?;
+    let (_, request) = parse_cip_message_with_depth(message, depth)?;
+    let (input, _) = parse_pad(input, message_size % 2 == 1)?;
+    let (input, path_size) = u8(input)?;
+    let (input, _reserved) = u8(input)?;
+    let (input, route_path) = parse_sized_path(input, path_size)?;
+    Ok((
+        input,
+        CipRequestData::UnconnectedSend {
+            priority_time_tick,
+            timeout_ticks,
+            request: Box::new(request),
+            route_path,
+        },
+    ))
+}
+
+fn parse_forward_open_response(input: &[u8]) -> IResult<&[u8], CipResponseData> {
+    let (input, o_t_connection_id) = le_u32(input)?;
+    let (input, t_o_connection_id) = le_u32(input)?;
+    let (input, connection_serial_number) = le_u16(input)?;
+    let (input, originator_vendor_id) = le_u16(input)?;
+    let (input, originator_serial_number) = le_u32(input)?;
+    let (input, o_t_api) = le_u32(input)?;
+    let (input, t_o_api) = le_u32(input)?;
+    Ok((
+        input,
+        CipResponseData::ForwardOpen {
+            o_t_connection_id,
+            t_o_connection_id,
+            connection_serial_number,
+            originator_vendor_id,
+            originator_serial_number,
+            o_t_api,
+            t_o_api,
+        },
+    ))
+}
+
+fn parse_forward_close_response(input: &[u8]) -> IResult<&[u8], CipResponseData> {
+    let (input, connection_serial_number) = le_u16(input)?;
+    let (input, originator_vendor_id) = le_u16(input)?;
+    let (input, originator_serial_number) = le_u32(input)?;
+    Ok((
+        input,
+        CipResponseData::ForwardClose {
+            connection_serial_number,
+            originator_vendor_id,
+            originator_serial_number,
+        },
+    ))
+}
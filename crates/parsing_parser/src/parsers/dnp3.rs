@@ -11,7 +11,7 @@ use nom::error::{Error, ErrorKind};
 #[allow(unused)]
 use nom::multi::count;
 #[allow(unused)]
-use nom::number::complete::{be_u16, be_u24, be_u32, le_u16, le_u24, le_u32, u8};
+use nom::number::complete::{be_u16, be_u24, be_u32, le_u16, le_u24, le_u32, le_u64, u8};
 #[allow(unused)]
 use nom::sequence::tuple;
 #[allow(unused)]
@@ -41,30 +41,171 @@ use std::ops::BitAnd;
 use std::ops::BitOr;
 #[allow(unused)]
 use std::ops::BitXor;
+use std::collections::{HashMap, VecDeque};
 
 use super::parse_l5_eof_layer;
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+// 跨报文重组时同时缓存的分片数及单个分片的大小上限
+const DNP3_MAX_PENDING_FRAGMENTS: usize = 64;
+const DNP3_MAX_FRAGMENT_SIZE: usize = 64 * 1024;
+
+/// Dnp3Header是一个数据包中的 DNP3 报文。
+/// 数据包可能含有多个链路帧，按 FIR/FIN/序号重组传输段；
+/// 无状态解析时只有分片在本数据包内完整，`application_layer` 才有值；
+/// 经 [`QuinPacket::parse_from_flow`] 解析时由连接上的 [`Dnp3Reassembler`] 补全跨数据包的分片。
+#[derive(Debug, PartialEq, Clone)]
 pub struct Dnp3Header {
     pub data_link_layer: DataLinkLayer,
+    pub transport_control: Option<TransportControl>,
+    pub segments: Vec<Dnp3Segment>,
+    pub application_layer: Option<Dnp3ApplicationLayer>,
+}
+
+/// Dnp3Segment是一个链路帧所携带的传输段(已去除 CRC 与传输层头部)
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Dnp3Segment {
     pub transport_control: TransportControl,
-    pub application_layer: Dnp3ApplicationLayer,
+    pub data: Vec<u8>,
 }
 
 pub fn parse_dnp3_header(input: &[u8]) -> IResult<&[u8], Dnp3Header> {
-    let (input, data_link_layer) = parse_data_link_layer(input)?;
-    let (input, transport_control) = parse_transport_control(input)?;
-    let (input, application_layer) = parse_dnp3_application_layer(input, data_link_layer.length)?;
+    let (mut input, (data_link_layer, mut user_data)) = parse_link_frame(input)?;
+    let key = (data_link_layer.source, data_link_layer.destination);
+    let mut reassembler = Dnp3Reassembler::new();
+    let mut segments = Vec::new();
+    let mut application_layer = None;
+
+    // 仅有链路层的帧(如链路状态、复位)不携带传输段
+    while !user_data.is_empty() {
+        let (data, transport_control) = parse_transport_control(&user_data).map_err(|_| {
+            nom::Err::Error(nom::error::Error::new(input, nom::error::ErrorKind::Verify))
+        })?;
+        let segment = Dnp3Segment {
+            transport_control,
+            data: data.to_vec(),
+        };
+        let complete = reassembler.push_segment(key, &segment);
+        let tr_final = segment.transport_control.tr_final;
+        segments.push(segment);
+        if let Some(complete) = complete {
+            application_layer = Some(complete.map_err(|_| {
+                nom::Err::Error(nom::error::Error::new(input, nom::error::ErrorKind::Verify))
+            })?);
+            break;
+        }
+        if tr_final == 1 {
+            break;
+        }
+
+        // 同一数据包内的后续链路帧
+        match peek(parse_data_link_layer)(input) {
+            Ok((_, next)) if (next.source, next.destination) == key => {}
+            _ => break,
+        }
+        let frame;
+        (input, frame) = parse_link_frame(input)?;
+        user_data = frame.1;
+    }
+
     Ok((
         input,
         Dnp3Header {
             data_link_layer,
-            transport_control,
+            transport_control: segments.first().map(|o| o.transport_control.clone()),
+            segments,
             application_layer,
         },
     ))
 }
 
+/// Dnp3Reassembler按链路地址(源, 目的)跨帧、跨 TCP 报文重组应用层分片，每个 TCP 连接使用一个实例，
+/// 通常作为 [`QuinFlow`](crate::QuinFlow) 的一部分使用。
+#[derive(Debug, Default)]
+pub struct Dnp3Reassembler {
+    buffers: HashMap<(u16, u16), (u8, Vec<u8>)>,
+    // 按开始重组的先后顺序记录链路地址，超出上限时淘汰最早的分片
+    order: VecDeque<(u16, u16)>,
+}
+
+impl Dnp3Reassembler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 推入数据包中的传输段，本数据包内未完整的分片以跨报文重组的结果补全
+    pub fn update(&mut self, header: &mut Dnp3Header) {
+        let application_layer = self.push(header);
+        if header.application_layer.is_none() {
+            header.application_layer = application_layer;
+        }
+    }
+
+    /// 推入一个数据包中的全部传输段，返回其中完成的最后一个应用层分片
+    pub fn push(&mut self, header: &Dnp3Header) -> Option<Dnp3ApplicationLayer> {
+        let key = (header.data_link_layer.source, header.data_link_layer.destination);
+        let mut application_layer = None;
+        for segment in &header.segments {
+            if let Some(Ok(complete)) = self.push_segment(key, segment) {
+                application_layer = Some(complete);
+            }
+        }
+        application_layer
+    }
+
+    /// 推入一个传输段: FIR 开始新的分片，序号不连续时丢弃已缓存的数据，FIN 时解析完整分片
+    pub fn push_segment(
+        &mut self,
+        key: (u16, u16),
+        segment: &Dnp3Segment,
+    ) -> Option<Result<Dnp3ApplicationLayer, nom::error::ErrorKind>> {
+        let tc = &segment.transport_control;
+        if tc.tr_first == 1 {
+            if self.buffers.insert(key, (tc.tr_sequence, segment.data.clone())).is_none() {
+                self.order.push_back(key);
+            }
+            while self.order.len() > DNP3_MAX_PENDING_FRAGMENTS {
+                if let Some(oldest) = self.order.pop_front() {
+                    self.buffers.remove(&oldest);
+                }
+            }
+        } else {
+            match self.buffers.get_mut(&key) {
+                Some((sequence, buffer))
+                    if (*sequence + 1) % 64 == tc.tr_sequence
+                        && buffer.len() + segment.data.len() <= DNP3_MAX_FRAGMENT_SIZE =>
+                {
+                    *sequence = tc.tr_sequence;
+                    buffer.extend_from_slice(&segment.data);
+                }
+                _ => {
+                    tracing::warn!(target: "PARSER(dnp3::Dnp3Reassembler)", ?key, "unexpected transport segment");
+                    self.remove(key);
+                    return None;
+                }
+            }
+        }
+
+        if tc.tr_final == 1 {
+            let fragment = self.remove(key)?;
+            return Some(
+                parse_dnp3_application_layer(&fragment)
+                    .map(|(_, o)| o)
+                    .map_err(|e| match e {
+                        nom::Err::Error(e) | nom::Err::Failure(e) => e.code,
+                        nom::Err::Incomplete(_) => nom::error::ErrorKind::Eof,
+                    }),
+            );
+        }
+        None
+    }
+
+    fn remove(&mut self, key: (u16, u16)) -> Option<Vec<u8>> {
+        let (_, fragment) = self.buffers.remove(&key)?;
+        self.order.retain(|o| *o != key);
+        Some(fragment)
+    }
+}
+
 pub fn parse_dnp3_layer<'a>(
     input: &'a [u8],
     link_layer: LinkLayer,
@@ -82,19 +223,27 @@ pub fn parse_dnp3_layer<'a>(
                 error = ?e
             );
 
-            let offset = match e {
-                nom::Err::Error(error) => input.len() - error.input.len(),
-                _ => usize::MAX
+            // CRC 校验失败以 Failure 返回，单独报告
+            let error = match e {
+                nom::Err::Error(error) => ParseError::ParsingHeader {
+                    protocol: current_prototype,
+                    offset: input.len() - error.input.len(),
+                },
+                nom::Err::Failure(error) => ParseError::ChecksumMismatch {
+                    protocol: current_prototype,
+                    offset: input.len() - error.input.len(),
+                },
+                _ => ParseError::ParsingHeader {
+                    protocol: current_prototype,
+                    offset: usize::MAX,
+                },
             };
 
             return QuinPacket::L4(L4Packet {
                 link_layer,
                 network_layer,
                 transport_layer,
-                error: Some(ParseError::ParsingHeader{
-                    protocol: current_prototype,
-                    offset
-                }),
+                error: Some(error),
                 remain: input,
             })
         }
@@ -143,7 +292,7 @@ pub struct TransportControl {
     pub tr_sequence: u8,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Dnp3ApplicationLayer {
     pub app_control: u8,
     pub function_code: u8,
@@ -152,8 +301,15 @@ pub struct Dnp3ApplicationLayer {
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Qualifier {
-    prefix_code: u8,
-    range_code: u8,
+    pub prefix_code: u8,
+    pub range_code: u8,
+}
+
+impl Qualifier {
+    #[inline]
+    pub fn to_u8(&self) -> u8 {
+        (self.prefix_code << 4) | self.range_code
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -163,15 +319,51 @@ pub enum NumOfItem {
     None,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct DataObject {
     pub obj: u16,
     pub qualifier: Qualifier,
     pub num_of_item: NumOfItem,
-    // TODO: Points
+    pub points: Vec<Dnp3Point>,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+impl DataObject {
+    #[inline]
+    pub fn group(&self) -> u8 {
+        (self.obj >> 8) as u8
+    }
+
+    #[inline]
+    pub fn variation(&self) -> u8 {
+        self.obj as u8
+    }
+}
+
+/// Dnp3Point是对象中的一个点: 点号来自前缀或范围，请求中可能不携带取值
+#[derive(Debug, PartialEq, Clone)]
+pub struct Dnp3Point {
+    pub index: u32,
+    pub value: Dnp3PointValue,
+}
+
+/// Dnp3PointValue按组/变体解析的点值，time 为 48 位毫秒时间(相对时间变体为 16 位偏移)
+#[derive(Debug, PartialEq, Clone)]
+pub enum Dnp3PointValue {
+    None,
+    Binary { flags: Option<u8>, state: bool, time: Option<u64> },
+    DoubleBit { flags: Option<u8>, state: u8, time: Option<u64> },
+    Counter { flags: Option<u8>, value: u32, time: Option<u64> },
+    Analog { flags: Option<u8>, value: f64, time: Option<u64> },
+    // g12v1/g12v2
+    Crob { control_code: u8, count: u8, on_time: u32, off_time: u32, status: u8 },
+    // g41
+    AnalogOutput { value: f64, status: u8 },
+    // g50/g51/g52
+    Time(u64),
+    Raw(Vec<u8>),
+}
+
+#[derive(Debug, PartialEq, Clone)]
 pub enum Dnp3ApplicationData {
     // 0x00
     Confirm,
@@ -187,12 +379,62 @@ pub enum Dnp3ApplicationData {
     Select {
         objects: Vec<DataObject>,
     },
+    // 0x04
+    Operate {
+        objects: Vec<DataObject>,
+    },
+    // 0x05
+    DirectOperate {
+        objects: Vec<DataObject>,
+    },
+    // 0x06
+    DirectOperateNoResponse {
+        objects: Vec<DataObject>,
+    },
+    // 0x07
+    ImmediateFreeze {
+        objects: Vec<DataObject>,
+    },
+    // 0x08
+    ImmediateFreezeNoResponse {
+        objects: Vec<DataObject>,
+    },
+    // 0x09
+    FreezeClear {
+        objects: Vec<DataObject>,
+    },
+    // 0x0a
+    FreezeClearNoResponse {
+        objects: Vec<DataObject>,
+    },
+    // 0x0b
+    FreezeAtTime {
+        objects: Vec<DataObject>,
+    },
+    // 0x0c
+    FreezeAtTimeNoResponse {
+        objects: Vec<DataObject>,
+    },
     // 0x0d
     ColdRestart,
     // 0x0e
     WarmRestart,
+    // 0x0f
+    InitializeData,
+    // 0x10
+    InitializeApplication {
+        objects: Vec<DataObject>,
+    },
+    // 0x11
+    StartApplication {
+        objects: Vec<DataObject>,
+    },
     // 0x12
-    StopApplication,
+    StopApplication {
+        objects: Vec<DataObject>,
+    },
+    // 0x13
+    SaveConfiguration,
     // 0x14
     EnableSpontaneousMessage {
         objects: Vec<DataObject>,
@@ -201,10 +443,50 @@ pub enum Dnp3ApplicationData {
     DisableSpontaneousMessage {
         objects: Vec<DataObject>,
     },
+    // 0x16
+    AssignClass {
+        objects: Vec<DataObject>,
+    },
+    // 0x17
+    DelayMeasure,
+    // 0x18
+    RecordCurrentTime,
     // 0x19
     OpenFile {
         objects: Vec<DataObject>,
     },
+    // 0x1a
+    CloseFile {
+        objects: Vec<DataObject>,
+    },
+    // 0x1b
+    DeleteFile {
+        objects: Vec<DataObject>,
+    },
+    // 0x1c
+    GetFileInfo {
+        objects: Vec<DataObject>,
+    },
+    // 0x1d
+    AuthenticateFile {
+        objects: Vec<DataObject>,
+    },
+    // 0x1e
+    AbortFile {
+        objects: Vec<DataObject>,
+    },
+    // 0x1f
+    ActivateConfig {
+        objects: Vec<DataObject>,
+    },
+    // 0x20
+    AuthenticateRequest {
+        objects: Vec<DataObject>,
+    },
+    // 0x21
+    AuthenticateRequestNoAck {
+        objects: Vec<DataObject>,
+    },
     // 0x81
     Response {
         internal_indications: u16,
@@ -215,9 +497,54 @@ pub enum Dnp3ApplicationData {
         internal_indications: u16,
         objects: Vec<DataObject>,
     },
+    // 0x83
+    AuthenticateResponse {
+        internal_indications: u16,
+        objects: Vec<DataObject>,
+    },
+}
+
+impl Dnp3ApplicationData {
+    /// 报文携带的对象，无对象的功能码返回空切片
+    pub fn objects(&self) -> &[DataObject] {
+        match self {
+            Dnp3ApplicationData::Read { objects, .. }
+            | Dnp3ApplicationData::Write { objects, .. }
+            | Dnp3ApplicationData::Select { objects, .. }
+            | Dnp3ApplicationData::Operate { objects, .. }
+            | Dnp3ApplicationData::DirectOperate { objects, .. }
+            | Dnp3ApplicationData::DirectOperateNoResponse { objects, .. }
+            | Dnp3ApplicationData::ImmediateFreeze { objects, .. }
+            | Dnp3ApplicationData::ImmediateFreezeNoResponse { objects, .. }
+            | Dnp3ApplicationData::FreezeClear { objects, .. }
+            | Dnp3ApplicationData::FreezeClearNoResponse { objects, .. }
+            | Dnp3ApplicationData::FreezeAtTime { objects, .. }
+            | Dnp3ApplicationData::FreezeAtTimeNoResponse { objects, .. }
+            | Dnp3ApplicationData::InitializeApplication { objects, .. }
+            | Dnp3ApplicationData::StartApplication { objects, .. }
+            | Dnp3ApplicationData::StopApplication { objects, .. }
+            | Dnp3ApplicationData::EnableSpontaneousMessage { objects, .. }
+            | Dnp3ApplicationData::DisableSpontaneousMessage { objects, .. }
+            | Dnp3ApplicationData::AssignClass { objects, .. }
+            | Dnp3ApplicationData::OpenFile { objects, .. }
+            | Dnp3ApplicationData::CloseFile { objects, .. }
+            | Dnp3ApplicationData::DeleteFile { objects, .. }
+            | Dnp3ApplicationData::GetFileInfo { objects, .. }
+            | Dnp3ApplicationData::AuthenticateFile { objects, .. }
+            | Dnp3ApplicationData::AbortFile { objects, .. }
+            | Dnp3ApplicationData::ActivateConfig { objects, .. }
+            | Dnp3ApplicationData::AuthenticateRequest { objects, .. }
+            | Dnp3ApplicationData::AuthenticateRequestNoAck { objects, .. }
+            | Dnp3ApplicationData::Response { objects, .. }
+            | Dnp3ApplicationData::UnsolicitedResponse { objects, .. }
+            | Dnp3ApplicationData::AuthenticateResponse { objects, .. } => objects,
+            _ => &[],
+        }
+    }
 }
 
 pub fn parse_data_link_layer(input: &[u8]) -> IResult<&[u8], DataLinkLayer> {
+    let frame_start = input;
     let (input, data_header_buffer) = peek(take(8usize))(input)?;
     let (input, _) = tag::<_, _, nom::error::Error<&[u8]>>([0x05, 0x64])(input)?;
     let (input, length) = u8(input)?;
@@ -235,8 +562,8 @@ pub fn parse_data_link_layer(input: &[u8]) -> IResult<&[u8], DataLinkLayer> {
     match crc16_0x3d65_check(data_header_crc, data_header_buffer, 0) {
         true => {}
         false => {
-            return Err(nom::Err::Error(nom::error::Error::new(
-                input,
+            return Err(nom::Err::Failure(nom::error::Error::new(
+                frame_start,
                 nom::error::ErrorKind::Verify,
             )))
         }
@@ -259,9 +586,9 @@ pub fn parse_data_link_layer(input: &[u8]) -> IResult<&[u8], DataLinkLayer> {
 
 pub fn parse_transport_control(input: &[u8]) -> IResult<&[u8], TransportControl> {
     let (input, (tr_final, tr_first, tr_sequence)) =
-        peek(bits::<_, _, nom::error::Error<(&[u8], usize)>, _, _>(
+        bits::<_, _, nom::error::Error<(&[u8], usize)>, _, _>(
             tuple((take_bits(1usize), take_bits(1usize), take_bits(6usize))),
-        ))(input)?;
+        )(input)?;
     Ok((
         input,
         TransportControl {
@@ -273,13 +600,14 @@ pub fn parse_transport_control(input: &[u8]) -> IResult<&[u8], TransportControl>
 }
 
 pub fn parse_data_chunk(input: &[u8], check_size: u8) -> IResult<&[u8], &[u8]> {
+    let chunk_start = input;
     let (input, data_chunk) = take(check_size as usize)(input)?;
     let (input, data_chunk_checksum) = le_u16(input)?;
     match crc16_0x3d65_check(data_chunk_checksum, data_chunk, 0) {
         true => {}
         false => {
-            return Err(nom::Err::Error(nom::error::Error::new(
-                input,
+            return Err(nom::Err::Failure(nom::error::Error::new(
+                chunk_start,
                 nom::error::ErrorKind::Verify,
             )))
         }
@@ -311,47 +639,64 @@ pub fn parse_data_chunks(input: &[u8], length: u8) -> IResult<&[u8], Vec<u8>> {
     Ok((input, data_chunks))
 }
 
-pub fn parse_dnp3_application_layer<'a>(
-    input: &'a [u8],
-    dl_length: u8,
-) -> IResult<&'a [u8], Dnp3ApplicationLayer> {
-    let (input, data_chunks) = parse_data_chunks(input, dl_length)?;
-
-    let mut data_bytes = data_chunks.as_slice();
-    data_bytes = &data_bytes[1..]; // ignore transport_control
-
-    let (data_bytes, app_control) =
-        u8::<_, nom::error::Error<&[u8]>>(data_bytes).map_err(|_| {
-            nom::Err::Error(nom::error::Error::new(input, nom::error::ErrorKind::Verify))
-        })?;
-
-    let (_data_bytes, function_code) =
-        u8::<_, nom::error::Error<&[u8]>>(data_bytes).map_err(|_| {
-            nom::Err::Error(nom::error::Error::new(input, nom::error::ErrorKind::Verify))
-        })?;
+/// 解析一个链路帧，返回链路层头部与去除 CRC 后的用户数据
+pub fn parse_link_frame(input: &[u8]) -> IResult<&[u8], (DataLinkLayer, Vec<u8>)> {
+    let (input, data_link_layer) = parse_data_link_layer(input)?;
+    let (input, user_data) = parse_data_chunks(input, data_link_layer.length)?;
+    Ok((input, (data_link_layer, user_data)))
+}
 
-    // tracing::trace!("app_control: {:x?}, function_code: {:x?}, remain: {:x?}", app_control, function_code, data_bytes);
-
-    // TODO: parsing objects & points
-    let app_data = match function_code {
-        0x00 => Dnp3ApplicationData::Confirm {},
-        0x01 => Dnp3ApplicationData::Read { objects: vec![] },
-        0x02 => Dnp3ApplicationData::Write { objects: vec![] },
-        0x03 => Dnp3ApplicationData::Select { objects: vec![] },
-        0x0d => Dnp3ApplicationData::ColdRestart {},
-        0x0e => Dnp3ApplicationData::WarmRestart {},
-        0x12 => Dnp3ApplicationData::StopApplication {},
-        0x14 => Dnp3ApplicationData::EnableSpontaneousMessage { objects: vec![] },
-        0x15 => Dnp3ApplicationData::DisableSpontaneousMessage { objects: vec![] },
-        0x19 => Dnp3ApplicationData::OpenFile { objects: vec![] },
-        0x81 => Dnp3ApplicationData::Response {
-            internal_indications: 0,
-            objects: vec![],
-        },
-        0x82 => Dnp3ApplicationData::UnsolicitedResponse {
-            internal_indications: 0,
-            objects: vec![],
-        },
+/// 解析重组后的完整应用层分片
+pub fn parse_dnp3_application_layer(input: &[u8]) -> IResult<&[u8], Dnp3ApplicationLayer> {
+    let (input, app_control) = u8(input)?;
+    let (input, function_code) = u8(input)?;
+
+    // 请求中仅读取、冻结、类分配等功能码的对象不携带数据
+    let (input, app_data) = match function_code {
+        0x00 => (input, Dnp3ApplicationData::Confirm),
+        0x01 => map(|i| parse_data_objects(i, false), |objects| Dnp3ApplicationData::Read { objects })(input)?,
+        0x02 => map(|i| parse_data_objects(i, true), |objects| Dnp3ApplicationData::Write { objects })(input)?,
+        0x03 => map(|i| parse_data_objects(i, true), |objects| Dnp3ApplicationData::Select { objects })(input)?,
+        0x04 => map(|i| parse_data_objects(i, true), |objects| Dnp3ApplicationData::Operate { objects })(input)?,
+        0x05 => map(|i| parse_data_objects(i, true), |objects| Dnp3ApplicationData::DirectOperate { objects })(input)?,
+        0x06 => map(|i| parse_data_objects(i, true), |objects| Dnp3ApplicationData::DirectOperateNoResponse { objects })(input)?,
+        0x07 => map(|i| parse_data_objects(i, false), |objects| Dnp3ApplicationData::ImmediateFreeze { objects })(input)?,
+        0x08 => map(|i| parse_data_objects(i, false), |objects| Dnp3ApplicationData::ImmediateFreezeNoResponse { objects })(input)?,
+        0x09 => map(|i| parse_data_objects(i, false), |objects| Dnp3ApplicationData::FreezeClear { objects })(input)?,
+        0x0a => map(|i| parse_data_objects(i, false), |objects| Dnp3ApplicationData::FreezeClearNoResponse { objects })(input)?,
+        0x0b => map(|i| parse_data_objects(i, true), |objects| Dnp3ApplicationData::FreezeAtTime { objects })(input)?,
+        0x0c => map(|i| parse_data_objects(i, true), |objects| Dnp3ApplicationData::FreezeAtTimeNoResponse { objects })(input)?,
+        0x0d => (input, Dnp3ApplicationData::ColdRestart),
+        0x0e => (input, Dnp3ApplicationData::WarmRestart),
+        0x0f => (input, Dnp3ApplicationData::InitializeData),
+        0x10 => map(|i| parse_data_objects(i, true), |objects| Dnp3ApplicationData::InitializeApplication { objects })(input)?,
+        0x11 => map(|i| parse_data_objects(i, true), |objects| Dnp3ApplicationData::StartApplication { objects })(input)?,
+        0x12 => map(|i| parse_data_objects(i, true), |objects| Dnp3ApplicationData::StopApplication { objects })(input)?,
+        0x13 => (input, Dnp3ApplicationData::SaveConfiguration),
+        0x14 => map(|i| parse_data_objects(i, false), |objects| Dnp3ApplicationData::EnableSpontaneousMessage { objects })(input)?,
+        0x15 => map(|i| parse_data_objects(i, false), |objects| Dnp3ApplicationData::DisableSpontaneousMessage { objects })(input)?,
+        0x16 => map(|i| parse_data_objects(i, false), |objects| Dnp3ApplicationData::AssignClass { objects })(input)?,
+        0x17 => (input, Dnp3ApplicationData::DelayMeasure),
+        0x18 => (input, Dnp3ApplicationData::RecordCurrentTime),
+        0x19 => map(|i| parse_data_objects(i, true), |objects| Dnp3ApplicationData::OpenFile { objects })(input)?,
+        0x1a => map(|i| parse_data_objects(i, true), |objects| Dnp3ApplicationData::CloseFile { objects })(input)?,
+        0x1b => map(|i| parse_data_objects(i, true), |objects| Dnp3ApplicationData::DeleteFile { objects })(input)?,
+        0x1c => map(|i| parse_data_objects(i, true), |objects| Dnp3ApplicationData::GetFileInfo { objects })(input)?,
+        0x1d => map(|i| parse_data_objects(i, true), |objects| Dnp3ApplicationData::AuthenticateFile { objects })(input)?,
+        0x1e => map(|i| parse_data_objects(i, true), |objects| Dnp3ApplicationData::AbortFile { objects })(input)?,
+        0x1f => map(|i| parse_data_objects(i, true), |objects| Dnp3ApplicationData::ActivateConfig { objects })(input)?,
+        0x20 => map(|i| parse_data_objects(i, true), |objects| Dnp3ApplicationData::AuthenticateRequest { objects })(input)?,
+        0x21 => map(|i| parse_data_objects(i, true), |objects| Dnp3ApplicationData::AuthenticateRequestNoAck { objects })(input)?,
+        0x81..=0x83 => {
+            // IIN1 在高字节, IIN2 在低字节
+            let (input, internal_indications) = be_u16(input)?;
+            let (input, objects) = parse_data_objects(input, true)?;
+            match function_code {
+                0x81 => (input, Dnp3ApplicationData::Response { internal_indications, objects }),
+                0x82 => (input, Dnp3ApplicationData::UnsolicitedResponse { internal_indications, objects }),
+                _ => (input, Dnp3ApplicationData::AuthenticateResponse { internal_indications, objects }),
+            }
+        }
         _ => {
             return Err(nom::Err::Error(nom::error::Error::new(
                 input,
@@ -369,3 +714,270 @@ pub fn parse_dnp3_application_layer<'a>(
         },
     ))
 }
+
+/// 解析对象列表；分段截断或格式异常的对象及其后续数据被丢弃而不报错
+pub fn parse_data_objects(input: &[u8], with_data: bool) -> IResult<&[u8], Vec<DataObject>> {
+    let mut objects = Vec::new();
+    let mut input = input;
+    while !input.is_empty() {
+        match parse_data_object(input, with_data) {
+            Ok((_input, Some(object))) => {
+                objects.push(object);
+                input = _input;
+            }
+            // 未知长度的对象，之后的数据无法定位
+            Ok((_input, None)) => break,
+            Err(e) => {
+                tracing::warn!(target: "PARSER(dnp3::parse_data_objects)", error = ?e, "malformed object");
+                break;
+            }
+        }
+    }
+    Ok((input, objects))
+}
+
+fn parse_num_of_item(input: &[u8], range_code: u8) -> IResult<&[u8], NumOfItem> {
+    match range_code {
+        0x00 | 0x03 => map(tuple((u8, u8)), |(start, stop)| NumOfItem::StartStop {
+            start: start as u32,
+            stop: stop as u32,
+        })(input),
+        0x01 | 0x04 => map(tuple((le_u16, le_u16)), |(start, stop)| NumOfItem::StartStop {
+            start: start as u32,
+            stop: stop as u32,
+        })(input),
+        0x02 | 0x05 => map(tuple((le_u32, le_u32)), |(start, stop)| NumOfItem::StartStop {
+            start,
+            stop,
+        })(input),
+        0x06 => Ok((input, NumOfItem::None)),
+        0x07 | 0x0b => map(u8, |n| NumOfItem::Qualifier(n as u32))(input),
+        0x08 => map(le_u16, |n| NumOfItem::Qualifier(n as u32))(input),
+        0x09 => map(le_u32, NumOfItem::Qualifier)(input),
+        _ => Err(nom::Err::Error(Error::new(input, ErrorKind::Verify))),
+    }
+}
+
+/// 解析单个对象头及其点，遇到无法确定长度的组/变体时返回 None
+pub fn parse_data_object(input: &[u8], with_data: bool) -> IResult<&[u8], Option<DataObject>> {
+    let (input, (group, variation, qualifier)) = tuple((u8, u8, u8))(input)?;
+    let qualifier = Qualifier {
+        prefix_code: (qualifier >> 4) & 0x07,
+        range_code: qualifier & 0x0f,
+    };
+    let (input, num_of_item) = parse_num_of_item(input, qualifier.range_code)?;
+    let obj = ((group as u16) << 8) | variation as u16;
+
+    let (start, count) = match num_of_item {
+        NumOfItem::StartStop { start, stop } if stop >= start => (start, (stop - start) as u64 + 1),
+        NumOfItem::StartStop { .. } => return Err(nom::Err::Error(Error::new(input, ErrorKind::Verify))),
+        NumOfItem::Qualifier(n) => (0, n as u64),
+        NumOfItem::None => (0, 0),
+    };
+    // 每个点至少占 1 位，防止异常的数量字段
+    if count > input.len() as u64 * 8 && (with_data || qualifier.prefix_code != 0) {
+        return Err(nom::Err::Error(Error::new(input, ErrorKind::Verify)));
+    }
+
+    let mut points = Vec::new();
+    let mut input = input;
+    if qualifier.prefix_code == 0 {
+        if with_data && count > 0 {
+            if let Some(bits) = packed_bits(group, variation) {
+                // 目标工具链尚无 u64::div_ceil
+                #[allow(clippy::manual_div_ceil)]
+                let (_input, packed) = take((count * bits as u64 + 7) / 8)(input)?;
+                input = _input;
+                for i in 0..count as usize {
+                    let offset = i * bits as usize;
+                    let value = (packed[offset / 8] >> (offset % 8)) & ((1 << bits) - 1);
+                    let value = match bits {
+                        1 => Dnp3PointValue::Binary { flags: None, state: value != 0, time: None },
+                        _ => Dnp3PointValue::DoubleBit { flags: None, state: value, time: None },
+                    };
+                    points.push(Dnp3Point { index: start + i as u32, value });
+                }
+            } else {
+                for i in 0..count as u32 {
+                    let value;
+                    (input, value) = match parse_point_value(input, group, variation)? {
+                        (_input, Some(value)) => (_input, value),
+                        (_, None) => return Ok((input, None)),
+                    };
+                    points.push(Dnp3Point { index: start + i, value });
+                }
+            }
+        }
+    } else {
+        for i in 0..count as u32 {
+            let (_input, (index, size)) = match qualifier.prefix_code {
+                0x01 => map(u8, |n| (n as u32, None))(input)?,
+                0x02 => map(le_u16, |n| (n as u32, None))(input)?,
+                0x03 => map(le_u32, |n| (n, None))(input)?,
+                0x04 => map(u8, |n| (i, Some(n as usize)))(input)?,
+                0x05 => map(le_u16, |n| (i, Some(n as usize)))(input)?,
+                0x06 => map(le_u32, |n| (i, Some(n as usize)))(input)?,
+                _ => return Err(nom::Err::Error(Error::new(input, ErrorKind::Verify))),
+            };
+            input = _input;
+            let value = match size {
+                Some(size) => {
+                    let raw;
+                    (input, raw) = take(size)(input)?;
+                    Dnp3PointValue::Raw(raw.to_vec())
+                }
+                None if with_data => match parse_point_value(input, group, variation)? {
+                    (_input, Some(value)) => {
+                        input = _input;
+                        value
+                    }
+                    (_, None) => return Ok((input, None)),
+                },
+                None => Dnp3PointValue::None,
+            };
+            points.push(Dnp3Point { index, value });
+        }
+    }
+
+    Ok((
+        input,
+        Some(DataObject {
+            obj,
+            qualifier,
+            num_of_item,
+            points,
+        }),
+    ))
+}
+
+/// 无前缀时按位打包的对象，返回每个点所占位数
+fn packed_bits(group: u8, variation: u8) -> Option<u8> {
+    match (group, variation) {
+        (1, 1) | (10, 1) | (12, 3) | (80, 1) => Some(1),
+        (3, 1) => Some(2),
+        _ => None,
+    }
+}
+
+fn le_time48(input: &[u8]) -> IResult<&[u8], u64> {
+    map(tuple((le_u16, le_u32)), |(low, high)| ((high as u64) << 16) | low as u64)(input)
+}
+
+fn le_i16_f64(input: &[u8]) -> IResult<&[u8], f64> {
+    map(le_u16, |n| n as i16 as f64)(input)
+}
+
+fn le_i32_f64(input: &[u8]) -> IResult<&[u8], f64> {
+    map(le_u32, |n| n as i32 as f64)(input)
+}
+
+fn le_f32_f64(input: &[u8]) -> IResult<&[u8], f64> {
+    map(le_u32, |n| f32::from_bits(n) as f64)(input)
+}
+
+fn le_f64(input: &[u8]) -> IResult<&[u8], f64> {
+    map(le_u64, f64::from_bits)(input)
+}
+
+/// 按组/变体解析一个点的取值，未知组/变体返回 None
+pub fn parse_point_value(input: &[u8], group: u8, variation: u8) -> IResult<&[u8], Option<Dnp3PointValue>> {
+    let binary = |(flags, time): (u8, Option<u64>)| Dnp3PointValue::Binary {
+        flags: Some(flags),
+        state: flags & 0x80 != 0,
+        time,
+    };
+    let double_bit = |(flags, time): (u8, Option<u64>)| Dnp3PointValue::DoubleBit {
+        flags: Some(flags),
+        state: (flags >> 6) & 0x03,
+        time,
+    };
+    let counter = |(flags, value, time): (Option<u8>, u32, Option<u64>)| Dnp3PointValue::Counter { flags, value, time };
+    let analog = |(flags, value, time): (Option<u8>, f64, Option<u64>)| Dnp3PointValue::Analog { flags, value, time };
+
+    let (input, value) = match (group, variation) {
+        (1, 2) | (2, 1) | (10, 2) | (11, 1) | (13, 1) => map(u8, |f| binary((f, None)))(input)?,
+        (2, 2) | (11, 2) | (13, 2) => map(tuple((u8, le_time48)), |(f, t)| binary((f, Some(t))))(input)?,
+        (2, 3) => map(tuple((u8, le_u16)), |(f, t)| binary((f, Some(t as u64))))(input)?,
+        (3, 2) | (4, 1) => map(u8, |f| double_bit((f, None)))(input)?,
+        (4, 2) => map(tuple((u8, le_time48)), |(f, t)| double_bit((f, Some(t))))(input)?,
+        (4, 3) => map(tuple((u8, le_u16)), |(f, t)| double_bit((f, Some(t as u64))))(input)?,
+        (12, 1) | (12, 2) => map(
+            tuple((u8, u8, le_u32, le_u32, u8)),
+            |(control_code, count, on_time, off_time, status)| Dnp3PointValue::Crob {
+                control_code,
+                count,
+                on_time,
+                off_time,
+                status,
+            },
+        )(input)?,
+        // 计数器及冻结计数器
+        (20, 1) | (20, 3) | (21, 1) | (21, 3) | (22, 1) | (22, 3) | (23, 1) | (23, 3) => {
+            map(tuple((u8, le_u32)), |(f, v)| counter((Some(f), v, None)))(input)?
+        }
+        (20, 2) | (20, 4) | (21, 2) | (21, 4) | (22, 2) | (22, 4) | (23, 2) | (23, 4) => {
+            map(tuple((u8, le_u16)), |(f, v)| counter((Some(f), v as u32, None)))(input)?
+        }
+        (20, 5) | (20, 7) | (21, 9) | (21, 11) => map(le_u32, |v| counter((None, v, None)))(input)?,
+        (20, 6) | (20, 8) | (21, 10) | (21, 12) => map(le_u16, |v| counter((None, v as u32, None)))(input)?,
+        (21, 5) | (21, 7) | (22, 5) | (22, 7) | (23, 5) | (23, 7) => {
+            map(tuple((u8, le_u32, le_time48)), |(f, v, t)| counter((Some(f), v, Some(t))))(input)?
+        }
+        (21, 6) | (21, 8) | (22, 6) | (22, 8) | (23, 6) | (23, 8) => {
+            map(tuple((u8, le_u16, le_time48)), |(f, v, t)| counter((Some(f), v as u32, Some(t))))(input)?
+        }
+        // 模拟量输入、冻结模拟量、模拟量事件及模拟量输出状态
+        (30, 1) | (31, 1) | (32, 1) | (33, 1) | (40, 1) | (42, 1) => {
+            map(tuple((u8, le_i32_f64)), |(f, v)| analog((Some(f), v, None)))(input)?
+        }
+        (30, 2) | (31, 2) | (32, 2) | (33, 2) | (40, 2) | (42, 2) => {
+            map(tuple((u8, le_i16_f64)), |(f, v)| analog((Some(f), v, None)))(input)?
+        }
+        (30, 3) | (31, 5) | (34, 2) => map(le_i32_f64, |v| analog((None, v, None)))(input)?,
+        (30, 4) | (31, 6) => map(le_i16_f64, |v| analog((None, v, None)))(input)?,
+        (34, 1) => map(le_u16, |v| analog((None, v as f64, None)))(input)?,
+        (34, 3) => map(le_f32_f64, |v| analog((None, v, None)))(input)?,
+        (30, 5) | (31, 7) | (32, 5) | (33, 5) | (40, 3) | (42, 5) => {
+            map(tuple((u8, le_f32_f64)), |(f, v)| analog((Some(f), v, None)))(input)?
+        }
+        (30, 6) | (31, 8) | (32, 6) | (33, 6) | (40, 4) | (42, 6) => {
+            map(tuple((u8, le_f64)), |(f, v)| analog((Some(f), v, None)))(input)?
+        }
+        (31, 3) | (32, 3) | (33, 3) | (42, 3) => {
+            map(tuple((u8, le_i32_f64, le_time48)), |(f, v, t)| analog((Some(f), v, Some(t))))(input)?
+        }
+        (31, 4) | (32, 4) | (33, 4) | (42, 4) => {
+            map(tuple((u8, le_i16_f64, le_time48)), |(f, v, t)| analog((Some(f), v, Some(t))))(input)?
+        }
+        (32, 7) | (33, 7) | (42, 7) => {
+            map(tuple((u8, le_f32_f64, le_time48)), |(f, v, t)| analog((Some(f), v, Some(t))))(input)?
+        }
+        (32, 8) | (33, 8) | (42, 8) => {
+            map(tuple((u8, le_f64, le_time48)), |(f, v, t)| analog((Some(f), v, Some(t))))(input)?
+        }
+        // 模拟量输出块
+        (41, 1) => map(tuple((le_i32_f64, u8)), |(value, status)| Dnp3PointValue::AnalogOutput { value, status })(input)?,
+        (41, 2) => map(tuple((le_i16_f64, u8)), |(value, status)| Dnp3PointValue::AnalogOutput { value, status })(input)?,
+        (41, 3) => map(tuple((le_f32_f64, u8)), |(value, status)| Dnp3PointValue::AnalogOutput { value, status })(input)?,
+        (41, 4) => map(tuple((le_f64, u8)), |(value, status)| Dnp3PointValue::AnalogOutput { value, status })(input)?,
+        // 时间对象
+        (50, 1) | (50, 3) | (51, 1) | (51, 2) => map(le_time48, Dnp3PointValue::Time)(input)?,
+        (50, 2) => map(tuple((le_time48, le_u32)), |(t, _)| Dnp3PointValue::Time(t))(input)?,
+        (50, 4) => map(tuple((le_time48, le_u32, u8)), |(t, _, _)| Dnp3PointValue::Time(t))(input)?,
+        (52, 1) | (52, 2) => map(le_u16, |t| Dnp3PointValue::Time(t as u64))(input)?,
+        // 设备属性: 数据类型 + 长度 + 值
+        (0, _) => {
+            let (input, (_data_type, length)) = peek(tuple((u8, u8)))(input)?;
+            map(take(length as usize + 2), |raw: &[u8]| Dnp3PointValue::Raw(raw.to_vec()))(input)?
+        }
+        // 八位位组串与虚拟终端，长度即变体
+        (110, _) | (111, _) | (112, _) | (113, _) => {
+            map(take(variation as usize), |raw: &[u8]| Dnp3PointValue::Raw(raw.to_vec()))(input)?
+        }
+        _ => {
+            tracing::warn!(target: "PARSER(dnp3::parse_point_value)", group, variation, "unknown object size");
+            return Ok((input, None));
+        }
+    };
+    Ok((input, Some(value)))
+}
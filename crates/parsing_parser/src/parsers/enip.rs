@@ -0,0 +1,450 @@
+use nom::bytes::complete::take;
+use nom::combinator::rest;
+use nom::number::complete::{be_u16, be_u32, le_u16, le_u32, le_u64, u8};
+use nom::IResult;
+
+use std::net::Ipv4Addr;
+
+use crate::errors::ParseError;
+use crate::layer::{ApplicationLayer, LinkLayer, NetworkLayer, TransportLayer};
+use crate::packet::{L4Packet, L5Packet, QuinPacket, QuinPacketOptions};
+use crate::protocol::ApplicationProtocol;
+use crate::ProtocolType;
+
+use super::cip::{parse_cip_message, CipMessage};
+use super::parse_l5_eof_layer;
+
+// EtherNet/IP 封装格式 (TCP/UDP 44818)
+//
+//    +---------+--------+----------------+--------+----------------+---------+------------------+
+//    | Command | Length | Session Handle | Status | Sender Context | Options | Command Data     |
+//    | 2 bytes | 2 bytes|    4 bytes     | 4 bytes|    8 bytes     | 4 bytes | Length bytes     |
+//    +---------+--------+----------------+--------+----------------+---------+------------------+
+//
+// 隐式 I/O (UDP 2222) 报文没有封装头，只有 CPF(Common Packet Format) 数据项。
+
+// 封装命令
+pub const ENIP_NOP: u16 = 0x0000;
+pub const ENIP_LIST_SERVICES: u16 = 0x0004;
+pub const ENIP_LIST_IDENTITY: u16 = 0x0063;
+pub const ENIP_LIST_INTERFACES: u16 = 0x0064;
+pub const ENIP_REGISTER_SESSION: u16 = 0x0065;
+pub const ENIP_UNREGISTER_SESSION: u16 = 0x0066;
+pub const ENIP_SEND_RR_DATA: u16 = 0x006f;
+pub const ENIP_SEND_UNIT_DATA: u16 = 0x0070;
+
+// CPF 数据项类型
+pub const CPF_NULL_ADDRESS: u16 = 0x0000;
+pub const CPF_LIST_IDENTITY: u16 = 0x000c;
+pub const CPF_CONNECTED_ADDRESS: u16 = 0x00a1;
+pub const CPF_CONNECTED_DATA: u16 = 0x00b1;
+pub const CPF_UNCONNECTED_DATA: u16 = 0x00b2;
+pub const CPF_SOCKADDR_O_T: u16 = 0x8000;
+pub const CPF_SOCKADDR_T_O: u16 = 0x8001;
+pub const CPF_SEQUENCED_ADDRESS: u16 = 0x8002;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct EncapsulationHeader {
+    pub command: u16,
+    pub length: u16,
+    pub session_handle: u32,
+    pub status: u32,
+    pub sender_context: u64,
+    pub options: u32,
+}
+
+/// EnipHeader是一个 EtherNet/IP 报文，隐式 I/O 报文的 `encapsulation` 为 None。
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct EnipHeader<'a> {
+    pub encapsulation: Option<EncapsulationHeader>,
+    pub data: EnipData<'a>,
+}
+
+impl<'a> EnipHeader<'a> {
+    pub fn command(&self) -> Option<u16> {
+        self.encapsulation.map(|encapsulation| encapsulation.command)
+    }
+
+    pub fn items(&self) -> &[CpfItem<'a>] {
+        match &self.data {
+            EnipData::List { items }
+            | EnipData::SendRRData { items, .. }
+            | EnipData::SendUnitData { items, .. }
+            | EnipData::ImplicitIo { items } => items,
+            _ => &[],
+        }
+    }
+
+    /// 报文中的全部 CIP 消息，包括 Multiple Service Packet、Unconnected Send 内嵌的消息
+    pub fn cip_messages(&self) -> Vec<&CipMessage<'a>> {
+        self.items()
+            .iter()
+            .filter_map(|item| match &item.data {
+                CpfItemData::UnconnectedData(message) => Some(message),
+                CpfItemData::ConnectedData { message, .. } => Some(message),
+                _ => None,
+            })
+            .flat_map(|message| message.flatten())
+            .collect()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum EnipData<'a> {
+    RegisterSession {
+        protocol_version: u16,
+        options_flags: u16,
+    },
+    /// ListIdentity/ListServices/ListInterfaces，请求不含数据项
+    List {
+        items: Vec<CpfItem<'a>>,
+    },
+    SendRRData {
+        interface_handle: u32,
+        timeout: u16,
+        items: Vec<CpfItem<'a>>,
+    },
+    SendUnitData {
+        interface_handle: u32,
+        timeout: u16,
+        items: Vec<CpfItem<'a>>,
+    },
+    ImplicitIo {
+        items: Vec<CpfItem<'a>>,
+    },
+    // Nop、UnRegisterSession 及未知命令
+    Raw(&'a [u8]),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CpfItem<'a> {
+    pub type_id: u16,
+    pub data: CpfItemData<'a>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum CpfItemData<'a> {
+    NullAddress,
+    ConnectedAddress {
+        connection_id: u32,
+    },
+    SequencedAddress {
+        connection_id: u32,
+        sequence_number: u32,
+    },
+    UnconnectedData(CipMessage<'a>),
+    ConnectedData {
+        sequence_count: u16,
+        message: CipMessage<'a>,
+    },
+    /// 隐式 I/O 数据
+    IoData(&'a [u8]),
+    Identity(EnipIdentity<'a>),
+    SockaddrInfo {
+        port: u16,
+        address: Ipv4Addr,
+    },
+    Raw(&'a [u8]),
+}
+
+/// EnipIdentity是 ListIdentity 应答中的设备标识
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct EnipIdentity<'a> {
+    pub encapsulation_version: u16,
+    pub port: u16,
+    pub address: Ipv4Addr,
+    pub vendor_id: u16,
+    pub device_type: u16,
+    pub product_code: u16,
+    pub revision_major: u8,
+    pub revision_minor: u8,
+    pub status: u16,
+    pub serial_number: u32,
+    pub product_name: &'a str,
+    pub state: u8,
+}
+
+fn parse_encapsulation_header(input: &[u8]) -> IResult<&[u8], EncapsulationHeader> {
+    let (input, command) = le_u16(input)?;
+    let (input, length) = le_u16(input)?;
+    let (input, session_handle) = le_u32(input)?;
+    let (input, status) = le_u32(input)?;
+    let (input, sender_context) = le_u64(input)?;
+    let (input, options) = le_u32(input)?;
+    Ok((
+        input,
+        EncapsulationHeader {
+            command,
+            length,
+            session_handle,
+            status,
+            sender_context,
+            options,
+        },
+    ))
+}
+
+// 套接字地址为大端序
+fn parse_sockaddr(input: &[u8]) -> IResult<&[u8], (u16, Ipv4Addr)> {
+    let (input, _family) = be_u16(input)?;
+    let (input, port) = be_u16(input)?;
+    let (input, address) = be_u32(input)?;
+    let (input, _zero) = take(8usize)(input)?;
+    Ok((input, (port, Ipv4Addr::from(address))))
+}
+
+fn parse_identity(input: &[u8]) -> IResult<&[u8], EnipIdentity> {
+    let (input, encapsulation_version) = le_u16(input)?;
+    let (input, (port, address)) = parse_sockaddr(input)?;
+    let (input, vendor_id) = le_u16(input)?;
+    let (input, device_type) = le_u16(input)?;
+    let (input, product_code) = le_u16(input)?;
+    let (input, revision_major) = u8(input)?;
+    let (input, revision_minor) = u8(input)?;
+    let (input, status) = le_u16(input)?;
+    let (input, serial_number) = le_u32(input)?;
+    let (input, name_length) = u8(input)?;
+    let (input, product_name) = take(name_length as usize)(input)?;
+    let product_name = std::str::from_utf8(product_name).map_err(|_| {
+        nom::Err::Error(nom::error::Error::new(input, nom::error::ErrorKind::Verify))
+    })?;
+    let (input, state) = u8(input)?;
+    Ok((
+        input,
+        EnipIdentity {
+            encapsulation_version,
+            port,
+            address,
+            vendor_id,
+            device_type,
+            product_code,
+            revision_major,
+            revision_minor,
+            status,
+            serial_number,
+            product_name,
+            state,
+        },
+    ))
+}
+
+fn parse_cpf_item_data(type_id: u16, input: &[u8], implicit: bool) -> IResult<&[u8], CpfItemData> {
+    match type_id {
+        CPF_NULL_ADDRESS => Ok((input, CpfItemData::NullAddress)),
+        CPF_CONNECTED_ADDRESS => {
+            let (input, connection_id) = le_u32(input)?;
+            Ok((input, CpfItemData::ConnectedAddress { connection_id }))
+        }
+        CPF_SEQUENCED_ADDRESS => {
+            let (input, connection_id) = le_u32(input)?;
+            let (input, sequence_number) = le_u32(input)?;
+            Ok((
+                input,
+                CpfItemData::SequencedAddress {
+                    connection_id,
+                    sequence_number,
+                },
+            ))
+        }
+        CPF_UNCONNECTED_DATA => {
+            let (input, message) = parse_cip_message(input)?;
+            Ok((input, CpfItemData::UnconnectedData(message)))
+        }
+        CPF_CONNECTED_DATA if implicit => {
+            let (input, data) = rest(input)?;
+            Ok((input, CpfItemData::IoData(data)))
+        }
+        CPF_CONNECTED_DATA => {
+            let (input, sequence_count) = le_u16(input)?;
+            let (input, message) = parse_cip_message(input)?;
+            Ok((
+                input,
+                CpfItemData::ConnectedData {
+                    sequence_count,
+                    message,
+                },
+            ))
+        }
+        CPF_LIST_IDENTITY => {
+            let (input, identity) = parse_identity(input)?;
+            Ok((input, CpfItemData::Identity(identity)))
+        }
+        CPF_SOCKADDR_O_T | CPF_SOCKADDR_T_O => {
+            let (input, (port, address)) = parse_sockaddr(input)?;
+            Ok((input, CpfItemData::SockaddrInfo { port, address }))
+        }
+        _ => {
+            let (input, data) = rest(input)?;
+            Ok((input, CpfItemData::Raw(data)))
+        }
+    }
+}
+
+fn parse_cpf_items(input: &[u8], implicit: bool) -> IResult<&[u8], Vec<CpfItem>> {
+    let (mut input, item_count) = le_u16(input)?;
+    let mut items = Vec::with_capacity(item_count as usize);
+    for _ in 0..item_count {
+        let (remain, type_id) = le_u16(input)?;
+        let (remain, length) = le_u16(remain)?;
+        let (remain, data) = take(length as usize)(remain)?;
+        let (_, data) = parse_cpf_item_data(type_id, data, implicit)?;
+        items.push(CpfItem { type_id, data });
+        input = remain;
+    }
+    Ok((input, items))
+}
+
+fn parse_enip_data(command: u16, input: &[u8]) -> IResult<&[u8], EnipData> {
+    match command {
+        ENIP_REGISTER_SESSION => {
+            let (input, protocol_version) = le_u16(input)?;
+            let (input, options_flags) = le_u16(input)?;
+            Ok((
+                input,
+                EnipData::RegisterSession {
+                    protocol_version,
+                    options_flags,
+                },
+            ))
+        }
+        ENIP_LIST_IDENTITY | ENIP_LIST_SERVICES | ENIP_LIST_INTERFACES => {
+            if input.is_empty() {
+                return Ok((input, EnipData::List { items: Vec::new() }));
+            }
+            let (input, items) = parse_cpf_items(input, false)?;
+            Ok((input, EnipData::List { items }))
+        }
+        ENIP_SEND_RR_DATA | ENIP_SEND_UNIT_DATA => {
+            let (input, interface_handle) = le_u32(input)?;
+            let (input, timeout) = le_u16(input)?;
+            let (input, items) = parse_cpf_items(input, false)?;
+            if command == ENIP_SEND_RR_DATA {
+                Ok((
+                    input,
+                    EnipData::SendRRData {
+                        interface_handle,
+                        timeout,
+                        items,
+                    },
+                ))
+            } else {
+                Ok((
+                    input,
+                    EnipData::SendUnitData {
+                        interface_handle,
+                        timeout,
+                        items,
+                    },
+                ))
+            }
+        }
+        _ => {
+            let (input, data) = rest(input)?;
+            Ok((input, EnipData::Raw(data)))
+        }
+    }
+}
+
+pub fn parse_enip_header(input: &[u8]) -> IResult<&[u8], EnipHeader> {
+    let (input, encapsulation) = parse_encapsulation_header(input)?;
+    let (input, data) = take(encapsulation.length as usize)(input)?;
+    let (_, data) = parse_enip_data(encapsulation.command, data)?;
+    Ok((
+        input,
+        EnipHeader {
+            encapsulation: Some(encapsulation),
+            data,
+        },
+    ))
+}
+
+pub fn parse_enip_io_header(input: &[u8]) -> IResult<&[u8], EnipHeader> {
+    let (input, items) = parse_cpf_items(input, true)?;
+    Ok((
+        input,
+        EnipHeader {
+            encapsulation: None,
+            data: EnipData::ImplicitIo { items },
+        },
+    ))
+}
+
+pub fn parse_enip_layer<'a>(
+    input: &'a [u8],
+    link_layer: LinkLayer,
+    network_layer: NetworkLayer<'a>,
+    transport_layer: TransportLayer<'a>,
+    options: &QuinPacketOptions,
+) -> QuinPacket<'a> {
+    parse_layer(parse_enip_header, input, link_layer, network_layer, transport_layer, options)
+}
+
+/// 隐式 I/O (UDP 2222)
+pub fn parse_enip_io_layer<'a>(
+    input: &'a [u8],
+    link_layer: LinkLayer,
+    network_layer: NetworkLayer<'a>,
+    transport_layer: TransportLayer<'a>,
+    options: &QuinPacketOptions,
+) -> QuinPacket<'a> {
+    parse_layer(parse_enip_io_header, input, link_layer, network_layer, transport_layer, options)
+}
+
+fn parse_layer<'a>(
+    parse_header: fn(&'a [u8]) -> IResult<&'a [u8], EnipHeader<'a>>,
+    input: &'a [u8],
+    link_layer: LinkLayer,
+    network_layer: NetworkLayer<'a>,
+    transport_layer: TransportLayer<'a>,
+    options: &QuinPacketOptions,
+) -> QuinPacket<'a> {
+    let current_prototype = ProtocolType::Application(ApplicationProtocol::Enip);
+
+    let (input, enip_header) = match parse_header(input) {
+        Ok(o) => o,
+        Err(e) => {
+            tracing::error!(
+                target: "PARSER(enip::parse_enip_layer)",
+                error = ?e
+            );
+
+            let offset = match e {
+                nom::Err::Error(error) => input.len() - error.input.len(),
+                _ => usize::MAX
+            };
+
+            return QuinPacket::L4(L4Packet {
+                link_layer,
+                network_layer,
+                transport_layer,
+                error: Some(ParseError::ParsingHeader{
+                    protocol: current_prototype,
+                    offset
+                }),
+                remain: input,
+            })
+        }
+    };
+
+    if Some(current_prototype) == options.stop {
+        let application_layer = ApplicationLayer::Enip(enip_header);
+        return QuinPacket::L5(L5Packet {
+            link_layer,
+            network_layer,
+            transport_layer,
+            application_layer,
+            error: None,
+            remain: input,
+        });
+    };
+
+    let application_layer = ApplicationLayer::Enip(enip_header);
+    parse_l5_eof_layer(
+        input,
+        link_layer,
+        network_layer,
+        transport_layer,
+        application_layer,
+        options,
+    )
+}
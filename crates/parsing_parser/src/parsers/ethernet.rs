@@ -2,12 +2,12 @@ use nom::number::complete::be_u16;
 
 use crate::errors::ParseError;
 use crate::field_type::*;
-use crate::layer::LinkLayer;
+use crate::layer::{LinkLayer, NetworkLayer, TransportLayer};
 use crate::packet::{L1Packet, L2Packet, QuinPacket, QuinPacketOptions};
 use crate::protocol::LinkProtocol;
 use crate::ProtocolType;
 
-use super::{parse_ipv4_layer, parse_ipv6_layer, parse_l2_eof_layer, parse_goose_layer, parse_vlan_layer};
+use super::{parse_ipv4_layer, parse_ipv6_layer, parse_l2_eof_layer, parse_goose_layer, parse_profinet_layer, parse_sv_layer, parse_vlan_layer};
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub struct EthernetHeader {
@@ -86,7 +86,15 @@ pub fn parse_ethernet_layer<'a>(input: &'a [u8], options: &QuinPacketOptions) ->
         }
         0x88B8 => { // GOOSE (Generic Object Oriented Substation event)
             let link_layer = LinkLayer::Ethernet(eth_header);
-            parse_goose_layer(input, link_layer, options)
+            parse_goose_layer(input, link_layer, NetworkLayer::Empty, TransportLayer::Empty, options)
+        }
+        0x88BA => { // SV (Sampled Values)
+            let link_layer = LinkLayer::Ethernet(eth_header);
+            parse_sv_layer(input, link_layer, NetworkLayer::Empty, TransportLayer::Empty, options)
+        }
+        0x8892 => { // PROFINET RT/DCP
+            let link_layer = LinkLayer::Ethernet(eth_header);
+            parse_profinet_layer(input, link_layer, NetworkLayer::Empty, TransportLayer::Empty, options)
         }
         _ => {
             let link_layer = LinkLayer::Ethernet(eth_header);
@@ -0,0 +1,244 @@
+#[allow(unused)]
+use nom::bytes::complete::{tag, take};
+#[allow(unused)]
+use nom::number::complete::{be_u16, be_u32, u8};
+#[allow(unused)]
+use nom::IResult;
+
+use std::collections::{HashMap, VecDeque};
+use std::net::IpAddr;
+
+use crate::layer::ApplicationLayer;
+use crate::packet::L5Packet;
+
+use super::fins_command::{self, parse_cmd_type as parse_command_cmd_type};
+use super::fins_response::{self, parse_cmd_type as parse_response_cmd_type};
+use super::fins_tcp_req;
+use super::fins_tcp_rsp;
+
+/// 关联请求时最多保留的未匹配请求数
+pub const FINS_MAX_TRANSACTIONS: usize = 4096;
+
+/// FinsHeader是 FINS 命令与响应共用的 10 字节头部(ICF/RSV/GCT/DNA/DNN/DUA/SNA/SNN/SUA/SID)。
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct FinsHeader {
+    pub fram_info: u8,
+    pub sys_save: u8,
+    pub gateway: u8,
+    pub dna: u8,
+    pub dnn: u8,
+    pub dua: u8,
+    pub sna: u8,
+    pub snn: u8,
+    pub sua: u8,
+    pub sid: u8,
+}
+
+impl FinsHeader {
+    /// ICF 第 6 位置位表示响应
+    pub fn is_response(&self) -> bool {
+        self.fram_info & 0x40 != 0
+    }
+}
+
+pub fn parse_fins_header(input: &[u8]) -> IResult<&[u8], FinsHeader> {
+    let (input, fram_info) = u8(input)?;
+    let (input, sys_save) = u8(input)?;
+    let (input, gateway) = u8(input)?;
+    let (input, dna) = u8(input)?;
+    let (input, dnn) = u8(input)?;
+    let (input, dua) = u8(input)?;
+    let (input, sna) = u8(input)?;
+    let (input, snn) = u8(input)?;
+    let (input, sua) = u8(input)?;
+    let (input, sid) = u8(input)?;
+    Ok((
+        input,
+        FinsHeader {
+            fram_info,
+            sys_save,
+            gateway,
+            dna,
+            dnn,
+            dua,
+            sna,
+            snn,
+            sua,
+            sid,
+        },
+    ))
+}
+
+/// FinsCommand是一条 FINS 命令帧，与传输方式(TCP/UDP)无关。
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct FinsCommand<'a> {
+    pub header: FinsHeader,
+    pub cmd_type: fins_command::CmdType<'a>,
+}
+
+pub fn parse_fins_command(input: &[u8]) -> IResult<&[u8], FinsCommand> {
+    let (input, header) = parse_fins_header(input)?;
+    let (input, cmd_type) = parse_command_cmd_type(input)?;
+    Ok((input, FinsCommand { header, cmd_type }))
+}
+
+/// FinsResponse是一条 FINS 响应帧，与传输方式(TCP/UDP)无关。
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct FinsResponse<'a> {
+    pub header: FinsHeader,
+    pub cmd_type: fins_response::CmdType<'a>,
+}
+
+impl<'a> FinsResponse<'a> {
+    /// 去除网络中继错误与 PLC 错误标志位后的结束码(MRES/SRES)
+    pub fn end_code(&self) -> u16 {
+        self.cmd_type.end_code & 0x7f3f
+    }
+
+    pub fn is_normal_completion(&self) -> bool {
+        fins_end_code_is_normal(self.cmd_type.end_code)
+    }
+}
+
+pub fn parse_fins_response(input: &[u8]) -> IResult<&[u8], FinsResponse> {
+    let (input, header) = parse_fins_header(input)?;
+    let (input, cmd_type) = parse_response_cmd_type(input)?;
+    Ok((input, FinsResponse { header, cmd_type }))
+}
+
+/// 结束码为正常结束(0x0000)，标志位(中继错误、PLC 致命/非致命错误)不参与判断
+pub fn fins_end_code_is_normal(end_code: u16) -> bool {
+    end_code & 0x7f3f == 0
+}
+
+/// FINS/TCP 封装头
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct FTH {
+    pub magic: u32,
+    pub length: u32,
+}
+
+pub fn parse_fth(input: &[u8]) -> IResult<&[u8], FTH> {
+    let (input, magic) = be_u32(input)?;
+    let (input, length) = be_u32(input)?;
+    Ok((input, FTH { magic, length }))
+}
+
+/// FinsArea是命令所访问的区域：区域代码与起始地址。
+/// Run 命令的运行模式也以 code 表示，地址为 0。
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct FinsArea {
+    pub code: u16,
+    pub address: u32,
+}
+
+/// FinsFrame统一 TCP/UDP 上的 FINS 命令与响应
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum FinsFrame<'b, 'a> {
+    Command(&'b FinsCommand<'a>),
+    Response(&'b FinsResponse<'a>),
+}
+
+impl<'b, 'a> FinsFrame<'b, 'a> {
+    /// FINS/TCP 握手报文不含 FINS 帧，返回 None
+    pub fn from_layer(application_layer: &'b ApplicationLayer<'a>) -> Option<Self> {
+        match application_layer {
+            ApplicationLayer::FinsTcpReq(fins) => match &fins.state {
+                fins_tcp_req::State::Connected { fh } => Some(FinsFrame::Command(fh)),
+                _ => None,
+            },
+            ApplicationLayer::FinsTcpRsp(fins) => match &fins.state {
+                fins_tcp_rsp::State::Connected { fh } => Some(FinsFrame::Response(fh)),
+                _ => None,
+            },
+            ApplicationLayer::FinsUdpReq(fins) => Some(FinsFrame::Command(&fins.fh)),
+            ApplicationLayer::FinsUdpRsp(fins) => Some(FinsFrame::Response(&fins.fh)),
+            _ => None,
+        }
+    }
+
+    pub fn header(&self) -> &'b FinsHeader {
+        match self {
+            FinsFrame::Command(command) => &command.header,
+            FinsFrame::Response(response) => &response.header,
+        }
+    }
+
+    pub fn cmd_code(&self) -> u16 {
+        match self {
+            FinsFrame::Command(command) => command.cmd_type.cmd_code,
+            FinsFrame::Response(response) => response.cmd_type.cmd_code,
+        }
+    }
+}
+
+/// FinsRequestInfo是为关联响应而保留的请求摘要
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct FinsRequestInfo {
+    pub cmd_code: u16,
+    pub areas: Vec<FinsArea>,
+}
+
+// (客户端 IP, 服务端 IP, 客户端节点, 服务端节点, SID)
+type FinsTransactionKey = (Option<IpAddr>, Option<IpAddr>, [u8; 3], [u8; 3], u8);
+
+/// FinsTransactions按 SID 将响应关联到对应的请求。
+/// 请求以 (IP, 源/目的节点地址, SID) 为键保留，SID 重用时覆盖，超过上限时淘汰最早的请求。
+#[derive(Debug, Default)]
+pub struct FinsTransactions {
+    requests: HashMap<FinsTransactionKey, FinsRequestInfo>,
+    order: VecDeque<FinsTransactionKey>,
+}
+
+impl FinsTransactions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 推入一个数据包，记录其中的 FINS 命令
+    pub fn push(&mut self, l5: &L5Packet) {
+        let command = match FinsFrame::from_layer(&l5.application_layer) {
+            Some(FinsFrame::Command(command)) => command,
+            _ => return,
+        };
+        let header = &command.header;
+        let key = (
+            l5.network_layer.get_src_ip(),
+            l5.network_layer.get_dst_ip(),
+            [header.sna, header.snn, header.sua],
+            [header.dna, header.dnn, header.dua],
+            header.sid,
+        );
+        let request = FinsRequestInfo {
+            cmd_code: command.cmd_type.cmd_code,
+            areas: command.cmd_type.order.areas(),
+        };
+        if self.requests.insert(key, request).is_none() {
+            self.order.push_back(key);
+        }
+        while self.order.len() > FINS_MAX_TRANSACTIONS {
+            if let Some(oldest) = self.order.pop_front() {
+                self.requests.remove(&oldest);
+            }
+        }
+    }
+
+    /// 查找响应所对应的请求，命令码不一致时视为未关联
+    pub fn request(&self, l5: &L5Packet) -> Option<&FinsRequestInfo> {
+        let response = match FinsFrame::from_layer(&l5.application_layer) {
+            Some(FinsFrame::Response(response)) => response,
+            _ => return None,
+        };
+        let header = &response.header;
+        let key = (
+            l5.network_layer.get_dst_ip(),
+            l5.network_layer.get_src_ip(),
+            [header.dna, header.dnn, header.dua],
+            [header.sna, header.snn, header.sua],
+            header.sid,
+        );
+        self.requests
+            .get(&key)
+            .filter(|request| request.cmd_code == response.cmd_type.cmd_code)
+    }
+}
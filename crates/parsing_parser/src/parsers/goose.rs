@@ -44,10 +44,10 @@ use std::ops::BitXor;
 use std::convert::TryInto;
 
 
-use super::parse_l3_eof_layer;
+use super::parse_l5_eof_layer;
 
 #[allow(non_camel_case_types)]
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct GooseHeader<'a> {
     pub appid: u16,
     pub length: u16,
@@ -76,9 +76,15 @@ pub fn parse_goose_header(input: &[u8]) -> IResult<&[u8], GooseHeader> {
     ))
 }
 
-pub fn parse_goose_layer<'a>(input: &'a [u8], link_layer: LinkLayer, options: &QuinPacketOptions) -> QuinPacket<'a> {
+pub fn parse_goose_layer<'a>(
+    input: &'a [u8],
+    link_layer: LinkLayer,
+    network_layer: NetworkLayer<'a>,
+    transport_layer: TransportLayer<'a>,
+    options: &QuinPacketOptions,
+) -> QuinPacket<'a> {
     info!(target: "PARSER(goose::parse_goose_layer)", "parsing Goose protocol.");
-    let current_prototype = ProtocolType::Network(NetworkProtocol::Goose);
+    let current_prototype = ProtocolType::Application(ApplicationProtocol::Goose);
 
     let (input, goose_header) = match parse_goose_header(input) {
         Ok(o) => o,
@@ -92,10 +98,12 @@ pub fn parse_goose_layer<'a>(input: &'a [u8], link_layer: LinkLayer, options: &Q
                 nom::Err::Error(error) => input.len() - error.input.len(),
                 _ => usize::MAX
             };
-            
-            return QuinPacket::L2(
-                L2Packet {
+
+            return QuinPacket::L4(
+                L4Packet {
                     link_layer,
+                    network_layer,
+                    transport_layer,
                     error: Some(ParseError::ParsingHeader{
                         protocol: current_prototype,
                         offset
@@ -107,63 +115,106 @@ pub fn parse_goose_layer<'a>(input: &'a [u8], link_layer: LinkLayer, options: &Q
     };
 
     if Some(current_prototype) == options.stop {
-        let network_layer = NetworkLayer::Goose(goose_header);
-        return QuinPacket::L3(
-            L3Packet {
+        let application_layer = ApplicationLayer::Goose(goose_header);
+        return QuinPacket::L5(
+            L5Packet {
                 link_layer,
                 network_layer,
+                transport_layer,
+                application_layer,
                 error: None,
                 remain: input,
             }
         )
     };
 
-    let network_layer = NetworkLayer::Goose(goose_header);
-    return parse_l3_eof_layer(input, link_layer, network_layer, options);
+    let application_layer = ApplicationLayer::Goose(goose_header);
+    return parse_l5_eof_layer(input, link_layer, network_layer, transport_layer, application_layer, options);
 }
 
 #[allow(non_camel_case_types)]
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct GoosePDU<'a> {
     pub gocb_ref: &'a [u8],
-    pub time_allowed_to_live: &'a [u8],
+    pub time_allowed_to_live: u32,
     pub dat_set: &'a [u8],
-    pub go_id: &'a [u8],
-    pub t: &'a [u8],
-    pub st_num: &'a [u8],
-    pub sq_num: &'a [u8],
-    pub simulation: &'a [u8],
-    pub conf_rev: &'a [u8],
-    pub nds_com: &'a [u8],
-    pub num_dat_set_entries: &'a [u8],
-    pub all_data: Vec<&'a [u8]>,
+    pub go_id: Option<&'a [u8]>,
+    pub t: UtcTime,
+    pub st_num: u32,
+    pub sq_num: u32,
+    pub simulation: bool,
+    pub conf_rev: u32,
+    pub nds_com: bool,
+    pub num_dat_set_entries: u32,
+    pub all_data: Vec<GooseData<'a>>,
+}
+
+/// GooseData是 IEC 61850-8-1 中 MMS Data 的 BER 编码取值
+#[derive(Debug, PartialEq, Clone)]
+pub enum GooseData<'a> {
+    Array(Vec<GooseData<'a>>),
+    Structure(Vec<GooseData<'a>>),
+    Boolean(bool),
+    BitString { padding: u8, bits: &'a [u8] },
+    Integer(i64),
+    Unsigned(u64),
+    Float(f64),
+    OctetString(&'a [u8]),
+    VisibleString(&'a [u8]),
+    BinaryTime(&'a [u8]),
+    MmsString(&'a [u8]),
+    UtcTime(UtcTime),
+    Unknown { tag: u8, value: &'a [u8] },
+}
+
+impl<'a> GooseData<'a> {
+    /// 按位序号(从最高位开始)读取位串，常用于 Quality 与 Dbpos
+    pub fn bit(&self, index: usize) -> Option<bool> {
+        match self {
+            GooseData::BitString { padding, bits } => {
+                if index + *padding as usize >= bits.len() * 8 {
+                    return None;
+                }
+                Some(bits[index / 8] & (0x80 >> (index % 8)) != 0)
+            }
+            _ => None,
+        }
+    }
 }
 
 pub fn parse_goose_pdu(input: &[u8]) -> IResult<&[u8], GoosePDU> {
     debug!(target: "PARSER(parse_goose_pdu)", "struct GoosePDU");
     let (input, gocb_ref) = ber_tl_v(input)?;
     let (input, time_allowed_to_live) = ber_tl_v(input)?;
+    let (input, time_allowed_to_live) = goose_unsigned(input, time_allowed_to_live)?;
     let (input, dat_set) = ber_tl_v(input)?;
-    let (input, go_id) = ber_tl_v(input)?;
+    let (input, go_id) = match input.first() {
+        Some(0x83) => {
+            let (input, go_id) = ber_tl_v(input)?;
+            (input, Some(go_id))
+        }
+        _ => (input, None),
+    };
     let (input, t) = ber_tl_v(input)?;
+    let t = match UtcTime::from_bytes(t) {
+        Some(o) => o,
+        None => return Err(goose_verify_error(input)),
+    };
     let (input, st_num) = ber_tl_v(input)?;
+    let (input, st_num) = goose_unsigned(input, st_num)?;
     let (input, sq_num) = ber_tl_v(input)?;
+    let (input, sq_num) = goose_unsigned(input, sq_num)?;
     let (input, simulation) = ber_tl_v(input)?;
+    let simulation = simulation.iter().any(|b| *b != 0);
     let (input, conf_rev) = ber_tl_v(input)?;
+    let (input, conf_rev) = goose_unsigned(input, conf_rev)?;
     let (input, nds_com) = ber_tl_v(input)?;
+    let nds_com = nds_com.iter().any(|b| *b != 0);
     let (input, num_dat_set_entries) = ber_tl_v(input)?;
-    let (input, _all_data_tl) = ber_tl(input)?;
-    /* LimitedLenVecLoopField Start */
-    let mut all_data = Vec::new();
-    let mut _all_data: &[u8];
-    let mut input = input;
-    let len_flag = input.len() - _all_data_tl.length as usize;
-    while input.len() > len_flag {
-        (input, _all_data) = ber_tl_v(input)?;
-        all_data.push(_all_data);
-    }
-    let input = input;
-    /* LimitedLenVecLoopField End. */
+    let (input, num_dat_set_entries) = goose_unsigned(input, num_dat_set_entries)?;
+    let (input, all_data_tl) = ber_tl(input)?;
+    let (input, all_data) = take(all_data_tl.length as usize)(input)?;
+    let (_, all_data) = parse_goose_data_list(all_data, 0)?;
     Ok((
         input,
         GoosePDU {
@@ -181,4 +232,79 @@ pub fn parse_goose_pdu(input: &[u8]) -> IResult<&[u8], GoosePDU> {
             all_data
         }
     ))
-}
\ No newline at end of file
+}
+
+fn goose_verify_error(input: &[u8]) -> nom::Err<Error<&[u8]>> {
+    nom::Err::Error(Error {
+        input,
+        code: ErrorKind::Verify,
+    })
+}
+
+fn goose_unsigned<'a>(input: &'a [u8], value: &[u8]) -> IResult<&'a [u8], u32> {
+    match ber_unsigned(value).and_then(|o| u32::try_from(o).ok()) {
+        Some(o) => Ok((input, o)),
+        None => Err(goose_verify_error(input)),
+    }
+}
+
+// Array/Structure 嵌套层数上限
+const GOOSE_MAX_DATA_DEPTH: usize = 16;
+
+fn parse_goose_data_list(input: &[u8], depth: usize) -> IResult<&[u8], Vec<GooseData>> {
+    let mut data = Vec::new();
+    let mut input = input;
+    while !input.is_empty() {
+        let _data;
+        (input, _data) = parse_goose_data_with_depth(input, depth)?;
+        data.push(_data);
+    }
+    Ok((input, data))
+}
+
+pub fn parse_goose_data(input: &[u8]) -> IResult<&[u8], GooseData> {
+    parse_goose_data_with_depth(input, 0)
+}
+
+fn parse_goose_data_with_depth(input: &[u8], depth: usize) -> IResult<&[u8], GooseData> {
+    let (input, tl) = ber_tl(input)?;
+    let (input, value) = take(tl.length as usize)(input)?;
+    let data = match tl.tag {
+        0xa1 | 0xa2 if depth >= GOOSE_MAX_DATA_DEPTH => return Err(goose_verify_error(input)),
+        0xa1 => GooseData::Array(parse_goose_data_list(value, depth + 1)?.1),
+        0xa2 => GooseData::Structure(parse_goose_data_list(value, depth + 1)?.1),
+        0x83 => GooseData::Boolean(value.iter().any(|b| *b != 0)),
+        0x84 => match value.split_first() {
+            Some((padding, bits)) if *padding < 8 => GooseData::BitString { padding: *padding, bits },
+            _ => return Err(goose_verify_error(input)),
+        },
+        0x85 => match ber_integer(value) {
+            Some(o) => GooseData::Integer(o),
+            None => return Err(goose_verify_error(input)),
+        },
+        0x86 => match ber_unsigned(value) {
+            Some(o) => GooseData::Unsigned(o),
+            None => return Err(goose_verify_error(input)),
+        },
+        // 浮点数首字节为指数位宽: 8 为单精度, 11 为双精度
+        0x87 => match value {
+            [0x08, rest @ ..] if rest.len() == 4 => {
+                GooseData::Float(f32::from_be_bytes(rest.try_into().unwrap()) as f64)
+            }
+            [0x0b, rest @ ..] if rest.len() == 8 => {
+                GooseData::Float(f64::from_be_bytes(rest.try_into().unwrap()))
+            }
+            _ => return Err(goose_verify_error(input)),
+        },
+        0x89 => GooseData::OctetString(value),
+        0x8a => GooseData::VisibleString(value),
+        0x8c => GooseData::BinaryTime(value),
+        0x90 => GooseData::MmsString(value),
+        0x91 => match UtcTime::from_bytes(value) {
+            Some(o) => GooseData::UtcTime(o),
+            None => return Err(goose_verify_error(input)),
+        },
+        tag => GooseData::Unknown { tag, value },
+    };
+    Ok((input, data))
+}
@@ -1,170 +1,463 @@
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::io::Read;
+
+use flate2::read::{DeflateDecoder, GzDecoder, ZlibDecoder};
+
 use super::parse_l5_eof_layer;
 use crate::{
     ApplicationLayer, ApplicationProtocol, L4Packet, L5Packet, LinkLayer, NetworkLayer, ParseError,
     ProtocolType, QuinPacket, QuinPacketOptions, TransportLayer,
 };
 
-#[derive(Debug, PartialEq, Clone, Copy)]
-pub enum HttpHeader<'a> {
+/// 单个消息(含缓存的报文体及解压结果)的最大长度
+pub const HTTP_MAX_MESSAGE_SIZE: usize = 16 * 1024 * 1024;
+/// 消息头(起始行至空行)的最大长度
+pub const HTTP_MAX_HEADER_SIZE: usize = 64 * 1024;
+/// 单个消息的最大头部数
+pub const HTTP_MAX_HEADERS: usize = 256;
+/// 每个连接上等待响应的最大请求数，超出时丢弃最早的请求
+pub const HTTP_MAX_PENDING_REQUESTS: usize = 64;
+
+const HTTP_METHODS: [&[u8]; 9] = [
+    b"GET ",
+    b"POST ",
+    b"PUT ",
+    b"DELETE ",
+    b"HEAD ",
+    b"OPTIONS ",
+    b"PATCH ",
+    b"CONNECT ",
+    b"TRACE ",
+];
+
+/// HttpHeader是一个 TCP 报文中的 HTTP 数据。
+/// 流水线请求在同一报文中会有多个消息；跨报文的消息只解析本报文内的部分，
+/// 完整的重组见 [`HttpFlow`]。
+#[derive(Debug, PartialEq, Clone)]
+pub struct HttpHeader<'a> {
+    pub messages: Vec<HttpMessage<'a>>,
+    /// 报文末尾不完整的消息头
+    pub partial: &'a [u8],
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum HttpMessage<'a> {
     Request {
         method: &'a str,
         path: &'a str,
         version: u8,
-        headers: [httparse::Header<'a>; 16],
+        headers: Vec<httparse::Header<'a>>,
+        /// 原始报文体(未去除 chunked 编码)
         content: &'a [u8],
+        /// 报文体是否完整
+        complete: bool,
     },
     Response {
         version: u8,
         code: u16,
         reason: &'a str,
-        headers: [httparse::Header<'a>; 16],
+        headers: Vec<httparse::Header<'a>>,
         content: &'a [u8],
+        complete: bool,
     },
 }
 
-pub fn parse_http_header(input: &[u8]) -> nom::IResult<&[u8], HttpHeader> {
-    let verify_error = Err(nom::Err::Error(nom::error::Error::new(
-        input,
-        nom::error::ErrorKind::Verify,
-    )));
+impl<'a> HttpMessage<'a> {
+    pub fn headers(&self) -> &[httparse::Header<'a>] {
+        match self {
+            HttpMessage::Request { headers, .. } => headers,
+            HttpMessage::Response { headers, .. } => headers,
+        }
+    }
 
-    let mut headers = [httparse::EMPTY_HEADER; 16];
+    /// 按名称(不区分大小写)查找第一个同名头部
+    pub fn header(&self, name: &str) -> Option<&'a [u8]> {
+        find_header(self.headers(), name)
+    }
 
-    let (_, rsp_opt) = nom::combinator::opt(nom::bytes::complete::tag(b"HTTP/"))(input)?;
-    if rsp_opt.is_none() {
-        // HTTP Request
-        let method;
-        let path;
-        let version;
-        let content;
+    pub fn content(&self) -> &'a [u8] {
+        match self {
+            HttpMessage::Request { content, .. } => content,
+            HttpMessage::Response { content, .. } => content,
+        }
+    }
 
-        let mut req = httparse::Request::new(&mut headers);
-        if let Ok(status) = req.parse(input) {
-            match status {
-                httparse::Status::Complete(offset) => {
-                    content = &input[offset..];
-                }
-                httparse::Status::Partial => {
-                    // http isn't complete
-                    // TODO: http flow
-                    tracing::debug!(target: "PARSER(http::parse_http_header)", "don't support partial yet.");
-                    return verify_error;
-                }
-            }
+    pub fn is_complete(&self) -> bool {
+        match self {
+            HttpMessage::Request { complete, .. } => *complete,
+            HttpMessage::Response { complete, .. } => *complete,
+        }
+    }
 
-            if let Some(m) = req.method {
-                method = m;
-            } else {
-                tracing::debug!(target: "PARSER(http::parse_http_header)", "request method error.");
-                return verify_error;
-            }
+    /// 去除 chunked 编码并按 Content-Encoding 解压后的报文体。
+    /// 消息不完整或解码失败时返回 None。
+    pub fn body(&self) -> Option<Cow<'a, [u8]>> {
+        if !self.is_complete() {
+            return None;
+        }
+        decode_body(self.headers(), self.content())
+    }
+}
 
-            if let Some(p) = req.path {
-                path = p;
-            } else {
-                tracing::debug!(target: "PARSER(http::parse_http_header)", "request path error.");
-                return verify_error;
-            }
+fn find_header<'a>(headers: &[httparse::Header<'a>], name: &str) -> Option<&'a [u8]> {
+    headers
+        .iter()
+        .find(|header| header.name.eq_ignore_ascii_case(name))
+        .map(|header| header.value)
+}
 
-            if let Some(v) = req.version {
-                version = v;
-            } else {
-                tracing::debug!(target: "PARSER(http::parse_http_header)", "request version error.");
-                return verify_error;
-            }
+fn header_str<'a>(headers: &[httparse::Header<'a>], name: &str) -> Option<&'a str> {
+    find_header(headers, name)
+        .and_then(|value| std::str::from_utf8(value).ok())
+        .map(|value| value.trim())
+}
+
+/// Transfer-Encoding 的最后一个编码为 chunked
+fn is_chunked(headers: &[httparse::Header]) -> bool {
+    match header_str(headers, "Transfer-Encoding") {
+        Some(value) => value
+            .rsplit(',')
+            .next()
+            .map(|encoding| encoding.trim().eq_ignore_ascii_case("chunked"))
+            .unwrap_or(false),
+        None => false,
+    }
+}
+
+fn content_length(headers: &[httparse::Header]) -> Result<Option<usize>, ()> {
+    match header_str(headers, "Content-Length") {
+        Some(value) => value.parse::<usize>().map(Some).map_err(|_| ()),
+        None => Ok(None),
+    }
+}
+
+enum BodyLength {
+    Empty,
+    Length(usize),
+    Chunked,
+    // 以连接关闭结束
+    Close,
+}
+
+/// 按 RFC 7230 3.3.3 确定报文体的边界
+fn body_length(
+    headers: &[httparse::Header],
+    response_code: Option<u16>,
+    head_request: bool,
+) -> Result<BodyLength, ()> {
+    if let Some(code) = response_code {
+        if head_request || (100..200).contains(&code) || code == 204 || code == 304 {
+            return Ok(BodyLength::Empty);
+        }
+    }
+    if is_chunked(headers) {
+        return Ok(BodyLength::Chunked);
+    }
+    match content_length(headers)? {
+        Some(length) => Ok(BodyLength::Length(length)),
+        None if response_code.is_some() => Ok(BodyLength::Close),
+        None => Ok(BodyLength::Empty),
+    }
+}
 
-            tracing::debug!(
-                "{:?}",
-                HttpHeader::Request {
-                    method,
-                    path,
-                    version,
-                    headers,
-                    content
+fn find_crlf(input: &[u8]) -> Option<usize> {
+    input.windows(2).position(|window| window == b"\r\n")
+}
+
+// (总长度, 各数据块)
+type HttpChunks<'a> = (usize, Vec<&'a [u8]>);
+
+enum ChunkedStatus<'a> {
+    Complete(HttpChunks<'a>),
+    // 不完整，值为最后一个未完整的数据块(或 trailer)的起始位置，可从此处继续扫描
+    Partial(usize),
+}
+
+/// 从 pos 处的 chunk-size 行开始扫描 chunked 报文体，返回的数据块只包含 pos 之后的部分
+fn scan_chunked(input: &[u8], mut pos: usize) -> Result<ChunkedStatus, ()> {
+    let mut chunks = Vec::new();
+    loop {
+        let start = pos;
+        let line_end = match find_crlf(&input[pos..]) {
+            Some(line_end) => line_end,
+            None => return Ok(ChunkedStatus::Partial(start)),
+        };
+        let line = std::str::from_utf8(&input[pos..pos + line_end]).map_err(|_| ())?;
+        // 忽略 chunk-ext
+        let size = line.split(';').next().unwrap_or("").trim();
+        let size = usize::from_str_radix(size, 16).map_err(|_| ())?;
+        pos += line_end + 2;
+
+        if size == 0 {
+            // trailer 以空行结束
+            loop {
+                let line_end = match find_crlf(&input[pos..]) {
+                    Some(line_end) => line_end,
+                    None => return Ok(ChunkedStatus::Partial(start)),
+                };
+                pos += line_end + 2;
+                if line_end == 0 {
+                    return Ok(ChunkedStatus::Complete((pos, chunks)));
                 }
-            );
+            }
+        }
+
+        if size > input.len() - pos || input.len() - pos - size < 2 {
+            return Ok(ChunkedStatus::Partial(start));
+        }
+        chunks.push(&input[pos..pos + size]);
+        pos += size;
+        if &input[pos..pos + 2] != b"\r\n" {
+            return Err(());
+        }
+        pos += 2;
+    }
+}
+
+/// 解析 chunked 报文体，不完整时返回 None
+fn parse_chunked(input: &[u8]) -> Result<Option<HttpChunks>, ()> {
+    match scan_chunked(input, 0)? {
+        ChunkedStatus::Complete(chunks) => Ok(Some(chunks)),
+        ChunkedStatus::Partial(_) => Ok(None),
+    }
+}
 
-            Ok((
-                input,
-                HttpHeader::Request {
-                    method,
-                    path,
-                    version,
-                    headers,
-                    content,
-                },
-            ))
-        } else {
-            tracing::debug!(target: "PARSER(http::parse_http_header)", "request version error.");
-            return verify_error;
+fn read_limited<R: Read>(reader: R) -> Option<Vec<u8>> {
+    let mut output = Vec::new();
+    reader
+        .take(HTTP_MAX_MESSAGE_SIZE as u64)
+        .read_to_end(&mut output)
+        .ok()?;
+    Some(output)
+}
+
+fn decode_body<'a>(headers: &[httparse::Header], content: &'a [u8]) -> Option<Cow<'a, [u8]>> {
+    let mut body = if is_chunked(headers) {
+        let (_, chunks) = parse_chunked(content).ok()??;
+        match chunks.len() {
+            0 => Cow::Borrowed(&content[..0]),
+            1 => Cow::Borrowed(chunks[0]),
+            _ => Cow::Owned(chunks.concat()),
         }
     } else {
-        // HTTP Response
-        let version;
-        let code;
-        let reason;
-        let content;
+        Cow::Borrowed(content)
+    };
+
+    // 多个编码按逆序解码
+    if let Some(encodings) = header_str(headers, "Content-Encoding") {
+        for encoding in encodings.rsplit(',') {
+            let encoding = encoding.trim().to_ascii_lowercase();
+            body = match encoding.as_str() {
+                "gzip" | "x-gzip" => Cow::Owned(read_limited(GzDecoder::new(&body[..]))?),
+                // deflate 应为 zlib 格式，部分实现直接发送原始 deflate 数据
+                "deflate" => Cow::Owned(
+                    read_limited(ZlibDecoder::new(&body[..]))
+                        .or_else(|| read_limited(DeflateDecoder::new(&body[..])))?,
+                ),
+                "identity" | "" => body,
+                _ => return None,
+            };
+        }
+    }
+    Some(body)
+}
+
+enum HttpParseStatus<'a> {
+    Complete(&'a [u8], HttpMessage<'a>),
+    // 消息头不完整
+    Partial,
+    Invalid,
+}
+
+/// 按消息头中(至第一个空行)的行数确定头部数组的长度，不超过 HTTP_MAX_HEADERS
+fn header_capacity(input: &[u8]) -> usize {
+    let mut count = 0;
+    let mut line_start = 0;
+    for (pos, &byte) in input.iter().enumerate().take(HTTP_MAX_HEADER_SIZE) {
+        if byte != b'\n' {
+            continue;
+        }
+        if pos == line_start || (pos == line_start + 1 && input[line_start] == b'\r') {
+            break;
+        }
+        count += 1;
+        if count > HTTP_MAX_HEADERS {
+            break;
+        }
+        line_start = pos + 1;
+    }
+    count.min(HTTP_MAX_HEADERS)
+}
+
+/// 解析一个消息，head_request 表示响应对应 HEAD 请求(无报文体)
+fn parse_http_message(input: &[u8], head_request: bool) -> HttpParseStatus {
+    let mut headers = vec![httparse::EMPTY_HEADER; header_capacity(input)];
 
+    let (offset, message) = if input.starts_with(b"HTTP/") {
+        // HTTP Response
         let mut rsp = httparse::Response::new(&mut headers);
-        if let Ok(status) = rsp.parse(input) {
-            match status {
-                httparse::Status::Complete(offset) => {
-                    content = &input[offset..];
-                }
-                httparse::Status::Partial => {
-                    // http isn't complete
-                    // TODO: http flow
-                    tracing::debug!(target: "PARSER(http::parse_http_header)", "don't support partial yet.");
-                    return verify_error;
-                }
+        let offset = match rsp.parse(input) {
+            Ok(httparse::Status::Complete(offset)) => offset,
+            Ok(httparse::Status::Partial) => return partial_status(input),
+            Err(e) => {
+                tracing::debug!(target: "PARSER(http::parse_http_message)", error = ?e, "response parse error.");
+                return HttpParseStatus::Invalid;
             }
-            if let Some(v) = rsp.version {
-                version = v;
-            } else {
-                tracing::debug!(target: "PARSER(http::parse_http_header)", "response version error.");
-                return verify_error;
+        };
+        let (version, code, reason) = match (rsp.version, rsp.code, rsp.reason) {
+            (Some(version), Some(code), Some(reason)) => (version, code, reason),
+            _ => return HttpParseStatus::Invalid,
+        };
+        let count = rsp.headers.len();
+        headers.truncate(count);
+        (
+            offset,
+            HttpMessage::Response {
+                version,
+                code,
+                reason,
+                headers,
+                content: &[],
+                complete: true,
+            },
+        )
+    } else {
+        // HTTP Request
+        let mut req = httparse::Request::new(&mut headers);
+        let offset = match req.parse(input) {
+            Ok(httparse::Status::Complete(offset)) => offset,
+            Ok(httparse::Status::Partial) => return partial_status(input),
+            Err(e) => {
+                tracing::debug!(target: "PARSER(http::parse_http_message)", error = ?e, "request parse error.");
+                return HttpParseStatus::Invalid;
             }
+        };
+        let (method, path, version) = match (req.method, req.path, req.version) {
+            (Some(method), Some(path), Some(version)) => (method, path, version),
+            _ => return HttpParseStatus::Invalid,
+        };
+        let count = req.headers.len();
+        headers.truncate(count);
+        (
+            offset,
+            HttpMessage::Request {
+                method,
+                path,
+                version,
+                headers,
+                content: &[],
+                complete: true,
+            },
+        )
+    };
 
-            if let Some(c) = rsp.code {
-                code = c;
-            } else {
-                tracing::debug!(target: "PARSER(http::parse_http_header)", "response code error.");
-                return verify_error;
-            }
+    let response_code = match &message {
+        HttpMessage::Response { code, .. } => Some(*code),
+        HttpMessage::Request { .. } => None,
+    };
+    let remain = &input[offset..];
+    let (length, complete) = match body_length(message.headers(), response_code, head_request) {
+        Ok(BodyLength::Empty) => (0, true),
+        Ok(BodyLength::Length(length)) => (length.min(remain.len()), length <= remain.len()),
+        Ok(BodyLength::Chunked) => match parse_chunked(remain) {
+            Ok(Some((length, _))) => (length, true),
+            Ok(None) => (remain.len(), false),
+            Err(()) => return HttpParseStatus::Invalid,
+        },
+        Ok(BodyLength::Close) => (remain.len(), false),
+        Err(()) => {
+            tracing::debug!(target: "PARSER(http::parse_http_message)", "content-length error.");
+            return HttpParseStatus::Invalid;
+        }
+    };
 
-            if let Some(r) = rsp.reason {
-                reason = r;
-            } else {
-                tracing::debug!(target: "PARSER(http::parse_http_header)", "response reason error.");
-                return verify_error;
-            }
+    let body = &remain[..length];
+    let message = match message {
+        HttpMessage::Request {
+            method,
+            path,
+            version,
+            headers,
+            ..
+        } => HttpMessage::Request {
+            method,
+            path,
+            version,
+            headers,
+            content: body,
+            complete,
+        },
+        HttpMessage::Response {
+            version,
+            code,
+            reason,
+            headers,
+            ..
+        } => HttpMessage::Response {
+            version,
+            code,
+            reason,
+            headers,
+            content: body,
+            complete,
+        },
+    };
+    HttpParseStatus::Complete(&remain[length..], message)
+}
 
-            tracing::debug!(
-                "{:?}",
-                HttpHeader::Response {
-                    version,
-                    code,
-                    reason,
-                    headers,
-                    content
-                }
-            );
+// 消息头超过长度上限仍不完整时视为无效
+fn partial_status<'a>(input: &[u8]) -> HttpParseStatus<'a> {
+    if input.len() > HTTP_MAX_HEADER_SIZE {
+        tracing::debug!(target: "PARSER(http::parse_http_message)", "header too large.");
+        return HttpParseStatus::Invalid;
+    }
+    HttpParseStatus::Partial
+}
+
+/// 起始行是否像 HTTP 消息，用于接受消息头跨报文的首个报文
+fn is_http_start(input: &[u8]) -> bool {
+    input.starts_with(b"HTTP/") || HTTP_METHODS.iter().any(|method| input.starts_with(method))
+}
 
-            Ok((
-                input,
-                HttpHeader::Response {
-                    version,
-                    code,
-                    reason,
-                    headers,
-                    content,
-                },
-            ))
-        } else {
-            tracing::debug!(target: "PARSER(http::parse_http_header)", "response parse error.");
-            return verify_error;
+pub fn parse_http_header(input: &[u8]) -> nom::IResult<&[u8], HttpHeader> {
+    let verify_error = Err(nom::Err::Error(nom::error::Error::new(
+        input,
+        nom::error::ErrorKind::Verify,
+    )));
+
+    let mut messages = Vec::new();
+    let mut remain = input;
+    while !remain.is_empty() {
+        match parse_http_message(remain, false) {
+            HttpParseStatus::Complete(rest, message) => {
+                messages.push(message);
+                remain = rest;
+            }
+            HttpParseStatus::Partial if !messages.is_empty() || is_http_start(remain) => {
+                return Ok((
+                    &remain[remain.len()..],
+                    HttpHeader {
+                        messages,
+                        partial: remain,
+                    },
+                ));
+            }
+            _ if messages.is_empty() => return verify_error,
+            // 其后的数据留给上层报告
+            _ => break,
         }
     }
+
+    tracing::debug!("{:?}", messages);
+
+    Ok((
+        remain,
+        HttpHeader {
+            messages,
+            partial: &[],
+        },
+    ))
 }
 
 pub fn parse_http_layer<'a>(
@@ -224,3 +517,287 @@ pub fn parse_http_layer<'a>(
         options,
     );
 }
+
+/// HttpRequest是重组后的完整请求
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct HttpRequest {
+    pub method: String,
+    pub path: String,
+    pub version: u8,
+    pub headers: Vec<(String, Vec<u8>)>,
+    /// 解码后的报文体，解码失败时为 None
+    pub body: Option<Vec<u8>>,
+}
+
+/// HttpResponse是重组后的完整响应
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct HttpResponse {
+    pub version: u8,
+    pub code: u16,
+    pub reason: String,
+    pub headers: Vec<(String, Vec<u8>)>,
+    pub body: Option<Vec<u8>>,
+}
+
+fn owned_headers(headers: &[httparse::Header]) -> Vec<(String, Vec<u8>)> {
+    headers
+        .iter()
+        .map(|header| (header.name.to_string(), header.value.to_vec()))
+        .collect()
+}
+
+fn find_owned_header<'h>(headers: &'h [(String, Vec<u8>)], name: &str) -> Option<&'h [u8]> {
+    headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| &value[..])
+}
+
+impl HttpRequest {
+    pub fn header(&self, name: &str) -> Option<&[u8]> {
+        find_owned_header(&self.headers, name)
+    }
+}
+
+impl HttpResponse {
+    pub fn header(&self, name: &str) -> Option<&[u8]> {
+        find_owned_header(&self.headers, name)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum HttpDirection {
+    ToServer,
+    ToClient,
+}
+
+/// HttpFlowMessage是 [`HttpFlow`] 重组出的消息，响应按顺序与请求配对
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum HttpFlowMessage {
+    Request(HttpRequest),
+    Response {
+        request: Option<HttpRequest>,
+        response: HttpResponse,
+    },
+}
+
+/// HttpFlow跨 TCP 报文重组 HTTP 消息，支持流水线请求。
+/// 每个 TCP 连接使用一个实例，报文需按序推入。
+#[derive(Debug, Default)]
+pub struct HttpFlow {
+    to_server: HttpStream,
+    to_client: HttpStream,
+    // 等待响应的请求
+    pending: VecDeque<HttpRequest>,
+    // 最近一次 update 完成的消息
+    completed: Vec<HttpFlowMessage>,
+}
+
+/// 一个方向上缓存的数据
+#[derive(Debug, Default)]
+struct HttpStream {
+    buffer: Vec<u8>,
+    // 缓存开头的消息头已完整时，报文体的接收进度，避免每个报文都重新解析
+    body: Option<HttpBodyWait>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum HttpBodyWait {
+    // 消息的结束位置
+    Length(usize),
+    // chunked 报文体中可继续扫描的位置
+    Chunked(usize),
+    // 以连接关闭结束
+    Close,
+}
+
+impl HttpStream {
+    /// 缓存开头的消息是否可能已完整；chunked 报文体不完整时更新扫描位置
+    fn is_ready(&mut self) -> Result<bool, ()> {
+        match self.body {
+            None => Ok(true),
+            Some(HttpBodyWait::Length(end)) => Ok(self.buffer.len() >= end),
+            Some(HttpBodyWait::Chunked(pos)) => match scan_chunked(&self.buffer, pos)? {
+                ChunkedStatus::Complete(_) => Ok(true),
+                ChunkedStatus::Partial(pos) => {
+                    self.body = Some(HttpBodyWait::Chunked(pos));
+                    Ok(false)
+                }
+            },
+            Some(HttpBodyWait::Close) => Ok(false),
+        }
+    }
+}
+
+/// 消息头完整而报文体不完整时的接收进度，位置相对于消息起始
+fn body_wait(input: &[u8], rest: &[u8], message: &HttpMessage, head_request: bool) -> Option<HttpBodyWait> {
+    let content = message.content();
+    let body_start = input.len() - rest.len() - content.len();
+    let response_code = match message {
+        HttpMessage::Response { code, .. } => Some(*code),
+        HttpMessage::Request { .. } => None,
+    };
+    match body_length(message.headers(), response_code, head_request).ok()? {
+        BodyLength::Empty => None,
+        BodyLength::Length(length) => Some(HttpBodyWait::Length(body_start.saturating_add(length))),
+        BodyLength::Chunked => match scan_chunked(input, body_start).ok()? {
+            ChunkedStatus::Complete(_) => None,
+            ChunkedStatus::Partial(pos) => Some(HttpBodyWait::Chunked(pos)),
+        },
+        BodyLength::Close => Some(HttpBodyWait::Close),
+    }
+}
+
+impl HttpFlow {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 推入数据包中的 TCP 负载，本数据包完成的消息见 [`HttpFlow::completed`]；
+    /// 服务端发出 FIN 时完成以连接关闭结束的响应
+    pub fn update(&mut self, direction: HttpDirection, payload: &[u8], fin: bool) {
+        let mut completed = self.push(direction, payload);
+        if fin && direction == HttpDirection::ToClient {
+            completed.extend(self.finish());
+        }
+        self.completed = completed;
+    }
+
+    /// 最近一个数据包中完成的消息
+    pub fn completed(&self) -> &[HttpFlowMessage] {
+        &self.completed
+    }
+
+    /// 推入一个 TCP 报文的负载，返回其中完成的消息
+    pub fn push(&mut self, direction: HttpDirection, payload: &[u8]) -> Vec<HttpFlowMessage> {
+        let stream = match direction {
+            HttpDirection::ToServer => &mut self.to_server,
+            HttpDirection::ToClient => &mut self.to_client,
+        };
+        if stream.buffer.len() + payload.len() > HTTP_MAX_MESSAGE_SIZE {
+            *stream = HttpStream::default();
+            return Vec::new();
+        }
+        stream.buffer.extend_from_slice(payload);
+        match stream.is_ready() {
+            Ok(true) => {}
+            Ok(false) => return Vec::new(),
+            Err(()) => {
+                *stream = HttpStream::default();
+                return Vec::new();
+            }
+        }
+        stream.body = None;
+        let mut buffer = std::mem::take(&mut stream.buffer);
+
+        let mut messages = Vec::new();
+        let mut consumed = 0;
+        let mut body = None;
+        while consumed < buffer.len() {
+            let head_request = direction == HttpDirection::ToClient
+                && self.pending.front().map(|request| request.method.as_str()) == Some("HEAD");
+            let input = &buffer[consumed..];
+            let (rest, message) = match parse_http_message(input, head_request) {
+                HttpParseStatus::Complete(rest, message) if message.is_complete() => (rest, message),
+                HttpParseStatus::Complete(rest, message) => {
+                    body = body_wait(input, rest, &message, head_request);
+                    break;
+                }
+                HttpParseStatus::Invalid => {
+                    // 无法识别的数据，丢弃该方向的缓存
+                    consumed = buffer.len();
+                    break;
+                }
+                HttpParseStatus::Partial => break,
+            };
+            consumed = buffer.len() - rest.len();
+            match message {
+                HttpMessage::Request { .. } => {
+                    let request = to_request(&message);
+                    self.pending.push_back(request.clone());
+                    if self.pending.len() > HTTP_MAX_PENDING_REQUESTS {
+                        self.pending.pop_front();
+                    }
+                    messages.push(HttpFlowMessage::Request(request));
+                }
+                HttpMessage::Response { code, .. } => {
+                    let response = to_response(&message);
+                    // 1xx 为临时响应，请求仍等待最终响应
+                    let request = if (100..200).contains(&code) {
+                        self.pending.front().cloned()
+                    } else {
+                        self.pending.pop_front()
+                    };
+                    messages.push(HttpFlowMessage::Response { request, response });
+                }
+            }
+        }
+
+        buffer.drain(..consumed);
+        let stream = match direction {
+            HttpDirection::ToServer => &mut self.to_server,
+            HttpDirection::ToClient => &mut self.to_client,
+        };
+        *stream = HttpStream { buffer, body };
+        messages
+    }
+
+    /// 连接关闭时调用，以连接关闭结束的响应在此时完成
+    pub fn finish(&mut self) -> Option<HttpFlowMessage> {
+        let buffer = std::mem::take(&mut self.to_client).buffer;
+        let head_request = self.pending.front().map(|request| request.method.as_str()) == Some("HEAD");
+        match parse_http_message(&buffer, head_request) {
+            HttpParseStatus::Complete(_, message @ HttpMessage::Response { .. })
+                if content_length(message.headers()) == Ok(None) && !is_chunked(message.headers()) =>
+            {
+                let response = HttpResponse {
+                    body: decode_body(message.headers(), message.content()).map(|body| body.into_owned()),
+                    ..to_response(&message)
+                };
+                Some(HttpFlowMessage::Response {
+                    request: self.pending.pop_front(),
+                    response,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+fn to_request(message: &HttpMessage) -> HttpRequest {
+    match message {
+        HttpMessage::Request {
+            method,
+            path,
+            version,
+            headers,
+            ..
+        } => HttpRequest {
+            method: method.to_string(),
+            path: path.to_string(),
+            version: *version,
+            headers: owned_headers(headers),
+            body: message.body().map(|body| body.into_owned()),
+        },
+        HttpMessage::Response { .. } => unreachable!(),
+    }
+}
+
+fn to_response(message: &HttpMessage) -> HttpResponse {
+    match message {
+        HttpMessage::Response {
+            version,
+            code,
+            reason,
+            headers,
+            ..
+        } => HttpResponse {
+            version: *version,
+            code: *code,
+            reason: reason.to_string(),
+            headers: owned_headers(headers),
+            body: message.body().map(|body| body.into_owned()),
+        },
+        HttpMessage::Request { .. } => unreachable!(),
+    }
+}
@@ -0,0 +1,269 @@
+use nom::bits::bits;
+use nom::bits::complete::take as take_bits;
+use nom::bytes::complete::{tag, take};
+use nom::combinator::map;
+use nom::number::complete::{le_u16, u8};
+use nom::sequence::tuple;
+use nom::IResult;
+
+use crate::errors::ParseError;
+use crate::layer::{ApplicationLayer, LinkLayer, NetworkLayer, TransportLayer};
+use crate::packet::{Iec101Options, L4Packet, L5Packet, QuinPacket, QuinPacketOptions};
+use crate::protocol::ApplicationProtocol;
+use crate::ProtocolType;
+
+use super::iec104::{parse_iec_asdu, IecAsdu};
+use super::parse_l5_eof_layer;
+
+// IEC 60870-5-101 FT1.2 Frame Format
+//
+//    Single Character:  +------+
+//                       | 0xE5 |
+//                       +------+
+//
+//    Fixed Length:      +------+---------+-----------+----------+------+
+//                       | 0x10 | Control | Link Addr | Checksum | 0x16 |
+//                       +------+---------+-----------+----------+------+
+//
+//    Variable Length:   +------+-----+-----+------+---------+-----------+------+----------+------+
+//                       | 0x68 |  L  |  L  | 0x68 | Control | Link Addr | ASDU | Checksum | 0x16 |
+//                       +------+-----+-----+------+---------+-----------+------+----------+------+
+//
+// 校验和为控制域、链路地址及ASDU各字节之和(模256)，L为上述字段总长度。
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Iec101Header {
+    pub frames: Vec<Iec101Frame>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Iec101Frame {
+    SingleChar,
+    Fixed {
+        control: Iec101Control,
+        link_addr: u16,
+    },
+    Variable {
+        length: u8,
+        control: Iec101Control,
+        link_addr: u16,
+        asdu: Option<IecAsdu>,
+    },
+}
+
+impl Iec101Frame {
+    pub fn control(&self) -> Option<&Iec101Control> {
+        match self {
+            Iec101Frame::SingleChar => None,
+            Iec101Frame::Fixed { control, .. } | Iec101Frame::Variable { control, .. } => {
+                Some(control)
+            }
+        }
+    }
+
+    pub fn asdu(&self) -> Option<&IecAsdu> {
+        match self {
+            Iec101Frame::Variable { asdu, .. } => asdu.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+/// 链路控制域。`prm`为1时为启动站报文，此时第5、4位为FCB/FCV，
+/// 否则为从动站报文，对应ACD/DFC。
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Iec101Control {
+    pub dir: u8,
+    pub prm: u8,
+    pub fcb_acd: u8,
+    pub fcv_dfc: u8,
+    pub function: u8,
+}
+
+pub fn parse_iec101_header<'a>(
+    input: &'a [u8],
+    iec101_options: &Iec101Options,
+) -> IResult<&'a [u8], Iec101Header> {
+    let mut frames = Vec::new();
+    let mut input = input;
+    while !input.is_empty() {
+        let (_input, frame) = parse_iec101_frame(input, iec101_options)?;
+        input = _input;
+        frames.push(frame);
+    }
+    Ok((input, Iec101Header { frames }))
+}
+
+pub fn parse_iec101_layer<'a>(
+    input: &'a [u8],
+    link_layer: LinkLayer,
+    network_layer: NetworkLayer<'a>,
+    transport_layer: TransportLayer<'a>,
+    options: &QuinPacketOptions,
+) -> QuinPacket<'a> {
+    let current_prototype = ProtocolType::Application(ApplicationProtocol::Iec101);
+
+    let (input, iec101_header) = match parse_iec101_header(input, &options.iec101) {
+        Ok(o) => o,
+        Err(e) => {
+            tracing::error!(
+                target: "PARSER(iec101::parse_iec101_layer)",
+                error = ?e
+            );
+
+            // 校验和错误以 Failure 返回，单独报告
+            let error = match e {
+                nom::Err::Error(error) => ParseError::ParsingHeader {
+                    protocol: current_prototype,
+                    offset: input.len() - error.input.len(),
+                },
+                nom::Err::Failure(error) => ParseError::ChecksumMismatch {
+                    protocol: current_prototype,
+                    offset: input.len() - error.input.len(),
+                },
+                _ => ParseError::ParsingHeader {
+                    protocol: current_prototype,
+                    offset: usize::MAX,
+                },
+            };
+
+            return QuinPacket::L4(L4Packet {
+                link_layer,
+                network_layer,
+                transport_layer,
+                error: Some(error),
+                remain: input,
+            });
+        }
+    };
+
+    if Some(current_prototype) == options.stop {
+        let application_layer = ApplicationLayer::Iec101(iec101_header);
+        return QuinPacket::L5(L5Packet {
+            link_layer,
+            network_layer,
+            transport_layer,
+            application_layer,
+            error: None,
+            remain: input,
+        });
+    };
+
+    let application_layer = ApplicationLayer::Iec101(iec101_header);
+    parse_l5_eof_layer(
+        input,
+        link_layer,
+        network_layer,
+        transport_layer,
+        application_layer,
+        options,
+    )
+}
+
+pub fn parse_iec101_frame<'a>(
+    input: &'a [u8],
+    iec101_options: &Iec101Options,
+) -> IResult<&'a [u8], Iec101Frame> {
+    let frame_start = input;
+    let (input, start) = u8(input)?;
+    match start {
+        0xe5 => Ok((input, Iec101Frame::SingleChar)),
+        0x10 => {
+            let (input, user_data) = take(1 + iec101_options.link_addr_size as usize)(input)?;
+            let (input, checksum) = u8(input)?;
+            let (input, _) = tag([0x16])(input)?;
+            verify_checksum(frame_start, user_data, checksum)?;
+            let (user_data, control) = parse_iec101_control(user_data)?;
+            let (_, link_addr) = parse_link_addr(user_data, iec101_options.link_addr_size)?;
+            Ok((input, Iec101Frame::Fixed { control, link_addr }))
+        }
+        0x68 => {
+            let (input, (length, length_repeat, _)) = tuple((u8, u8, tag([0x68])))(input)?;
+            if length != length_repeat {
+                return Err(nom::Err::Error(nom::error::Error::new(
+                    input,
+                    nom::error::ErrorKind::Verify,
+                )));
+            }
+            let (input, user_data) = take(length as usize)(input)?;
+            let (input, checksum) = u8(input)?;
+            let (input, _) = tag([0x16])(input)?;
+            verify_checksum(frame_start, user_data, checksum)?;
+            let (user_data, control) = parse_iec101_control(user_data)?;
+            let (user_data, link_addr) = parse_link_addr(user_data, iec101_options.link_addr_size)?;
+            let asdu = match user_data.is_empty() {
+                true => None,
+                false => {
+                    let (remain, asdu) = parse_iec_asdu(user_data, &iec101_options.asdu)?;
+                    if !remain.is_empty() {
+                        return Err(nom::Err::Error(nom::error::Error::new(
+                            remain,
+                            nom::error::ErrorKind::Verify,
+                        )));
+                    }
+                    Some(asdu)
+                }
+            };
+            Ok((
+                input,
+                Iec101Frame::Variable {
+                    length,
+                    control,
+                    link_addr,
+                    asdu,
+                },
+            ))
+        }
+        _ => Err(nom::Err::Error(nom::error::Error::new(
+            frame_start,
+            nom::error::ErrorKind::Verify,
+        ))),
+    }
+}
+
+fn parse_iec101_control(input: &[u8]) -> IResult<&[u8], Iec101Control> {
+    let (input, (dir, prm, fcb_acd, fcv_dfc, function)): (&[u8], (u8, u8, u8, u8, u8)) =
+        bits::<_, _, nom::error::Error<(&[u8], usize)>, _, _>(tuple((
+            take_bits(1usize),
+            take_bits(1usize),
+            take_bits(1usize),
+            take_bits(1usize),
+            take_bits(4usize),
+        )))(input)?;
+    Ok((
+        input,
+        Iec101Control {
+            dir,
+            prm,
+            fcb_acd,
+            fcv_dfc,
+            function,
+        },
+    ))
+}
+
+fn parse_link_addr(input: &[u8], size: u8) -> IResult<&[u8], u16> {
+    match size {
+        0 => Ok((input, 0)),
+        1 => map(u8, |v| v as u16)(input),
+        2 => le_u16(input),
+        _ => Err(nom::Err::Error(nom::error::Error::new(
+            input,
+            nom::error::ErrorKind::Verify,
+        ))),
+    }
+}
+
+fn verify_checksum<'a>(
+    frame_start: &'a [u8],
+    user_data: &[u8],
+    checksum: u8,
+) -> IResult<&'a [u8], ()> {
+    let sum = user_data.iter().fold(0u8, |acc, b| acc.wrapping_add(*b));
+    match sum == checksum {
+        true => Ok((frame_start, ())),
+        false => Err(nom::Err::Failure(nom::error::Error::new(
+            frame_start,
+            nom::error::ErrorKind::Verify,
+        ))),
+    }
+}
@@ -25,7 +25,8 @@ use crate::field_type::*;
 use crate::layer::{ApplicationLayer, LinkLayer, NetworkLayer, TransportLayer};
 #[allow(unused)]
 use crate::packet::{
-    L1Packet, L2Packet, L3Packet, L4Packet, L5Packet, QuinPacket, QuinPacketOptions,
+    IecAsduOptions, L1Packet, L2Packet, L3Packet, L4Packet, L5Packet, QuinPacket,
+    QuinPacketOptions,
 };
 #[allow(unused)]
 use crate::protocol::*;
@@ -51,13 +52,16 @@ pub struct Iec104Header {
     pub iec104_blocks: Vec<Iec104Block>,
 }
 
-pub fn parse_iec104_header(input: &[u8]) -> IResult<&[u8], Iec104Header> {
+pub fn parse_iec104_header<'a>(
+    input: &'a [u8],
+    asdu_options: &IecAsduOptions,
+) -> IResult<&'a [u8], Iec104Header> {
     /* UnlimitedVecLoopField Start */
     let mut iec104_blocks = Vec::new();
     let mut _iec104_blocks: Iec104Block;
     let mut input = input;
     while input.len() > 0 {
-        (input, _iec104_blocks) = parse_iec104_block(input)?;
+        (input, _iec104_blocks) = parse_iec104_block(input, asdu_options)?;
         iec104_blocks.push(_iec104_blocks);
     }
     let input = input;
@@ -74,7 +78,7 @@ pub fn parse_iec104_layer<'a>(
 ) -> QuinPacket<'a> {
     let current_prototype = ProtocolType::Application(ApplicationProtocol::Iec104);
 
-    let (input, iec104_header) = match parse_iec104_header(input) {
+    let (input, iec104_header) = match parse_iec104_header(input, &options.iec104) {
         Ok(o) => o,
         Err(e) => {
             tracing::error!(
@@ -616,6 +620,40 @@ pub enum IoaTypeEnum {
     F_SC_NB_1 {},
 }
 
+impl IoaTypeEnum {
+    /// 单点/双点/步调节命令的命令状态(SCS/DCS/RCS)
+    pub fn command_state(&self) -> Option<u8> {
+        match self {
+            IoaTypeEnum::C_SC_NA_1 { sco_on, .. } | IoaTypeEnum::C_SC_TA_1 { sco_on, .. } => {
+                Some(*sco_on)
+            }
+            IoaTypeEnum::C_DC_NA_1 { dco_on, .. } | IoaTypeEnum::C_DC_TA_1 { dco_on, .. } => {
+                Some(*dco_on)
+            }
+            IoaTypeEnum::C_RC_NA_1 { rco_up, .. } | IoaTypeEnum::C_RC_TA_1 { rco_up, .. } => {
+                Some(*rco_up)
+            }
+            _ => None,
+        }
+    }
+
+    /// 设点命令的设定值，归一化值换算到[-1, 1)
+    pub fn setpoint_value(&self) -> Option<f64> {
+        match self {
+            IoaTypeEnum::C_SE_NA_1 { nva_u16, .. } | IoaTypeEnum::C_SE_TA_1 { nva_u16, .. } => {
+                Some(*nva_u16 as i16 as f64 / 32768.0)
+            }
+            IoaTypeEnum::C_SE_NB_1 { sva, .. } | IoaTypeEnum::C_SE_TB_1 { sva, .. } => {
+                Some(*sva as i16 as f64)
+            }
+            IoaTypeEnum::C_SE_NC_1 { flt, .. } | IoaTypeEnum::C_SE_TC_1 { flt, .. } => {
+                Some(f32::from_bits(*flt) as f64)
+            }
+            _ => None,
+        }
+    }
+}
+
 #[allow(non_camel_case_types)]
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Ioa {
@@ -664,6 +702,7 @@ pub struct Iec104Block {
     pub type_block: TypeBlock,
 }
 
+// 按 IEC 60870-5-4，SVA、R32 浮点与 BCR 计数量等多字节数值均为低字节在前
 pub fn parse_ioa_type_enum(input: &[u8], type_id: u8) -> IResult<&[u8], IoaTypeEnum> {
     let (input, ioa_type_enum) = match type_id {
         0x01 => {
@@ -964,7 +1003,7 @@ pub fn parse_ioa_type_enum(input: &[u8], type_id: u8) -> IResult<&[u8], IoaTypeE
             ))
         }
         0x0b => {
-            let (input, sva) = be_u16(input)?;
+            let (input, sva) = le_u16(input)?;
             let (input, (qds_iv, qds_nt, qds_sb, qds_bl, _, qds_ov)): (
                 &[u8],
                 (u8, u8, u8, u8, u8, u8),
@@ -989,7 +1028,7 @@ pub fn parse_ioa_type_enum(input: &[u8], type_id: u8) -> IResult<&[u8], IoaTypeE
             ))
         }
         0x0c => {
-            let (input, sva) = be_u16(input)?;
+            let (input, sva) = le_u16(input)?;
             let (input, (qds_iv, qds_nt, qds_sb, qds_bl, _, qds_ov)): (
                 &[u8],
                 (u8, u8, u8, u8, u8, u8),
@@ -1023,7 +1062,7 @@ pub fn parse_ioa_type_enum(input: &[u8], type_id: u8) -> IResult<&[u8], IoaTypeE
             ))
         }
         0x0d => {
-            let (input, flt) = be_u32(input)?;
+            let (input, flt) = le_u32(input)?;
             let (input, (qds_iv, qds_nt, qds_sb, qds_bl, _, qds_ov)): (
                 &[u8],
                 (u8, u8, u8, u8, u8, u8),
@@ -1048,7 +1087,7 @@ pub fn parse_ioa_type_enum(input: &[u8], type_id: u8) -> IResult<&[u8], IoaTypeE
             ))
         }
         0x0e => {
-            let (input, flt) = be_u32(input)?;
+            let (input, flt) = le_u32(input)?;
             let (input, (qds_iv, qds_nt, qds_sb, qds_bl, _, qds_ov)): (
                 &[u8],
                 (u8, u8, u8, u8, u8, u8),
@@ -1082,7 +1121,7 @@ pub fn parse_ioa_type_enum(input: &[u8], type_id: u8) -> IResult<&[u8], IoaTypeE
             ))
         }
         0x0f => {
-            let (input, bcr_count) = be_u32(input)?;
+            let (input, bcr_count) = le_u32(input)?;
             let (input, (bcr_iv, bcr_ca, bcr_cy, bcr_sq)): (&[u8], (u8, u8, u8, u8)) =
                 bits::<_, _, nom::error::Error<(&[u8], usize)>, _, _>(tuple((
                     take_bits(1usize),
@@ -1102,7 +1141,7 @@ pub fn parse_ioa_type_enum(input: &[u8], type_id: u8) -> IResult<&[u8], IoaTypeE
             ))
         }
         0x10 => {
-            let (input, bcr_count) = be_u32(input)?;
+            let (input, bcr_count) = le_u32(input)?;
             let (input, (bcr_iv, bcr_ca, bcr_cy, bcr_sq)): (&[u8], (u8, u8, u8, u8)) =
                 bits::<_, _, nom::error::Error<(&[u8], usize)>, _, _>(tuple((
                     take_bits(1usize),
@@ -1447,7 +1486,7 @@ pub fn parse_ioa_type_enum(input: &[u8], type_id: u8) -> IResult<&[u8], IoaTypeE
             ))
         }
         0x23 => {
-            let (input, sva) = be_u16(input)?;
+            let (input, sva) = le_u16(input)?;
             let (input, (qds_iv, qds_nt, qds_sb, qds_bl, _, qds_ov)): (
                 &[u8],
                 (u8, u8, u8, u8, u8, u8),
@@ -1509,7 +1548,7 @@ pub fn parse_ioa_type_enum(input: &[u8], type_id: u8) -> IResult<&[u8], IoaTypeE
             ))
         }
         0x24 => {
-            let (input, flt) = be_u32(input)?;
+            let (input, flt) = le_u32(input)?;
             let (input, (qds_iv, qds_nt, qds_sb, qds_bl, _, qds_ov)): (
                 &[u8],
                 (u8, u8, u8, u8, u8, u8),
@@ -1571,7 +1610,7 @@ pub fn parse_ioa_type_enum(input: &[u8], type_id: u8) -> IResult<&[u8], IoaTypeE
             ))
         }
         0x25 => {
-            let (input, bcr_count) = be_u32(input)?;
+            let (input, bcr_count) = le_u32(input)?;
             let (input, (bcr_iv, bcr_ca, bcr_cy, bcr_sq)): (&[u8], (u8, u8, u8, u8)) =
                 bits::<_, _, nom::error::Error<(&[u8], usize)>, _, _>(tuple((
                     take_bits(1usize),
@@ -1697,7 +1736,7 @@ pub fn parse_ioa_type_enum(input: &[u8], type_id: u8) -> IResult<&[u8], IoaTypeE
             ))
         }
         0x31 => {
-            let (input, sva) = be_u16(input)?;
+            let (input, sva) = le_u16(input)?;
             let (input, (qos_ql, qos_se)): (&[u8], (u8, u8)) =
                 bits::<_, _, nom::error::Error<(&[u8], usize)>, _, _>(tuple((
                     take_bits(7usize),
@@ -1713,7 +1752,7 @@ pub fn parse_ioa_type_enum(input: &[u8], type_id: u8) -> IResult<&[u8], IoaTypeE
             ))
         }
         0x32 => {
-            let (input, flt) = be_u32(input)?;
+            let (input, flt) = le_u32(input)?;
             let (input, (qos_ql, qos_se)): (&[u8], (u8, u8)) =
                 bits::<_, _, nom::error::Error<(&[u8], usize)>, _, _>(tuple((
                     take_bits(7usize),
@@ -1946,7 +1985,7 @@ pub fn parse_ioa_type_enum(input: &[u8], type_id: u8) -> IResult<&[u8], IoaTypeE
             ))
         }
         0x3e => {
-            let (input, sva) = be_u16(input)?;
+            let (input, sva) = le_u16(input)?;
             let (input, (qos_ql, qos_se)): (&[u8], (u8, u8)) =
                 bits::<_, _, nom::error::Error<(&[u8], usize)>, _, _>(tuple((
                     take_bits(7usize),
@@ -1999,7 +2038,7 @@ pub fn parse_ioa_type_enum(input: &[u8], type_id: u8) -> IResult<&[u8], IoaTypeE
             ))
         }
         0x3f => {
-            let (input, flt) = be_u32(input)?;
+            let (input, flt) = le_u32(input)?;
             let (input, (qos_ql, qos_se)): (&[u8], (u8, u8)) =
                 bits::<_, _, nom::error::Error<(&[u8], usize)>, _, _>(tuple((
                     take_bits(7usize),
@@ -2206,7 +2245,7 @@ pub fn parse_ioa_type_enum(input: &[u8], type_id: u8) -> IResult<&[u8], IoaTypeE
             ))
         }
         0x6f => {
-            let (input, sva) = be_u16(input)?;
+            let (input, sva) = le_u16(input)?;
             let (input, (qpm_pop, qpm_lpc, qpm_kpa)): (&[u8], (u8, u8, u8)) =
                 bits::<_, _, nom::error::Error<(&[u8], usize)>, _, _>(tuple((
                     take_bits(1usize),
@@ -2224,7 +2263,7 @@ pub fn parse_ioa_type_enum(input: &[u8], type_id: u8) -> IResult<&[u8], IoaTypeE
             ))
         }
         0x70 => {
-            let (input, flt) = be_u32(input)?;
+            let (input, flt) = le_u32(input)?;
             let (input, (qpm_pop, qpm_lpc, qpm_kpa)): (&[u8], (u8, u8, u8)) =
                 bits::<_, _, nom::error::Error<(&[u8], usize)>, _, _>(tuple((
                     take_bits(1usize),
@@ -2258,13 +2297,29 @@ pub fn parse_ioa_type_enum(input: &[u8], type_id: u8) -> IResult<&[u8], IoaTypeE
     Ok((input, ioa_type_enum))
 }
 
-pub fn parse_ioa(input: &[u8], type_id: u8) -> IResult<&[u8], Ioa> {
-    let (input, ioa) = le_u24(input)?;
+// 按配置长度读取小端地址字段
+fn parse_iec_address(input: &[u8], size: u8) -> IResult<&[u8], u32> {
+    match size {
+        1 => map(u8, |v| v as u32)(input),
+        2 => map(le_u16, |v| v as u32)(input),
+        3 => le_u24(input),
+        _ => Err(nom::Err::Error(nom::error::Error::new(
+            input,
+            nom::error::ErrorKind::Verify,
+        ))),
+    }
+}
+
+pub fn parse_ioa(input: &[u8], type_id: u8, ioa_size: u8) -> IResult<&[u8], Ioa> {
+    let (input, ioa) = parse_iec_address(input, ioa_size)?;
     let (input, ioa_type_enum) = parse_ioa_type_enum(input, type_id)?;
     Ok((input, Ioa { ioa, ioa_type_enum }))
 }
 
-pub fn parse_iec_asdu(input: &[u8]) -> IResult<&[u8], IecAsdu> {
+pub fn parse_iec_asdu<'a>(
+    input: &'a [u8],
+    asdu_options: &IecAsduOptions,
+) -> IResult<&'a [u8], IecAsdu> {
     let (input, type_id) = u8(input)?;
     let (input, (sq, num_ix, test, negative, cause_tx)): (&[u8], (u8, u8, u8, u8, u8)) =
         bits::<_, _, nom::error::Error<(&[u8], usize)>, _, _>(tuple((
@@ -2274,15 +2329,46 @@ pub fn parse_iec_asdu(input: &[u8]) -> IResult<&[u8], IecAsdu> {
             take_bits(1usize),
             take_bits(6usize),
         )))(input)?;
-    let (input, oa) = u8(input)?;
-    let (input, addr) = le_u16(input)?;
+    let (input, oa) = match asdu_options.cot_size {
+        1 => (input, 0),
+        2 => u8(input)?,
+        _ => {
+            return Err(nom::Err::Error(nom::error::Error::new(
+                input,
+                nom::error::ErrorKind::Verify,
+            )))
+        }
+    };
+    if asdu_options.ca_size > 2 {
+        return Err(nom::Err::Error(nom::error::Error::new(
+            input,
+            nom::error::ErrorKind::Verify,
+        )));
+    }
+    let (input, addr) = parse_iec_address(input, asdu_options.ca_size)?;
+    let addr = addr as u16;
     /* LimitedCountVecLoopField Start */
     let mut ioa_array = Vec::new();
     let mut _ioa_array: Ioa;
     let mut input = input;
-    for _ in 0..(num_ix as usize) {
-        (input, _ioa_array) = parse_ioa(input, type_id)?;
+    if sq == 1 && num_ix > 0 {
+        // SQ=1时仅第一个信息对象携带地址，其后地址依次递增
+        (input, _ioa_array) = parse_ioa(input, type_id, asdu_options.ioa_size)?;
+        let first_ioa = _ioa_array.ioa;
         ioa_array.push(_ioa_array);
+        for i in 1..(num_ix as u32) {
+            let (_input, ioa_type_enum) = parse_ioa_type_enum(input, type_id)?;
+            input = _input;
+            ioa_array.push(Ioa {
+                ioa: first_ioa + i,
+                ioa_type_enum,
+            });
+        }
+    } else {
+        for _ in 0..(num_ix as usize) {
+            (input, _ioa_array) = parse_ioa(input, type_id, asdu_options.ioa_size)?;
+            ioa_array.push(_ioa_array);
+        }
     }
     let input = input;
     /* LimitedCountVecLoopField End. */
@@ -2302,16 +2388,17 @@ pub fn parse_iec_asdu(input: &[u8]) -> IResult<&[u8], IecAsdu> {
     ))
 }
 
-pub fn parse_type_block(
-    input: &[u8],
+pub fn parse_type_block<'a>(
+    input: &'a [u8],
     apci_txid_tmp: u16,
     apci_rxid_tmp: u16,
-) -> IResult<&[u8], TypeBlock> {
+    asdu_options: &IecAsduOptions,
+) -> IResult<&'a [u8], TypeBlock> {
     if apci_txid_tmp & 0x01u16 == 0x00u16 {
         let type104: u8 = 0x00;
         let apci_txid = (apci_txid_tmp >> 1).try_into().unwrap();
         let apci_rxid = (apci_rxid_tmp >> 1).try_into().unwrap();
-        let (input, iec_asdu) = parse_iec_asdu(input)?;
+        let (input, iec_asdu) = parse_iec_asdu(input, asdu_options)?;
         Ok((
             input,
             TypeBlock::TypeI {
@@ -2343,7 +2430,10 @@ pub fn parse_type_block(
     }
 }
 
-pub fn parse_iec104_block(input: &[u8]) -> IResult<&[u8], Iec104Block> {
+pub fn parse_iec104_block<'a>(
+    input: &'a [u8],
+    asdu_options: &IecAsduOptions,
+) -> IResult<&'a [u8], Iec104Block> {
     let (input, start) = u8(input)?;
     if !(start == 0x68) {
         return Err(nom::Err::Error(nom::error::Error::new(
@@ -2354,7 +2444,7 @@ pub fn parse_iec104_block(input: &[u8]) -> IResult<&[u8], Iec104Block> {
     let (input, apdu_len) = u8(input)?;
     let (input, apci_txid_tmp) = le_u16(input)?;
     let (input, apci_rxid_tmp) = le_u16(input)?;
-    let (input, type_block) = parse_type_block(input, apci_txid_tmp, apci_rxid_tmp)?;
+    let (input, type_block) = parse_type_block(input, apci_txid_tmp, apci_rxid_tmp, asdu_options)?;
     Ok((
         input,
         Iec104Block {
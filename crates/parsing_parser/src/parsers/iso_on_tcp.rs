@@ -8,7 +8,7 @@ use crate::errors::ParseError;
 use crate::layer::{ApplicationLayer, LinkLayer, NetworkLayer, TransportLayer};
 use crate::packet::{L4Packet, L5Packet, QuinPacket, QuinPacketOptions};
 
-use super::{parse_l5_eof_layer, parse_mms_layer, parse_s7comm_layer};
+use super::{parse_l5_eof_layer, parse_mms_layer, parse_s7comm_layer, parse_s7comm_plus_layer};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct IsoOnTcpHeader {
@@ -88,6 +88,9 @@ pub fn parse_iso_on_tcp_layer<'a>(
                 0x32 => {
                     parse_s7comm_layer(input, link_layer, network_layer, transport_layer, options)
                 }
+                0x72 => {
+                    parse_s7comm_plus_layer(input, link_layer, network_layer, transport_layer, options)
+                }
                 _ => parse_mms_layer(input, link_layer, network_layer, transport_layer, options),
             }
         }
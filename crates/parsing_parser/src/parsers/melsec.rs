@@ -0,0 +1,471 @@
+use nom::bytes::complete::take;
+use nom::combinator::rest;
+use nom::number::complete::{be_u16, le_u16, le_u24, le_u32, u8};
+use nom::IResult;
+
+use crate::errors::ParseError;
+use crate::layer::{ApplicationLayer, LinkLayer, NetworkLayer, TransportLayer};
+use crate::packet::{L4Packet, L5Packet, QuinPacket, QuinPacketOptions};
+use crate::protocol::ApplicationProtocol;
+use crate::ProtocolType;
+
+use super::parse_l5_eof_layer;
+
+// MELSEC MC 协议(SLMP) 3E/4E 二进制帧，除副头部外均为小端
+//
+//    +-----------+-----------------+---------+----+-----------+---------+-------------+----------------+
+//    | Subheader | 4E: Serial/0000 | Network | PC | Module I/O| Station | Data Length | Timer/EndCode  |
+//    | 2 bytes   | 4 bytes         | 1 byte  | 1  | 2 bytes   | 1 byte  | 2 bytes     | 2 bytes + Data |
+//    +-----------+-----------------+---------+----+-----------+---------+-------------+----------------+
+//
+// 请求数据为 Command(2) + Subcommand(2) + 命令数据，响应数据为结束码之后的内容。
+
+// 副头部
+pub const MELSEC_SUBHEADER_3E_REQUEST: u16 = 0x5000;
+pub const MELSEC_SUBHEADER_3E_RESPONSE: u16 = 0xd000;
+pub const MELSEC_SUBHEADER_4E_REQUEST: u16 = 0x5400;
+pub const MELSEC_SUBHEADER_4E_RESPONSE: u16 = 0xd400;
+
+// 命令
+pub const MELSEC_BATCH_READ: u16 = 0x0401;
+pub const MELSEC_BATCH_WRITE: u16 = 0x1401;
+pub const MELSEC_RANDOM_READ: u16 = 0x0403;
+pub const MELSEC_RANDOM_WRITE: u16 = 0x1402;
+pub const MELSEC_BLOCK_READ: u16 = 0x0406;
+pub const MELSEC_BLOCK_WRITE: u16 = 0x1406;
+pub const MELSEC_REMOTE_RUN: u16 = 0x1001;
+pub const MELSEC_REMOTE_STOP: u16 = 0x1002;
+pub const MELSEC_REMOTE_PAUSE: u16 = 0x1003;
+pub const MELSEC_REMOTE_LATCH_CLEAR: u16 = 0x1005;
+pub const MELSEC_REMOTE_RESET: u16 = 0x1006;
+pub const MELSEC_READ_CPU_MODEL: u16 = 0x0101;
+pub const MELSEC_MEMORY_READ: u16 = 0x0613;
+pub const MELSEC_MEMORY_WRITE: u16 = 0x1613;
+
+// 常用软元件代码
+pub const MELSEC_DEVICE_SM: u16 = 0x91;
+pub const MELSEC_DEVICE_SD: u16 = 0xa9;
+pub const MELSEC_DEVICE_X: u16 = 0x9c;
+pub const MELSEC_DEVICE_Y: u16 = 0x9d;
+pub const MELSEC_DEVICE_M: u16 = 0x90;
+pub const MELSEC_DEVICE_L: u16 = 0x92;
+pub const MELSEC_DEVICE_B: u16 = 0xa0;
+pub const MELSEC_DEVICE_D: u16 = 0xa8;
+pub const MELSEC_DEVICE_W: u16 = 0xb4;
+pub const MELSEC_DEVICE_R: u16 = 0xaf;
+pub const MELSEC_DEVICE_ZR: u16 = 0xb0;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum MelsecFrameType {
+    E3,
+    E4 { serial: u16 },
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MelsecHeader<'a> {
+    pub subheader: u16,
+    pub frame_type: MelsecFrameType,
+    pub network: u8,
+    pub pc: u8,
+    pub module_io: u16,
+    pub station: u8,
+    pub data_length: u16,
+    pub pdu: MelsecPdu<'a>,
+}
+
+impl<'a> MelsecHeader<'a> {
+    pub fn request(&self) -> Option<&MelsecRequest<'a>> {
+        match &self.pdu {
+            MelsecPdu::Request(request) => Some(request),
+            MelsecPdu::Response { .. } => None,
+        }
+    }
+
+    pub fn end_code(&self) -> Option<u16> {
+        match self.pdu {
+            MelsecPdu::Response { end_code, .. } => Some(end_code),
+            MelsecPdu::Request(_) => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum MelsecPdu<'a> {
+    Request(MelsecRequest<'a>),
+    /// 响应不携带命令，批量读的数据或异常时的出错信息保存在`data`中
+    Response { end_code: u16, data: &'a [u8] },
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MelsecRequest<'a> {
+    pub monitoring_timer: u16,
+    pub command: u16,
+    pub subcommand: u16,
+    pub data: MelsecRequestData<'a>,
+}
+
+impl<'a> MelsecRequest<'a> {
+    /// 请求访问的软元件及点数，随机读写每个软元件按 1 点计
+    pub fn devices(&self) -> Vec<(MelsecDevice, u16)> {
+        match &self.data {
+            MelsecRequestData::BatchRead { device, points }
+            | MelsecRequestData::BatchWrite { device, points, .. } => vec![(*device, *points)],
+            MelsecRequestData::RandomRead { devices } => devices.iter().map(|device| (*device, 1)).collect(),
+            MelsecRequestData::RandomWrite { devices } => {
+                devices.iter().map(|value| (value.device, 1)).collect()
+            }
+            MelsecRequestData::BlockRead { blocks } | MelsecRequestData::BlockWrite { blocks } => {
+                blocks.iter().map(|block| (block.device, block.points)).collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// MelsecDevice是软元件代码与软元件编号。Q 系列代码 1 字节，iQ-R 扩展格式 2 字节，统一为 u16
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct MelsecDevice {
+    pub code: u16,
+    pub number: u32,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MelsecDeviceValue<'a> {
+    pub device: MelsecDevice,
+    pub value: &'a [u8],
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MelsecBlock<'a> {
+    pub device: MelsecDevice,
+    pub points: u16,
+    /// 仅多块批量写
+    pub values: &'a [u8],
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum MelsecRequestData<'a> {
+    BatchRead {
+        device: MelsecDevice,
+        points: u16,
+    },
+    BatchWrite {
+        device: MelsecDevice,
+        points: u16,
+        values: &'a [u8],
+    },
+    /// 字访问在前，双字访问在后
+    RandomRead {
+        devices: Vec<MelsecDevice>,
+    },
+    RandomWrite {
+        devices: Vec<MelsecDeviceValue<'a>>,
+    },
+    BlockRead {
+        blocks: Vec<MelsecBlock<'a>>,
+    },
+    BlockWrite {
+        blocks: Vec<MelsecBlock<'a>>,
+    },
+    RemoteRun {
+        force: bool,
+        clear_mode: u8,
+    },
+    /// 远程 STOP/PAUSE/RESET/锁存清除
+    RemoteOperation,
+    MemoryRead {
+        address: u32,
+        length: u16,
+    },
+    MemoryWrite {
+        address: u32,
+        length: u16,
+        values: &'a [u8],
+    },
+    Raw(&'a [u8]),
+}
+
+// 子命令 bit1 置位时为 iQ-R 扩展的软元件格式
+fn is_extended(subcommand: u16) -> bool {
+    subcommand & 0x0002 != 0
+}
+
+// 子命令 bit0 置位时以位为单位访问
+fn is_bit_unit(subcommand: u16) -> bool {
+    subcommand & 0x0001 != 0
+}
+
+fn parse_device(input: &[u8], extended: bool) -> IResult<&[u8], MelsecDevice> {
+    if extended {
+        let (input, number) = le_u32(input)?;
+        let (input, code) = le_u16(input)?;
+        Ok((input, MelsecDevice { code, number }))
+    } else {
+        let (input, number) = le_u24(input)?;
+        let (input, code) = u8(input)?;
+        Ok((
+            input,
+            MelsecDevice {
+                code: code as u16,
+                number,
+            },
+        ))
+    }
+}
+
+fn parse_devices(input: &[u8], count: usize, extended: bool) -> IResult<&[u8], Vec<MelsecDevice>> {
+    let mut devices = Vec::with_capacity(count);
+    let mut input = input;
+    for _ in 0..count {
+        let (remain, device) = parse_device(input, extended)?;
+        devices.push(device);
+        input = remain;
+    }
+    Ok((input, devices))
+}
+
+fn parse_device_values<'a>(
+    input: &'a [u8],
+    count: usize,
+    extended: bool,
+    value_size: usize,
+) -> IResult<&'a [u8], Vec<MelsecDeviceValue<'a>>> {
+    let mut devices = Vec::with_capacity(count);
+    let mut input = input;
+    for _ in 0..count {
+        let (remain, device) = parse_device(input, extended)?;
+        let (remain, value) = take(value_size)(remain)?;
+        devices.push(MelsecDeviceValue { device, value });
+        input = remain;
+    }
+    Ok((input, devices))
+}
+
+fn parse_blocks(input: &[u8], subcommand: u16, write: bool) -> IResult<&[u8], Vec<MelsecBlock>> {
+    let (input, word_blocks) = u8(input)?;
+    let (mut input, bit_blocks) = u8(input)?;
+    let mut blocks = Vec::new();
+    for _ in 0..(word_blocks as usize + bit_blocks as usize) {
+        let (remain, device) = parse_device(input, is_extended(subcommand))?;
+        let (remain, points) = le_u16(remain)?;
+        // 位块的点数也以字(16 位)为单位
+        let (remain, values) = if write {
+            take(points as usize * 2)(remain)?
+        } else {
+            (remain, &remain[..0])
+        };
+        blocks.push(MelsecBlock { device, points, values });
+        input = remain;
+    }
+    Ok((input, blocks))
+}
+
+fn parse_request_data(input: &[u8], command: u16, subcommand: u16) -> IResult<&[u8], MelsecRequestData> {
+    let extended = is_extended(subcommand);
+    match command {
+        MELSEC_BATCH_READ => {
+            let (input, device) = parse_device(input, extended)?;
+            let (input, points) = le_u16(input)?;
+            Ok((input, MelsecRequestData::BatchRead { device, points }))
+        }
+        MELSEC_BATCH_WRITE => {
+            let (input, device) = parse_device(input, extended)?;
+            let (input, points) = le_u16(input)?;
+            // 位单位每字节 2 点，字单位每点 2 字节；目标工具链尚无 usize::div_ceil
+            #[allow(clippy::manual_div_ceil)]
+            let size = if is_bit_unit(subcommand) {
+                (points as usize + 1) / 2
+            } else {
+                points as usize * 2
+            };
+            let (input, values) = take(size)(input)?;
+            Ok((input, MelsecRequestData::BatchWrite { device, points, values }))
+        }
+        MELSEC_RANDOM_READ => {
+            let (input, word_points) = u8(input)?;
+            let (input, dword_points) = u8(input)?;
+            let (input, devices) = parse_devices(input, word_points as usize + dword_points as usize, extended)?;
+            Ok((input, MelsecRequestData::RandomRead { devices }))
+        }
+        MELSEC_RANDOM_WRITE if is_bit_unit(subcommand) => {
+            let (input, bit_points) = u8(input)?;
+            // iQ-R 扩展格式的位值占 2 字节
+            let value_size = if extended { 2 } else { 1 };
+            let (input, devices) = parse_device_values(input, bit_points as usize, extended, value_size)?;
+            Ok((input, MelsecRequestData::RandomWrite { devices }))
+        }
+        MELSEC_RANDOM_WRITE => {
+            let (input, word_points) = u8(input)?;
+            let (input, dword_points) = u8(input)?;
+            let (input, mut devices) = parse_device_values(input, word_points as usize, extended, 2)?;
+            let (input, dwords) = parse_device_values(input, dword_points as usize, extended, 4)?;
+            devices.extend(dwords);
+            Ok((input, MelsecRequestData::RandomWrite { devices }))
+        }
+        MELSEC_BLOCK_READ => {
+            let (input, blocks) = parse_blocks(input, subcommand, false)?;
+            Ok((input, MelsecRequestData::BlockRead { blocks }))
+        }
+        MELSEC_BLOCK_WRITE => {
+            let (input, blocks) = parse_blocks(input, subcommand, true)?;
+            Ok((input, MelsecRequestData::BlockWrite { blocks }))
+        }
+        MELSEC_REMOTE_RUN => {
+            // 0x0001 不强制执行，0x0003 强制执行
+            let (input, mode) = le_u16(input)?;
+            let (input, clear_mode) = u8(input)?;
+            let (input, _reserved) = rest(input)?;
+            Ok((
+                input,
+                MelsecRequestData::RemoteRun {
+                    force: mode == 0x0003,
+                    clear_mode,
+                },
+            ))
+        }
+        MELSEC_REMOTE_STOP | MELSEC_REMOTE_PAUSE | MELSEC_REMOTE_LATCH_CLEAR | MELSEC_REMOTE_RESET => {
+            let (input, _) = rest(input)?;
+            Ok((input, MelsecRequestData::RemoteOperation))
+        }
+        MELSEC_MEMORY_READ => {
+            let (input, address) = le_u32(input)?;
+            let (input, length) = le_u16(input)?;
+            Ok((input, MelsecRequestData::MemoryRead { address, length }))
+        }
+        MELSEC_MEMORY_WRITE => {
+            let (input, address) = le_u32(input)?;
+            let (input, length) = le_u16(input)?;
+            let (input, values) = take(length as usize * 2)(input)?;
+            Ok((input, MelsecRequestData::MemoryWrite { address, length, values }))
+        }
+        _ => {
+            let (input, data) = rest(input)?;
+            Ok((input, MelsecRequestData::Raw(data)))
+        }
+    }
+}
+
+fn parse_request(input: &[u8]) -> IResult<&[u8], MelsecRequest> {
+    let (input, monitoring_timer) = le_u16(input)?;
+    let (input, command) = le_u16(input)?;
+    let (input, subcommand) = le_u16(input)?;
+    let data = match parse_request_data(input, command, subcommand) {
+        Ok((_, data)) => data,
+        Err(_) => MelsecRequestData::Raw(input),
+    };
+    Ok((
+        &input[input.len()..],
+        MelsecRequest {
+            monitoring_timer,
+            command,
+            subcommand,
+            data,
+        },
+    ))
+}
+
+pub fn parse_melsec_header(input: &[u8]) -> IResult<&[u8], MelsecHeader> {
+    let (input, subheader) = be_u16(input)?;
+    let (input, frame_type) = match subheader {
+        MELSEC_SUBHEADER_3E_REQUEST | MELSEC_SUBHEADER_3E_RESPONSE => (input, MelsecFrameType::E3),
+        MELSEC_SUBHEADER_4E_REQUEST | MELSEC_SUBHEADER_4E_RESPONSE => {
+            let (input, serial) = le_u16(input)?;
+            let (input, _reserved) = le_u16(input)?;
+            (input, MelsecFrameType::E4 { serial })
+        }
+        _ => {
+            return Err(nom::Err::Error(nom::error::Error::new(
+                input,
+                nom::error::ErrorKind::Tag,
+            )))
+        }
+    };
+    let (input, network) = u8(input)?;
+    let (input, pc) = u8(input)?;
+    let (input, module_io) = le_u16(input)?;
+    let (input, station) = u8(input)?;
+    let (input, data_length) = le_u16(input)?;
+    // 数据长度从监视定时器/结束码开始计算
+    let (input, data) = take(data_length as usize)(input)?;
+    let pdu = match subheader {
+        MELSEC_SUBHEADER_3E_REQUEST | MELSEC_SUBHEADER_4E_REQUEST => {
+            let (_, request) = parse_request(data)?;
+            MelsecPdu::Request(request)
+        }
+        _ => {
+            let (data, end_code) = le_u16(data)?;
+            MelsecPdu::Response { end_code, data }
+        }
+    };
+    Ok((
+        input,
+        MelsecHeader {
+            subheader,
+            frame_type,
+            network,
+            pc,
+            module_io,
+            station,
+            data_length,
+            pdu,
+        },
+    ))
+}
+
+pub fn parse_melsec_layer<'a>(
+    input: &'a [u8],
+    link_layer: LinkLayer,
+    network_layer: NetworkLayer<'a>,
+    transport_layer: TransportLayer<'a>,
+    options: &QuinPacketOptions,
+) -> QuinPacket<'a> {
+    let current_prototype = ProtocolType::Application(ApplicationProtocol::Melsec);
+
+    let (input, melsec_header) = match parse_melsec_header(input) {
+        Ok(o) => o,
+        Err(e) => {
+            tracing::error!(
+                target: "PARSER(melsec::parse_melsec_layer)",
+                error = ?e
+            );
+
+            let offset = match e {
+                nom::Err::Error(error) => input.len() - error.input.len(),
+                _ => usize::MAX
+            };
+
+            return QuinPacket::L4(L4Packet {
+                link_layer,
+                network_layer,
+                transport_layer,
+                error: Some(ParseError::ParsingHeader{
+                    protocol: current_prototype,
+                    offset
+                }),
+                remain: input,
+            })
+        }
+    };
+
+    if Some(current_prototype) == options.stop {
+        let application_layer = ApplicationLayer::Melsec(melsec_header);
+        return QuinPacket::L5(L5Packet {
+            link_layer,
+            network_layer,
+            transport_layer,
+            application_layer,
+            error: None,
+            remain: input,
+        });
+    };
+
+    let application_layer = ApplicationLayer::Melsec(melsec_header);
+    parse_l5_eof_layer(
+        input,
+        link_layer,
+        network_layer,
+        transport_layer,
+        application_layer,
+        options,
+    )
+}
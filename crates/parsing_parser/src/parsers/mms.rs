@@ -45,10 +45,18 @@ use std::ops::BitOr;
 #[allow(unused)]
 use std::ops::BitXor;
 
+use super::goose::{parse_goose_data, GooseData};
 use super::parse_l5_eof_layer;
 
+/// MMS Data 与 GOOSE allData 采用相同的 BER 编码
+pub type MmsData<'a> = GooseData<'a>;
+
+pub fn parse_mms_data(input: &[u8]) -> IResult<&[u8], MmsData> {
+    parse_goose_data(input)
+}
+
 #[allow(non_camel_case_types)]
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct MmsHeader<'a> {
     pub osi_protocol_stack: OsiProtocolStack<'a>,
     pub mms_pdu: MmsPdu<'a>,
@@ -435,20 +443,20 @@ pub enum DataAccessError {
 }
 
 #[allow(non_camel_case_types)]
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum AccessResult<'a> {
     AccessResultFailure { data_access_error: DataAccessError },
-    AccessResultSuccess { data: &'a [u8] },
+    AccessResultSuccess { data: MmsData<'a> },
 }
 
 #[allow(non_camel_case_types)]
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct AccessResultStruct<'a> {
     pub access_result: AccessResult<'a>,
 }
 
 #[allow(non_camel_case_types)]
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct ListOfAccessResult<'a> {
     pub loar: Vec<AccessResultStruct<'a>>,
 }
@@ -506,7 +514,7 @@ pub enum WriteResponseEnum {
 }
 
 #[allow(non_camel_case_types)]
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum ConfirmedServiceRequestEnum<'a> {
     GetNameListRequest {
         object_class: ObjectClass<'a>,
@@ -518,15 +526,40 @@ pub enum ConfirmedServiceRequestEnum<'a> {
     },
     WriteRequest {
         variable_access_specification_enum: VariableAccessSpecificationEnum<'a>,
-        lod: Vec<&'a [u8]>,
+        lod: Vec<MmsData<'a>>,
     },
     GetNamedVariableListAttributesRequest {
         object_name: ObjectName<'a>,
     },
+    ObtainFileRequest {
+        source_file: Vec<&'a [u8]>,
+        destination_file: Vec<&'a [u8]>,
+    },
+    FileOpenRequest {
+        file_name: Vec<&'a [u8]>,
+        initial_position: u32,
+    },
+    FileReadRequest {
+        frsm_id: u32,
+    },
+    FileCloseRequest {
+        frsm_id: u32,
+    },
+    FileRenameRequest {
+        current_file_name: Vec<&'a [u8]>,
+        new_file_name: Vec<&'a [u8]>,
+    },
+    FileDeleteRequest {
+        file_name: Vec<&'a [u8]>,
+    },
+    FileDirectoryRequest {
+        file_specification: Vec<&'a [u8]>,
+        continue_after: Vec<&'a [u8]>,
+    },
 }
 
 #[allow(non_camel_case_types)]
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum ConfirmedServiceResponseEnum<'a> {
     GetNameListResponse {
         list_of_identifier: ListOfIdentifier<'a>,
@@ -547,10 +580,25 @@ pub enum ConfirmedServiceResponseEnum<'a> {
         mms_deleteable: u8,
         list_of_variable_specification: ListOfVariableSpecification<'a>,
     },
+    ObtainFileResponse {},
+    FileOpenResponse {
+        frsm_id: u32,
+    },
+    FileReadResponse {
+        file_data: &'a [u8],
+        more_follows: u8,
+    },
+    FileCloseResponse {},
+    FileRenameResponse {},
+    FileDeleteResponse {},
+    FileDirectoryResponse {
+        list_of_directory_entry: Vec<Vec<&'a [u8]>>,
+        more_follows: u8,
+    },
 }
 
 #[allow(non_camel_case_types)]
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum ConfirmedServiceResponse<'a> {
     None {},
     WithData {
@@ -559,7 +607,7 @@ pub enum ConfirmedServiceResponse<'a> {
 }
 
 #[allow(non_camel_case_types)]
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum UnConfirmedEnum<'a> {
     InformationReport {
         variable_access_specification_enum: VariableAccessSpecificationEnum<'a>,
@@ -568,7 +616,7 @@ pub enum UnConfirmedEnum<'a> {
 }
 
 #[allow(non_camel_case_types)]
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum MmsPduEnum<'a> {
     ConfirmedRequestPDU {
         invoke_id: u16,
@@ -599,7 +647,7 @@ pub enum MmsPduEnum<'a> {
 }
 
 #[allow(non_camel_case_types)]
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct MmsPdu<'a> {
     pub mms_pdu_enum: MmsPduEnum<'a>,
 }
@@ -1184,8 +1232,9 @@ fn parse_object_scope_object_scope_vmd(input: &[u8]) -> IResult<&[u8], ObjectSco
 
 fn parse_object_scope_object_scope_domain(input: &[u8]) -> IResult<&[u8], ObjectScope> {
     debug!(target: "PARSER(parse_object_scope_object_scope_domain)", "struct ObjectScopeDomain");
+    // domainSpecific 仅包含 Domain 名称
     let (input, object_scope_domain_id) = take(input.len() as usize)(input)?;
-    let (input, object_scope_item_id) = take(input.len() as usize)(input)?;
+    let object_scope_item_id: &[u8] = &[];
     Ok((
         input,
         ObjectScope::ObjectScopeDomain {
@@ -1228,8 +1277,8 @@ fn parse_object_name_object_name_vmd(input: &[u8]) -> IResult<&[u8], ObjectName>
 
 fn parse_object_name_object_name_domain(input: &[u8]) -> IResult<&[u8], ObjectName> {
     debug!(target: "PARSER(parse_object_name_object_name_domain)", "struct ObjectNameDomain");
-    let (input, object_name_domain_id) = take(input.len() as usize)(input)?;
-    let (input, object_name_item_id) = take(input.len() as usize)(input)?;
+    let (input, object_name_domain_id) = ber_tl_v(input)?;
+    let (input, object_name_item_id) = ber_tl_v(input)?;
     Ok((
         input,
         ObjectName::ObjectNameDomain {
@@ -1267,7 +1316,8 @@ pub fn parse_object_name(input: &[u8], _object_name_tl_tag: u8) -> IResult<&[u8]
 fn parse_variable_specification_name(input: &[u8]) -> IResult<&[u8], VariableSpecification> {
     debug!(target: "PARSER(parse_variable_specification_name)", "struct Name");
     let (input, _object_name_tl) = ber_tl(input)?;
-    let (input, object_name) = parse_object_name(input, _object_name_tl.tag)?;
+    let (input, _object_name) = take(_object_name_tl.length as usize)(input)?;
+    let (_, object_name) = parse_object_name(_object_name, _object_name_tl.tag)?;
     Ok((input, VariableSpecification::Name { object_name }))
 }
 
@@ -1470,6 +1520,7 @@ pub fn parse_data_access_error(
 
 fn parse_access_result_access_result_failure(input: &[u8]) -> IResult<&[u8], AccessResult> {
     debug!(target: "PARSER(parse_access_result_access_result_failure)", "struct AccessResultFailure");
+    let (input, _failure_tl) = ber_tl(input)?;
     let (input, _data_access_error_tl) = ber_tl(input)?;
     let (input, data_access_error) = parse_data_access_error(input, _data_access_error_tl.tag)?;
     Ok((
@@ -1480,7 +1531,7 @@ fn parse_access_result_access_result_failure(input: &[u8]) -> IResult<&[u8], Acc
 
 fn parse_access_result_access_result_success(input: &[u8]) -> IResult<&[u8], AccessResult> {
     debug!(target: "PARSER(parse_access_result_access_result_success)", "struct AccessResultSuccess");
-    let (input, data) = ber_tl_v(input)?;
+    let (input, data) = parse_mms_data(input)?;
     Ok((input, AccessResult::AccessResultSuccess { data }))
 }
 
@@ -1489,20 +1540,17 @@ pub fn parse_access_result(
     _access_result_tl_tag: u8,
 ) -> IResult<&[u8], AccessResult> {
     debug!(target: "PARSER(parse_access_result)", "enum AccessResult");
-    let (input, access_result) = match _access_result_tl_tag.bitand(0x1f) {
-        0x0 => parse_access_result_access_result_failure(input),
-        0x01 => parse_access_result_access_result_success(input),
-        _ => Err(nom::Err::Error(nom::error::Error::new(
-            input,
-            nom::error::ErrorKind::Verify,
-        ))),
+    // failure 为 [0] IMPLICIT DataAccessError，其余标签均为 Data
+    let (input, access_result) = match _access_result_tl_tag {
+        0x80 => parse_access_result_access_result_failure(input),
+        _ => parse_access_result_access_result_success(input),
     }?;
     Ok((input, access_result))
 }
 
 pub fn parse_access_result_struct(input: &[u8]) -> IResult<&[u8], AccessResultStruct> {
     debug!(target: "PARSER(parse_access_result_struct)", "struct AccessResultStruct");
-    let (input, _access_result_tl) = ber_tl(input)?;
+    let (_, _access_result_tl) = peek(ber_tl)(input)?;
     let (input, access_result) = parse_access_result(input, _access_result_tl.tag)?;
     Ok((input, AccessResultStruct { access_result }))
 }
@@ -1533,7 +1581,7 @@ pub fn parse_list_of_identifier(input: &[u8]) -> IResult<&[u8], ListOfIdentifier
     let mut input = input;
     let len_flag = input.len() - _loar_tl.length as usize;
     while input.len() > len_flag {
-        (input, _loar) = take(input.len() as usize)(input)?;
+        (input, _loar) = ber_tl_v(input)?;
         loar.push(_loar);
     }
     let input = input;
@@ -1541,6 +1589,68 @@ pub fn parse_list_of_identifier(input: &[u8]) -> IResult<&[u8], ListOfIdentifier
     Ok((input, ListOfIdentifier { loar }))
 }
 
+/// 解析服务标签，返回的`tag`为服务编号(不含类别位)。
+/// 编号大于30的服务(如文件服务)使用高标签号形式，第二字节为编号。
+fn parse_service_tl(input: &[u8]) -> IResult<&[u8], BerTL> {
+    let (_input, tag) = u8(input)?;
+    if tag.bitand(0x1f) == 0x1f {
+        let (_, number) = peek(u8)(_input)?;
+        if number.bitand(0x80) != 0 {
+            return Err(nom::Err::Error(nom::error::Error::new(
+                input,
+                nom::error::ErrorKind::Tag,
+            )));
+        }
+        // 以编号字节作为标签解析长度
+        return ber_tl(_input);
+    }
+    let (input, tl) = ber_tl(input)?;
+    Ok((
+        input,
+        BerTL {
+            tag: tl.tag.bitand(0x1f),
+            length: tl.length,
+        },
+    ))
+}
+
+/// FileName ::= SEQUENCE OF GraphicString
+fn parse_file_name(input: &[u8]) -> IResult<&[u8], Vec<&[u8]>> {
+    let mut file_name = Vec::new();
+    let mut input = input;
+    while !input.is_empty() {
+        let (_input, _file_name) = ber_tl_v(input)?;
+        file_name.push(_file_name);
+        input = _input;
+    }
+    Ok((input, file_name))
+}
+
+/// moreFollows [1] IMPLICIT BOOLEAN DEFAULT TRUE
+fn parse_more_follows(input: &[u8]) -> IResult<&[u8], u8> {
+    match input.first() {
+        Some(0x81) => {
+            let (input, _more_follows) = ber_tl_v(input)?;
+            let (_, more_follows) = u8(_more_follows)?;
+            Ok((input, more_follows))
+        }
+        _ => Ok((input, 1)),
+    }
+}
+
+fn parse_ber_unsigned(input: &[u8]) -> IResult<&[u8], u32> {
+    if input.is_empty() || input.len() > 5 {
+        return Err(nom::Err::Error(nom::error::Error::new(
+            input,
+            nom::error::ErrorKind::Verify,
+        )));
+    }
+    let value = input
+        .iter()
+        .fold(0u32, |acc, b| acc.wrapping_shl(8) | *b as u32);
+    Ok((&input[input.len()..], value))
+}
+
 pub fn parse_init_detail_request(input: &[u8]) -> IResult<&[u8], InitDetailRequest> {
     debug!(target: "PARSER(parse_init_detail_request)", "struct InitDetailRequest");
     let (input, proposed_version_number) = ber_tl_v(input)?;
@@ -1587,7 +1697,8 @@ fn parse_variable_access_specification_enum_varibale_list_name(
 ) -> IResult<&[u8], VariableAccessSpecificationEnum> {
     debug!(target: "PARSER(parse_variable_access_specification_enum_varibale_list_name)", "struct VaribaleListName");
     let (input, _object_name_tl) = ber_tl(input)?;
-    let (input, object_name) = parse_object_name(input, _object_name_tl.tag)?;
+    let (input, _object_name) = take(_object_name_tl.length as usize)(input)?;
+    let (_, object_name) = parse_object_name(_object_name, _object_name_tl.tag)?;
     Ok((
         input,
         VariableAccessSpecificationEnum::VaribaleListName { object_name },
@@ -1696,10 +1807,22 @@ fn parse_confirmed_service_request_enum_get_name_list_request(
     input: &[u8],
 ) -> IResult<&[u8], ConfirmedServiceRequestEnum> {
     debug!(target: "PARSER(parse_confirmed_service_request_enum_get_name_list_request)", "struct GetNameListRequest");
+    // objectClass [0] 与 objectScope [1] 均为显式标签包裹的 CHOICE
     let (input, _object_class_tl) = ber_tl(input)?;
-    let (input, object_class) = parse_object_class(input, _object_class_tl.tag)?;
+    let (input, _object_class) = take(_object_class_tl.length as usize)(input)?;
+    let (_, _object_class_value) = peek(ber_tl_v)(_object_class)?;
+    let (_, _object_class_value) = parse_ber_unsigned(_object_class_value)?;
+    let (_, object_class) = parse_object_class(_object_class, _object_class_value as u8)?;
     let (input, _object_scope_tl) = ber_tl(input)?;
-    let (input, object_scope) = parse_object_scope(input, _object_scope_tl.tag)?;
+    let (input, _object_scope) = take(_object_scope_tl.length as usize)(input)?;
+    let (_object_scope, _object_scope_choice_tl) = ber_tl(_object_scope)?;
+    let (_, _object_scope) = take(_object_scope_choice_tl.length as usize)(_object_scope)?;
+    let (_, object_scope) = parse_object_scope(_object_scope, _object_scope_choice_tl.tag)?;
+    // continueAfter [2] 可选，跳过
+    let input = match input.first() {
+        Some(0x82) => ber_tl_v(input)?.0,
+        _ => input,
+    };
     Ok((
         input,
         ConfirmedServiceRequestEnum::GetNameListRequest {
@@ -1737,14 +1860,13 @@ fn parse_confirmed_service_request_enum_write_request(
     let (input, variable_access_specification_enum) =
         parse_variable_access_specification_enum(input, _variable_access_specification_tl.tag)?;
     let (input, _list_of_data_tl) = ber_tl(input)?;
-    let (input, _lod_tl) = ber_tl(input)?;
     /* LimitedLenVecLoopField Start */
     let mut lod = Vec::new();
-    let mut _lod: &[u8];
+    let mut _lod: MmsData;
     let mut input = input;
-    let len_flag = input.len() - _lod_tl.length as usize;
+    let len_flag = input.len() - _list_of_data_tl.length as usize;
     while input.len() > len_flag {
-        (input, _lod) = ber_tl_v(input)?;
+        (input, _lod) = parse_mms_data(input)?;
         lod.push(_lod);
     }
     let input = input;
@@ -1763,26 +1885,145 @@ fn parse_confirmed_service_request_enum_get_named_variable_list_attributes_reque
 ) -> IResult<&[u8], ConfirmedServiceRequestEnum> {
     debug!(target: "PARSER(parse_confirmed_service_request_enum_get_named_variable_list_attributes_request)", "struct GetNamedVariableListAttributesRequest");
     let (input, _object_name_tl) = ber_tl(input)?;
-    let (input, object_name) = parse_object_name(input, _object_name_tl.tag)?;
+    let (input, _object_name) = take(_object_name_tl.length as usize)(input)?;
+    let (_, object_name) = parse_object_name(_object_name, _object_name_tl.tag)?;
     Ok((
         input,
         ConfirmedServiceRequestEnum::GetNamedVariableListAttributesRequest { object_name },
     ))
 }
 
+fn parse_confirmed_service_request_enum_obtain_file_request(
+    input: &[u8],
+) -> IResult<&[u8], ConfirmedServiceRequestEnum> {
+    debug!(target: "PARSER(parse_confirmed_service_request_enum_obtain_file_request)", "struct ObtainFileRequest");
+    let mut source_file = Vec::new();
+    let mut destination_file = Vec::new();
+    let mut input = input;
+    while !input.is_empty() {
+        let (_input, _field_tl) = ber_tl(input)?;
+        let (_input, _field) = take(_field_tl.length as usize)(_input)?;
+        match _field_tl.tag.bitand(0x1f) {
+            // sourceFileServer [0] 可选，忽略
+            0x01 => (_, source_file) = parse_file_name(_field)?,
+            0x02 => (_, destination_file) = parse_file_name(_field)?,
+            _ => {}
+        }
+        input = _input;
+    }
+    Ok((
+        input,
+        ConfirmedServiceRequestEnum::ObtainFileRequest {
+            source_file,
+            destination_file,
+        },
+    ))
+}
+
+fn parse_confirmed_service_request_enum_file_open_request(
+    input: &[u8],
+) -> IResult<&[u8], ConfirmedServiceRequestEnum> {
+    debug!(target: "PARSER(parse_confirmed_service_request_enum_file_open_request)", "struct FileOpenRequest");
+    let (input, _file_name) = ber_tl_v(input)?;
+    let (_, file_name) = parse_file_name(_file_name)?;
+    let (input, _initial_position) = ber_tl_v(input)?;
+    let (_, initial_position) = parse_ber_unsigned(_initial_position)?;
+    Ok((
+        input,
+        ConfirmedServiceRequestEnum::FileOpenRequest {
+            file_name,
+            initial_position,
+        },
+    ))
+}
+
+fn parse_confirmed_service_request_enum_file_read_request(
+    input: &[u8],
+) -> IResult<&[u8], ConfirmedServiceRequestEnum> {
+    debug!(target: "PARSER(parse_confirmed_service_request_enum_file_read_request)", "struct FileReadRequest");
+    let (input, frsm_id) = parse_ber_unsigned(input)?;
+    Ok((input, ConfirmedServiceRequestEnum::FileReadRequest { frsm_id }))
+}
+
+fn parse_confirmed_service_request_enum_file_close_request(
+    input: &[u8],
+) -> IResult<&[u8], ConfirmedServiceRequestEnum> {
+    debug!(target: "PARSER(parse_confirmed_service_request_enum_file_close_request)", "struct FileCloseRequest");
+    let (input, frsm_id) = parse_ber_unsigned(input)?;
+    Ok((input, ConfirmedServiceRequestEnum::FileCloseRequest { frsm_id }))
+}
+
+fn parse_confirmed_service_request_enum_file_rename_request(
+    input: &[u8],
+) -> IResult<&[u8], ConfirmedServiceRequestEnum> {
+    debug!(target: "PARSER(parse_confirmed_service_request_enum_file_rename_request)", "struct FileRenameRequest");
+    let (input, _current_file_name) = ber_tl_v(input)?;
+    let (_, current_file_name) = parse_file_name(_current_file_name)?;
+    let (input, _new_file_name) = ber_tl_v(input)?;
+    let (_, new_file_name) = parse_file_name(_new_file_name)?;
+    Ok((
+        input,
+        ConfirmedServiceRequestEnum::FileRenameRequest {
+            current_file_name,
+            new_file_name,
+        },
+    ))
+}
+
+fn parse_confirmed_service_request_enum_file_delete_request(
+    input: &[u8],
+) -> IResult<&[u8], ConfirmedServiceRequestEnum> {
+    debug!(target: "PARSER(parse_confirmed_service_request_enum_file_delete_request)", "struct FileDeleteRequest");
+    let (input, file_name) = parse_file_name(input)?;
+    Ok((input, ConfirmedServiceRequestEnum::FileDeleteRequest { file_name }))
+}
+
+fn parse_confirmed_service_request_enum_file_directory_request(
+    input: &[u8],
+) -> IResult<&[u8], ConfirmedServiceRequestEnum> {
+    debug!(target: "PARSER(parse_confirmed_service_request_enum_file_directory_request)", "struct FileDirectoryRequest");
+    let mut file_specification = Vec::new();
+    let mut continue_after = Vec::new();
+    let mut input = input;
+    while !input.is_empty() {
+        let (_input, _field_tl) = ber_tl(input)?;
+        let (_input, _field) = take(_field_tl.length as usize)(_input)?;
+        match _field_tl.tag.bitand(0x1f) {
+            0x0 => (_, file_specification) = parse_file_name(_field)?,
+            0x01 => (_, continue_after) = parse_file_name(_field)?,
+            _ => {}
+        }
+        input = _input;
+    }
+    Ok((
+        input,
+        ConfirmedServiceRequestEnum::FileDirectoryRequest {
+            file_specification,
+            continue_after,
+        },
+    ))
+}
+
 pub fn parse_confirmed_service_request_enum(
     input: &[u8],
     _service_tl_tag: u8,
 ) -> IResult<&[u8], ConfirmedServiceRequestEnum> {
     debug!(target: "PARSER(parse_confirmed_service_request_enum)", "enum ConfirmedServiceRequestEnum");
-    let (input, confirmed_service_request_enum) = match _service_tl_tag.bitand(0x1f) {
-        0x0 => parse_confirmed_service_request_enum_get_name_list_request(input),
+    let (input, confirmed_service_request_enum) = match _service_tl_tag {
+        0x01 => parse_confirmed_service_request_enum_get_name_list_request(input),
         0x02 => parse_confirmed_service_request_enum_identify_request(input),
         0x04 => parse_confirmed_service_request_enum_read_request(input),
         0x05 => parse_confirmed_service_request_enum_write_request(input),
         0x0c => {
             parse_confirmed_service_request_enum_get_named_variable_list_attributes_request(input)
         }
+        0x2e => parse_confirmed_service_request_enum_obtain_file_request(input),
+        0x48 => parse_confirmed_service_request_enum_file_open_request(input),
+        0x49 => parse_confirmed_service_request_enum_file_read_request(input),
+        0x4a => parse_confirmed_service_request_enum_file_close_request(input),
+        0x4b => parse_confirmed_service_request_enum_file_rename_request(input),
+        0x4c => parse_confirmed_service_request_enum_file_delete_request(input),
+        0x4d => parse_confirmed_service_request_enum_file_directory_request(input),
         _ => Err(nom::Err::Error(nom::error::Error::new(
             input,
             nom::error::ErrorKind::Verify,
@@ -1795,10 +2036,8 @@ fn parse_confirmed_service_response_enum_get_name_list_response(
     input: &[u8],
 ) -> IResult<&[u8], ConfirmedServiceResponseEnum> {
     debug!(target: "PARSER(parse_confirmed_service_response_enum_get_name_list_response)", "struct GetNameListResponse");
-    let (input, _list_of_identifier_tl) = ber_tl(input)?;
     let (input, list_of_identifier) = parse_list_of_identifier(input)?;
-    let (input, _more_follows_tl) = ber_tl(input)?;
-    let (input, more_follows) = u8(input)?;
+    let (input, more_follows) = parse_more_follows(input)?;
     Ok((
         input,
         ConfirmedServiceResponseEnum::GetNameListResponse {
@@ -1830,7 +2069,15 @@ fn parse_confirmed_service_response_enum_read_response(
 ) -> IResult<&[u8], ConfirmedServiceResponseEnum> {
     debug!(target: "PARSER(parse_confirmed_service_response_enum_read_response)", "struct ReadResponse");
     let (input, _read_response_tl) = ber_tl(input)?;
-    if _read_response_tl.length == 0 {
+    // variableAccessSpecification [0] 可选，跳过
+    let (input, _list_of_access_result_tl) = match _read_response_tl.tag {
+        0xa0 => {
+            let (input, _) = take(_read_response_tl.length as usize)(input)?;
+            ber_tl(input)?
+        }
+        _ => (input, _read_response_tl),
+    };
+    if _list_of_access_result_tl.length == 0 {
         return Ok((
             input,
             ConfirmedServiceResponseEnum::ReadResponse {
@@ -1838,7 +2085,6 @@ fn parse_confirmed_service_response_enum_read_response(
             },
         ));
     }
-    let (input, _list_of_access_result_tl) = ber_tl(input)?;
     /* LimitedLenVecLoopField Start */
     let mut list_of_access_result = Vec::new();
     let mut _list_of_access_result: AccessResultStruct;
@@ -1889,19 +2135,81 @@ fn parse_confirmed_service_response_enum_get_named_variable_list_attributes_resp
     ))
 }
 
+fn parse_confirmed_service_response_enum_file_open_response(
+    input: &[u8],
+) -> IResult<&[u8], ConfirmedServiceResponseEnum> {
+    debug!(target: "PARSER(parse_confirmed_service_response_enum_file_open_response)", "struct FileOpenResponse");
+    let (input, _frsm_id) = ber_tl_v(input)?;
+    let (_, frsm_id) = parse_ber_unsigned(_frsm_id)?;
+    // fileAttributes [1]，跳过
+    let (input, _file_attributes) = ber_tl_v(input)?;
+    Ok((input, ConfirmedServiceResponseEnum::FileOpenResponse { frsm_id }))
+}
+
+fn parse_confirmed_service_response_enum_file_read_response(
+    input: &[u8],
+) -> IResult<&[u8], ConfirmedServiceResponseEnum> {
+    debug!(target: "PARSER(parse_confirmed_service_response_enum_file_read_response)", "struct FileReadResponse");
+    let (input, file_data) = ber_tl_v(input)?;
+    let (input, more_follows) = parse_more_follows(input)?;
+    Ok((
+        input,
+        ConfirmedServiceResponseEnum::FileReadResponse {
+            file_data,
+            more_follows,
+        },
+    ))
+}
+
+fn parse_confirmed_service_response_enum_file_directory_response(
+    input: &[u8],
+) -> IResult<&[u8], ConfirmedServiceResponseEnum> {
+    debug!(target: "PARSER(parse_confirmed_service_response_enum_file_directory_response)", "struct FileDirectoryResponse");
+    let (input, _list_of_directory_entry_tl) = ber_tl(input)?;
+    /* LimitedLenVecLoopField Start */
+    let mut list_of_directory_entry = Vec::new();
+    let mut _directory_entry: &[u8];
+    let mut input = input;
+    let len_flag = input.len() - _list_of_directory_entry_tl.length as usize;
+    while input.len() > len_flag {
+        // DirectoryEntry 仅解析 fileName [0]，忽略 fileAttributes [1]
+        (input, _directory_entry) = ber_tl_v(input)?;
+        let (_, _file_name) = ber_tl_v(_directory_entry)?;
+        let (_, file_name) = parse_file_name(_file_name)?;
+        list_of_directory_entry.push(file_name);
+    }
+    let input = input;
+    /* LimitedLenVecLoopField End. */
+    let (input, more_follows) = parse_more_follows(input)?;
+    Ok((
+        input,
+        ConfirmedServiceResponseEnum::FileDirectoryResponse {
+            list_of_directory_entry,
+            more_follows,
+        },
+    ))
+}
+
 pub fn parse_confirmed_service_response_enum(
     input: &[u8],
     _service_tl_tag: u8,
 ) -> IResult<&[u8], ConfirmedServiceResponseEnum> {
     debug!(target: "PARSER(parse_confirmed_service_response_enum)", "enum ConfirmedServiceResponseEnum");
-    let (input, confirmed_service_response_enum) = match _service_tl_tag.bitand(0x1f) {
-        0x0 => parse_confirmed_service_response_enum_get_name_list_response(input),
+    let (input, confirmed_service_response_enum) = match _service_tl_tag {
+        0x01 => parse_confirmed_service_response_enum_get_name_list_response(input),
         0x02 => parse_confirmed_service_response_enum_identify_response(input),
         0x04 => parse_confirmed_service_response_enum_read_response(input),
         0x05 => parse_confirmed_service_response_enum_write_response(input),
         0x0c => {
             parse_confirmed_service_response_enum_get_named_variable_list_attributes_response(input)
         }
+        0x2e => Ok((input, ConfirmedServiceResponseEnum::ObtainFileResponse {})),
+        0x48 => parse_confirmed_service_response_enum_file_open_response(input),
+        0x49 => parse_confirmed_service_response_enum_file_read_response(input),
+        0x4a => Ok((input, ConfirmedServiceResponseEnum::FileCloseResponse {})),
+        0x4b => Ok((input, ConfirmedServiceResponseEnum::FileRenameResponse {})),
+        0x4c => Ok((input, ConfirmedServiceResponseEnum::FileDeleteResponse {})),
+        0x4d => parse_confirmed_service_response_enum_file_directory_response(input),
         _ => Err(nom::Err::Error(nom::error::Error::new(
             input,
             nom::error::ErrorKind::Verify,
@@ -1919,8 +2227,9 @@ fn parse_confirmed_service_response_with_data(
     input: &[u8],
 ) -> IResult<&[u8], ConfirmedServiceResponse> {
     debug!(target: "PARSER(parse_confirmed_service_response_with_data)", "struct WithData");
-    let (input, _service_tl) = ber_tl(input)?;
-    let (input, service) = parse_confirmed_service_response_enum(input, _service_tl.tag)?;
+    let (input, _service_tl) = parse_service_tl(input)?;
+    let (input, _service) = take(_service_tl.length as usize)(input)?;
+    let (_, service) = parse_confirmed_service_response_enum(_service, _service_tl.tag)?;
     Ok((input, ConfirmedServiceResponse::WithData { service }))
 }
 
@@ -1991,8 +2300,9 @@ fn parse_mms_pdu_enum_confirmed_request_pdu(input: &[u8]) -> IResult<&[u8], MmsP
             nom::error::ErrorKind::Verify,
         )));
     }
-    let (input, _service_tl) = ber_tl(input)?;
-    let (input, service) = parse_confirmed_service_request_enum(input, _service_tl.tag)?;
+    let (input, _service_tl) = parse_service_tl(input)?;
+    let (input, _service) = take(_service_tl.length as usize)(input)?;
+    let (_, service) = parse_confirmed_service_request_enum(_service, _service_tl.tag)?;
     Ok((
         input,
         MmsPduEnum::ConfirmedRequestPDU { invoke_id, service },
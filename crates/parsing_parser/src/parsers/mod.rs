@@ -1,47 +1,70 @@
 pub mod eof;
 pub mod http;
 pub mod bacnet;
+pub mod cip;
 pub mod dnp3;
+pub mod enip;
 pub mod ethernet;
+pub mod fins;
+pub mod fins_command;
+pub mod fins_response;
 pub mod fins_tcp_req;
 pub mod fins_tcp_rsp;
 pub mod fins_udp_req;
 pub mod fins_udp_rsp;
 pub mod goose;
+pub mod iec101;
 pub mod iec104;
 pub mod ipv4;
 pub mod ipv6;
 pub mod iso_on_tcp;
+pub mod melsec;
 pub mod mms;
 pub mod modbus_req;
 pub mod modbus_rsp;
 pub mod opcua;
+pub mod pnio_cm;
+pub mod profinet;
 pub mod s7comm;
+pub mod s7comm_plus;
 pub mod sv;
 pub mod tcp;
+pub mod tristation;
 pub mod udp;
 pub mod vlan;
 
 pub use eof::*;
-pub use http::{parse_http_layer, HttpHeader};
+pub use http::{
+    parse_http_layer, HttpDirection, HttpFlow, HttpFlowMessage, HttpHeader, HttpMessage, HttpRequest,
+    HttpResponse,
+};
 pub use bacnet::{parse_bacnet_layer, BacnetHeader};
-pub use dnp3::{parse_dnp3_layer, Dnp3Header};
+pub use cip::{CipMessage, CipPath};
+pub use dnp3::{parse_dnp3_layer, Dnp3Header, Dnp3Reassembler};
+pub use enip::{parse_enip_io_layer, parse_enip_layer, EnipHeader};
 pub use ethernet::{parse_ethernet_layer, EthernetHeader};
+pub use fins::{FinsCommand, FinsFrame, FinsHeader, FinsResponse, FinsTransactions};
 pub use fins_tcp_req::{parse_fins_tcp_req_layer, FinsTcpReqHeader};
 pub use fins_tcp_rsp::{parse_fins_tcp_rsp_layer, FinsTcpRspHeader};
 pub use fins_udp_req::{parse_fins_udp_req_layer, FinsUdpReqHeader};
 pub use fins_udp_rsp::{parse_fins_udp_rsp_layer, FinsUdpRspHeader};
-pub use goose::{parse_goose_layer, GooseHeader};
+pub use goose::{parse_goose_layer, GooseData, GooseHeader, GoosePDU};
+pub use iec101::{parse_iec101_layer, Iec101Header};
 pub use iec104::{parse_iec104_layer, Iec104Header};
 pub use ipv4::{parse_ipv4_layer, Ipv4Header};
 pub use ipv6::{parse_ipv6_layer, Ipv6Header};
 pub use iso_on_tcp::{parse_iso_on_tcp_layer, IsoOnTcpHeader};
-pub use mms::{parse_mms_layer, MmsHeader};
+pub use melsec::{parse_melsec_layer, MelsecDevice, MelsecHeader, MelsecPdu, MelsecRequest, MelsecRequestData};
+pub use mms::{parse_mms_layer, MmsData, MmsHeader};
 pub use modbus_req::{parse_modbus_req_layer, ModbusReqHeader};
 pub use modbus_rsp::{parse_modbus_rsp_layer, ModbusRspHeader};
-pub use opcua::{parse_opcua_layer, OpcuaHeader};
+pub use opcua::{parse_opcua_layer, OpcuaHeader, OpcuaSecureChannels};
+pub use pnio_cm::{parse_pnio_cm_layer, PnioBlock, PnioBlockData, PnioCmHeader};
+pub use profinet::{parse_profinet_layer, DcpBlock, DcpBlockValue, DcpHeader, ProfinetHeader, ProfinetPdu};
 pub use s7comm::{parse_s7comm_layer, S7commHeader};
-pub use sv::{parse_sv_layer, SvHeader};
+pub use s7comm_plus::{parse_s7comm_plus_layer, S7commPlusHeader};
+pub use sv::{parse_sv_layer, Asdu as SvAsdu, SvChannel, SvChannelMap, SvHeader, SvMeasurement, SvQuality, SvSample};
 pub use tcp::{parse_tcp_layer, TcpHeader};
+pub use tristation::{parse_tristation_layer, TristationCommand, TristationHeader};
 pub use udp::{parse_udp_layer, UdpHeader};
 pub use vlan::{parse_vlan_layer, VlanHeader};
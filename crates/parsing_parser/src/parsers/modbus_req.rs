@@ -1,7 +1,7 @@
 use nom::bytes::complete::take;
-use nom::combinator::eof;
+use nom::combinator::{eof, rest};
 use nom::multi::count;
-use nom::number::complete::{be_u16, u8};
+use nom::number::complete::{be_u16, le_u16, le_u32, u8};
 use nom::IResult;
 
 use crate::errors::ParseError;
@@ -126,6 +126,15 @@ pub enum Data<'a> {
     ReadFIFOQueue {
         fifo_pointer_address: u16,
     },
+    EncapsulatedInterfaceTransport {
+        mei_type: u8,
+        mei_data: MeiReqData<'a>,
+    },
+    Umas {
+        session_key: u8,
+        umas_function_code: u8,
+        umas_data: UmasData<'a>,
+    },
 }
 
 fn parse_data(input: &[u8], function_code: u8) -> IResult<&[u8], Data> {
@@ -147,6 +156,8 @@ fn parse_data(input: &[u8], function_code: u8) -> IResult<&[u8], Data> {
         0x16 => parse_mask_write_register(input),
         0x17 => parse_read_write_multiple_registers(input),
         0x18 => parse_read_fifo_queue(input),
+        0x2b => parse_encapsulated_interface_transport(input),
+        0x5a => parse_umas(input),
         _ => Err(nom::Err::Error(nom::error::Error::new(
             input,
             nom::error::ErrorKind::Verify,
@@ -368,6 +379,341 @@ fn parse_read_fifo_queue(input: &[u8]) -> IResult<&[u8], Data> {
     ))
 }
 
+fn parse_encapsulated_interface_transport(input: &[u8]) -> IResult<&[u8], Data> {
+    let (input, mei_type) = u8(input)?;
+    let (input, mei_data) = match mei_type {
+        0x0e => parse_read_device_identification(input),
+        _ => {
+            let (input, data) = rest(input)?;
+            Ok((input, MeiReqData::Unknown { data }))
+        }
+    }?;
+    Ok((
+        input,
+        Data::EncapsulatedInterfaceTransport { mei_type, mei_data },
+    ))
+}
+
+/// MeiReqData是 Modbus Encapsulated Interface Transport(功能码 0x2B) 请求按 MEI 类型解析出的内容。
+#[derive(Debug, PartialEq, Clone)]
+pub enum MeiReqData<'a> {
+    ReadDeviceIdentification {
+        read_device_id_code: u8,
+        object_id: u8,
+    },
+    Unknown {
+        data: &'a [u8],
+    },
+}
+
+fn parse_read_device_identification(input: &[u8]) -> IResult<&[u8], MeiReqData> {
+    let (input, read_device_id_code) = u8(input)?;
+    let (input, object_id) = u8(input)?;
+    Ok((
+        input,
+        MeiReqData::ReadDeviceIdentification {
+            read_device_id_code,
+            object_id,
+        },
+    ))
+}
+
+fn parse_umas(input: &[u8]) -> IResult<&[u8], Data> {
+    let (input, session_key) = u8(input)?;
+    let (input, umas_function_code) = u8(input)?;
+    let (input, umas_data) = parse_umas_data(input, umas_function_code)?;
+    Ok((
+        input,
+        Data::Umas {
+            session_key,
+            umas_function_code,
+            umas_data,
+        },
+    ))
+}
+
+/// UmasData是 Schneider UMAS 请求(Modbus 功能码 0x5A)按子功能码解析出的内容。
+///
+/// UMAS 字段为小端序，未公开含义的字段保留原始值。
+#[derive(Debug, PartialEq, Clone)]
+pub enum UmasData<'a> {
+    InitComm {
+        max_frame_size_flag: u8,
+    },
+    ReadId {},
+    ReadProjectInfo {
+        sub_code: u8,
+    },
+    ReadPlcInfo {},
+    ReadCardInfo {},
+    Repeat {
+        data: &'a [u8],
+    },
+    TakePlcReservation {
+        reservation_data: &'a [u8],
+    },
+    ReleasePlcReservation {},
+    KeepAlive {},
+    ReadMemoryBlock {
+        range: u8,
+        block_number: u16,
+        offset: u16,
+        unknown: u16,
+        number_of_bytes: u16,
+    },
+    WriteMemoryBlock {
+        range: u8,
+        block_number: u16,
+        offset: u16,
+        unknown: u16,
+        number_of_bytes: u16,
+        data: &'a [u8],
+    },
+    ReadVariables {
+        crc: u32,
+        variable_count: u8,
+        variables: &'a [u8],
+    },
+    WriteVariables {
+        crc: u32,
+        variable_count: u8,
+        variables: &'a [u8],
+    },
+    ReadCoilsRegisters {
+        data: &'a [u8],
+    },
+    WriteCoilsRegisters {
+        data: &'a [u8],
+    },
+    InitializeUpload {
+        unknown: u16,
+    },
+    UploadBlock {
+        unknown: u16,
+        block_number: u16,
+    },
+    EndStrategyUpload {
+        unknown: u16,
+    },
+    InitializeDownload {
+        unknown: u16,
+        data: &'a [u8],
+    },
+    DownloadBlock {
+        unknown: u16,
+        block_number: u16,
+        data_length: u16,
+        data: &'a [u8],
+    },
+    EndStrategyDownload {
+        unknown: u16,
+    },
+    ReadEthMasterData {
+        data: &'a [u8],
+    },
+    StartPlc {
+        data: &'a [u8],
+    },
+    StopPlc {
+        data: &'a [u8],
+    },
+    MonitorPlc {
+        data: &'a [u8],
+    },
+    CheckPlc {
+        data: &'a [u8],
+    },
+    ReadIoObject {
+        data: &'a [u8],
+    },
+    WriteIoObject {
+        data: &'a [u8],
+    },
+    GetStatusModule {
+        data: &'a [u8],
+    },
+    Unknown {
+        data: &'a [u8],
+    },
+}
+
+fn parse_umas_data(input: &[u8], umas_function_code: u8) -> IResult<&[u8], UmasData> {
+    let (input, umas_data) = match umas_function_code {
+        0x01 => parse_umas_init_comm(input),
+        0x02 => Ok((input, UmasData::ReadId {})),
+        0x03 => parse_umas_read_project_info(input),
+        0x04 => Ok((input, UmasData::ReadPlcInfo {})),
+        0x06 => Ok((input, UmasData::ReadCardInfo {})),
+        0x0a => map_umas_rest(input, |data| UmasData::Repeat { data }),
+        0x10 => map_umas_rest(input, |reservation_data| UmasData::TakePlcReservation {
+            reservation_data,
+        }),
+        0x11 => Ok((input, UmasData::ReleasePlcReservation {})),
+        0x12 => Ok((input, UmasData::KeepAlive {})),
+        0x20 => parse_umas_read_memory_block(input),
+        0x21 => parse_umas_write_memory_block(input),
+        0x22 => parse_umas_read_variables(input),
+        0x23 => parse_umas_write_variables(input),
+        0x24 => map_umas_rest(input, |data| UmasData::ReadCoilsRegisters { data }),
+        0x25 => map_umas_rest(input, |data| UmasData::WriteCoilsRegisters { data }),
+        0x30 => parse_umas_initialize_upload(input),
+        0x31 => parse_umas_upload_block(input),
+        0x32 => parse_umas_end_strategy_upload(input),
+        0x33 => parse_umas_initialize_download(input),
+        0x34 => parse_umas_download_block(input),
+        0x35 => parse_umas_end_strategy_download(input),
+        0x39 => map_umas_rest(input, |data| UmasData::ReadEthMasterData { data }),
+        0x40 => map_umas_rest(input, |data| UmasData::StartPlc { data }),
+        0x41 => map_umas_rest(input, |data| UmasData::StopPlc { data }),
+        0x50 => map_umas_rest(input, |data| UmasData::MonitorPlc { data }),
+        0x58 => map_umas_rest(input, |data| UmasData::CheckPlc { data }),
+        0x70 => map_umas_rest(input, |data| UmasData::ReadIoObject { data }),
+        0x71 => map_umas_rest(input, |data| UmasData::WriteIoObject { data }),
+        0x73 => map_umas_rest(input, |data| UmasData::GetStatusModule { data }),
+        _ => map_umas_rest(input, |data| UmasData::Unknown { data }),
+    }?;
+    Ok((input, umas_data))
+}
+
+#[inline(always)]
+fn map_umas_rest<'a, F>(input: &'a [u8], f: F) -> IResult<&'a [u8], UmasData<'a>>
+where
+    F: Fn(&'a [u8]) -> UmasData<'a>,
+{
+    let (input, data) = rest(input)?;
+    Ok((input, f(data)))
+}
+
+fn parse_umas_init_comm(input: &[u8]) -> IResult<&[u8], UmasData> {
+    let (input, max_frame_size_flag) = u8(input)?;
+    Ok((
+        input,
+        UmasData::InitComm {
+            max_frame_size_flag,
+        },
+    ))
+}
+
+fn parse_umas_read_project_info(input: &[u8]) -> IResult<&[u8], UmasData> {
+    let (input, sub_code) = u8(input)?;
+    Ok((input, UmasData::ReadProjectInfo { sub_code }))
+}
+
+fn parse_umas_read_memory_block(input: &[u8]) -> IResult<&[u8], UmasData> {
+    let (input, range) = u8(input)?;
+    let (input, block_number) = le_u16(input)?;
+    let (input, offset) = le_u16(input)?;
+    let (input, unknown) = le_u16(input)?;
+    let (input, number_of_bytes) = le_u16(input)?;
+    Ok((
+        input,
+        UmasData::ReadMemoryBlock {
+            range,
+            block_number,
+            offset,
+            unknown,
+            number_of_bytes,
+        },
+    ))
+}
+
+fn parse_umas_write_memory_block(input: &[u8]) -> IResult<&[u8], UmasData> {
+    let (input, range) = u8(input)?;
+    let (input, block_number) = le_u16(input)?;
+    let (input, offset) = le_u16(input)?;
+    let (input, unknown) = le_u16(input)?;
+    let (input, number_of_bytes) = le_u16(input)?;
+    let (input, data) = take(number_of_bytes as usize)(input)?;
+    Ok((
+        input,
+        UmasData::WriteMemoryBlock {
+            range,
+            block_number,
+            offset,
+            unknown,
+            number_of_bytes,
+            data,
+        },
+    ))
+}
+
+fn parse_umas_read_variables(input: &[u8]) -> IResult<&[u8], UmasData> {
+    let (input, crc) = le_u32(input)?;
+    let (input, variable_count) = u8(input)?;
+    let (input, variables) = rest(input)?;
+    Ok((
+        input,
+        UmasData::ReadVariables {
+            crc,
+            variable_count,
+            variables,
+        },
+    ))
+}
+
+fn parse_umas_write_variables(input: &[u8]) -> IResult<&[u8], UmasData> {
+    let (input, crc) = le_u32(input)?;
+    let (input, variable_count) = u8(input)?;
+    let (input, variables) = rest(input)?;
+    Ok((
+        input,
+        UmasData::WriteVariables {
+            crc,
+            variable_count,
+            variables,
+        },
+    ))
+}
+
+fn parse_umas_initialize_upload(input: &[u8]) -> IResult<&[u8], UmasData> {
+    let (input, unknown) = le_u16(input)?;
+    Ok((input, UmasData::InitializeUpload { unknown }))
+}
+
+fn parse_umas_upload_block(input: &[u8]) -> IResult<&[u8], UmasData> {
+    let (input, unknown) = le_u16(input)?;
+    let (input, block_number) = le_u16(input)?;
+    Ok((
+        input,
+        UmasData::UploadBlock {
+            unknown,
+            block_number,
+        },
+    ))
+}
+
+fn parse_umas_end_strategy_upload(input: &[u8]) -> IResult<&[u8], UmasData> {
+    let (input, unknown) = le_u16(input)?;
+    Ok((input, UmasData::EndStrategyUpload { unknown }))
+}
+
+fn parse_umas_initialize_download(input: &[u8]) -> IResult<&[u8], UmasData> {
+    let (input, unknown) = le_u16(input)?;
+    let (input, data) = rest(input)?;
+    Ok((input, UmasData::InitializeDownload { unknown, data }))
+}
+
+fn parse_umas_download_block(input: &[u8]) -> IResult<&[u8], UmasData> {
+    let (input, unknown) = le_u16(input)?;
+    let (input, block_number) = le_u16(input)?;
+    let (input, data_length) = le_u16(input)?;
+    let (input, data) = take(data_length as usize)(input)?;
+    Ok((
+        input,
+        UmasData::DownloadBlock {
+            unknown,
+            block_number,
+            data_length,
+            data,
+        },
+    ))
+}
+
+fn parse_umas_end_strategy_download(input: &[u8]) -> IResult<&[u8], UmasData> {
+    let (input, unknown) = le_u16(input)?;
+    Ok((input, UmasData::EndStrategyDownload { unknown }))
+}
+
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub struct ReadFileRecordSubRequest {
     pub ref_type: u8,
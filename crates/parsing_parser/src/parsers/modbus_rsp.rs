@@ -1,6 +1,7 @@
 use nom::bits::bits;
 use nom::bits::complete::take as take_bits;
 use nom::bytes::complete::take;
+use nom::combinator::rest;
 use nom::error::Error;
 use nom::multi::count;
 use nom::number::complete::{be_u16, u8};
@@ -70,6 +71,8 @@ fn parse_pdu(input: &[u8]) -> IResult<&[u8], PDU> {
         0x16 => parse_mask_write_register(input),
         0x17 => parse_read_write_multiple_registers(input),
         0x18 => parse_read_fifo_queue(input),
+        0x2b => parse_encapsulated_interface_transport(input),
+        0x5a => parse_umas(input),
         0x81 => parse_read_coils_exc(input),
         0x82 => parse_read_discre_inputs_exc(input),
         0x83 => parse_read_holding_registers_exc(input),
@@ -87,6 +90,8 @@ fn parse_pdu(input: &[u8]) -> IResult<&[u8], PDU> {
         0x96 => parse_mask_write_register_exc(input),
         0x97 => parse_read_write_multiple_registers_exc(input),
         0x98 => parse_read_fifo_queue_exc(input),
+        0xab => parse_encapsulated_interface_transport_exc(input),
+        0xda => parse_umas_exc(input),
         _ => Err(nom::Err::Error(nom::error::Error::new(
             input,
             nom::error::ErrorKind::Verify,
@@ -175,6 +180,15 @@ pub enum Data<'a> {
         fifo_count: u16,
         fifo_value_register: &'a [u8],
     },
+    EncapsulatedInterfaceTransport {
+        mei_type: u8,
+        mei_data: MeiRspData<'a>,
+    },
+    Umas {
+        session_key: u8,
+        status_code: u8,
+        umas_data: &'a [u8],
+    },
     ReadCoilsExc {
         exception_code: u8,
     },
@@ -226,6 +240,12 @@ pub enum Data<'a> {
     ReadFIFOQueueExc {
         exception_code: u8,
     },
+    EncapsulatedInterfaceTransportExc {
+        exception_code: u8,
+    },
+    UmasExc {
+        exception_code: u8,
+    },
 }
 
 fn parse_read_coils(input: &[u8]) -> IResult<&[u8], Data> {
@@ -445,6 +465,116 @@ fn parse_read_fifo_queue(input: &[u8]) -> IResult<&[u8], Data> {
     ))
 }
 
+fn parse_encapsulated_interface_transport(input: &[u8]) -> IResult<&[u8], Data> {
+    let (input, mei_type) = u8(input)?;
+    let (input, mei_data) = match mei_type {
+        0x0e => parse_read_device_identification(input),
+        _ => {
+            let (input, data) = rest(input)?;
+            Ok((input, MeiRspData::Unknown { data }))
+        }
+    }?;
+    Ok((
+        input,
+        Data::EncapsulatedInterfaceTransport { mei_type, mei_data },
+    ))
+}
+
+/// MeiRspData是 Modbus Encapsulated Interface Transport(功能码 0x2B) 响应按 MEI 类型解析出的内容。
+#[derive(Debug, PartialEq, Clone)]
+pub enum MeiRspData<'a> {
+    ReadDeviceIdentification {
+        read_device_id_code: u8,
+        conformity_level: u8,
+        more_follows: bool,
+        next_object_id: u8,
+        number_of_objects: u8,
+        objects: Vec<DeviceIdObject<'a>>,
+    },
+    Unknown {
+        data: &'a [u8],
+    },
+}
+
+fn parse_read_device_identification(input: &[u8]) -> IResult<&[u8], MeiRspData> {
+    let (input, read_device_id_code) = u8(input)?;
+    let (input, conformity_level) = u8(input)?;
+    // 0x00: no more object, 0xFF: other identification object are available
+    let (input, more_follows) = u8(input)?;
+    let (input, next_object_id) = u8(input)?;
+    let (input, number_of_objects) = u8(input)?;
+    let (input, objects) = count(parse_device_id_object, number_of_objects as usize)(input)?;
+    Ok((
+        input,
+        MeiRspData::ReadDeviceIdentification {
+            read_device_id_code,
+            conformity_level,
+            more_follows: more_follows == 0xff,
+            next_object_id,
+            number_of_objects,
+            objects,
+        },
+    ))
+}
+
+/// DeviceIdObject是 Read Device Identification 响应中的设备标识对象。
+#[derive(Debug, PartialEq, Clone)]
+pub struct DeviceIdObject<'a> {
+    pub object_id: u8,
+    pub object_length: u8,
+    pub object_value: &'a [u8],
+}
+
+impl<'a> DeviceIdObject<'a> {
+    /// 返回 Modbus 规范中 Basic/Regular 类别对象的名称，私有对象(0x80-0xFF)返回 None。
+    pub fn object_name(&self) -> Option<&'static str> {
+        match self.object_id {
+            0x00 => Some("VendorName"),
+            0x01 => Some("ProductCode"),
+            0x02 => Some("MajorMinorRevision"),
+            0x03 => Some("VendorUrl"),
+            0x04 => Some("ProductName"),
+            0x05 => Some("ModelName"),
+            0x06 => Some("UserApplicationName"),
+            _ => None,
+        }
+    }
+
+    /// 以字符串形式返回对象值，非 UTF-8 内容返回 None。
+    pub fn object_value_str(&self) -> Option<&'a str> {
+        std::str::from_utf8(self.object_value).ok()
+    }
+}
+
+fn parse_device_id_object(input: &[u8]) -> IResult<&[u8], DeviceIdObject> {
+    let (input, object_id) = u8(input)?;
+    let (input, object_length) = u8(input)?;
+    let (input, object_value) = take(object_length as usize)(input)?;
+    Ok((
+        input,
+        DeviceIdObject {
+            object_id,
+            object_length,
+            object_value,
+        },
+    ))
+}
+
+fn parse_umas(input: &[u8]) -> IResult<&[u8], Data> {
+    let (input, session_key) = u8(input)?;
+    // 0xFE: OK, 0xFD: Error
+    let (input, status_code) = u8(input)?;
+    let (input, umas_data) = rest(input)?;
+    Ok((
+        input,
+        Data::Umas {
+            session_key,
+            status_code,
+            umas_data,
+        },
+    ))
+}
+
 fn parse_read_coils_exc(input: &[u8]) -> IResult<&[u8], Data> {
     let (input, exception_code) = u8(input)?;
     Ok((input, Data::ReadDiscreteInputsExc { exception_code }))
@@ -533,6 +663,19 @@ fn parse_read_fifo_queue_exc(input: &[u8]) -> IResult<&[u8], Data> {
     Ok((input, Data::ReadFIFOQueueExc { exception_code }))
 }
 
+fn parse_encapsulated_interface_transport_exc(input: &[u8]) -> IResult<&[u8], Data> {
+    let (input, exception_code) = u8(input)?;
+    Ok((
+        input,
+        Data::EncapsulatedInterfaceTransportExc { exception_code },
+    ))
+}
+
+fn parse_umas_exc(input: &[u8]) -> IResult<&[u8], Data> {
+    let (input, exception_code) = u8(input)?;
+    Ok((input, Data::UmasExc { exception_code }))
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct ReadFileRecordSubRequest<'a> {
     pub file_rsp_len: u8,
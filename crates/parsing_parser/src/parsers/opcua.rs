@@ -11,7 +11,10 @@ use nom::error::{Error, ErrorKind};
 #[allow(unused)]
 use nom::multi::count;
 #[allow(unused)]
-use nom::number::complete::{be_u16, be_u24, be_u32, be_u64, le_u16, le_u24, le_u32, le_u64, u8};
+use nom::number::complete::{
+    be_u16, be_u24, be_u32, be_u64, le_f32, le_f64, le_i16, le_i32, le_i64, le_i8, le_u16, le_u24,
+    le_u32, le_u64, u8,
+};
 #[allow(unused)]
 use nom::sequence::tuple;
 #[allow(unused)]
@@ -45,10 +48,20 @@ use std::ops::BitOr;
 #[allow(unused)]
 use std::ops::BitXor;
 
+use std::collections::{HashMap, VecDeque};
+
 use super::parse_l5_eof_layer;
 
+pub const SECURITY_POLICY_NONE: &str = "http://opcfoundation.org/UA/SecurityPolicy#None";
+
+// 单个消息重组缓存的上限
+const OPCUA_MAX_MESSAGE_SIZE: usize = 16 * 1024 * 1024;
+// 每个连接跟踪的安全通道数与未完成重组的消息数上限，超出时淘汰最早的
+const OPCUA_MAX_SECURE_CHANNELS: usize = 16;
+const OPCUA_MAX_PENDING_MESSAGES: usize = 16;
+
 #[allow(non_camel_case_types)]
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct OpcuaHeader<'a> {
     pub message_type: u32,
     pub message_type_enum: MessageTypeEnum<'a>,
@@ -124,6 +137,251 @@ pub fn parse_opcua_layer<'a>(
     );
 }
 
+/// OpcuaChannelSecurity是一个安全通道经 OpenSecureChannel 协商的安全配置。
+/// `security_mode`取自明文的 OpenSecureChannel 请求(1 None, 2 Sign, 3 SignAndEncrypt)；
+/// 安全策略非 None 时该请求已加密，模式未知，此时无法区分 Sign 与 SignAndEncrypt。
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct OpcuaChannelSecurity {
+    pub security_policy_uri: String,
+    pub security_mode: Option<u32>,
+    pub sender_certificate: Vec<u8>,
+    pub receiver_certificate_thumbprint: Vec<u8>,
+    pub token_ids: Vec<u32>,
+}
+
+impl OpcuaChannelSecurity {
+    pub fn is_policy_none(&self) -> bool {
+        self.security_policy_uri == SECURITY_POLICY_NONE
+    }
+
+    /// 仅策略非 None 且模式确定为 SignAndEncrypt 时消息正文按加密处理；
+    /// 模式未知时仍尝试解码，解码失败的正文保留为 Undecoded
+    pub fn is_encrypted(&self) -> bool {
+        !self.is_policy_none() && self.security_mode == Some(3)
+    }
+}
+
+/// OpcuaMessage是重组后的完整消息，`body`为序列头之后的数据
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct OpcuaMessage {
+    pub message_type: u32,
+    pub secure_channel_id: u32,
+    pub security_request_id: u32,
+    pub body: Vec<u8>,
+}
+
+impl OpcuaMessage {
+    pub fn msg_variant_info(&self) -> Option<MsgVariantInfo> {
+        parse_msg_variant_info(&self.body, 0x46).ok().map(|(_, o)| o)
+    }
+}
+
+/// OpcuaSecureChannels按安全通道跟踪安全配置并跨 TCP 报文重组分块消息，每个 TCP 连接使用一个实例，
+/// 通常作为 [`QuinFlow`](crate::QuinFlow) 的一部分使用。确定加密的通道上的消息不做重组与解码。
+#[derive(Debug, Default)]
+pub struct OpcuaSecureChannels {
+    channels: HashMap<u32, OpcuaChannelSecurity>,
+    channel_order: VecDeque<u32>,
+    // 新建通道(通道号为0)的请求，等待响应分配通道号
+    pending: Option<OpcuaChannelSecurity>,
+    chunks: HashMap<(u32, u32), Vec<u8>>,
+    chunk_order: VecDeque<(u32, u32)>,
+    // 最近一个跨报文重组完成的消息，供 update 后的解码结果引用
+    message: Option<OpcuaMessage>,
+}
+
+impl OpcuaSecureChannels {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn channel(&self, secure_channel_id: u32) -> Option<&OpcuaChannelSecurity> {
+        self.channels.get(&secure_channel_id)
+    }
+
+    /// 推入数据包中的 OPC UA 报文并按通道状态修正解码结果：加密通道上的正文不解码，
+    /// 跨报文重组完成的消息以完整正文解码
+    pub fn update<'a>(&'a mut self, header: &mut OpcuaHeader<'a>) {
+        let message = self.push(header);
+        let (secure_channel_id, body, msg_variant_info) = match &mut header.message_type_enum {
+            MessageTypeEnum::Message {
+                secure_channel_id,
+                body,
+                msg_variant_info,
+                ..
+            }
+            | MessageTypeEnum::CloseSecureChannel {
+                secure_channel_id,
+                body,
+                msg_variant_info,
+                ..
+            } => (*secure_channel_id, *body, msg_variant_info),
+            _ => return,
+        };
+        if matches!(self.channels.get(&secure_channel_id), Some(o) if o.is_encrypted()) {
+            *msg_variant_info = MsgVariantInfo::Undecoded { data: body };
+            return;
+        }
+        match message {
+            Some(message) if message.body.len() > body.len() => {
+                let message = &*self.message.insert(message);
+                if let Ok((_, o)) = parse_msg_body(&message.body, 0x46) {
+                    *msg_variant_info = o;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// 推入一个数据包中的 OPC UA 报文，消息的最终块到达时返回完整消息
+    pub fn push(&mut self, header: &OpcuaHeader) -> Option<OpcuaMessage> {
+        match &header.message_type_enum {
+            MessageTypeEnum::OpenSecureChannel {
+                secure_channel_id,
+                security_policy_uri,
+                sender_certificate,
+                receiver_certificate_thumbprint,
+                msg_variant_info,
+                ..
+            } => {
+                let security = OpcuaChannelSecurity {
+                    security_policy_uri: security_policy_uri.to_string(),
+                    security_mode: None,
+                    sender_certificate: sender_certificate.to_vec(),
+                    receiver_certificate_thumbprint: receiver_certificate_thumbprint.to_vec(),
+                    token_ids: Vec::new(),
+                };
+                self.push_open(*secure_channel_id, security, msg_variant_info.service());
+                None
+            }
+            MessageTypeEnum::Message {
+                chunk_type,
+                secure_channel_id,
+                security_request_id,
+                body,
+                ..
+            }
+            | MessageTypeEnum::CloseSecureChannel {
+                chunk_type,
+                secure_channel_id,
+                security_request_id,
+                body,
+                ..
+            } => self.push_chunk(
+                header.message_type,
+                *chunk_type,
+                *secure_channel_id,
+                *security_request_id,
+                body,
+            ),
+            _ => None,
+        }
+    }
+
+    fn push_open(
+        &mut self,
+        secure_channel_id: u32,
+        mut security: OpcuaChannelSecurity,
+        service: Option<&ServiceEnum>,
+    ) {
+        let mut token_id = None;
+        match service {
+            Some(ServiceEnum::OpenSecureChannelRequest { security_mode, .. }) => {
+                security.security_mode = Some(*security_mode)
+            }
+            Some(ServiceEnum::OpenSecureChannelResponse { security_token, .. }) => {
+                token_id = Some(security_token.token_id)
+            }
+            _ => {}
+        }
+        if secure_channel_id == 0 {
+            self.pending = Some(security);
+            return;
+        }
+
+        if !self.channels.contains_key(&secure_channel_id) {
+            let channel = self.pending.take().unwrap_or_else(|| security.clone());
+            self.channels.insert(secure_channel_id, channel);
+            self.channel_order.push_back(secure_channel_id);
+            while self.channel_order.len() > OPCUA_MAX_SECURE_CHANNELS {
+                if let Some(oldest) = self.channel_order.pop_front() {
+                    self.channels.remove(&oldest);
+                }
+            }
+        }
+        let channel = match self.channels.get_mut(&secure_channel_id) {
+            Some(o) => o,
+            None => return,
+        };
+        if security.security_mode.is_some() {
+            channel.security_mode = security.security_mode;
+        }
+        if let Some(token_id) = token_id {
+            if !channel.token_ids.contains(&token_id) {
+                channel.token_ids.push(token_id);
+            }
+        }
+    }
+
+    /// 'C' 缓存中间块，'F' 返回完整消息，'A' 丢弃已缓存的块
+    fn push_chunk(
+        &mut self,
+        message_type: u32,
+        chunk_type: u8,
+        secure_channel_id: u32,
+        security_request_id: u32,
+        body: &[u8],
+    ) -> Option<OpcuaMessage> {
+        let key = (secure_channel_id, security_request_id);
+        if matches!(self.channels.get(&secure_channel_id), Some(o) if o.is_encrypted()) {
+            self.remove_chunks(key);
+            return None;
+        }
+
+        match chunk_type {
+            0x43 => {
+                if !self.chunks.contains_key(&key) {
+                    self.chunks.insert(key, Vec::new());
+                    self.chunk_order.push_back(key);
+                    while self.chunk_order.len() > OPCUA_MAX_PENDING_MESSAGES {
+                        if let Some(oldest) = self.chunk_order.pop_front() {
+                            self.chunks.remove(&oldest);
+                        }
+                    }
+                }
+                let buffer = self.chunks.entry(key).or_default();
+                if buffer.len() + body.len() > OPCUA_MAX_MESSAGE_SIZE {
+                    tracing::warn!(target: "PARSER(opcua::OpcuaSecureChannels)", ?key, "message too large");
+                    self.remove_chunks(key);
+                    return None;
+                }
+                buffer.extend_from_slice(body);
+                None
+            }
+            0x46 => {
+                let mut buffer = self.remove_chunks(key).unwrap_or_default();
+                buffer.extend_from_slice(body);
+                Some(OpcuaMessage {
+                    message_type,
+                    secure_channel_id,
+                    security_request_id,
+                    body: buffer,
+                })
+            }
+            _ => {
+                self.remove_chunks(key);
+                None
+            }
+        }
+    }
+
+    fn remove_chunks(&mut self, key: (u32, u32)) -> Option<Vec<u8>> {
+        let buffer = self.chunks.remove(&key)?;
+        self.chunk_order.retain(|o| *o != key);
+        Some(buffer)
+    }
+}
+
 #[allow(non_camel_case_types)]
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct OpcuaString<'a> {
@@ -147,7 +405,7 @@ pub enum ServerIndexEnum {
 
 #[allow(non_camel_case_types)]
 #[derive(Debug, PartialEq, Eq, Clone)]
-pub enum NodeidInfo {
+pub enum NodeidInfo<'a> {
     TB {
         nodeid_numeric: u8,
     },
@@ -161,12 +419,15 @@ pub enum NodeidInfo {
     },
     String {
         nodeid_namespace: u16,
+        nodeid_string: &'a str,
     },
     Guid {
         nodeid_namespace: u16,
+        nodeid_guid: &'a [u8],
     },
     Opaque {
         nodeid_namespace: u16,
+        nodeid_opaque: &'a [u8],
     },
 }
 
@@ -192,16 +453,19 @@ pub enum ExpandedNodeIdInfo<'a> {
     },
     String {
         nodeid_namespace: u16,
+        nodeid_string: &'a str,
         namespace_enum: NamespaceEnum<'a>,
         server_index_enum: ServerIndexEnum,
     },
     Guid {
         nodeid_namespace: u16,
+        nodeid_guid: &'a [u8],
         namespace_enum: NamespaceEnum<'a>,
         server_index_enum: ServerIndexEnum,
     },
     Opaque {
         nodeid_namespace: u16,
+        nodeid_opaque: &'a [u8],
         namespace_enum: NamespaceEnum<'a>,
         server_index_enum: ServerIndexEnum,
     },
@@ -211,7 +475,7 @@ pub enum ExpandedNodeIdInfo<'a> {
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct RequestHeader<'a> {
     pub nodeid_encodingmask: u8,
-    pub nodeid_info: NodeidInfo,
+    pub nodeid_info: NodeidInfo<'a>,
     pub timestamp: u64,
     pub request_handle: u32,
     pub sl_symbolic_id: u8,
@@ -236,8 +500,242 @@ pub struct RequestHeader<'a> {
 
 #[allow(non_camel_case_types)]
 #[derive(Debug, PartialEq, Eq, Clone)]
+pub enum NodeIdentifier<'a> {
+    Numeric(u32),
+    String(&'a str),
+    Guid(&'a [u8]),
+    Opaque(&'a [u8]),
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct NodeId<'a> {
+    pub namespace: u16,
+    pub identifier: NodeIdentifier<'a>,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ExpandedNodeId<'a> {
+    pub node_id: NodeId<'a>,
+    pub namespace_uri: Option<&'a str>,
+    pub server_index: Option<u32>,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct QualifiedName<'a> {
+    pub namespace: u16,
+    pub name: &'a str,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LocalizedText<'a> {
+    pub locale: Option<&'a str>,
+    pub text: Option<&'a str>,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ExtensionObject<'a> {
+    pub type_id: NodeId<'a>,
+    pub encoding: u8,
+    pub body: &'a [u8],
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, PartialEq, Clone)]
+pub enum OpcuaVariant<'a> {
+    Null,
+    Boolean(bool),
+    SByte(i8),
+    Byte(u8),
+    Int16(i16),
+    UInt16(u16),
+    Int32(i32),
+    UInt32(u32),
+    Int64(i64),
+    UInt64(u64),
+    Float(f32),
+    Double(f64),
+    String(&'a str),
+    DateTime(i64),
+    Guid(&'a [u8]),
+    ByteString(&'a [u8]),
+    XmlElement(&'a [u8]),
+    NodeId(NodeId<'a>),
+    ExpandedNodeId(ExpandedNodeId<'a>),
+    StatusCode(u32),
+    QualifiedName(QualifiedName<'a>),
+    LocalizedText(LocalizedText<'a>),
+    ExtensionObject(ExtensionObject<'a>),
+    DataValue(Box<DataValue<'a>>),
+    Variant(Box<OpcuaVariant<'a>>),
+    DiagnosticInfo,
+    Array(Vec<OpcuaVariant<'a>>),
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, PartialEq, Clone)]
+pub struct DataValue<'a> {
+    pub value: Option<OpcuaVariant<'a>>,
+    pub status: Option<u32>,
+    pub source_timestamp: Option<i64>,
+    pub server_timestamp: Option<i64>,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ResponseHeader<'a> {
+    pub timestamp: u64,
+    pub request_handle: u32,
+    pub service_result: u32,
+    pub string_table: Vec<&'a str>,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ReadValueId<'a> {
+    pub node_id: NodeId<'a>,
+    pub attribute_id: u32,
+    pub index_range: &'a str,
+    pub data_encoding: QualifiedName<'a>,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, PartialEq, Clone)]
+pub struct WriteValue<'a> {
+    pub node_id: NodeId<'a>,
+    pub attribute_id: u32,
+    pub index_range: &'a str,
+    pub value: DataValue<'a>,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, PartialEq, Clone)]
+pub struct CallMethodRequest<'a> {
+    pub object_id: NodeId<'a>,
+    pub method_id: NodeId<'a>,
+    pub input_arguments: Vec<OpcuaVariant<'a>>,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, PartialEq, Clone)]
+pub struct CallMethodResult<'a> {
+    pub status_code: u32,
+    pub input_argument_results: Vec<u32>,
+    pub output_arguments: Vec<OpcuaVariant<'a>>,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct BrowseDescription<'a> {
+    pub node_id: NodeId<'a>,
+    pub browse_direction: u32,
+    pub reference_type_id: NodeId<'a>,
+    pub include_subtypes: bool,
+    pub node_class_mask: u32,
+    pub result_mask: u32,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ReferenceDescription<'a> {
+    pub reference_type_id: NodeId<'a>,
+    pub is_forward: bool,
+    pub node_id: ExpandedNodeId<'a>,
+    pub browse_name: QualifiedName<'a>,
+    pub display_name: LocalizedText<'a>,
+    pub node_class: u32,
+    pub type_definition: ExpandedNodeId<'a>,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct BrowseResult<'a> {
+    pub status_code: u32,
+    pub continuation_point: &'a [u8],
+    pub references: Vec<ReferenceDescription<'a>>,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ApplicationDescription<'a> {
+    pub application_uri: &'a str,
+    pub product_uri: &'a str,
+    pub application_name: LocalizedText<'a>,
+    pub application_type: u32,
+    pub gateway_server_uri: &'a str,
+    pub discovery_profile_uri: &'a str,
+    pub discovery_urls: Vec<&'a str>,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct UserTokenPolicy<'a> {
+    pub policy_id: &'a str,
+    pub token_type: u32,
+    pub issued_token_type: &'a str,
+    pub issuer_endpoint_url: &'a str,
+    pub security_policy_uri: &'a str,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct EndpointDescription<'a> {
+    pub endpoint_url: &'a str,
+    pub server: ApplicationDescription<'a>,
+    pub server_certificate: &'a [u8],
+    pub security_mode: u32,
+    pub security_policy_uri: &'a str,
+    pub user_identity_tokens: Vec<UserTokenPolicy<'a>>,
+    pub transport_profile_uri: &'a str,
+    pub security_level: u8,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum UserIdentityToken<'a> {
+    None {},
+    Anonymous {
+        policy_id: &'a str,
+    },
+    UserName {
+        policy_id: &'a str,
+        user_name: &'a str,
+        password: &'a [u8],
+        encryption_algorithm: &'a str,
+    },
+    X509 {
+        policy_id: &'a str,
+        certificate_data: &'a [u8],
+    },
+    Issued {
+        policy_id: &'a str,
+        token_data: &'a [u8],
+        encryption_algorithm: &'a str,
+    },
+    Other {
+        extension_object: ExtensionObject<'a>,
+    },
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ChannelSecurityToken {
+    pub channel_id: u32,
+    pub token_id: u32,
+    pub created_at: i64,
+    pub revised_lifetime: u32,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum ServiceEnum<'a> {
-    ServiceFault {},
+    ServiceFault {
+        response_header: ResponseHeader<'a>,
+    },
     FindServersRequest {},
     FindServersResponse {},
     FindServersOnNetworkRequest {},
@@ -250,21 +748,65 @@ pub enum ServiceEnum<'a> {
         profile_uris_array_size: u32,
         profile_uris_array_string_items: Vec<OpcuaString<'a>>,
     },
-    GetEndpointsResponse {},
+    GetEndpointsResponse {
+        response_header: ResponseHeader<'a>,
+        endpoints: Vec<EndpointDescription<'a>>,
+    },
     RegisterServerRequest {},
     RegisterServerResponse {},
     RegisterServer2Request {},
     RegisterServer2Response {},
-    OpenSecureChannelRequest {},
-    OpenSecureChannelResponse {},
-    CloseSecureChannelRequest {},
-    CloseSecureChannelResponse {},
-    CreateSessionRequest {},
-    CreateSessionResponse {},
-    ActivateSessionRequest {},
-    ActivateSessionResponse {},
-    CloseSessionRequest {},
-    CloseSessionResponse {},
+    OpenSecureChannelRequest {
+        request_header: RequestHeader<'a>,
+        client_protocol_version: u32,
+        request_type: u32,
+        security_mode: u32,
+        requested_lifetime: u32,
+    },
+    OpenSecureChannelResponse {
+        response_header: ResponseHeader<'a>,
+        server_protocol_version: u32,
+        security_token: ChannelSecurityToken,
+    },
+    CloseSecureChannelRequest {
+        request_header: RequestHeader<'a>,
+    },
+    CloseSecureChannelResponse {
+        response_header: ResponseHeader<'a>,
+    },
+    CreateSessionRequest {
+        request_header: RequestHeader<'a>,
+        client_description: ApplicationDescription<'a>,
+        server_uri: &'a str,
+        endpoint_url: &'a str,
+        session_name: &'a str,
+        requested_session_timeout: f64,
+        max_response_message_size: u32,
+    },
+    CreateSessionResponse {
+        response_header: ResponseHeader<'a>,
+        session_id: NodeId<'a>,
+        authentication_token: NodeId<'a>,
+        revised_session_timeout: f64,
+        server_endpoints: Vec<EndpointDescription<'a>>,
+        max_request_message_size: u32,
+    },
+    ActivateSessionRequest {
+        request_header: RequestHeader<'a>,
+        locale_ids: Vec<&'a str>,
+        user_identity_token: UserIdentityToken<'a>,
+    },
+    ActivateSessionResponse {
+        response_header: ResponseHeader<'a>,
+        results: Vec<u32>,
+    },
+    CloseSessionRequest {
+        request_header: RequestHeader<'a>,
+        delete_subscriptions: bool,
+    },
+    CloseSessionResponse {
+        response_header: ResponseHeader<'a>,
+    },
     CancelRequest {},
     CancelResponse {},
     AddNodesRequest {},
@@ -275,8 +817,16 @@ pub enum ServiceEnum<'a> {
     DeleteNodesResponse {},
     DeleteReferencesRequest {},
     DeleteReferencesResponse {},
-    BrowseRequest {},
-    BrowseResponse {},
+    BrowseRequest {
+        request_header: RequestHeader<'a>,
+        view_id: NodeId<'a>,
+        requested_max_references_per_node: u32,
+        nodes_to_browse: Vec<BrowseDescription<'a>>,
+    },
+    BrowseResponse {
+        response_header: ResponseHeader<'a>,
+        results: Vec<BrowseResult<'a>>,
+    },
     BrowseNextRequest {},
     BrowseNextResponse {},
     TranslateBrowsePathsToNodeIdsRequest {},
@@ -289,16 +839,36 @@ pub enum ServiceEnum<'a> {
     QueryFirstResponse {},
     QueryNextRequest {},
     QueryNextResponse {},
-    ReadRequest {},
-    ReadResponse {},
+    ReadRequest {
+        request_header: RequestHeader<'a>,
+        max_age: f64,
+        timestamps_to_return: u32,
+        nodes_to_read: Vec<ReadValueId<'a>>,
+    },
+    ReadResponse {
+        response_header: ResponseHeader<'a>,
+        results: Vec<DataValue<'a>>,
+    },
     HistoryReadRequest {},
     HistoryReadResponse {},
-    WriteRequest {},
-    WriteResponse {},
+    WriteRequest {
+        request_header: RequestHeader<'a>,
+        nodes_to_write: Vec<WriteValue<'a>>,
+    },
+    WriteResponse {
+        response_header: ResponseHeader<'a>,
+        results: Vec<u32>,
+    },
     HistoryUpdateRequest {},
     HistoryUpdateResponse {},
-    CallRequest {},
-    CallResponse {},
+    CallRequest {
+        request_header: RequestHeader<'a>,
+        methods_to_call: Vec<CallMethodRequest<'a>>,
+    },
+    CallResponse {
+        response_header: ResponseHeader<'a>,
+        results: Vec<CallMethodResult<'a>>,
+    },
     CreateMonitoredItemsRequest {},
     CreateMonitoredItemsResponse {},
     ModifyMonitoredItemsRequest {},
@@ -330,7 +900,7 @@ pub enum ServiceEnum<'a> {
 }
 
 #[allow(non_camel_case_types)]
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum ServiceNodeidInfo<'a> {
     TB {
         service_nodeid_numeric: u8,
@@ -352,7 +922,7 @@ pub enum ServiceNodeidInfo<'a> {
 }
 
 #[allow(non_camel_case_types)]
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum MsgVariantInfo<'a> {
     Abort {
         error: &'a str,
@@ -362,10 +932,14 @@ pub enum MsgVariantInfo<'a> {
         service_nodeid_encodingmask: u8,
         service_nodeid_info: ServiceNodeidInfo<'a>,
     },
+    /// 中间块('C')或无法解码的正文(如加密通道)，跨块重组见 [`OpcuaSecureChannels`]
+    Undecoded {
+        data: &'a [u8],
+    },
 }
 
 #[allow(non_camel_case_types)]
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum MessageTypeEnum<'a> {
     Hello {
         chunk_type: u8,
@@ -405,10 +979,32 @@ pub enum MessageTypeEnum<'a> {
         security_token_id: u32,
         security_sequence_number: u32,
         security_request_id: u32,
+        body: &'a [u8],
+        msg_variant_info: MsgVariantInfo<'a>,
+    },
+    /// 安全策略非 None 时，序列头与正文均已加密
+    OpenSecureChannel {
+        chunk_type: u8,
+        transport_size: u32,
+        secure_channel_id: u32,
+        security_policy_uri: &'a str,
+        sender_certificate: &'a [u8],
+        receiver_certificate_thumbprint: &'a [u8],
+        security_sequence_number: Option<u32>,
+        security_request_id: Option<u32>,
+        body: &'a [u8],
+        msg_variant_info: MsgVariantInfo<'a>,
+    },
+    CloseSecureChannel {
+        chunk_type: u8,
+        transport_size: u32,
+        secure_channel_id: u32,
+        security_token_id: u32,
+        security_sequence_number: u32,
+        security_request_id: u32,
+        body: &'a [u8],
         msg_variant_info: MsgVariantInfo<'a>,
     },
-    OpenSecureChannel {},
-    CloseSecureChannel {},
 }
 
 pub fn parse_opcua_string(input: &[u8]) -> IResult<&[u8], OpcuaString> {
@@ -531,17 +1127,38 @@ fn parse_nodeid_info_numeric(input: &[u8]) -> IResult<&[u8], NodeidInfo> {
 
 fn parse_nodeid_info_string(input: &[u8]) -> IResult<&[u8], NodeidInfo> {
     let (input, nodeid_namespace) = le_u16(input)?;
-    Ok((input, NodeidInfo::String { nodeid_namespace }))
+    let (input, nodeid_string) = parse_opcua_str(input)?;
+    Ok((
+        input,
+        NodeidInfo::String {
+            nodeid_namespace,
+            nodeid_string,
+        },
+    ))
 }
 
 fn parse_nodeid_info_guid(input: &[u8]) -> IResult<&[u8], NodeidInfo> {
     let (input, nodeid_namespace) = le_u16(input)?;
-    Ok((input, NodeidInfo::Guid { nodeid_namespace }))
+    let (input, nodeid_guid) = take(16usize)(input)?;
+    Ok((
+        input,
+        NodeidInfo::Guid {
+            nodeid_namespace,
+            nodeid_guid,
+        },
+    ))
 }
 
 fn parse_nodeid_info_opaque(input: &[u8]) -> IResult<&[u8], NodeidInfo> {
     let (input, nodeid_namespace) = le_u16(input)?;
-    Ok((input, NodeidInfo::Opaque { nodeid_namespace }))
+    let (input, nodeid_opaque) = parse_opcua_byte_string(input)?;
+    Ok((
+        input,
+        NodeidInfo::Opaque {
+            nodeid_namespace,
+            nodeid_opaque,
+        },
+    ))
 }
 
 pub fn parse_nodeid_info(input: &[u8], nodeid_encodingmask: u8) -> IResult<&[u8], NodeidInfo> {
@@ -614,6 +1231,7 @@ pub fn parse_expanded_node_id_info(
         ))
     } else if expanded_nodeid_encodingmask == 0x03 {
         let (input, nodeid_namespace) = le_u16(input)?;
+        let (input, nodeid_string) = parse_opcua_str(input)?;
         let (input, namespace_enum) =
             parse_namespace_enum(input, expanded_nodeid_has_namespace_uri)?;
         let (input, server_index_enum) =
@@ -622,12 +1240,14 @@ pub fn parse_expanded_node_id_info(
             input,
             ExpandedNodeIdInfo::String {
                 nodeid_namespace,
+                nodeid_string,
                 namespace_enum,
                 server_index_enum,
             },
         ))
     } else if expanded_nodeid_encodingmask == 0x04 {
         let (input, nodeid_namespace) = le_u16(input)?;
+        let (input, nodeid_guid) = take(16usize)(input)?;
         let (input, namespace_enum) =
             parse_namespace_enum(input, expanded_nodeid_has_namespace_uri)?;
         let (input, server_index_enum) =
@@ -636,12 +1256,14 @@ pub fn parse_expanded_node_id_info(
             input,
             ExpandedNodeIdInfo::Guid {
                 nodeid_namespace,
+                nodeid_guid,
                 namespace_enum,
                 server_index_enum,
             },
         ))
     } else if expanded_nodeid_encodingmask == 0x05 {
         let (input, nodeid_namespace) = le_u16(input)?;
+        let (input, nodeid_opaque) = parse_opcua_byte_string(input)?;
         let (input, namespace_enum) =
             parse_namespace_enum(input, expanded_nodeid_has_namespace_uri)?;
         let (input, server_index_enum) =
@@ -650,6 +1272,7 @@ pub fn parse_expanded_node_id_info(
             input,
             ExpandedNodeIdInfo::Opaque {
                 nodeid_namespace,
+                nodeid_opaque,
                 namespace_enum,
                 server_index_enum,
             },
@@ -669,7 +1292,7 @@ pub fn parse_request_header(input: &[u8]) -> IResult<&[u8], RequestHeader> {
             take_bits(4usize),
         )))(input)?;
     let (input, nodeid_info) = parse_nodeid_info(input, nodeid_encodingmask)?;
-    let (input, timestamp) = be_u64(input)?;
+    let (input, timestamp) = le_u64(input)?;
     let (input, request_handle) = le_u32(input)?;
     let (
         input,
@@ -744,6 +1367,11 @@ pub fn parse_request_header(input: &[u8]) -> IResult<&[u8], RequestHeader> {
         take_bits(1usize),
         take_bits(1usize),
     )))(input)?;
+    // additionalHeader 携带 body 时跳过
+    let input = match encodingmask_has_binary_body | encodingmask_has_xml_body {
+        0 => input,
+        _ => parse_opcua_byte_string(input)?.0,
+    };
     Ok((
         input,
         RequestHeader {
@@ -773,8 +1401,542 @@ pub fn parse_request_header(input: &[u8]) -> IResult<&[u8], RequestHeader> {
     ))
 }
 
+// 嵌套 Variant / DataValue 的最大深度
+const OPCUA_MAX_VARIANT_DEPTH: u8 = 8;
+
+fn opcua_verify_error<T>(input: &[u8]) -> IResult<&[u8], T> {
+    Err(nom::Err::Error(nom::error::Error::new(
+        input,
+        nom::error::ErrorKind::Verify,
+    )))
+}
+
+pub fn parse_opcua_str(input: &[u8]) -> IResult<&[u8], &str> {
+    let (input, _string) = parse_opcua_byte_string(input)?;
+    match std::str::from_utf8(_string) {
+        Ok(o) => Ok((input, o)),
+        Err(_) => opcua_verify_error(input),
+    }
+}
+
+// 长度为 -1 表示 null，按空串处理
+pub fn parse_opcua_byte_string(input: &[u8]) -> IResult<&[u8], &[u8]> {
+    let (input, _len) = le_u32(input)?;
+    let mut _len = _len;
+    if _len == 0xffffffff {
+        _len = 0;
+    }
+    take(_len as usize)(input)
+}
+
+fn parse_opcua_boolean(input: &[u8]) -> IResult<&[u8], bool> {
+    map(u8, |v| v != 0)(input)
+}
+
+fn parse_opcua_array<'a, T, F>(input: &'a [u8], f: F) -> IResult<&'a [u8], Vec<T>>
+where
+    F: FnMut(&'a [u8]) -> IResult<&'a [u8], T>,
+{
+    let (input, array_size) = le_i32(input)?;
+    if array_size <= 0 {
+        return Ok((input, Vec::new()));
+    }
+    count(f, array_size as usize)(input)
+}
+
+fn parse_node_identifier(input: &[u8], encodingmask: u8) -> IResult<&[u8], NodeId> {
+    let (input, (namespace, identifier)) = match encodingmask {
+        0x0 => map(u8, |v| (0, NodeIdentifier::Numeric(v as u32)))(input)?,
+        0x01 => map(tuple((u8, le_u16)), |(ns, v)| {
+            (ns as u16, NodeIdentifier::Numeric(v as u32))
+        })(input)?,
+        0x02 => map(tuple((le_u16, le_u32)), |(ns, v)| (ns, NodeIdentifier::Numeric(v)))(input)?,
+        0x03 => map(tuple((le_u16, parse_opcua_str)), |(ns, v)| {
+            (ns, NodeIdentifier::String(v))
+        })(input)?,
+        0x04 => map(tuple((le_u16, take(16usize))), |(ns, v)| {
+            (ns, NodeIdentifier::Guid(v))
+        })(input)?,
+        0x05 => map(tuple((le_u16, parse_opcua_byte_string)), |(ns, v)| {
+            (ns, NodeIdentifier::Opaque(v))
+        })(input)?,
+        _ => return opcua_verify_error(input),
+    };
+    Ok((
+        input,
+        NodeId {
+            namespace,
+            identifier,
+        },
+    ))
+}
+
+pub fn parse_node_id(input: &[u8]) -> IResult<&[u8], NodeId> {
+    let (input, encodingmask) = u8(input)?;
+    parse_node_identifier(input, encodingmask)
+}
+
+pub fn parse_expanded_node_id(input: &[u8]) -> IResult<&[u8], ExpandedNodeId> {
+    let (input, encodingmask) = u8(input)?;
+    let (input, node_id) = parse_node_identifier(input, encodingmask & 0x0f)?;
+    let (input, namespace_uri) = match encodingmask & 0x80 {
+        0 => (input, None),
+        _ => map(parse_opcua_str, Some)(input)?,
+    };
+    let (input, server_index) = match encodingmask & 0x40 {
+        0 => (input, None),
+        _ => map(le_u32, Some)(input)?,
+    };
+    Ok((
+        input,
+        ExpandedNodeId {
+            node_id,
+            namespace_uri,
+            server_index,
+        },
+    ))
+}
+
+fn parse_qualified_name(input: &[u8]) -> IResult<&[u8], QualifiedName> {
+    let (input, namespace) = le_u16(input)?;
+    let (input, name) = parse_opcua_str(input)?;
+    Ok((input, QualifiedName { namespace, name }))
+}
+
+fn parse_localized_text(input: &[u8]) -> IResult<&[u8], LocalizedText> {
+    let (input, encodingmask) = u8(input)?;
+    let (input, locale) = match encodingmask & 0x01 {
+        0 => (input, None),
+        _ => map(parse_opcua_str, Some)(input)?,
+    };
+    let (input, text) = match encodingmask & 0x02 {
+        0 => (input, None),
+        _ => map(parse_opcua_str, Some)(input)?,
+    };
+    Ok((input, LocalizedText { locale, text }))
+}
+
+pub fn parse_extension_object(input: &[u8]) -> IResult<&[u8], ExtensionObject> {
+    let (input, type_id) = parse_node_id(input)?;
+    let (input, encoding) = u8(input)?;
+    let (input, body) = match encoding {
+        0x0 => (input, &input[..0]),
+        0x01 | 0x02 => parse_opcua_byte_string(input)?,
+        _ => return opcua_verify_error(input),
+    };
+    Ok((
+        input,
+        ExtensionObject {
+            type_id,
+            encoding,
+            body,
+        },
+    ))
+}
+
+// DiagnosticInfo 仅做跳过，innerDiagnosticInfo 位于末尾，循环处理
+fn parse_diagnostic_info(input: &[u8]) -> IResult<&[u8], ()> {
+    let mut input = input;
+    loop {
+        let (_input, encodingmask) = u8(input)?;
+        input = _input;
+        for bit in [0x01u8, 0x02, 0x04, 0x08] {
+            if encodingmask & bit != 0 {
+                (input, _) = le_i32(input)?;
+            }
+        }
+        if encodingmask & 0x10 != 0 {
+            (input, _) = parse_opcua_str(input)?;
+        }
+        if encodingmask & 0x20 != 0 {
+            (input, _) = le_u32(input)?;
+        }
+        if encodingmask & 0x40 == 0 {
+            return Ok((input, ()));
+        }
+    }
+}
+
+fn parse_opcua_variant_value(
+    input: &[u8],
+    type_id: u8,
+    depth: u8,
+) -> IResult<&[u8], OpcuaVariant> {
+    match type_id {
+        0 => Ok((input, OpcuaVariant::Null)),
+        1 => map(parse_opcua_boolean, OpcuaVariant::Boolean)(input),
+        2 => map(le_i8, OpcuaVariant::SByte)(input),
+        3 => map(u8, OpcuaVariant::Byte)(input),
+        4 => map(le_i16, OpcuaVariant::Int16)(input),
+        5 => map(le_u16, OpcuaVariant::UInt16)(input),
+        6 => map(le_i32, OpcuaVariant::Int32)(input),
+        7 => map(le_u32, OpcuaVariant::UInt32)(input),
+        8 => map(le_i64, OpcuaVariant::Int64)(input),
+        9 => map(le_u64, OpcuaVariant::UInt64)(input),
+        10 => map(le_f32, OpcuaVariant::Float)(input),
+        11 => map(le_f64, OpcuaVariant::Double)(input),
+        12 => map(parse_opcua_str, OpcuaVariant::String)(input),
+        13 => map(le_i64, OpcuaVariant::DateTime)(input),
+        14 => map(take(16usize), OpcuaVariant::Guid)(input),
+        15 => map(parse_opcua_byte_string, OpcuaVariant::ByteString)(input),
+        16 => map(parse_opcua_byte_string, OpcuaVariant::XmlElement)(input),
+        17 => map(parse_node_id, OpcuaVariant::NodeId)(input),
+        18 => map(parse_expanded_node_id, OpcuaVariant::ExpandedNodeId)(input),
+        19 => map(le_u32, OpcuaVariant::StatusCode)(input),
+        20 => map(parse_qualified_name, OpcuaVariant::QualifiedName)(input),
+        21 => map(parse_localized_text, OpcuaVariant::LocalizedText)(input),
+        22 => map(parse_extension_object, OpcuaVariant::ExtensionObject)(input),
+        23 => {
+            let (input, data_value) = parse_data_value_depth(input, depth + 1)?;
+            Ok((input, OpcuaVariant::DataValue(Box::new(data_value))))
+        }
+        24 => {
+            let (input, variant) = parse_opcua_variant_depth(input, depth + 1)?;
+            Ok((input, OpcuaVariant::Variant(Box::new(variant))))
+        }
+        25 => map(parse_diagnostic_info, |_| OpcuaVariant::DiagnosticInfo)(input),
+        _ => opcua_verify_error(input),
+    }
+}
+
+fn parse_opcua_variant_depth(input: &[u8], depth: u8) -> IResult<&[u8], OpcuaVariant> {
+    if depth > OPCUA_MAX_VARIANT_DEPTH {
+        return opcua_verify_error(input);
+    }
+    let (input, encodingmask) = u8(input)?;
+    let type_id = encodingmask & 0x3f;
+    if encodingmask & 0x80 == 0 {
+        return parse_opcua_variant_value(input, type_id, depth);
+    }
+    let (input, array) =
+        parse_opcua_array(input, |input| parse_opcua_variant_value(input, type_id, depth))?;
+    // 多维数组的维度信息，跳过
+    let input = match encodingmask & 0x40 {
+        0 => input,
+        _ => parse_opcua_array(input, le_i32)?.0,
+    };
+    Ok((input, OpcuaVariant::Array(array)))
+}
+
+pub fn parse_opcua_variant(input: &[u8]) -> IResult<&[u8], OpcuaVariant> {
+    parse_opcua_variant_depth(input, 0)
+}
+
+fn parse_data_value_depth(input: &[u8], depth: u8) -> IResult<&[u8], DataValue> {
+    let (input, encodingmask) = u8(input)?;
+    let (input, value) = match encodingmask & 0x01 {
+        0 => (input, None),
+        _ => map(|input| parse_opcua_variant_depth(input, depth), Some)(input)?,
+    };
+    let (input, status) = match encodingmask & 0x02 {
+        0 => (input, None),
+        _ => map(le_u32, Some)(input)?,
+    };
+    let (input, source_timestamp) = match encodingmask & 0x04 {
+        0 => (input, None),
+        _ => map(le_i64, Some)(input)?,
+    };
+    let (input, server_timestamp) = match encodingmask & 0x08 {
+        0 => (input, None),
+        _ => map(le_i64, Some)(input)?,
+    };
+    // source / server picoseconds
+    let input = match encodingmask & 0x10 {
+        0 => input,
+        _ => le_u16(input)?.0,
+    };
+    let input = match encodingmask & 0x20 {
+        0 => input,
+        _ => le_u16(input)?.0,
+    };
+    Ok((
+        input,
+        DataValue {
+            value,
+            status,
+            source_timestamp,
+            server_timestamp,
+        },
+    ))
+}
+
+pub fn parse_data_value(input: &[u8]) -> IResult<&[u8], DataValue> {
+    parse_data_value_depth(input, 0)
+}
+
+pub fn parse_response_header(input: &[u8]) -> IResult<&[u8], ResponseHeader> {
+    let (input, timestamp) = le_u64(input)?;
+    let (input, request_handle) = le_u32(input)?;
+    let (input, service_result) = le_u32(input)?;
+    let (input, _) = parse_diagnostic_info(input)?;
+    let (input, string_table) = parse_opcua_array(input, parse_opcua_str)?;
+    let (input, _additional_header) = parse_extension_object(input)?;
+    Ok((
+        input,
+        ResponseHeader {
+            timestamp,
+            request_handle,
+            service_result,
+            string_table,
+        },
+    ))
+}
+
+fn parse_read_value_id(input: &[u8]) -> IResult<&[u8], ReadValueId> {
+    let (input, node_id) = parse_node_id(input)?;
+    let (input, attribute_id) = le_u32(input)?;
+    let (input, index_range) = parse_opcua_str(input)?;
+    let (input, data_encoding) = parse_qualified_name(input)?;
+    Ok((
+        input,
+        ReadValueId {
+            node_id,
+            attribute_id,
+            index_range,
+            data_encoding,
+        },
+    ))
+}
+
+fn parse_write_value(input: &[u8]) -> IResult<&[u8], WriteValue> {
+    let (input, node_id) = parse_node_id(input)?;
+    let (input, attribute_id) = le_u32(input)?;
+    let (input, index_range) = parse_opcua_str(input)?;
+    let (input, value) = parse_data_value(input)?;
+    Ok((
+        input,
+        WriteValue {
+            node_id,
+            attribute_id,
+            index_range,
+            value,
+        },
+    ))
+}
+
+fn parse_call_method_request(input: &[u8]) -> IResult<&[u8], CallMethodRequest> {
+    let (input, object_id) = parse_node_id(input)?;
+    let (input, method_id) = parse_node_id(input)?;
+    let (input, input_arguments) = parse_opcua_array(input, parse_opcua_variant)?;
+    Ok((
+        input,
+        CallMethodRequest {
+            object_id,
+            method_id,
+            input_arguments,
+        },
+    ))
+}
+
+fn parse_call_method_result(input: &[u8]) -> IResult<&[u8], CallMethodResult> {
+    let (input, status_code) = le_u32(input)?;
+    let (input, input_argument_results) = parse_opcua_array(input, le_u32)?;
+    let (input, _input_argument_diagnostic_infos) =
+        parse_opcua_array(input, parse_diagnostic_info)?;
+    let (input, output_arguments) = parse_opcua_array(input, parse_opcua_variant)?;
+    Ok((
+        input,
+        CallMethodResult {
+            status_code,
+            input_argument_results,
+            output_arguments,
+        },
+    ))
+}
+
+fn parse_browse_description(input: &[u8]) -> IResult<&[u8], BrowseDescription> {
+    let (input, node_id) = parse_node_id(input)?;
+    let (input, browse_direction) = le_u32(input)?;
+    let (input, reference_type_id) = parse_node_id(input)?;
+    let (input, include_subtypes) = parse_opcua_boolean(input)?;
+    let (input, node_class_mask) = le_u32(input)?;
+    let (input, result_mask) = le_u32(input)?;
+    Ok((
+        input,
+        BrowseDescription {
+            node_id,
+            browse_direction,
+            reference_type_id,
+            include_subtypes,
+            node_class_mask,
+            result_mask,
+        },
+    ))
+}
+
+fn parse_reference_description(input: &[u8]) -> IResult<&[u8], ReferenceDescription> {
+    let (input, reference_type_id) = parse_node_id(input)?;
+    let (input, is_forward) = parse_opcua_boolean(input)?;
+    let (input, node_id) = parse_expanded_node_id(input)?;
+    let (input, browse_name) = parse_qualified_name(input)?;
+    let (input, display_name) = parse_localized_text(input)?;
+    let (input, node_class) = le_u32(input)?;
+    let (input, type_definition) = parse_expanded_node_id(input)?;
+    Ok((
+        input,
+        ReferenceDescription {
+            reference_type_id,
+            is_forward,
+            node_id,
+            browse_name,
+            display_name,
+            node_class,
+            type_definition,
+        },
+    ))
+}
+
+fn parse_browse_result(input: &[u8]) -> IResult<&[u8], BrowseResult> {
+    let (input, status_code) = le_u32(input)?;
+    let (input, continuation_point) = parse_opcua_byte_string(input)?;
+    let (input, references) = parse_opcua_array(input, parse_reference_description)?;
+    Ok((
+        input,
+        BrowseResult {
+            status_code,
+            continuation_point,
+            references,
+        },
+    ))
+}
+
+fn parse_application_description(input: &[u8]) -> IResult<&[u8], ApplicationDescription> {
+    let (input, application_uri) = parse_opcua_str(input)?;
+    let (input, product_uri) = parse_opcua_str(input)?;
+    let (input, application_name) = parse_localized_text(input)?;
+    let (input, application_type) = le_u32(input)?;
+    let (input, gateway_server_uri) = parse_opcua_str(input)?;
+    let (input, discovery_profile_uri) = parse_opcua_str(input)?;
+    let (input, discovery_urls) = parse_opcua_array(input, parse_opcua_str)?;
+    Ok((
+        input,
+        ApplicationDescription {
+            application_uri,
+            product_uri,
+            application_name,
+            application_type,
+            gateway_server_uri,
+            discovery_profile_uri,
+            discovery_urls,
+        },
+    ))
+}
+
+fn parse_user_token_policy(input: &[u8]) -> IResult<&[u8], UserTokenPolicy> {
+    let (input, policy_id) = parse_opcua_str(input)?;
+    let (input, token_type) = le_u32(input)?;
+    let (input, issued_token_type) = parse_opcua_str(input)?;
+    let (input, issuer_endpoint_url) = parse_opcua_str(input)?;
+    let (input, security_policy_uri) = parse_opcua_str(input)?;
+    Ok((
+        input,
+        UserTokenPolicy {
+            policy_id,
+            token_type,
+            issued_token_type,
+            issuer_endpoint_url,
+            security_policy_uri,
+        },
+    ))
+}
+
+fn parse_endpoint_description(input: &[u8]) -> IResult<&[u8], EndpointDescription> {
+    let (input, endpoint_url) = parse_opcua_str(input)?;
+    let (input, server) = parse_application_description(input)?;
+    let (input, server_certificate) = parse_opcua_byte_string(input)?;
+    let (input, security_mode) = le_u32(input)?;
+    let (input, security_policy_uri) = parse_opcua_str(input)?;
+    let (input, user_identity_tokens) = parse_opcua_array(input, parse_user_token_policy)?;
+    let (input, transport_profile_uri) = parse_opcua_str(input)?;
+    let (input, security_level) = u8(input)?;
+    Ok((
+        input,
+        EndpointDescription {
+            endpoint_url,
+            server,
+            server_certificate,
+            security_mode,
+            security_policy_uri,
+            user_identity_tokens,
+            transport_profile_uri,
+            security_level,
+        },
+    ))
+}
+
+// SignatureData: algorithm + signature
+fn parse_signature_data(input: &[u8]) -> IResult<&[u8], ()> {
+    let (input, _algorithm) = parse_opcua_str(input)?;
+    let (input, _signature) = parse_opcua_byte_string(input)?;
+    Ok((input, ()))
+}
+
+// 按 ExtensionObject 的 TypeId 解码身份令牌:
+// 321 Anonymous, 324 UserName, 327 X509, 940 Issued
+fn parse_user_identity_token(input: &[u8]) -> IResult<&[u8], UserIdentityToken> {
+    let (input, extension_object) = parse_extension_object(input)?;
+    if extension_object.encoding != 0x01 {
+        return match extension_object.encoding {
+            0x0 => Ok((input, UserIdentityToken::None {})),
+            _ => Ok((input, UserIdentityToken::Other { extension_object })),
+        };
+    }
+    let body = extension_object.body;
+    let user_identity_token = match extension_object.type_id {
+        NodeId {
+            namespace: 0,
+            identifier: NodeIdentifier::Numeric(321),
+        } => {
+            let (_, policy_id) = parse_opcua_str(body)?;
+            UserIdentityToken::Anonymous { policy_id }
+        }
+        NodeId {
+            namespace: 0,
+            identifier: NodeIdentifier::Numeric(324),
+        } => {
+            let (body, policy_id) = parse_opcua_str(body)?;
+            let (body, user_name) = parse_opcua_str(body)?;
+            let (body, password) = parse_opcua_byte_string(body)?;
+            let (_, encryption_algorithm) = parse_opcua_str(body)?;
+            UserIdentityToken::UserName {
+                policy_id,
+                user_name,
+                password,
+                encryption_algorithm,
+            }
+        }
+        NodeId {
+            namespace: 0,
+            identifier: NodeIdentifier::Numeric(327),
+        } => {
+            let (body, policy_id) = parse_opcua_str(body)?;
+            let (_, certificate_data) = parse_opcua_byte_string(body)?;
+            UserIdentityToken::X509 {
+                policy_id,
+                certificate_data,
+            }
+        }
+        NodeId {
+            namespace: 0,
+            identifier: NodeIdentifier::Numeric(940),
+        } => {
+            let (body, policy_id) = parse_opcua_str(body)?;
+            let (body, token_data) = parse_opcua_byte_string(body)?;
+            let (_, encryption_algorithm) = parse_opcua_str(body)?;
+            UserIdentityToken::Issued {
+                policy_id,
+                token_data,
+                encryption_algorithm,
+            }
+        }
+        _ => UserIdentityToken::Other { extension_object },
+    };
+    Ok((input, user_identity_token))
+}
+
 fn parse_service_enum_service_fault(input: &[u8]) -> IResult<&[u8], ServiceEnum> {
-    Ok((input, ServiceEnum::ServiceFault {}))
+    let (input, response_header) = parse_response_header(input)?;
+    Ok((input, ServiceEnum::ServiceFault { response_header }))
 }
 
 fn parse_service_enum_find_servers_request(input: &[u8]) -> IResult<&[u8], ServiceEnum> {
@@ -832,7 +1994,15 @@ fn parse_service_enum_get_endpoints_request(input: &[u8]) -> IResult<&[u8], Serv
 }
 
 fn parse_service_enum_get_endpoints_response(input: &[u8]) -> IResult<&[u8], ServiceEnum> {
-    Ok((input, ServiceEnum::GetEndpointsResponse {}))
+    let (input, response_header) = parse_response_header(input)?;
+    let (input, endpoints) = parse_opcua_array(input, parse_endpoint_description)?;
+    Ok((
+        input,
+        ServiceEnum::GetEndpointsResponse {
+            response_header,
+            endpoints,
+        },
+    ))
 }
 
 fn parse_service_enum_register_server_request(input: &[u8]) -> IResult<&[u8], ServiceEnum> {
@@ -852,43 +2022,155 @@ fn parse_service_enum_register_server2_response(input: &[u8]) -> IResult<&[u8],
 }
 
 fn parse_service_enum_open_secure_channel_request(input: &[u8]) -> IResult<&[u8], ServiceEnum> {
-    Ok((input, ServiceEnum::OpenSecureChannelRequest {}))
+    let (input, request_header) = parse_request_header(input)?;
+    let (input, client_protocol_version) = le_u32(input)?;
+    let (input, request_type) = le_u32(input)?;
+    let (input, security_mode) = le_u32(input)?;
+    let (input, _client_nonce) = parse_opcua_byte_string(input)?;
+    let (input, requested_lifetime) = le_u32(input)?;
+    Ok((
+        input,
+        ServiceEnum::OpenSecureChannelRequest {
+            request_header,
+            client_protocol_version,
+            request_type,
+            security_mode,
+            requested_lifetime,
+        },
+    ))
 }
 
 fn parse_service_enum_open_secure_channel_response(input: &[u8]) -> IResult<&[u8], ServiceEnum> {
-    Ok((input, ServiceEnum::OpenSecureChannelResponse {}))
+    let (input, response_header) = parse_response_header(input)?;
+    let (input, server_protocol_version) = le_u32(input)?;
+    let (input, channel_id) = le_u32(input)?;
+    let (input, token_id) = le_u32(input)?;
+    let (input, created_at) = le_i64(input)?;
+    let (input, revised_lifetime) = le_u32(input)?;
+    let (input, _server_nonce) = parse_opcua_byte_string(input)?;
+    Ok((
+        input,
+        ServiceEnum::OpenSecureChannelResponse {
+            response_header,
+            server_protocol_version,
+            security_token: ChannelSecurityToken {
+                channel_id,
+                token_id,
+                created_at,
+                revised_lifetime,
+            },
+        },
+    ))
 }
 
 fn parse_service_enum_close_secure_channel_request(input: &[u8]) -> IResult<&[u8], ServiceEnum> {
-    Ok((input, ServiceEnum::CloseSecureChannelRequest {}))
+    let (input, request_header) = parse_request_header(input)?;
+    Ok((input, ServiceEnum::CloseSecureChannelRequest { request_header }))
 }
 
 fn parse_service_enum_close_secure_channel_response(input: &[u8]) -> IResult<&[u8], ServiceEnum> {
-    Ok((input, ServiceEnum::CloseSecureChannelResponse {}))
+    let (input, response_header) = parse_response_header(input)?;
+    Ok((input, ServiceEnum::CloseSecureChannelResponse { response_header }))
 }
 
 fn parse_service_enum_create_session_request(input: &[u8]) -> IResult<&[u8], ServiceEnum> {
-    Ok((input, ServiceEnum::CreateSessionRequest {}))
+    let (input, request_header) = parse_request_header(input)?;
+    let (input, client_description) = parse_application_description(input)?;
+    let (input, server_uri) = parse_opcua_str(input)?;
+    let (input, endpoint_url) = parse_opcua_str(input)?;
+    let (input, session_name) = parse_opcua_str(input)?;
+    let (input, _client_nonce) = parse_opcua_byte_string(input)?;
+    let (input, _client_certificate) = parse_opcua_byte_string(input)?;
+    let (input, requested_session_timeout) = le_f64(input)?;
+    let (input, max_response_message_size) = le_u32(input)?;
+    Ok((
+        input,
+        ServiceEnum::CreateSessionRequest {
+            request_header,
+            client_description,
+            server_uri,
+            endpoint_url,
+            session_name,
+            requested_session_timeout,
+            max_response_message_size,
+        },
+    ))
 }
 
 fn parse_service_enum_create_session_response(input: &[u8]) -> IResult<&[u8], ServiceEnum> {
-    Ok((input, ServiceEnum::CreateSessionResponse {}))
+    let (input, response_header) = parse_response_header(input)?;
+    let (input, session_id) = parse_node_id(input)?;
+    let (input, authentication_token) = parse_node_id(input)?;
+    let (input, revised_session_timeout) = le_f64(input)?;
+    let (input, _server_nonce) = parse_opcua_byte_string(input)?;
+    let (input, _server_certificate) = parse_opcua_byte_string(input)?;
+    let (input, server_endpoints) = parse_opcua_array(input, parse_endpoint_description)?;
+    let (input, _server_software_certificates) = parse_opcua_array(input, |input| {
+        tuple((parse_opcua_byte_string, parse_opcua_byte_string))(input)
+    })?;
+    let (input, _) = parse_signature_data(input)?;
+    let (input, max_request_message_size) = le_u32(input)?;
+    Ok((
+        input,
+        ServiceEnum::CreateSessionResponse {
+            response_header,
+            session_id,
+            authentication_token,
+            revised_session_timeout,
+            server_endpoints,
+            max_request_message_size,
+        },
+    ))
 }
 
 fn parse_service_enum_activate_session_request(input: &[u8]) -> IResult<&[u8], ServiceEnum> {
-    Ok((input, ServiceEnum::ActivateSessionRequest {}))
+    let (input, request_header) = parse_request_header(input)?;
+    let (input, _) = parse_signature_data(input)?;
+    let (input, _client_software_certificates) = parse_opcua_array(input, |input| {
+        tuple((parse_opcua_byte_string, parse_opcua_byte_string))(input)
+    })?;
+    let (input, locale_ids) = parse_opcua_array(input, parse_opcua_str)?;
+    let (input, user_identity_token) = parse_user_identity_token(input)?;
+    let (input, _) = parse_signature_data(input)?;
+    Ok((
+        input,
+        ServiceEnum::ActivateSessionRequest {
+            request_header,
+            locale_ids,
+            user_identity_token,
+        },
+    ))
 }
 
 fn parse_service_enum_activate_session_response(input: &[u8]) -> IResult<&[u8], ServiceEnum> {
-    Ok((input, ServiceEnum::ActivateSessionResponse {}))
+    let (input, response_header) = parse_response_header(input)?;
+    let (input, _server_nonce) = parse_opcua_byte_string(input)?;
+    let (input, results) = parse_opcua_array(input, le_u32)?;
+    let (input, _diagnostic_infos) = parse_opcua_array(input, parse_diagnostic_info)?;
+    Ok((
+        input,
+        ServiceEnum::ActivateSessionResponse {
+            response_header,
+            results,
+        },
+    ))
 }
 
 fn parse_service_enum_close_session_request(input: &[u8]) -> IResult<&[u8], ServiceEnum> {
-    Ok((input, ServiceEnum::CloseSessionRequest {}))
+    let (input, request_header) = parse_request_header(input)?;
+    let (input, delete_subscriptions) = parse_opcua_boolean(input)?;
+    Ok((
+        input,
+        ServiceEnum::CloseSessionRequest {
+            request_header,
+            delete_subscriptions,
+        },
+    ))
 }
 
 fn parse_service_enum_close_session_response(input: &[u8]) -> IResult<&[u8], ServiceEnum> {
-    Ok((input, ServiceEnum::CloseSessionResponse {}))
+    let (input, response_header) = parse_response_header(input)?;
+    Ok((input, ServiceEnum::CloseSessionResponse { response_header }))
 }
 
 fn parse_service_enum_cancel_request(input: &[u8]) -> IResult<&[u8], ServiceEnum> {
@@ -932,11 +2214,34 @@ fn parse_service_enum_delete_references_response(input: &[u8]) -> IResult<&[u8],
 }
 
 fn parse_service_enum_browse_request(input: &[u8]) -> IResult<&[u8], ServiceEnum> {
-    Ok((input, ServiceEnum::BrowseRequest {}))
+    let (input, request_header) = parse_request_header(input)?;
+    let (input, view_id) = parse_node_id(input)?;
+    let (input, _view_timestamp) = le_i64(input)?;
+    let (input, _view_version) = le_u32(input)?;
+    let (input, requested_max_references_per_node) = le_u32(input)?;
+    let (input, nodes_to_browse) = parse_opcua_array(input, parse_browse_description)?;
+    Ok((
+        input,
+        ServiceEnum::BrowseRequest {
+            request_header,
+            view_id,
+            requested_max_references_per_node,
+            nodes_to_browse,
+        },
+    ))
 }
 
 fn parse_service_enum_browse_response(input: &[u8]) -> IResult<&[u8], ServiceEnum> {
-    Ok((input, ServiceEnum::BrowseResponse {}))
+    let (input, response_header) = parse_response_header(input)?;
+    let (input, results) = parse_opcua_array(input, parse_browse_result)?;
+    let (input, _diagnostic_infos) = parse_opcua_array(input, parse_diagnostic_info)?;
+    Ok((
+        input,
+        ServiceEnum::BrowseResponse {
+            response_header,
+            results,
+        },
+    ))
 }
 
 fn parse_service_enum_browse_next_request(input: &[u8]) -> IResult<&[u8], ServiceEnum> {
@@ -992,11 +2297,32 @@ fn parse_service_enum_query_next_response(input: &[u8]) -> IResult<&[u8], Servic
 }
 
 fn parse_service_enum_read_request(input: &[u8]) -> IResult<&[u8], ServiceEnum> {
-    Ok((input, ServiceEnum::ReadRequest {}))
+    let (input, request_header) = parse_request_header(input)?;
+    let (input, max_age) = le_f64(input)?;
+    let (input, timestamps_to_return) = le_u32(input)?;
+    let (input, nodes_to_read) = parse_opcua_array(input, parse_read_value_id)?;
+    Ok((
+        input,
+        ServiceEnum::ReadRequest {
+            request_header,
+            max_age,
+            timestamps_to_return,
+            nodes_to_read,
+        },
+    ))
 }
 
 fn parse_service_enum_read_response(input: &[u8]) -> IResult<&[u8], ServiceEnum> {
-    Ok((input, ServiceEnum::ReadResponse {}))
+    let (input, response_header) = parse_response_header(input)?;
+    let (input, results) = parse_opcua_array(input, parse_data_value)?;
+    let (input, _diagnostic_infos) = parse_opcua_array(input, parse_diagnostic_info)?;
+    Ok((
+        input,
+        ServiceEnum::ReadResponse {
+            response_header,
+            results,
+        },
+    ))
 }
 
 fn parse_service_enum_history_read_request(input: &[u8]) -> IResult<&[u8], ServiceEnum> {
@@ -1008,11 +2334,28 @@ fn parse_service_enum_history_read_response(input: &[u8]) -> IResult<&[u8], Serv
 }
 
 fn parse_service_enum_write_request(input: &[u8]) -> IResult<&[u8], ServiceEnum> {
-    Ok((input, ServiceEnum::WriteRequest {}))
+    let (input, request_header) = parse_request_header(input)?;
+    let (input, nodes_to_write) = parse_opcua_array(input, parse_write_value)?;
+    Ok((
+        input,
+        ServiceEnum::WriteRequest {
+            request_header,
+            nodes_to_write,
+        },
+    ))
 }
 
 fn parse_service_enum_write_response(input: &[u8]) -> IResult<&[u8], ServiceEnum> {
-    Ok((input, ServiceEnum::WriteResponse {}))
+    let (input, response_header) = parse_response_header(input)?;
+    let (input, results) = parse_opcua_array(input, le_u32)?;
+    let (input, _diagnostic_infos) = parse_opcua_array(input, parse_diagnostic_info)?;
+    Ok((
+        input,
+        ServiceEnum::WriteResponse {
+            response_header,
+            results,
+        },
+    ))
 }
 
 fn parse_service_enum_history_update_request(input: &[u8]) -> IResult<&[u8], ServiceEnum> {
@@ -1024,11 +2367,28 @@ fn parse_service_enum_history_update_response(input: &[u8]) -> IResult<&[u8], Se
 }
 
 fn parse_service_enum_call_request(input: &[u8]) -> IResult<&[u8], ServiceEnum> {
-    Ok((input, ServiceEnum::CallRequest {}))
+    let (input, request_header) = parse_request_header(input)?;
+    let (input, methods_to_call) = parse_opcua_array(input, parse_call_method_request)?;
+    Ok((
+        input,
+        ServiceEnum::CallRequest {
+            request_header,
+            methods_to_call,
+        },
+    ))
 }
 
 fn parse_service_enum_call_response(input: &[u8]) -> IResult<&[u8], ServiceEnum> {
-    Ok((input, ServiceEnum::CallResponse {}))
+    let (input, response_header) = parse_response_header(input)?;
+    let (input, results) = parse_opcua_array(input, parse_call_method_result)?;
+    let (input, _diagnostic_infos) = parse_opcua_array(input, parse_diagnostic_info)?;
+    Ok((
+        input,
+        ServiceEnum::CallResponse {
+            response_header,
+            results,
+        },
+    ))
 }
 
 fn parse_service_enum_create_monitored_items_request(input: &[u8]) -> IResult<&[u8], ServiceEnum> {
@@ -1292,6 +2652,21 @@ pub fn parse_service_nodeid_info(
     Ok((input, service_nodeid_info))
 }
 
+impl<'a> MsgVariantInfo<'a> {
+    pub fn service(&self) -> Option<&ServiceEnum<'a>> {
+        match self {
+            MsgVariantInfo::Service {
+                service_nodeid_info:
+                    ServiceNodeidInfo::TB { service_enum, .. }
+                    | ServiceNodeidInfo::FB { service_enum, .. }
+                    | ServiceNodeidInfo::Numeric { service_enum, .. },
+                ..
+            } => Some(service_enum),
+            _ => None,
+        }
+    }
+}
+
 pub fn parse_msg_variant_info(input: &[u8], chunk_type: u8) -> IResult<&[u8], MsgVariantInfo> {
     let (input, msg_variant_info) = match chunk_type {
         0x41 => {
@@ -1343,6 +2718,18 @@ pub fn parse_msg_variant_info(input: &[u8], chunk_type: u8) -> IResult<&[u8], Ms
     Ok((input, msg_variant_info))
 }
 
+/// 中间块不解码；最终块解码失败时(通常为加密正文)保留原始数据，不视为解析错误
+fn parse_msg_body(input: &[u8], chunk_type: u8) -> IResult<&[u8], MsgVariantInfo> {
+    let undecoded = (&input[input.len()..], MsgVariantInfo::Undecoded { data: input });
+    match chunk_type {
+        0x43 => Ok(undecoded),
+        _ => match parse_msg_variant_info(input, chunk_type) {
+            Err(nom::Err::Error(_)) => Ok(undecoded),
+            o => o,
+        },
+    }
+}
+
 pub fn parse_message_type_enum(input: &[u8], message_type: u32) -> IResult<&[u8], MessageTypeEnum> {
     let (input, message_type_enum) = match message_type {
         0x48454c => {
@@ -1468,7 +2855,8 @@ pub fn parse_message_type_enum(input: &[u8], message_type: u32) -> IResult<&[u8]
             let (input, security_token_id) = le_u32(input)?;
             let (input, security_sequence_number) = le_u32(input)?;
             let (input, security_request_id) = le_u32(input)?;
-            let (input, msg_variant_info) = parse_msg_variant_info(input, chunk_type)?;
+            let body = input;
+            let (input, msg_variant_info) = parse_msg_body(input, chunk_type)?;
             Ok((
                 input,
                 MessageTypeEnum::Message {
@@ -1478,12 +2866,80 @@ pub fn parse_message_type_enum(input: &[u8], message_type: u32) -> IResult<&[u8]
                     security_token_id,
                     security_sequence_number,
                     security_request_id,
+                    body,
+                    msg_variant_info,
+                },
+            ))
+        }
+        0x4f504e => {
+            let (input, chunk_type) = u8(input)?;
+            let (input, transport_size) = le_u32(input)?;
+            let (input, secure_channel_id) = le_u32(input)?;
+            let (input, security_policy_uri) = parse_opcua_str(input)?;
+            let (input, sender_certificate) = parse_opcua_byte_string(input)?;
+            let (input, receiver_certificate_thumbprint) = parse_opcua_byte_string(input)?;
+            let (input, security_sequence_number, security_request_id, body, msg_variant_info) =
+                match security_policy_uri {
+                    SECURITY_POLICY_NONE => {
+                        let (input, security_sequence_number) = le_u32(input)?;
+                        let (input, security_request_id) = le_u32(input)?;
+                        let body = input;
+                        let (input, msg_variant_info) = parse_msg_body(input, chunk_type)?;
+                        (
+                            input,
+                            Some(security_sequence_number),
+                            Some(security_request_id),
+                            body,
+                            msg_variant_info,
+                        )
+                    }
+                    _ => (
+                        &input[input.len()..],
+                        None,
+                        None,
+                        input,
+                        MsgVariantInfo::Undecoded { data: input },
+                    ),
+                };
+            Ok((
+                input,
+                MessageTypeEnum::OpenSecureChannel {
+                    chunk_type,
+                    transport_size,
+                    secure_channel_id,
+                    security_policy_uri,
+                    sender_certificate,
+                    receiver_certificate_thumbprint,
+                    security_sequence_number,
+                    security_request_id,
+                    body,
+                    msg_variant_info,
+                },
+            ))
+        }
+        0x434c4f => {
+            let (input, chunk_type) = u8(input)?;
+            let (input, transport_size) = le_u32(input)?;
+            let (input, secure_channel_id) = le_u32(input)?;
+            let (input, security_token_id) = le_u32(input)?;
+            let (input, security_sequence_number) = le_u32(input)?;
+            let (input, security_request_id) = le_u32(input)?;
+            let body = input;
+            let (input, msg_variant_info) = parse_msg_body(input, chunk_type)?;
+            Ok((
+                input,
+                MessageTypeEnum::CloseSecureChannel {
+                    chunk_type,
+                    transport_size,
+                    secure_channel_id,
+                    security_token_id,
+                    security_sequence_number,
+                    security_request_id,
+                    body,
                     msg_variant_info,
                 },
             ))
         }
-        0x4f504e => Ok((input, MessageTypeEnum::OpenSecureChannel {})),
-        0x434c4f => Ok((input, MessageTypeEnum::CloseSecureChannel {})),
         _ => Err(nom::Err::Error(nom::error::Error::new(
             input,
             nom::error::ErrorKind::Verify,
@@ -0,0 +1,472 @@
+use nom::bytes::complete::take;
+use nom::combinator::rest;
+use nom::number::complete::{be_u16, be_u32, u16 as endian_u16, u32 as endian_u32, u8};
+use nom::number::Endianness;
+use nom::IResult;
+
+use crate::errors::ParseError;
+use crate::field_type::*;
+use crate::layer::{ApplicationLayer, LinkLayer, NetworkLayer, TransportLayer};
+use crate::packet::{L4Packet, L5Packet, QuinPacket, QuinPacketOptions};
+use crate::protocol::ApplicationProtocol;
+use crate::ProtocolType;
+
+use super::parse_l5_eof_layer;
+
+// PNIO-CM (PROFINET IO Context Manager)，承载于 DCE/RPC 无连接协议 (UDP 34964)
+//
+//    +-------------------------+----------------------------------+---------------------+
+//    | DCE/RPC CL Header       | NDR Header                       | PNIO Blocks         |
+//    | 80 bytes                | (响应先有 4 字节 PNIO Status)     |                     |
+//    |                         | ArgsMaximum/ArgsLength/Array     |                     |
+//    +-------------------------+----------------------------------+---------------------+
+
+// DCE/RPC 报文类型
+pub const DCERPC_REQUEST: u8 = 0x00;
+pub const DCERPC_RESPONSE: u8 = 0x02;
+pub const DCERPC_FAULT: u8 = 0x03;
+
+// PNIO 接口操作号
+pub const PNIO_OPNUM_CONNECT: u16 = 0;
+pub const PNIO_OPNUM_RELEASE: u16 = 1;
+pub const PNIO_OPNUM_READ: u16 = 2;
+pub const PNIO_OPNUM_WRITE: u16 = 3;
+pub const PNIO_OPNUM_CONTROL: u16 = 4;
+pub const PNIO_OPNUM_READ_IMPLICIT: u16 = 5;
+
+// PNIO 块类型
+pub const PNIO_BLOCK_IOD_WRITE_REQ: u16 = 0x0008;
+pub const PNIO_BLOCK_IOD_READ_REQ: u16 = 0x0009;
+pub const PNIO_BLOCK_IOD_WRITE_RES: u16 = 0x8008;
+pub const PNIO_BLOCK_IOD_READ_RES: u16 = 0x8009;
+pub const PNIO_BLOCK_AR_REQ: u16 = 0x0101;
+pub const PNIO_BLOCK_AR_RES: u16 = 0x8101;
+
+// 多记录写入的索引
+pub const PNIO_INDEX_WRITE_MULTIPLE: u16 = 0xe040;
+
+/// DceRpcHeader是 DCE/RPC 无连接协议头，UUID 保持报文中的原始字节
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DceRpcHeader {
+    pub rpc_version: u8,
+    pub packet_type: u8,
+    pub flags1: u8,
+    pub flags2: u8,
+    pub data_representation: [u8; 3],
+    pub object_uuid: [u8; 16],
+    pub interface_uuid: [u8; 16],
+    pub activity_uuid: [u8; 16],
+    pub server_boot_time: u32,
+    pub interface_version: u32,
+    pub sequence_number: u32,
+    pub opnum: u16,
+    pub fragment_length: u16,
+    pub fragment_number: u16,
+}
+
+impl DceRpcHeader {
+    /// 数据表示格式第 1 字节高 4 位为 1 时为小端
+    pub fn endianness(&self) -> Endianness {
+        if self.data_representation[0] & 0x10 != 0 {
+            Endianness::Little
+        } else {
+            Endianness::Big
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PnioCmHeader<'a> {
+    pub rpc: DceRpcHeader,
+    /// 非 PNIO 接口(如端点映射)或无法解析的报文体为 None
+    pub pnio: Option<PnioCmData<'a>>,
+    pub body: &'a [u8],
+}
+
+impl<'a> PnioCmHeader<'a> {
+    pub fn is_request(&self) -> bool {
+        self.rpc.packet_type == DCERPC_REQUEST
+    }
+
+    pub fn blocks(&self) -> &[PnioBlock<'a>] {
+        match &self.pnio {
+            Some(pnio) => &pnio.blocks,
+            None => &[],
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PnioCmData<'a> {
+    /// 仅响应携带，4 字节依次为 ErrorCode/ErrorDecode/ErrorCode1/ErrorCode2
+    pub status: Option<u32>,
+    pub args_length: u32,
+    pub blocks: Vec<PnioBlock<'a>>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PnioBlock<'a> {
+    pub block_type: u16,
+    pub version_high: u8,
+    pub version_low: u8,
+    pub data: PnioBlockData<'a>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum PnioBlockData<'a> {
+    /// IODReadReq/IODWriteReq 及其响应头
+    Record {
+        sequence_number: u16,
+        ar_uuid: [u8; 16],
+        api: u32,
+        slot: u16,
+        subslot: u16,
+        index: u16,
+        record_data_length: u32,
+        /// 写请求与读响应的记录数据
+        record_data: &'a [u8],
+    },
+    Ar {
+        ar_type: u16,
+        ar_uuid: [u8; 16],
+        session_key: u16,
+        cm_initiator_mac: MacAddress,
+        /// 仅 ARBlockReq
+        station_name: Option<&'a str>,
+    },
+    /// IODControlReq/Res 等控制块
+    Control {
+        ar_uuid: [u8; 16],
+        session_key: u16,
+        control_command: u16,
+    },
+    Raw(&'a [u8]),
+}
+
+fn uuid(input: &[u8]) -> IResult<&[u8], [u8; 16]> {
+    let (input, value) = take(16usize)(input)?;
+    let mut uuid = [0u8; 16];
+    uuid.copy_from_slice(value);
+    Ok((input, uuid))
+}
+
+pub fn parse_dcerpc_header(input: &[u8]) -> IResult<&[u8], DceRpcHeader> {
+    let (input, rpc_version) = u8(input)?;
+    if rpc_version != 4 {
+        return Err(nom::Err::Error(nom::error::Error::new(
+            input,
+            nom::error::ErrorKind::Verify,
+        )));
+    }
+    let (input, packet_type) = u8(input)?;
+    let (input, flags1) = u8(input)?;
+    let (input, flags2) = u8(input)?;
+    let (input, data_representation) = slice_u8_3(input)?;
+    let endianness = if data_representation[0] & 0x10 != 0 {
+        Endianness::Little
+    } else {
+        Endianness::Big
+    };
+    let (input, _serial_high) = u8(input)?;
+    let (input, object_uuid) = uuid(input)?;
+    let (input, interface_uuid) = uuid(input)?;
+    let (input, activity_uuid) = uuid(input)?;
+    let (input, server_boot_time) = endian_u32(endianness)(input)?;
+    let (input, interface_version) = endian_u32(endianness)(input)?;
+    let (input, sequence_number) = endian_u32(endianness)(input)?;
+    let (input, opnum) = endian_u16(endianness)(input)?;
+    let (input, _interface_hint) = endian_u16(endianness)(input)?;
+    let (input, _activity_hint) = endian_u16(endianness)(input)?;
+    let (input, fragment_length) = endian_u16(endianness)(input)?;
+    let (input, fragment_number) = endian_u16(endianness)(input)?;
+    let (input, _auth_protocol) = u8(input)?;
+    let (input, _serial_low) = u8(input)?;
+    Ok((
+        input,
+        DceRpcHeader {
+            rpc_version,
+            packet_type,
+            flags1,
+            flags2,
+            data_representation,
+            object_uuid,
+            interface_uuid,
+            activity_uuid,
+            server_boot_time,
+            interface_version,
+            sequence_number,
+            opnum,
+            fragment_length,
+            fragment_number,
+        },
+    ))
+}
+
+// PNIO 块内容总是大端
+fn parse_record_block(input: &[u8]) -> IResult<&[u8], PnioBlockData> {
+    let (input, sequence_number) = be_u16(input)?;
+    let (input, ar_uuid) = uuid(input)?;
+    let (input, api) = be_u32(input)?;
+    let (input, slot) = be_u16(input)?;
+    let (input, subslot) = be_u16(input)?;
+    let (input, _padding) = take(2usize)(input)?;
+    let (input, index) = be_u16(input)?;
+    let (input, record_data_length) = be_u32(input)?;
+    // 头部剩余部分为目标 AR、填充或附加值
+    let (input, _) = rest(input)?;
+    Ok((
+        input,
+        PnioBlockData::Record {
+            sequence_number,
+            ar_uuid,
+            api,
+            slot,
+            subslot,
+            index,
+            record_data_length,
+            record_data: &[],
+        },
+    ))
+}
+
+fn parse_ar_block(input: &[u8], block_type: u16) -> IResult<&[u8], PnioBlockData> {
+    let (input, ar_type) = be_u16(input)?;
+    let (input, ar_uuid) = uuid(input)?;
+    let (input, session_key) = be_u16(input)?;
+    let (input, cm_initiator_mac) = mac_address(input)?;
+    if block_type != PNIO_BLOCK_AR_REQ {
+        return Ok((
+            input,
+            PnioBlockData::Ar {
+                ar_type,
+                ar_uuid,
+                session_key,
+                cm_initiator_mac,
+                station_name: None,
+            },
+        ));
+    }
+    let (input, _object_uuid) = uuid(input)?;
+    let (input, _properties) = be_u32(input)?;
+    let (input, _activity_timeout) = be_u16(input)?;
+    let (input, _udp_rt_port) = be_u16(input)?;
+    let (input, name_length) = be_u16(input)?;
+    let (input, name) = take(name_length as usize)(input)?;
+    let station_name = std::str::from_utf8(name).ok();
+    Ok((
+        input,
+        PnioBlockData::Ar {
+            ar_type,
+            ar_uuid,
+            session_key,
+            cm_initiator_mac,
+            station_name,
+        },
+    ))
+}
+
+fn parse_control_block(input: &[u8]) -> IResult<&[u8], PnioBlockData> {
+    let (input, _reserved) = take(2usize)(input)?;
+    let (input, ar_uuid) = uuid(input)?;
+    let (input, session_key) = be_u16(input)?;
+    let (input, _reserved) = take(2usize)(input)?;
+    let (input, control_command) = be_u16(input)?;
+    Ok((
+        input,
+        PnioBlockData::Control {
+            ar_uuid,
+            session_key,
+            control_command,
+        },
+    ))
+}
+
+fn parse_pnio_block(input: &[u8]) -> IResult<&[u8], PnioBlock> {
+    let (input, block_type) = be_u16(input)?;
+    let (input, block_length) = be_u16(input)?;
+    let (input, version_high) = u8(input)?;
+    let (input, version_low) = u8(input)?;
+    // block_length 包含版本号的 2 字节
+    let (input, block) = take((block_length as usize).saturating_sub(2))(input)?;
+    let result = match block_type {
+        PNIO_BLOCK_IOD_WRITE_REQ | PNIO_BLOCK_IOD_READ_REQ | PNIO_BLOCK_IOD_WRITE_RES
+        | PNIO_BLOCK_IOD_READ_RES => parse_record_block(block),
+        PNIO_BLOCK_AR_REQ | PNIO_BLOCK_AR_RES => parse_ar_block(block, block_type),
+        0x0110..=0x0118 | 0x8110..=0x8118 => parse_control_block(block),
+        _ => Ok((&block[block.len()..], PnioBlockData::Raw(block))),
+    };
+    let data = match result {
+        Ok((_, data)) => data,
+        Err(_) => PnioBlockData::Raw(block),
+    };
+    // 写请求、读响应的记录数据紧随 IOD 头块之后，不计入块长度
+    let (input, data) = match data {
+        PnioBlockData::Record {
+            sequence_number,
+            ar_uuid,
+            api,
+            slot,
+            subslot,
+            index,
+            record_data_length,
+            ..
+        } if matches!(block_type, PNIO_BLOCK_IOD_WRITE_REQ | PNIO_BLOCK_IOD_READ_RES) => {
+            let (input, record_data) = take((record_data_length as usize).min(input.len()))(input)?;
+            (
+                input,
+                PnioBlockData::Record {
+                    sequence_number,
+                    ar_uuid,
+                    api,
+                    slot,
+                    subslot,
+                    index,
+                    record_data_length,
+                    record_data,
+                },
+            )
+        }
+        data => (input, data),
+    };
+    Ok((
+        input,
+        PnioBlock {
+            block_type,
+            version_high,
+            version_low,
+            data,
+        },
+    ))
+}
+
+// 多记录写入中各 IODWriteReq 按 4 字节对齐
+fn parse_write_multiple(input: &[u8]) -> IResult<&[u8], Vec<PnioBlock>> {
+    let mut blocks = Vec::new();
+    let mut data = input;
+    while !data.is_empty() {
+        let (remain, block) = parse_pnio_block(data)?;
+        let consumed = data.len() - remain.len();
+        let (remain, _padding) = take(((4 - consumed % 4) % 4).min(remain.len()))(remain)?;
+        blocks.push(block);
+        data = remain;
+    }
+    Ok((data, blocks))
+}
+
+fn parse_pnio_cm_data(input: &[u8], is_response: bool, endianness: Endianness) -> IResult<&[u8], PnioCmData> {
+    let (input, status) = if is_response {
+        let (input, status) = be_u32(input)?;
+        (input, Some(status))
+    } else {
+        (input, None)
+    };
+    // 请求为 ArgsMaximum，响应为 ArgsLength
+    let (input, args_length) = endian_u32(endianness)(input)?;
+    let (input, args_length) = if is_response {
+        (input, args_length)
+    } else {
+        endian_u32(endianness)(input)?
+    };
+    let (input, _maximum_count) = endian_u32(endianness)(input)?;
+    let (input, _offset) = endian_u32(endianness)(input)?;
+    let (input, actual_count) = endian_u32(endianness)(input)?;
+    let (input, mut data) = take(actual_count as usize)(input)?;
+    let mut blocks = Vec::new();
+    while !data.is_empty() {
+        let (remain, block) = parse_pnio_block(data)?;
+        let multiple = match &block.data {
+            PnioBlockData::Record {
+                index: PNIO_INDEX_WRITE_MULTIPLE,
+                record_data,
+                ..
+            } if block.block_type == PNIO_BLOCK_IOD_WRITE_REQ => parse_write_multiple(record_data).ok(),
+            _ => None,
+        };
+        blocks.push(block);
+        // 多记录写入的各记录依次追加在外层块之后
+        if let Some((_, multiple)) = multiple {
+            blocks.extend(multiple);
+        }
+        data = remain;
+    }
+    Ok((
+        input,
+        PnioCmData {
+            status,
+            args_length,
+            blocks,
+        },
+    ))
+}
+
+pub fn parse_pnio_cm_header(input: &[u8]) -> IResult<&[u8], PnioCmHeader> {
+    let (input, rpc) = parse_dcerpc_header(input)?;
+    let (input, body) = take(rpc.fragment_length as usize)(input)?;
+    let pnio = match rpc.packet_type {
+        DCERPC_REQUEST | DCERPC_RESPONSE => {
+            parse_pnio_cm_data(body, rpc.packet_type == DCERPC_RESPONSE, rpc.endianness())
+                .ok()
+                .map(|(_, pnio)| pnio)
+        }
+        _ => None,
+    };
+    Ok((input, PnioCmHeader { rpc, pnio, body }))
+}
+
+pub fn parse_pnio_cm_layer<'a>(
+    input: &'a [u8],
+    link_layer: LinkLayer,
+    network_layer: NetworkLayer<'a>,
+    transport_layer: TransportLayer<'a>,
+    options: &QuinPacketOptions,
+) -> QuinPacket<'a> {
+    let current_prototype = ProtocolType::Application(ApplicationProtocol::PnioCm);
+
+    let (input, pnio_cm_header) = match parse_pnio_cm_header(input) {
+        Ok(o) => o,
+        Err(e) => {
+            tracing::error!(
+                target: "PARSER(pnio_cm::parse_pnio_cm_layer)",
+                error = ?e
+            );
+
+            let offset = match e {
+                nom::Err::Error(error) => input.len() - error.input.len(),
+                _ => usize::MAX
+            };
+
+            return QuinPacket::L4(L4Packet {
+                link_layer,
+                network_layer,
+                transport_layer,
+                error: Some(ParseError::ParsingHeader{
+                    protocol: current_prototype,
+                    offset
+                }),
+                remain: input,
+            })
+        }
+    };
+
+    if Some(current_prototype) == options.stop {
+        let application_layer = ApplicationLayer::PnioCm(pnio_cm_header);
+        return QuinPacket::L5(L5Packet {
+            link_layer,
+            network_layer,
+            transport_layer,
+            application_layer,
+            error: None,
+            remain: input,
+        });
+    };
+
+    let application_layer = ApplicationLayer::PnioCm(pnio_cm_header);
+    parse_l5_eof_layer(
+        input,
+        link_layer,
+        network_layer,
+        transport_layer,
+        application_layer,
+        options,
+    )
+}
@@ -0,0 +1,461 @@
+use nom::bytes::complete::take;
+use nom::combinator::rest;
+use nom::number::complete::{be_u16, be_u32, u8};
+use nom::IResult;
+
+use std::net::Ipv4Addr;
+
+use crate::errors::ParseError;
+use crate::field_type::*;
+use crate::layer::{ApplicationLayer, LinkLayer, NetworkLayer, TransportLayer};
+use crate::packet::{L4Packet, L5Packet, QuinPacket, QuinPacketOptions};
+use crate::protocol::ApplicationProtocol;
+use crate::ProtocolType;
+
+use super::parse_l5_eof_layer;
+
+// PROFINET RT (EtherType 0x8892)
+//
+//    +----------+----------------------------------------------+
+//    | FrameID  | 数据                                         |
+//    | 2 bytes  | 周期数据 + CycleCounter/DataStatus/TransferStatus |
+//    |          | 或 Alarm、DCP 报文                           |
+//    +----------+----------------------------------------------+
+
+// FrameID
+pub const PN_FRAME_ID_ALARM_HIGH: u16 = 0xfc01;
+pub const PN_FRAME_ID_ALARM_LOW: u16 = 0xfe01;
+pub const PN_FRAME_ID_DCP_HELLO: u16 = 0xfefc;
+pub const PN_FRAME_ID_DCP_GET_SET: u16 = 0xfefd;
+pub const PN_FRAME_ID_DCP_IDENTIFY_REQUEST: u16 = 0xfefe;
+pub const PN_FRAME_ID_DCP_IDENTIFY_RESPONSE: u16 = 0xfeff;
+
+// DCP ServiceID
+pub const DCP_SERVICE_GET: u8 = 0x03;
+pub const DCP_SERVICE_SET: u8 = 0x04;
+pub const DCP_SERVICE_IDENTIFY: u8 = 0x05;
+pub const DCP_SERVICE_HELLO: u8 = 0x06;
+
+// DCP Option
+pub const DCP_OPTION_IP: u8 = 0x01;
+pub const DCP_OPTION_DEVICE_PROPERTIES: u8 = 0x02;
+pub const DCP_OPTION_DHCP: u8 = 0x03;
+pub const DCP_OPTION_CONTROL: u8 = 0x05;
+pub const DCP_OPTION_DEVICE_INITIATIVE: u8 = 0x06;
+pub const DCP_OPTION_ALL_SELECTOR: u8 = 0xff;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ProfinetHeader<'a> {
+    pub frame_id: u16,
+    pub pdu: ProfinetPdu<'a>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ProfinetPdu<'a> {
+    /// RT Class 1/2/3 周期数据
+    Cyclic {
+        data: &'a [u8],
+        cycle_counter: u16,
+        data_status: u8,
+        transfer_status: u8,
+    },
+    Alarm(ProfinetAlarm<'a>),
+    Dcp(DcpHeader<'a>),
+    // 保留或未支持的 FrameID
+    Raw(&'a [u8]),
+}
+
+impl<'a> ProfinetPdu<'a> {
+    pub fn dcp(&self) -> Option<&DcpHeader<'a>> {
+        match self {
+            ProfinetPdu::Dcp(dcp) => Some(dcp),
+            _ => None,
+        }
+    }
+}
+
+/// ProfinetAlarm是 RTA(Acyclic Real-Time) 报文，DATA 报文携带报警通知块
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ProfinetAlarm<'a> {
+    pub dst_endpoint: u16,
+    pub src_endpoint: u16,
+    // 低 4 位: 1 DATA, 2 NACK, 3 ACK, 4 ERR
+    pub pdu_type: u8,
+    pub add_flags: u8,
+    pub send_seq_num: u16,
+    pub ack_seq_num: u16,
+    pub notification: Option<AlarmNotification<'a>>,
+    pub var_part: &'a [u8],
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct AlarmNotification<'a> {
+    pub block_type: u16,
+    pub alarm_type: u16,
+    pub api: u32,
+    pub slot: u16,
+    pub subslot: u16,
+    pub module_ident: u32,
+    pub submodule_ident: u32,
+    pub alarm_specifier: u16,
+    pub data: &'a [u8],
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DcpHeader<'a> {
+    pub service_id: u8,
+    // 0: 请求, 1: 响应成功, 5: 不支持
+    pub service_type: u8,
+    pub xid: u32,
+    /// Identify 请求中为响应延迟，其余为保留
+    pub response_delay: u16,
+    pub data_length: u16,
+    pub blocks: Vec<DcpBlock<'a>>,
+}
+
+impl<'a> DcpHeader<'a> {
+    pub fn is_request(&self) -> bool {
+        self.service_type == 0
+    }
+
+    pub fn name_of_station(&self) -> Option<&'a str> {
+        self.blocks.iter().find_map(|block| match block.value {
+            DcpBlockValue::NameOfStation(name) => Some(name),
+            _ => None,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DcpBlock<'a> {
+    pub option: u8,
+    pub suboption: u8,
+    /// Set 请求中为 BlockQualifier(1 为永久保存)，响应中为 BlockInfo
+    pub qualifier: Option<u16>,
+    pub value: DcpBlockValue<'a>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum DcpBlockValue<'a> {
+    MacAddress(MacAddress),
+    IpParameter {
+        address: Ipv4Addr,
+        subnet_mask: Ipv4Addr,
+        gateway: Ipv4Addr,
+    },
+    TypeOfStation(&'a str),
+    NameOfStation(&'a str),
+    DeviceId {
+        vendor_id: u16,
+        device_id: u16,
+    },
+    DeviceRole(u8),
+    AliasName(&'a str),
+    /// Control 响应: 被响应的 Option/SubOption 及错误码
+    ControlResponse {
+        option: u8,
+        suboption: u8,
+        error: u8,
+    },
+    /// Get 请求只列出 Option/SubOption
+    Empty,
+    Raw(&'a [u8]),
+}
+
+fn parse_station_string(input: &[u8]) -> IResult<&[u8], &str> {
+    match std::str::from_utf8(input) {
+        Ok(value) => Ok((&input[input.len()..], value)),
+        Err(_) => Err(nom::Err::Error(nom::error::Error::new(
+            input,
+            nom::error::ErrorKind::Verify,
+        ))),
+    }
+}
+
+fn parse_dcp_block_value(option: u8, suboption: u8, input: &[u8]) -> IResult<&[u8], DcpBlockValue> {
+    match (option, suboption) {
+        (DCP_OPTION_IP, 0x01) => {
+            let (input, mac) = mac_address(input)?;
+            Ok((input, DcpBlockValue::MacAddress(mac)))
+        }
+        (DCP_OPTION_IP, 0x02) | (DCP_OPTION_IP, 0x03) => {
+            let (input, address) = address4(input)?;
+            let (input, subnet_mask) = address4(input)?;
+            let (input, gateway) = address4(input)?;
+            // Full IP Suite 之后为 DNS 服务器地址
+            let (input, _) = rest(input)?;
+            Ok((
+                input,
+                DcpBlockValue::IpParameter {
+                    address,
+                    subnet_mask,
+                    gateway,
+                },
+            ))
+        }
+        (DCP_OPTION_DEVICE_PROPERTIES, 0x01) => {
+            let (input, value) = parse_station_string(input)?;
+            Ok((input, DcpBlockValue::TypeOfStation(value)))
+        }
+        (DCP_OPTION_DEVICE_PROPERTIES, 0x02) => {
+            let (input, value) = parse_station_string(input)?;
+            Ok((input, DcpBlockValue::NameOfStation(value)))
+        }
+        (DCP_OPTION_DEVICE_PROPERTIES, 0x03) => {
+            let (input, vendor_id) = be_u16(input)?;
+            let (input, device_id) = be_u16(input)?;
+            Ok((input, DcpBlockValue::DeviceId { vendor_id, device_id }))
+        }
+        (DCP_OPTION_DEVICE_PROPERTIES, 0x04) => {
+            let (input, role) = u8(input)?;
+            let (input, _reserved) = rest(input)?;
+            Ok((input, DcpBlockValue::DeviceRole(role)))
+        }
+        (DCP_OPTION_DEVICE_PROPERTIES, 0x06) => {
+            let (input, value) = parse_station_string(input)?;
+            Ok((input, DcpBlockValue::AliasName(value)))
+        }
+        (DCP_OPTION_CONTROL, 0x04) => {
+            let (input, option) = u8(input)?;
+            let (input, suboption) = u8(input)?;
+            let (input, error) = u8(input)?;
+            Ok((input, DcpBlockValue::ControlResponse { option, suboption, error }))
+        }
+        _ => {
+            let (input, value) = rest(input)?;
+            Ok((input, DcpBlockValue::Raw(value)))
+        }
+    }
+}
+
+fn parse_dcp_block(input: &[u8], service_id: u8, service_type: u8) -> IResult<&[u8], DcpBlock> {
+    let (input, option) = u8(input)?;
+    let (input, suboption) = u8(input)?;
+    // Get 请求的块没有长度
+    if service_id == DCP_SERVICE_GET && service_type == 0 {
+        return Ok((
+            input,
+            DcpBlock {
+                option,
+                suboption,
+                qualifier: None,
+                value: DcpBlockValue::Empty,
+            },
+        ));
+    }
+    let (input, block_length) = be_u16(input)?;
+    let (input, block) = take(block_length as usize)(input)?;
+    // 块按 2 字节对齐
+    let (input, _) = take((block_length % 2) as usize)(input)?;
+
+    // Set 请求带 BlockQualifier；响应带 BlockInfo，Control 响应块除外
+    let has_qualifier = (service_id == DCP_SERVICE_SET && service_type == 0)
+        || (service_type != 0 && option != DCP_OPTION_CONTROL);
+    let (block, qualifier) = if has_qualifier && block.len() >= 2 {
+        let (block, qualifier) = be_u16(block)?;
+        (block, Some(qualifier))
+    } else {
+        (block, None)
+    };
+    let value = match parse_dcp_block_value(option, suboption, block) {
+        Ok((_, value)) => value,
+        Err(_) => DcpBlockValue::Raw(block),
+    };
+    Ok((
+        input,
+        DcpBlock {
+            option,
+            suboption,
+            qualifier,
+            value,
+        },
+    ))
+}
+
+pub fn parse_dcp_header(input: &[u8]) -> IResult<&[u8], DcpHeader> {
+    let (input, service_id) = u8(input)?;
+    let (input, service_type) = u8(input)?;
+    let (input, xid) = be_u32(input)?;
+    let (input, response_delay) = be_u16(input)?;
+    let (input, data_length) = be_u16(input)?;
+    let (input, mut data) = take(data_length as usize)(input)?;
+    let mut blocks = Vec::new();
+    while !data.is_empty() {
+        let (remain, block) = parse_dcp_block(data, service_id, service_type)?;
+        blocks.push(block);
+        data = remain;
+    }
+    // 以太网最小帧填充
+    let (input, _padding) = rest(input)?;
+    Ok((
+        input,
+        DcpHeader {
+            service_id,
+            service_type,
+            xid,
+            response_delay,
+            data_length,
+            blocks,
+        },
+    ))
+}
+
+fn parse_alarm_notification(input: &[u8]) -> IResult<&[u8], AlarmNotification> {
+    let (input, block_type) = be_u16(input)?;
+    let (input, block_length) = be_u16(input)?;
+    // block_length 包含 2 字节的版本号
+    let (input, block) = take(block_length as usize)(input)?;
+    let (block, _version) = take(2usize)(block)?;
+    let (block, alarm_type) = be_u16(block)?;
+    let (block, api) = be_u32(block)?;
+    let (block, slot) = be_u16(block)?;
+    let (block, subslot) = be_u16(block)?;
+    let (block, module_ident) = be_u32(block)?;
+    let (block, submodule_ident) = be_u32(block)?;
+    let (block, alarm_specifier) = be_u16(block)?;
+    Ok((
+        input,
+        AlarmNotification {
+            block_type,
+            alarm_type,
+            api,
+            slot,
+            subslot,
+            module_ident,
+            submodule_ident,
+            alarm_specifier,
+            data: block,
+        },
+    ))
+}
+
+fn parse_alarm(input: &[u8]) -> IResult<&[u8], ProfinetAlarm> {
+    let (input, dst_endpoint) = be_u16(input)?;
+    let (input, src_endpoint) = be_u16(input)?;
+    let (input, pdu_type) = u8(input)?;
+    let (input, add_flags) = u8(input)?;
+    let (input, send_seq_num) = be_u16(input)?;
+    let (input, ack_seq_num) = be_u16(input)?;
+    let (input, var_part_length) = be_u16(input)?;
+    let (input, var_part) = take(var_part_length as usize)(input)?;
+    let (input, _padding) = rest(input)?;
+    // DATA 报文的报警通知块 (0x0001 高优先级, 0x0002 低优先级)
+    let notification = match (pdu_type & 0x0f, parse_alarm_notification(var_part)) {
+        (1, Ok((_, notification))) if notification.block_type <= 0x0002 => Some(notification),
+        _ => None,
+    };
+    Ok((
+        input,
+        ProfinetAlarm {
+            dst_endpoint,
+            src_endpoint,
+            pdu_type,
+            add_flags,
+            send_seq_num,
+            ack_seq_num,
+            notification,
+            var_part,
+        },
+    ))
+}
+
+fn parse_cyclic(input: &[u8]) -> IResult<&[u8], ProfinetPdu> {
+    // APDU Status 位于帧末尾
+    if input.len() < 4 {
+        return Err(nom::Err::Error(nom::error::Error::new(
+            input,
+            nom::error::ErrorKind::Eof,
+        )));
+    }
+    let (status, data) = (&input[input.len() - 4..], &input[..input.len() - 4]);
+    let (status, cycle_counter) = be_u16(status)?;
+    let (status, data_status) = u8(status)?;
+    let (status, transfer_status) = u8(status)?;
+    Ok((
+        status,
+        ProfinetPdu::Cyclic {
+            data,
+            cycle_counter,
+            data_status,
+            transfer_status,
+        },
+    ))
+}
+
+pub fn parse_profinet_header(input: &[u8]) -> IResult<&[u8], ProfinetHeader> {
+    let (input, frame_id) = be_u16(input)?;
+    let (input, pdu) = match frame_id {
+        0x0100..=0xfbff => parse_cyclic(input)?,
+        PN_FRAME_ID_ALARM_HIGH | PN_FRAME_ID_ALARM_LOW => {
+            let (input, alarm) = parse_alarm(input)?;
+            (input, ProfinetPdu::Alarm(alarm))
+        }
+        PN_FRAME_ID_DCP_HELLO..=PN_FRAME_ID_DCP_IDENTIFY_RESPONSE => {
+            let (input, dcp) = parse_dcp_header(input)?;
+            (input, ProfinetPdu::Dcp(dcp))
+        }
+        _ => {
+            let (input, data) = rest(input)?;
+            (input, ProfinetPdu::Raw(data))
+        }
+    };
+    Ok((input, ProfinetHeader { frame_id, pdu }))
+}
+
+pub fn parse_profinet_layer<'a>(
+    input: &'a [u8],
+    link_layer: LinkLayer,
+    network_layer: NetworkLayer<'a>,
+    transport_layer: TransportLayer<'a>,
+    options: &QuinPacketOptions,
+) -> QuinPacket<'a> {
+    let current_prototype = ProtocolType::Application(ApplicationProtocol::Profinet);
+
+    let (input, profinet_header) = match parse_profinet_header(input) {
+        Ok(o) => o,
+        Err(e) => {
+            tracing::error!(
+                target: "PARSER(profinet::parse_profinet_layer)",
+                error = ?e
+            );
+
+            let offset = match e {
+                nom::Err::Error(error) => input.len() - error.input.len(),
+                _ => usize::MAX
+            };
+
+            return QuinPacket::L4(L4Packet {
+                link_layer,
+                network_layer,
+                transport_layer,
+                error: Some(ParseError::ParsingHeader{
+                    protocol: current_prototype,
+                    offset
+                }),
+                remain: input,
+            })
+        }
+    };
+
+    if Some(current_prototype) == options.stop {
+        let application_layer = ApplicationLayer::Profinet(profinet_header);
+        return QuinPacket::L5(L5Packet {
+            link_layer,
+            network_layer,
+            transport_layer,
+            application_layer,
+            error: None,
+            remain: input,
+        });
+    };
+
+    let application_layer = ApplicationLayer::Profinet(profinet_header);
+    parse_l5_eof_layer(
+        input,
+        link_layer,
+        network_layer,
+        transport_layer,
+        application_layer,
+        options,
+    )
+}
@@ -41,7 +41,7 @@ use std::ops::BitXor;
 
 use super::parse_l5_eof_layer;
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct S7commHeader<'a> {
     pub header: Header,
     pub parameter: Parameter<'a>,
@@ -170,12 +170,13 @@ pub struct ParamItem {
     pub syntax_id_enum: SyntaxIdEnum,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct RspReadData<'a> {
     pub return_code: u8,
     pub transport_size: u8,
     pub length: u16,
     pub data: &'a [u8],
+    pub value: S7Value<'a>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -183,7 +184,57 @@ pub struct RspWriteData {
     pub return_code: u8,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+/// S7Value是按 transport size 解码后的数据项取值。
+#[derive(Debug, PartialEq, Clone)]
+pub enum S7Value<'a> {
+    Null,
+    Bit(bool),
+    Byte(u8),
+    Char(u8),
+    Word(u16),
+    Int(i16),
+    DWord(u32),
+    DInt(i32),
+    Real(f32),
+    String(&'a [u8]),
+    Array(Vec<S7Value<'a>>),
+    Raw(&'a [u8]),
+}
+
+impl<'a> S7Value<'a> {
+    /// 数值类型转换为 f64 便于规则比较，数组要求每个元素都为数值。
+    pub fn numeric_values(&self) -> Option<Vec<f64>> {
+        match self {
+            S7Value::Bit(v) => Some(vec![*v as u8 as f64]),
+            S7Value::Byte(v) | S7Value::Char(v) => Some(vec![*v as f64]),
+            S7Value::Word(v) => Some(vec![*v as f64]),
+            S7Value::Int(v) => Some(vec![*v as f64]),
+            S7Value::DWord(v) => Some(vec![*v as f64]),
+            S7Value::DInt(v) => Some(vec![*v as f64]),
+            S7Value::Real(v) => Some(vec![*v as f64]),
+            S7Value::Array(values) => {
+                let mut out = Vec::with_capacity(values.len());
+                for value in values {
+                    out.extend(value.numeric_values()?);
+                }
+                Some(out)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// S7VarValue将数据项取值与其 S7any 地址对应起来。
+#[derive(Debug, PartialEq, Clone)]
+pub struct S7VarValue<'a> {
+    pub area: u8,
+    pub db_number: u16,
+    pub byte_address: u32,
+    pub bit_address: u8,
+    pub value: S7Value<'a>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
 pub enum JobParam<'a> {
     SetupCommunication {
         reserved: u8,
@@ -199,6 +250,7 @@ pub enum JobParam<'a> {
         item_count: u8,
         items: Vec<ParamItem>,
         standard_items: Vec<RspReadData<'a>>,
+        values: Vec<S7VarValue<'a>>,
     },
     RequestDownload {
         function_status: u8,
@@ -246,7 +298,7 @@ pub enum JobParam<'a> {
     },
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum AckdataParam<'a> {
     SetupCommunication {
         reserved: u8,
@@ -295,7 +347,146 @@ pub enum UserdataParamInfo {
     EmptyInfo {},
 }
 
+/// S7时间戳，各字段为 BCD 解码后的数值。
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct S7Timestamp {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+    pub millisecond: u16,
+    pub weekday: u8,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct BlockCountItem {
+    pub block_type: u8,
+    pub block_count: u16,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct BlockListItem {
+    pub block_number: u16,
+    pub block_flags: u8,
+    pub block_language: u8,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SzlRecord<'a> {
+    pub data: &'a [u8],
+}
+
+/// UserdataData是按 function group 与 subfunction 解码后的 Userdata 数据部分。
+#[derive(Debug, PartialEq, Clone)]
+pub enum UserdataData<'a> {
+    // function group 0x2: Cyclic data
+    CyclicRequest {
+        item_count: u8,
+        interval_timebase: Option<u8>,
+        interval_time: Option<u8>,
+        items: Vec<ParamItem>,
+    },
+    CyclicResponse {
+        item_count: u8,
+        items: Vec<RspReadData<'a>>,
+    },
+    // function group 0x3: Block functions
+    ListBlocksRequest {},
+    ListBlocksResponse {
+        items: Vec<BlockCountItem>,
+    },
+    ListBlocksOfTypeRequest {
+        block_type: u8,
+    },
+    ListBlocksOfTypeResponse {
+        items: Vec<BlockListItem>,
+    },
+    BlockInfoRequest {
+        block_type: u8,
+        block_number: &'a str,
+        filesystem: u8,
+    },
+    BlockInfoResponse {
+        block_type: u8,
+        block_flags: u8,
+        block_language: u8,
+        subblock_type: u8,
+        block_number: u16,
+        load_memory_length: u32,
+        mc7_code_length: u16,
+        author: &'a [u8],
+        family: &'a [u8],
+        name: &'a [u8],
+        version: u8,
+        checksum: u16,
+    },
+    // function group 0x4: CPU functions
+    ReadSzlRequest {
+        szl_id: u16,
+        szl_index: u16,
+    },
+    ReadSzlResponse {
+        szl_id: u16,
+        szl_index: u16,
+        record_length: u16,
+        record_count: u16,
+        records: Vec<SzlRecord<'a>>,
+    },
+    // function group 0x5: Security
+    PlcPassword {
+        password: &'a [u8],
+    },
+    // function group 0x7: Time functions
+    Clock {
+        timestamp: S7Timestamp,
+    },
+    Empty {},
+    Unknown {
+        data: &'a [u8],
+    },
+}
+
+impl<'a> UserdataData<'a> {
+    /// 解码 PLC password 报文中的口令，返回去除尾部空格后的明文字节。
+    pub fn decoded_password(&self) -> Option<Vec<u8>> {
+        match self {
+            UserdataData::PlcPassword { password } => {
+                let mut decoded = Vec::with_capacity(password.len());
+                for (i, byte) in password.iter().enumerate() {
+                    let mut value = byte ^ 0x55;
+                    if i > 1 {
+                        value ^= password[i - 2];
+                    }
+                    decoded.push(value);
+                }
+                while decoded.last() == Some(&0x20) {
+                    decoded.pop();
+                }
+                Some(decoded)
+            }
+            _ => None,
+        }
+    }
+
+    /// 从 SZL-ID 0x0011 (模块标识) 的应答中提取 PLC 订货号。
+    pub fn order_number(&self) -> Option<&'a str> {
+        match self {
+            UserdataData::ReadSzlResponse {
+                szl_id, records, ..
+            } if szl_id & 0x00ff == 0x11 => records
+                .iter()
+                .filter(|record| record.data.len() >= 22)
+                .find(|record| record.data[0..2] == [0x00, 0x01])
+                .and_then(|record| std::str::from_utf8(&record.data[2..22]).ok())
+                .map(|s| s.trim_end_matches([' ', '\0'])),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
 pub enum Parameter<'a> {
     Job {
         function_code: u8,
@@ -319,6 +510,7 @@ pub enum Parameter<'a> {
         data_transport_size: u8,
         data_length: u16,
         data: &'a [u8],
+        userdata_data: UserdataData<'a>,
     },
 }
 
@@ -471,13 +663,17 @@ pub fn parse_rsp_read_data(input: &[u8]) -> IResult<&[u8], RspReadData> {
     let (input, transport_size) = u8(input)?;
     let (input, length) = be_u16(input)?;
     let mut length_tmp = length;
-    if (length_tmp % 8) != 0 {
+    // REAL 与 OCTET STRING 的长度单位为字节，其余为 bit
+    if transport_size == 0x07 || transport_size == 0x09 {
+        // pass
+    } else if (length_tmp % 8) != 0 {
         length_tmp /= 8;
         length_tmp += 1;
     } else {
         length_tmp /= 8;
     }
     let (input, data) = take(length_tmp as usize)(input)?;
+    let value = decode_data_item_value(transport_size, data);
     let mut input = input;
     let mut _fill_byte: u8;
     if (length_tmp % 2 != 0) && (input.len()) != 0 {
@@ -490,10 +686,100 @@ pub fn parse_rsp_read_data(input: &[u8]) -> IResult<&[u8], RspReadData> {
             transport_size,
             length,
             data,
+            value,
         },
     ))
 }
 
+fn split_values<'a>(data: &'a [u8], size: usize, f: fn(&'a [u8]) -> S7Value<'a>) -> S7Value<'a> {
+    if data.len() == size {
+        f(data)
+    } else if !data.is_empty() && data.len() % size == 0 {
+        S7Value::Array(data.chunks(size).map(f).collect())
+    } else {
+        S7Value::Raw(data)
+    }
+}
+
+/// 仅依据数据项的 transport size 解码 (ReadVar 应答中无法得知请求的类型)。
+pub fn decode_data_item_value(transport_size: u8, data: &[u8]) -> S7Value {
+    match (transport_size, data.len()) {
+        (0x00, _) => S7Value::Null,
+        (0x03, 1) => S7Value::Bit(data[0] & 0x01 != 0),
+        (0x04, 1) => S7Value::Byte(data[0]),
+        (0x04, 2) => S7Value::Word(u16::from_be_bytes([data[0], data[1]])),
+        (0x04, 4) => S7Value::DWord(u32::from_be_bytes([data[0], data[1], data[2], data[3]])),
+        (0x05, 2) => S7Value::Int(i16::from_be_bytes([data[0], data[1]])),
+        (0x05, 4) => S7Value::DInt(i32::from_be_bytes([data[0], data[1], data[2], data[3]])),
+        (0x07, _) => split_values(data, 4, |v| {
+            S7Value::Real(f32::from_be_bytes([v[0], v[1], v[2], v[3]]))
+        }),
+        _ => S7Value::Raw(data),
+    }
+}
+
+/// 依据请求项 (S7any) 的 transport size 解码数据项。
+pub fn decode_param_item_value(item_transport_size: u8, data: &[u8]) -> S7Value {
+    match item_transport_size {
+        // BIT
+        0x01 => match data.len() {
+            1 => S7Value::Bit(data[0] & 0x01 != 0),
+            _ => S7Value::Raw(data),
+        },
+        // BYTE
+        0x02 => split_values(data, 1, |v| S7Value::Byte(v[0])),
+        // CHAR
+        0x03 => split_values(data, 1, |v| S7Value::Char(v[0])),
+        // WORD
+        0x04 => split_values(data, 2, |v| S7Value::Word(u16::from_be_bytes([v[0], v[1]]))),
+        // INT
+        0x05 => split_values(data, 2, |v| S7Value::Int(i16::from_be_bytes([v[0], v[1]]))),
+        // DWORD
+        0x06 => split_values(data, 4, |v| {
+            S7Value::DWord(u32::from_be_bytes([v[0], v[1], v[2], v[3]]))
+        }),
+        // DINT
+        0x07 => split_values(data, 4, |v| {
+            S7Value::DInt(i32::from_be_bytes([v[0], v[1], v[2], v[3]]))
+        }),
+        // REAL
+        0x08 => split_values(data, 4, |v| {
+            S7Value::Real(f32::from_be_bytes([v[0], v[1], v[2], v[3]]))
+        }),
+        // S7 STRING: max length + actual length + chars
+        0x13 => match data {
+            [_max_length, length, chars @ ..] if (*length as usize) <= chars.len() => {
+                S7Value::String(&chars[..*length as usize])
+            }
+            _ => S7Value::Raw(data),
+        },
+        _ => decode_data_item_value(0xff, data),
+    }
+}
+
+fn pair_var_values<'a>(items: &[ParamItem], standard_items: &[RspReadData<'a>]) -> Vec<S7VarValue<'a>> {
+    items
+        .iter()
+        .zip(standard_items.iter())
+        .filter_map(|(item, standard_item)| match item.syntax_id_enum {
+            SyntaxIdEnum::S7any {
+                transport_size,
+                item_db_numer,
+                item_area,
+                item_address,
+                ..
+            } => Some(S7VarValue {
+                area: item_area,
+                db_number: item_db_numer,
+                byte_address: item_address >> 3,
+                bit_address: (item_address & 0x07) as u8,
+                value: decode_param_item_value(transport_size, standard_item.data),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
 pub fn parse_rsp_write_data(input: &[u8]) -> IResult<&[u8], RspWriteData> {
     let (input, return_code) = u8(input)?;
     Ok((input, RspWriteData { return_code }))
@@ -525,12 +811,14 @@ fn parse_job_param_write_var(input: &[u8]) -> IResult<&[u8], JobParam> {
     let (input, item_count) = u8(input)?;
     let (input, items) = count(parse_param_item, item_count as usize)(input)?;
     let (input, standard_items) = count(parse_rsp_read_data, item_count as usize)(input)?;
+    let values = pair_var_values(&items, &standard_items);
     Ok((
         input,
         JobParam::WriteVar {
             item_count,
             items,
             standard_items,
+            values,
         },
     ))
 }
@@ -943,6 +1231,272 @@ pub fn parse_userdata_param_info(
     Ok((input, userdata_param_info))
 }
 
+fn bcd_to_u8(value: u8) -> u8 {
+    (value >> 4) * 10 + (value & 0x0f)
+}
+
+fn parse_s7_timestamp(input: &[u8]) -> IResult<&[u8], S7Timestamp> {
+    let (input, _reserved) = u8(input)?;
+    let (input, year1) = u8(input)?;
+    let (input, year2) = u8(input)?;
+    let (input, month) = u8(input)?;
+    let (input, day) = u8(input)?;
+    let (input, hour) = u8(input)?;
+    let (input, minute) = u8(input)?;
+    let (input, second) = u8(input)?;
+    let (input, msec_weekday) = be_u16(input)?;
+    let millisecond = (msec_weekday >> 12) * 100
+        + ((msec_weekday >> 8) & 0x0f) * 10
+        + ((msec_weekday >> 4) & 0x0f);
+    Ok((
+        input,
+        S7Timestamp {
+            year: bcd_to_u8(year1) as u16 * 100 + bcd_to_u8(year2) as u16,
+            month: bcd_to_u8(month),
+            day: bcd_to_u8(day),
+            hour: bcd_to_u8(hour),
+            minute: bcd_to_u8(minute),
+            second: bcd_to_u8(second),
+            millisecond,
+            weekday: (msec_weekday & 0x0f) as u8,
+        },
+    ))
+}
+
+fn parse_userdata_cyclic(input: &[u8], subfunction: u8, is_request: bool) -> IResult<&[u8], UserdataData> {
+    let (input, _reserved) = u8(input)?;
+    let (input, item_count) = u8(input)?;
+    if is_request {
+        let (input, (interval_timebase, interval_time)) = match subfunction {
+            0x01 | 0x05 => {
+                let (input, interval_timebase) = u8(input)?;
+                let (input, interval_time) = u8(input)?;
+                (input, (Some(interval_timebase), Some(interval_time)))
+            }
+            _ => (input, (None, None)),
+        };
+        let (input, items) = count(parse_param_item, item_count as usize)(input)?;
+        Ok((
+            input,
+            UserdataData::CyclicRequest {
+                item_count,
+                interval_timebase,
+                interval_time,
+                items,
+            },
+        ))
+    } else {
+        let (input, items) = count(parse_rsp_read_data, item_count as usize)(input)?;
+        Ok((input, UserdataData::CyclicResponse { item_count, items }))
+    }
+}
+
+fn parse_block_count_item(input: &[u8]) -> IResult<&[u8], BlockCountItem> {
+    let (input, _prefix) = u8(input)?;
+    let (input, block_type) = u8(input)?;
+    let (input, block_count) = be_u16(input)?;
+    Ok((
+        input,
+        BlockCountItem {
+            block_type,
+            block_count,
+        },
+    ))
+}
+
+fn parse_block_list_item(input: &[u8]) -> IResult<&[u8], BlockListItem> {
+    let (input, block_number) = be_u16(input)?;
+    let (input, block_flags) = u8(input)?;
+    let (input, block_language) = u8(input)?;
+    Ok((
+        input,
+        BlockListItem {
+            block_number,
+            block_flags,
+            block_language,
+        },
+    ))
+}
+
+fn parse_block_info_request(input: &[u8]) -> IResult<&[u8], UserdataData> {
+    let (input, _prefix) = u8(input)?;
+    let (input, block_type) = u8(input)?;
+    let (input, _block_number) = take(5usize)(input)?;
+    let block_number = match std::str::from_utf8(_block_number) {
+        Ok(o) => o,
+        Err(_) => {
+            return Err(nom::Err::Error(nom::error::Error::new(
+                input,
+                nom::error::ErrorKind::Verify,
+            )))
+        }
+    };
+    let (input, filesystem) = u8(input)?;
+    Ok((
+        input,
+        UserdataData::BlockInfoRequest {
+            block_type,
+            block_number,
+            filesystem,
+        },
+    ))
+}
+
+fn parse_block_info_response(input: &[u8]) -> IResult<&[u8], UserdataData> {
+    let (input, _constant) = u8(input)?;
+    let (input, block_type) = u8(input)?;
+    let (input, _) = take(6usize)(input)?;
+    let (input, _unknown) = u8(input)?;
+    let (input, block_flags) = u8(input)?;
+    let (input, block_language) = u8(input)?;
+    let (input, subblock_type) = u8(input)?;
+    let (input, block_number) = be_u16(input)?;
+    let (input, load_memory_length) = be_u32(input)?;
+    // block security + code/interface timestamp + SBB/ADD/local data length
+    let (input, _) = take(22usize)(input)?;
+    let (input, mc7_code_length) = be_u16(input)?;
+    let (input, author) = take(8usize)(input)?;
+    let (input, family) = take(8usize)(input)?;
+    let (input, name) = take(8usize)(input)?;
+    let (input, version) = u8(input)?;
+    let (input, _unknown) = u8(input)?;
+    let (input, checksum) = be_u16(input)?;
+    Ok((
+        input,
+        UserdataData::BlockInfoResponse {
+            block_type,
+            block_flags,
+            block_language,
+            subblock_type,
+            block_number,
+            load_memory_length,
+            mc7_code_length,
+            author,
+            family,
+            name,
+            version,
+            checksum,
+        },
+    ))
+}
+
+fn parse_userdata_block(input: &[u8], subfunction: u8, is_request: bool) -> IResult<&[u8], UserdataData> {
+    match (subfunction, is_request) {
+        (0x01, true) => Ok((input, UserdataData::ListBlocksRequest {})),
+        (0x01, false) => {
+            let (input, items) = count(parse_block_count_item, input.len() / 4)(input)?;
+            Ok((input, UserdataData::ListBlocksResponse { items }))
+        }
+        (0x02, true) => {
+            let (input, _prefix) = u8(input)?;
+            let (input, block_type) = u8(input)?;
+            Ok((input, UserdataData::ListBlocksOfTypeRequest { block_type }))
+        }
+        (0x02, false) => {
+            let (input, items) = count(parse_block_list_item, input.len() / 4)(input)?;
+            Ok((input, UserdataData::ListBlocksOfTypeResponse { items }))
+        }
+        (0x03, true) => parse_block_info_request(input),
+        (0x03, false) => parse_block_info_response(input),
+        _ => Err(nom::Err::Error(nom::error::Error::new(
+            input,
+            nom::error::ErrorKind::Verify,
+        ))),
+    }
+}
+
+fn parse_userdata_cpu(input: &[u8], subfunction: u8, is_request: bool) -> IResult<&[u8], UserdataData> {
+    match (subfunction, is_request) {
+        (0x01, true) => {
+            let (input, szl_id) = be_u16(input)?;
+            let (input, szl_index) = be_u16(input)?;
+            Ok((input, UserdataData::ReadSzlRequest { szl_id, szl_index }))
+        }
+        (0x01, false) => {
+            let (input, szl_id) = be_u16(input)?;
+            let (input, szl_index) = be_u16(input)?;
+            let (input, record_length) = be_u16(input)?;
+            let (input, record_count) = be_u16(input)?;
+            // 后续分片不含 SZL 头，记录长度超出剩余数据时视为无法解码
+            if record_count != 0 && record_length as usize > input.len() {
+                return Err(nom::Err::Error(nom::error::Error::new(
+                    input,
+                    nom::error::ErrorKind::Verify,
+                )));
+            }
+            // 分片应答只携带部分记录，按实际长度截取
+            let available = match record_length {
+                0 => 0,
+                _ => std::cmp::min(record_count as usize, input.len() / record_length as usize),
+            };
+            let (input, records) = count(
+                map(take(record_length as usize), |data| SzlRecord { data }),
+                available,
+            )(input)?;
+            Ok((
+                input,
+                UserdataData::ReadSzlResponse {
+                    szl_id,
+                    szl_index,
+                    record_length,
+                    record_count,
+                    records,
+                },
+            ))
+        }
+        _ => Err(nom::Err::Error(nom::error::Error::new(
+            input,
+            nom::error::ErrorKind::Verify,
+        ))),
+    }
+}
+
+fn parse_userdata_security(input: &[u8], subfunction: u8, is_request: bool) -> IResult<&[u8], UserdataData> {
+    match (subfunction, is_request) {
+        (0x01, true) => {
+            let (input, password) = take(8usize)(input)?;
+            Ok((input, UserdataData::PlcPassword { password }))
+        }
+        _ => Ok((input, UserdataData::Empty {})),
+    }
+}
+
+fn parse_userdata_time(input: &[u8], subfunction: u8, is_request: bool) -> IResult<&[u8], UserdataData> {
+    // read clock 的应答与 set clock 的请求携带时间戳
+    match (subfunction, is_request) {
+        (0x01, false) | (0x03, false) | (0x02, true) | (0x04, true) => {
+            let (input, timestamp) = parse_s7_timestamp(input)?;
+            Ok((input, UserdataData::Clock { timestamp }))
+        }
+        _ => Ok((input, UserdataData::Empty {})),
+    }
+}
+
+pub fn parse_userdata_data(
+    input: &[u8],
+    parameter_type: u8,
+    function_group: u8,
+    subfunction: u8,
+) -> UserdataData {
+    let is_request = parameter_type == 0x04;
+    let result = match function_group {
+        0x02 => parse_userdata_cyclic(input, subfunction, is_request),
+        0x03 => parse_userdata_block(input, subfunction, is_request),
+        0x04 => parse_userdata_cpu(input, subfunction, is_request),
+        0x05 => parse_userdata_security(input, subfunction, is_request),
+        0x07 => parse_userdata_time(input, subfunction, is_request),
+        _ => Err(nom::Err::Error(nom::error::Error::new(
+            input,
+            nom::error::ErrorKind::Verify,
+        ))),
+    };
+    match result {
+        Ok((_, userdata_data)) => userdata_data,
+        Err(_) if input.is_empty() => UserdataData::Empty {},
+        Err(_) => UserdataData::Unknown { data: input },
+    }
+}
+
 pub fn parse_parameter<'a>(input: &'a [u8], header: &Header) -> IResult<&'a [u8], Parameter<'a>> {
     let (input, parameter) = match header.rosctr {
         0x01 => {
@@ -988,6 +1542,8 @@ pub fn parse_parameter<'a>(input: &'a [u8], header: &Header) -> IResult<&'a [u8]
             let (input, data_transport_size) = u8(input)?;
             let (input, data_length) = be_u16(input)?;
             let (input, data) = take(data_length as usize)(input)?;
+            let userdata_data =
+                parse_userdata_data(data, parameter_type, function_group, subfunction);
             Ok((
                 input,
                 Parameter::Userdata {
@@ -1003,6 +1559,7 @@ pub fn parse_parameter<'a>(input: &'a [u8], header: &Header) -> IResult<&'a [u8]
                     data_transport_size,
                     data_length,
                     data,
+                    userdata_data,
                 },
             ))
         }
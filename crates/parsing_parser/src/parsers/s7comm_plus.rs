@@ -0,0 +1,557 @@
+use nom::bytes::complete::take;
+use nom::combinator::rest;
+use nom::multi::count;
+use nom::number::complete::{be_f32, be_f64, be_i16, be_u16, be_u32, be_u64, u8};
+use nom::IResult;
+
+use crate::errors::ParseError;
+use crate::field_type::*;
+use crate::layer::{ApplicationLayer, LinkLayer, NetworkLayer, TransportLayer};
+use crate::packet::{L4Packet, L5Packet, QuinPacket, QuinPacketOptions};
+use crate::protocol::ApplicationProtocol;
+use crate::ProtocolType;
+
+use super::parse_l5_eof_layer;
+
+// S7comm-plus Frame Format (S7-1200/S7-1500)
+//
+//    +----------+---------+-------------+-----------------+------------------+-----------+
+//    | ID(0x72) | Version | Data Length | Integrity (V2)  | Data (opcode...) | Trailer   |
+//    |  1 byte  | 1 byte  |   2 bytes   | 1 + 32 bytes    |   Data Length    | 4 bytes   |
+//    +----------+---------+-------------+-----------------+------------------+-----------+
+#[derive(Debug, PartialEq, Clone)]
+pub struct S7commPlusHeader<'a> {
+    pub frame_header: FrameHeader,
+    pub integrity_part: Option<IntegrityPart<'a>>,
+    pub data: S7commPlusData<'a>,
+    pub frame_trailer: Option<FrameHeader>,
+}
+
+pub fn parse_s7comm_plus_header(input: &[u8]) -> IResult<&[u8], S7commPlusHeader> {
+    let (input, frame_header) = parse_frame_header(input)?;
+    let (input, data_part) = take(frame_header.data_length as usize)(input)?;
+    let (data_part, integrity_part) = parse_integrity_part(data_part, frame_header.version)?;
+    let (_, data) = parse_s7comm_plus_data(data_part)?;
+    let (input, frame_trailer) = parse_frame_trailer(input)?;
+    Ok((
+        input,
+        S7commPlusHeader {
+            frame_header,
+            integrity_part,
+            data,
+            frame_trailer,
+        },
+    ))
+}
+
+pub fn parse_s7comm_plus_layer<'a>(
+    input: &'a [u8],
+    link_layer: LinkLayer,
+    network_layer: NetworkLayer<'a>,
+    transport_layer: TransportLayer<'a>,
+    options: &QuinPacketOptions,
+) -> QuinPacket<'a> {
+    let current_prototype = ProtocolType::Application(ApplicationProtocol::S7commPlus);
+
+    let (input, s7comm_plus_header) = match parse_s7comm_plus_header(input) {
+        Ok(o) => o,
+        Err(e) => {
+            tracing::error!(
+                target: "PARSER(s7comm_plus::parse_s7comm_plus_layer)",
+                error = ?e
+            );
+
+            let offset = match e {
+                nom::Err::Error(error) => input.len() - error.input.len(),
+                _ => usize::MAX
+            };
+
+            return QuinPacket::L4(L4Packet {
+                link_layer,
+                network_layer,
+                transport_layer,
+                error: Some(ParseError::ParsingHeader{
+                    protocol: current_prototype,
+                    offset
+                }),
+                remain: input,
+            })
+        }
+    };
+
+    if Some(current_prototype) == options.stop {
+        let application_layer = ApplicationLayer::S7commPlus(s7comm_plus_header);
+        return QuinPacket::L5(L5Packet {
+            link_layer,
+            network_layer,
+            transport_layer,
+            application_layer,
+            error: None,
+            remain: input,
+        });
+    };
+
+    let application_layer = ApplicationLayer::S7commPlus(s7comm_plus_header);
+    parse_l5_eof_layer(
+        input,
+        link_layer,
+        network_layer,
+        transport_layer,
+        application_layer,
+        options,
+    )
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct FrameHeader {
+    pub protocol_id: u8,
+    // 0x01: V1, 0x02: V2, 0x03: V3, 0xfe: Light, 0xff: KeepAlive
+    pub version: u8,
+    pub data_length: u16,
+}
+
+fn parse_frame_header(input: &[u8]) -> IResult<&[u8], FrameHeader> {
+    let (input, protocol_id) = u8(input)?;
+    if protocol_id != 0x72 {
+        return Err(nom::Err::Error(nom::error::Error::new(
+            input,
+            nom::error::ErrorKind::Verify,
+        )));
+    }
+    let (input, version) = u8(input)?;
+    let (input, data_length) = be_u16(input)?;
+    Ok((
+        input,
+        FrameHeader {
+            protocol_id,
+            version,
+            data_length,
+        },
+    ))
+}
+
+fn parse_frame_trailer(input: &[u8]) -> IResult<&[u8], Option<FrameHeader>> {
+    if input.len() < 4 || input[0] != 0x72 {
+        return Ok((input, None));
+    }
+    let (input, frame_trailer) = parse_frame_header(input)?;
+    Ok((input, Some(frame_trailer)))
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct IntegrityPart<'a> {
+    pub digest_length: u8,
+    pub digest: &'a [u8],
+}
+
+fn parse_integrity_part(input: &[u8], version: u8) -> IResult<&[u8], Option<IntegrityPart>> {
+    // V2 帧在数据前携带 32 字节摘要，V3 帧在部分固件中同样如此
+    if (version == 0x02 || version == 0x03) && input.first() == Some(&0x20) {
+        let (input, digest_length) = u8(input)?;
+        let (input, digest) = take(digest_length as usize)(input)?;
+        Ok((
+            input,
+            Some(IntegrityPart {
+                digest_length,
+                digest,
+            }),
+        ))
+    } else {
+        Ok((input, None))
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum S7commPlusData<'a> {
+    Request {
+        opcode: u8,
+        reserved1: u16,
+        function: u16,
+        reserved2: u16,
+        sequence_number: u16,
+        session_id: u32,
+        transport_flags: u8,
+        items: Vec<S7commPlusItem<'a>>,
+        payload: &'a [u8],
+    },
+    Response {
+        opcode: u8,
+        reserved1: u16,
+        function: u16,
+        reserved2: u16,
+        sequence_number: u16,
+        transport_flags: u8,
+        items: Vec<S7commPlusItem<'a>>,
+        payload: &'a [u8],
+    },
+    Notification {
+        opcode: u8,
+        subscription_object_id: u32,
+        payload: &'a [u8],
+    },
+    Unknown {
+        opcode: u8,
+        payload: &'a [u8],
+    },
+}
+
+impl<'a> S7commPlusData<'a> {
+    #[inline]
+    pub fn get_opcode(&self) -> u8 {
+        match self {
+            Self::Request { opcode, .. } => *opcode,
+            Self::Response { opcode, .. } => *opcode,
+            Self::Notification { opcode, .. } => *opcode,
+            Self::Unknown { opcode, .. } => *opcode,
+        }
+    }
+
+    #[inline]
+    pub fn get_function(&self) -> Option<u16> {
+        match self {
+            Self::Request { function, .. } => Some(*function),
+            Self::Response { function, .. } => Some(*function),
+            _ => None,
+        }
+    }
+}
+
+fn parse_s7comm_plus_data(input: &[u8]) -> IResult<&[u8], S7commPlusData> {
+    let (input, opcode) = u8(input)?;
+    match opcode {
+        0x31 => parse_request(input, opcode),
+        0x32 | 0x02 => parse_response(input, opcode),
+        0x33 => parse_notification(input, opcode),
+        _ => {
+            let (input, payload) = rest(input)?;
+            Ok((input, S7commPlusData::Unknown { opcode, payload }))
+        }
+    }
+}
+
+fn parse_request(input: &[u8], opcode: u8) -> IResult<&[u8], S7commPlusData> {
+    let (input, reserved1) = be_u16(input)?;
+    let (input, function) = be_u16(input)?;
+    let (input, reserved2) = be_u16(input)?;
+    let (input, sequence_number) = be_u16(input)?;
+    let (input, session_id) = be_u32(input)?;
+    let (input, transport_flags) = u8(input)?;
+    let (input, payload) = rest(input)?;
+    let items = parse_items_by_function(payload, function, true);
+    Ok((
+        input,
+        S7commPlusData::Request {
+            opcode,
+            reserved1,
+            function,
+            reserved2,
+            sequence_number,
+            session_id,
+            transport_flags,
+            items,
+            payload,
+        },
+    ))
+}
+
+fn parse_response(input: &[u8], opcode: u8) -> IResult<&[u8], S7commPlusData> {
+    let (input, reserved1) = be_u16(input)?;
+    let (input, function) = be_u16(input)?;
+    let (input, reserved2) = be_u16(input)?;
+    let (input, sequence_number) = be_u16(input)?;
+    let (input, transport_flags) = u8(input)?;
+    let (input, payload) = rest(input)?;
+    let items = parse_items_by_function(payload, function, false);
+    Ok((
+        input,
+        S7commPlusData::Response {
+            opcode,
+            reserved1,
+            function,
+            reserved2,
+            sequence_number,
+            transport_flags,
+            items,
+            payload,
+        },
+    ))
+}
+
+fn parse_notification(input: &[u8], opcode: u8) -> IResult<&[u8], S7commPlusData> {
+    let (input, subscription_object_id) = be_u32(input)?;
+    let (input, payload) = rest(input)?;
+    Ok((
+        input,
+        S7commPlusData::Notification {
+            opcode,
+            subscription_object_id,
+            payload,
+        },
+    ))
+}
+
+/// 按功能码跳过 body 的固定前缀后，尽力解析 object/attribute 元素列表。
+///
+/// 元素解码失败时仅返回已解析的部分，完整 body 始终保留在 `payload` 中。
+fn parse_items_by_function(input: &[u8], function: u16, is_request: bool) -> Vec<S7commPlusItem> {
+    let input = match (function, is_request) {
+        // CreateObject Request: request id + value + unknown
+        (0x04ca, true) => match parse_create_object_request_prefix(input) {
+            Ok((input, _)) => input,
+            Err(_) => return Vec::new(),
+        },
+        // CreateObject Response: return value + object ids
+        (0x04ca, false) => match parse_create_object_response_prefix(input) {
+            Ok((input, _)) => input,
+            Err(_) => return Vec::new(),
+        },
+        _ => input,
+    };
+    parse_items(input)
+}
+
+fn parse_create_object_request_prefix(input: &[u8]) -> IResult<&[u8], ()> {
+    let (input, _request_id) = be_u32(input)?;
+    let (input, _value) = parse_p_value(input)?;
+    let (input, _unknown) = be_u32(input)?;
+    Ok((input, ()))
+}
+
+fn parse_create_object_response_prefix(input: &[u8]) -> IResult<&[u8], ()> {
+    let (input, _return_value) = vlq_u64(input)?;
+    let (input, object_id_count) = u8(input)?;
+    let (input, _object_ids) = count(vlq_u32, object_id_count as usize)(input)?;
+    Ok((input, ()))
+}
+
+fn parse_items(input: &[u8]) -> Vec<S7commPlusItem> {
+    let mut items = Vec::new();
+    let mut input = input;
+    while let Some(0xa1..=0xa3) = input.first() {
+        match parse_item(input) {
+            Ok((_input, item)) => {
+                input = _input;
+                items.push(item);
+            }
+            Err(_) => break,
+        }
+    }
+    items
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum S7commPlusItem<'a> {
+    StartOfObject {
+        relation_id: u32,
+        class_id: u32,
+        class_flags: u32,
+        attribute_id: u32,
+        attribute_id_flags: Option<u32>,
+    },
+    TerminatingObject {},
+    Attribute {
+        attribute_id: u32,
+        value: PValue<'a>,
+    },
+}
+
+fn parse_item(input: &[u8]) -> IResult<&[u8], S7commPlusItem> {
+    let (input, tag) = u8(input)?;
+    match tag {
+        0xa1 => {
+            let (input, relation_id) = be_u32(input)?;
+            let (input, class_id) = vlq_u32(input)?;
+            let (input, class_flags) = vlq_u32(input)?;
+            let (input, attribute_id) = vlq_u32(input)?;
+            let (input, attribute_id_flags) = if attribute_id != 0 {
+                let (input, attribute_id_flags) = vlq_u32(input)?;
+                (input, Some(attribute_id_flags))
+            } else {
+                (input, None)
+            };
+            Ok((
+                input,
+                S7commPlusItem::StartOfObject {
+                    relation_id,
+                    class_id,
+                    class_flags,
+                    attribute_id,
+                    attribute_id_flags,
+                },
+            ))
+        }
+        0xa2 => Ok((input, S7commPlusItem::TerminatingObject {})),
+        0xa3 => {
+            let (input, attribute_id) = vlq_u32(input)?;
+            let (input, value) = parse_p_value(input)?;
+            Ok((
+                input,
+                S7commPlusItem::Attribute {
+                    attribute_id,
+                    value,
+                },
+            ))
+        }
+        _ => Err(nom::Err::Error(nom::error::Error::new(
+            input,
+            nom::error::ErrorKind::Switch,
+        ))),
+    }
+}
+
+/// PValue是 S7comm-plus 中带数据类型的值，`flags` 的 0x10 位表示数组。
+#[derive(Debug, PartialEq, Clone)]
+pub enum PValue<'a> {
+    Null,
+    Bool(bool),
+    USInt(u8),
+    UInt(u16),
+    UDInt(u32),
+    ULInt(u64),
+    SInt(i8),
+    Int(i16),
+    DInt(i32),
+    LInt(i64),
+    Byte(u8),
+    Word(u16),
+    DWord(u32),
+    LWord(u64),
+    Real(f32),
+    LReal(f64),
+    Timestamp(u64),
+    Timespan(i64),
+    Rid(u32),
+    Aid(u32),
+    Blob(&'a [u8]),
+    WString(&'a str),
+    Array {
+        datatype: u8,
+        elements: Vec<PValue<'a>>,
+    },
+}
+
+fn parse_p_value(input: &[u8]) -> IResult<&[u8], PValue> {
+    let (input, flags) = u8(input)?;
+    let (input, datatype) = u8(input)?;
+    if flags & 0x10 != 0 {
+        let (input, array_size) = vlq_u32(input)?;
+        // 除 Null 外每个元素至少占 1 字节，据此限制数组长度，避免按报文中的长度预分配
+        if datatype == 0x00 || array_size as usize > input.len() {
+            return Err(nom::Err::Error(nom::error::Error::new(
+                input,
+                nom::error::ErrorKind::TooLarge,
+            )));
+        }
+        let (input, elements) = count(
+            |input| parse_p_value_by_datatype(input, datatype),
+            array_size as usize,
+        )(input)?;
+        Ok((input, PValue::Array { datatype, elements }))
+    } else {
+        parse_p_value_by_datatype(input, datatype)
+    }
+}
+
+fn parse_p_value_by_datatype(input: &[u8], datatype: u8) -> IResult<&[u8], PValue> {
+    match datatype {
+        0x00 => Ok((input, PValue::Null)),
+        0x01 => {
+            let (input, v) = u8(input)?;
+            Ok((input, PValue::Bool(v != 0)))
+        }
+        0x02 => {
+            let (input, v) = u8(input)?;
+            Ok((input, PValue::USInt(v)))
+        }
+        // UInt/Int 为定长 2 字节，UDInt/DInt 及 64 位整数才使用 VLQ 编码
+        0x03 => {
+            let (input, v) = be_u16(input)?;
+            Ok((input, PValue::UInt(v)))
+        }
+        0x04 => {
+            let (input, v) = vlq_u32(input)?;
+            Ok((input, PValue::UDInt(v)))
+        }
+        0x05 => {
+            let (input, v) = vlq_u64(input)?;
+            Ok((input, PValue::ULInt(v)))
+        }
+        0x06 => {
+            let (input, v) = u8(input)?;
+            Ok((input, PValue::SInt(v as i8)))
+        }
+        0x07 => {
+            let (input, v) = be_i16(input)?;
+            Ok((input, PValue::Int(v)))
+        }
+        0x08 => {
+            let (input, v) = vlq_i32(input)?;
+            Ok((input, PValue::DInt(v)))
+        }
+        0x09 => {
+            let (input, v) = vlq_i64(input)?;
+            Ok((input, PValue::LInt(v)))
+        }
+        0x0a => {
+            let (input, v) = u8(input)?;
+            Ok((input, PValue::Byte(v)))
+        }
+        0x0b => {
+            let (input, v) = be_u16(input)?;
+            Ok((input, PValue::Word(v)))
+        }
+        0x0c => {
+            let (input, v) = be_u32(input)?;
+            Ok((input, PValue::DWord(v)))
+        }
+        0x0d => {
+            let (input, v) = be_u64(input)?;
+            Ok((input, PValue::LWord(v)))
+        }
+        0x0e => {
+            let (input, v) = be_f32(input)?;
+            Ok((input, PValue::Real(v)))
+        }
+        0x0f => {
+            let (input, v) = be_f64(input)?;
+            Ok((input, PValue::LReal(v)))
+        }
+        0x10 => {
+            let (input, v) = be_u64(input)?;
+            Ok((input, PValue::Timestamp(v)))
+        }
+        0x11 => {
+            let (input, v) = vlq_i64(input)?;
+            Ok((input, PValue::Timespan(v)))
+        }
+        0x12 => {
+            let (input, v) = be_u32(input)?;
+            Ok((input, PValue::Rid(v)))
+        }
+        0x13 => {
+            let (input, v) = vlq_u32(input)?;
+            Ok((input, PValue::Aid(v)))
+        }
+        0x14 => {
+            let (input, length) = vlq_u32(input)?;
+            let (input, v) = take(length as usize)(input)?;
+            Ok((input, PValue::Blob(v)))
+        }
+        0x15 => {
+            let (input, length) = vlq_u32(input)?;
+            let (input, v) = take(length as usize)(input)?;
+            match std::str::from_utf8(v) {
+                Ok(v) => Ok((input, PValue::WString(v))),
+                Err(_) => Err(nom::Err::Error(nom::error::Error::new(
+                    input,
+                    nom::error::ErrorKind::Verify,
+                ))),
+            }
+        }
+        // Variant / Struct 等复合类型暂不展开
+        _ => Err(nom::Err::Error(nom::error::Error::new(
+            input,
+            nom::error::ErrorKind::Switch,
+        ))),
+    }
+}
@@ -44,7 +44,7 @@ use std::ops::BitXor;
 use std::convert::TryInto;
 
 
-use super::parse_l4_eof_layer;
+use super::parse_l5_eof_layer;
 
 #[allow(non_camel_case_types)]
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -78,9 +78,15 @@ pub fn parse_sv_header(input: &[u8]) -> IResult<&[u8], SvHeader> {
     ))
 }
 
-pub fn parse_sv_layer<'a>(input: &'a [u8], link_layer: LinkLayer, network_layer: NetworkLayer<'a>, options: &QuinPacketOptions) -> QuinPacket<'a> {
+pub fn parse_sv_layer<'a>(
+    input: &'a [u8],
+    link_layer: LinkLayer,
+    network_layer: NetworkLayer<'a>,
+    transport_layer: TransportLayer<'a>,
+    options: &QuinPacketOptions,
+) -> QuinPacket<'a> {
     info!(target: "PARSER(sv::parse_sv_layer)", "parsing Sv protocol.");
-    let current_prototype = ProtocolType::Transport(TransportProtocol::Sv);
+    let current_prototype = ProtocolType::Application(ApplicationProtocol::Sv);
 
     let (input, sv_header) = match parse_sv_header(input) {
         Ok(o) => o,
@@ -95,14 +101,15 @@ pub fn parse_sv_layer<'a>(input: &'a [u8], link_layer: LinkLayer, network_layer:
                 _ => usize::MAX
             };
 
-            return QuinPacket::L3(
-                L3Packet {
+            return QuinPacket::L4(
+                L4Packet {
                     link_layer,
                     network_layer,
+                    transport_layer,
                     error: Some(ParseError::ParsingHeader{
-                    protocol: current_prototype,
-                    offset
-                }),
+                        protocol: current_prototype,
+                        offset
+                    }),
                     remain: input,
                 }
             )
@@ -110,20 +117,21 @@ pub fn parse_sv_layer<'a>(input: &'a [u8], link_layer: LinkLayer, network_layer:
     };
 
     if Some(current_prototype) == options.stop {
-        let transport_layer = TransportLayer::Sv(sv_header);
-        return QuinPacket::L4(
-            L4Packet {
+        let application_layer = ApplicationLayer::Sv(sv_header);
+        return QuinPacket::L5(
+            L5Packet {
                 link_layer,
                 network_layer,
                 transport_layer,
+                application_layer,
                 error: None,
                 remain: input,
             }
         )
     };
 
-    let transport_layer = TransportLayer::Sv(sv_header);
-    return parse_l4_eof_layer(input, link_layer, network_layer, transport_layer, options);
+    let application_layer = ApplicationLayer::Sv(sv_header);
+    return parse_l5_eof_layer(input, link_layer, network_layer, transport_layer, application_layer, options);
 }
 
 #[allow(non_camel_case_types)]
@@ -131,44 +139,223 @@ pub fn parse_sv_layer<'a>(input: &'a [u8], link_layer: LinkLayer, network_layer:
 pub struct Asdu<'a> {
     pub asdu_tl: BerTL,
     pub sv_id: &'a [u8],
-    pub cmp_cnt: &'a [u8],
-    pub conf_rev: &'a [u8],
-    pub smp_synch: &'a [u8],
-    pub seq_data: &'a [u8],
+    pub dat_set: Option<&'a [u8]>,
+    pub smp_cnt: u16,
+    pub conf_rev: u32,
+    pub refr_tm: Option<UtcTime>,
+    pub smp_synch: u8,
+    pub smp_rate: Option<u16>,
+    pub seq_data: Vec<SvSample>,
+    pub smp_mod: Option<u16>,
+}
+
+/// SvSample是 seqData 中一个通道的采样值(INT32)与品质位
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct SvSample {
+    pub value: i32,
+    pub quality: SvQuality,
+}
+
+/// SvQuality是 IEC 61850-7-3 的品质位(9-2LE 中编码为 32 位)
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct SvQuality(pub u32);
+
+impl SvQuality {
+    /// validity: 0 good, 1 invalid, 2 reserved, 3 questionable
+    #[inline]
+    pub fn validity(&self) -> u8 {
+        (self.0 & 0x03) as u8
+    }
+
+    #[inline]
+    pub fn is_good(&self) -> bool {
+        self.validity() == 0
+    }
+
+    #[inline]
+    pub fn detail_qual(&self) -> u16 {
+        ((self.0 >> 2) & 0xff) as u16
+    }
+
+    #[inline]
+    pub fn is_substituted(&self) -> bool {
+        self.0 & 0x0400 != 0
+    }
+
+    #[inline]
+    pub fn is_test(&self) -> bool {
+        self.0 & 0x0800 != 0
+    }
+
+    #[inline]
+    pub fn is_operator_blocked(&self) -> bool {
+        self.0 & 0x1000 != 0
+    }
+
+    #[inline]
+    pub fn is_derived(&self) -> bool {
+        self.0 & 0x2000 != 0
+    }
+}
+
+/// SvChannel是 seqData 中的一个通道定义: 名称与比例系数(工程值 = 原始值 * scale)
+#[derive(Debug, PartialEq, Clone)]
+pub struct SvChannel {
+    pub name: String,
+    pub scale: f64,
+}
+
+/// SvChannelMap用于将 seqData 按顺序映射为命名的工程量
+#[derive(Debug, PartialEq, Clone)]
+pub struct SvChannelMap {
+    pub channels: Vec<SvChannel>,
+}
+
+impl SvChannelMap {
+    pub fn new(channels: Vec<(&str, f64)>) -> Self {
+        Self {
+            channels: channels
+                .into_iter()
+                .map(|(name, scale)| SvChannel { name: name.to_string(), scale })
+                .collect(),
+        }
+    }
+}
+
+impl Default for SvChannelMap {
+    /// 9-2LE 默认通道: 电流单位 1mA, 电压单位 10mV
+    fn default() -> Self {
+        Self::new(vec![
+            ("Ia", 0.001), ("Ib", 0.001), ("Ic", 0.001), ("In", 0.001),
+            ("Va", 0.01), ("Vb", 0.01), ("Vc", 0.01), ("Vn", 0.01),
+        ])
+    }
+}
+
+/// SvMeasurement是按通道映射与缩放后的采样值
+#[derive(Debug, PartialEq, Clone)]
+pub struct SvMeasurement {
+    pub name: String,
+    pub value: f64,
+    pub quality: SvQuality,
+}
+
+impl<'a> Asdu<'a> {
+    /// 按通道映射输出工程量，超出映射的通道以 `ch<序号>` 命名且不缩放
+    pub fn measurements(&self, channel_map: &SvChannelMap) -> Vec<SvMeasurement> {
+        self.seq_data
+            .iter()
+            .enumerate()
+            .map(|(index, sample)| match channel_map.channels.get(index) {
+                Some(channel) => SvMeasurement {
+                    name: channel.name.clone(),
+                    value: sample.value as f64 * channel.scale,
+                    quality: sample.quality,
+                },
+                None => SvMeasurement {
+                    name: format!("ch{}", index),
+                    value: sample.value as f64,
+                    quality: sample.quality,
+                },
+            })
+            .collect()
+    }
 }
 
 #[allow(non_camel_case_types)]
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct SavPDU<'a> {
-    pub no_asdu: &'a [u8],
+    pub no_asdu: u16,
     pub seq_asdu_tl: BerTL,
     pub seq_asdu: Vec<Asdu<'a>>,
 }
 
+fn sv_verify_error(input: &[u8]) -> nom::Err<Error<&[u8]>> {
+    nom::Err::Error(Error {
+        input,
+        code: ErrorKind::Verify,
+    })
+}
+
+fn sv_unsigned<'a, T: TryFrom<u64>>(input: &'a [u8], value: &[u8]) -> Result<T, nom::Err<Error<&'a [u8]>>> {
+    match ber_unsigned(value).and_then(|o| T::try_from(o).ok()) {
+        Some(o) => Ok(o),
+        None => Err(sv_verify_error(input)),
+    }
+}
+
+pub fn parse_seq_data(input: &[u8]) -> IResult<&[u8], Vec<SvSample>> {
+    if input.len() % 8 != 0 {
+        return Err(sv_verify_error(input));
+    }
+    let mut seq_data = Vec::new();
+    let mut input = input;
+    while !input.is_empty() {
+        let (value, quality);
+        (input, (value, quality)) = tuple((be_u32, be_u32))(input)?;
+        seq_data.push(SvSample { value: value as i32, quality: SvQuality(quality) });
+    }
+    Ok((input, seq_data))
+}
+
 pub fn parse_asdu(input: &[u8]) -> IResult<&[u8], Asdu> {
     debug!(target: "PARSER(parse_asdu)", "struct Asdu");
     let (input, asdu_tl) = ber_tl(input)?;
-    let (input, sv_id) = ber_tl_v(input)?;
-    let (input, cmp_cnt) = ber_tl_v(input)?;
-    let (input, conf_rev) = ber_tl_v(input)?;
-    let (input, smp_synch) = ber_tl_v(input)?;
-    let (input, seq_data) = ber_tl_v(input)?;
-    Ok((
-        input,
-        Asdu {
-            asdu_tl,
-            sv_id,
-            cmp_cnt,
-            conf_rev,
-            smp_synch,
-            seq_data
+    let (input, asdu) = take(asdu_tl.length as usize)(input)?;
+    let mut sv_id = None;
+    let mut dat_set = None;
+    let mut smp_cnt = None;
+    let mut conf_rev = None;
+    let mut refr_tm = None;
+    let mut smp_synch = None;
+    let mut smp_rate = None;
+    let mut seq_data = None;
+    let mut smp_mod = None;
+    let mut rest = asdu;
+    while !rest.is_empty() {
+        let (_rest, tl) = ber_tl(rest)?;
+        let (_rest, value) = take(tl.length as usize)(_rest)?;
+        match tl.tag {
+            0x80 => sv_id = Some(value),
+            0x81 => dat_set = Some(value),
+            0x82 => smp_cnt = Some(sv_unsigned(rest, value)?),
+            0x83 => conf_rev = Some(sv_unsigned(rest, value)?),
+            0x84 => match UtcTime::from_bytes(value) {
+                Some(o) => refr_tm = Some(o),
+                None => return Err(sv_verify_error(rest)),
+            },
+            0x85 => smp_synch = Some(sv_unsigned(rest, value)?),
+            0x86 => smp_rate = Some(sv_unsigned(rest, value)?),
+            0x87 => seq_data = Some(parse_seq_data(value)?.1),
+            0x88 => smp_mod = Some(sv_unsigned(rest, value)?),
+            _ => warn!(target: "PARSER(parse_asdu)", tag = tl.tag, "unknown asdu field"),
         }
-    ))
+        rest = _rest;
+    }
+    match (sv_id, smp_cnt, conf_rev, smp_synch, seq_data) {
+        (Some(sv_id), Some(smp_cnt), Some(conf_rev), Some(smp_synch), Some(seq_data)) => Ok((
+            input,
+            Asdu {
+                asdu_tl,
+                sv_id,
+                dat_set,
+                smp_cnt,
+                conf_rev,
+                refr_tm,
+                smp_synch,
+                smp_rate,
+                seq_data,
+                smp_mod
+            }
+        )),
+        _ => Err(sv_verify_error(asdu)),
+    }
 }
 
 pub fn parse_sav_pdu(input: &[u8]) -> IResult<&[u8], SavPDU> {
     debug!(target: "PARSER(parse_sav_pdu)", "struct SavPDU");
     let (input, no_asdu) = ber_tl_v(input)?;
+    let no_asdu = sv_unsigned(input, no_asdu)?;
     let (input, seq_asdu_tl) = ber_tl(input)?;
     /* LimitedLenVecLoopField Start */
     let mut seq_asdu = Vec::new();
@@ -11,9 +11,9 @@ use crate::protocol::TransportProtocol;
 use crate::ProtocolType;
 
 use super::{
-    parse_bacnet_layer, parse_dnp3_layer, parse_fins_tcp_req_layer, parse_fins_tcp_rsp_layer,
-    parse_http_layer, parse_iec104_layer, parse_iso_on_tcp_layer, parse_l4_eof_layer,
-    parse_modbus_req_layer, parse_modbus_rsp_layer, parse_opcua_layer,
+    parse_bacnet_layer, parse_dnp3_layer, parse_enip_layer, parse_fins_tcp_req_layer, parse_fins_tcp_rsp_layer,
+    parse_http_layer, parse_iec101_layer, parse_iec104_layer, parse_iso_on_tcp_layer, parse_l4_eof_layer,
+    parse_melsec_layer, parse_modbus_req_layer, parse_modbus_rsp_layer, parse_opcua_layer,
 };
 
 // TCP Header Format
@@ -161,10 +161,18 @@ pub fn parse_tcp_layer<'a>(
         return parse_l4_eof_layer(input, link_layer, network_layer, transport_layer, options);
     }
     match tcp_header.src_port {
-        80 => {
+        port if options.iec101.ports.contains(port) => {
+            let transport_layer = TransportLayer::Tcp(tcp_header);
+            parse_iec101_layer(input, link_layer, network_layer, transport_layer, options)
+        }
+        port if options.http.ports.contains(port) => {
             let transport_layer = TransportLayer::Tcp(tcp_header);
             parse_http_layer(input, link_layer, network_layer, transport_layer, options)
         }
+        port if options.melsec.ports.contains(port) => {
+            let transport_layer = TransportLayer::Tcp(tcp_header);
+            parse_melsec_layer(input, link_layer, network_layer, transport_layer, options)
+        }
         102 => {
             let transport_layer = TransportLayer::Tcp(tcp_header);
             parse_iso_on_tcp_layer(input, link_layer, network_layer, transport_layer, options)
@@ -189,15 +197,27 @@ pub fn parse_tcp_layer<'a>(
             let transport_layer = TransportLayer::Tcp(tcp_header);
             parse_dnp3_layer(input, link_layer, network_layer, transport_layer, options)
         }
+        44818 => {
+            let transport_layer = TransportLayer::Tcp(tcp_header);
+            parse_enip_layer(input, link_layer, network_layer, transport_layer, options)
+        }
         47808 => {
             let transport_layer = TransportLayer::Tcp(tcp_header);
             parse_bacnet_layer(input, link_layer, network_layer, transport_layer, options)
         }
         _ => match tcp_header.dst_port {
-            80 => {
+            port if options.iec101.ports.contains(port) => {
+                let transport_layer = TransportLayer::Tcp(tcp_header);
+                parse_iec101_layer(input, link_layer, network_layer, transport_layer, options)
+            }
+            port if options.http.ports.contains(port) => {
                 let transport_layer = TransportLayer::Tcp(tcp_header);
                 parse_http_layer(input, link_layer, network_layer, transport_layer, options)
             }
+            port if options.melsec.ports.contains(port) => {
+                let transport_layer = TransportLayer::Tcp(tcp_header);
+                parse_melsec_layer(input, link_layer, network_layer, transport_layer, options)
+            }
             102 => {
                 let transport_layer = TransportLayer::Tcp(tcp_header);
                 parse_iso_on_tcp_layer(input, link_layer, network_layer, transport_layer, options)
@@ -222,6 +242,10 @@ pub fn parse_tcp_layer<'a>(
                 let transport_layer = TransportLayer::Tcp(tcp_header);
                 parse_dnp3_layer(input, link_layer, network_layer, transport_layer, options)
             }
+            44818 => {
+                let transport_layer = TransportLayer::Tcp(tcp_header);
+                parse_enip_layer(input, link_layer, network_layer, transport_layer, options)
+            }
             47808 => {
                 let transport_layer = TransportLayer::Tcp(tcp_header);
                 parse_bacnet_layer(input, link_layer, network_layer, transport_layer, options)
@@ -0,0 +1,205 @@
+use nom::bytes::complete::take;
+use nom::combinator::rest;
+use nom::number::complete::{le_u16, u8};
+use nom::IResult;
+
+use crate::errors::ParseError;
+use crate::layer::{ApplicationLayer, LinkLayer, NetworkLayer, TransportLayer};
+use crate::packet::{L4Packet, L5Packet, QuinPacket, QuinPacketOptions};
+use crate::protocol::ApplicationProtocol;
+use crate::ProtocolType;
+
+use super::parse_l5_eof_layer;
+
+// TriStation (Triconex 安全仪表系统工程协议，UDP 1502)，多字节字段为小端
+//
+//    +--------------+---------+---------+------------------------------+---------+
+//    | Message Type | Channel | Length  | Data                         | CRC16   |
+//    | 1 byte       | 1 byte  | 2 bytes | Length bytes                 | 2 bytes |
+//    +--------------+---------+---------+------------------------------+---------+
+//
+// 执行命令报文的 Data 为 Command(1) + Node(1) + Sequence(2) + 命令数据。
+// 报文格式与命令码参考 TRITON/TRISIS 的公开分析，CRC 不做校验。
+
+// 报文类型
+pub const TRISTATION_CONNECT_REQUEST: u8 = 1;
+pub const TRISTATION_CONNECT_RESPONSE: u8 = 2;
+pub const TRISTATION_DISCONNECT_REQUEST: u8 = 3;
+pub const TRISTATION_DISCONNECT_RESPONSE: u8 = 4;
+pub const TRISTATION_EXECUTION_COMMAND: u8 = 5;
+pub const TRISTATION_PING: u8 = 6;
+pub const TRISTATION_CONNECTION_LIMIT: u8 = 7;
+pub const TRISTATION_NOT_CONNECTED: u8 = 8;
+pub const TRISTATION_MPS_DEAD: u8 = 9;
+pub const TRISTATION_ACCESS_DENIED: u8 = 10;
+pub const TRISTATION_CONNECTION_FAILED: u8 = 11;
+
+// 命令码
+pub const TRISTATION_START_DOWNLOAD_ALL: u8 = 0x01;
+pub const TRISTATION_START_DOWNLOAD_CHANGE: u8 = 0x02;
+pub const TRISTATION_UPDATE_CONFIGURATION: u8 = 0x03;
+pub const TRISTATION_UPLOAD_CONFIGURATION: u8 = 0x04;
+pub const TRISTATION_LOAD_VECTOR_TABLE: u8 = 0x07;
+pub const TRISTATION_SET_CALENDAR: u8 = 0x08;
+pub const TRISTATION_END_DOWNLOAD_ALL: u8 = 0x0b;
+pub const TRISTATION_END_DOWNLOAD_CHANGE: u8 = 0x0c;
+pub const TRISTATION_CANCEL_DOWNLOAD_CHANGE: u8 = 0x0d;
+pub const TRISTATION_SET_MULTIPLE_POINT_VALUES: u8 = 0x11;
+pub const TRISTATION_GET_CP_STATUS: u8 = 0x14;
+pub const TRISTATION_RUN_PROGRAM: u8 = 0x15;
+pub const TRISTATION_HALT_PROGRAM: u8 = 0x16;
+pub const TRISTATION_PAUSE_PROGRAM: u8 = 0x17;
+pub const TRISTATION_GET_CHASSIS_STATUS: u8 = 0x19;
+pub const TRISTATION_SET_POINT_VALUES: u8 = 0x1c;
+pub const TRISTATION_GET_POINT_VALUES: u8 = 0x1d;
+pub const TRISTATION_GET_MP_STATUS: u8 = 0x1e;
+pub const TRISTATION_SET_RETENTIVE_VALUES: u8 = 0x1f;
+pub const TRISTATION_ALLOCATE_PROGRAM: u8 = 0x30;
+pub const TRISTATION_ALLOCATE_FUNCTION: u8 = 0x31;
+
+/// 修改控制程序或运行状态的命令
+pub const TRISTATION_PROGRAM_CHANGE_COMMANDS: &[u8] = &[
+    TRISTATION_START_DOWNLOAD_ALL,
+    TRISTATION_START_DOWNLOAD_CHANGE,
+    TRISTATION_UPDATE_CONFIGURATION,
+    TRISTATION_LOAD_VECTOR_TABLE,
+    TRISTATION_END_DOWNLOAD_ALL,
+    TRISTATION_END_DOWNLOAD_CHANGE,
+    TRISTATION_RUN_PROGRAM,
+    TRISTATION_HALT_PROGRAM,
+    TRISTATION_PAUSE_PROGRAM,
+    TRISTATION_ALLOCATE_PROGRAM,
+    TRISTATION_ALLOCATE_FUNCTION,
+];
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TristationHeader<'a> {
+    pub message_type: u8,
+    pub channel: u8,
+    pub length: u16,
+    /// 仅执行命令报文
+    pub command: Option<TristationCommand<'a>>,
+    pub data: &'a [u8],
+    pub crc: u16,
+}
+
+impl<'a> TristationHeader<'a> {
+    pub fn command_code(&self) -> Option<u8> {
+        self.command.as_ref().map(|command| command.code)
+    }
+
+    pub fn is_program_change(&self) -> bool {
+        matches!(self.command_code(), Some(code) if TRISTATION_PROGRAM_CHANGE_COMMANDS.contains(&code))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TristationCommand<'a> {
+    pub code: u8,
+    pub node: u8,
+    pub sequence: u16,
+    pub data: &'a [u8],
+}
+
+fn parse_tristation_command(input: &[u8]) -> IResult<&[u8], TristationCommand> {
+    let (input, code) = u8(input)?;
+    let (input, node) = u8(input)?;
+    let (input, sequence) = le_u16(input)?;
+    let (input, data) = rest(input)?;
+    Ok((
+        input,
+        TristationCommand {
+            code,
+            node,
+            sequence,
+            data,
+        },
+    ))
+}
+
+pub fn parse_tristation_header(input: &[u8]) -> IResult<&[u8], TristationHeader> {
+    let (input, message_type) = u8(input)?;
+    if !(TRISTATION_CONNECT_REQUEST..=TRISTATION_CONNECTION_FAILED).contains(&message_type) {
+        return Err(nom::Err::Error(nom::error::Error::new(
+            input,
+            nom::error::ErrorKind::Verify,
+        )));
+    }
+    let (input, channel) = u8(input)?;
+    let (input, length) = le_u16(input)?;
+    let (input, data) = take(length as usize)(input)?;
+    let (input, crc) = le_u16(input)?;
+    let command = match message_type {
+        TRISTATION_EXECUTION_COMMAND => parse_tristation_command(data).ok().map(|(_, command)| command),
+        _ => None,
+    };
+    Ok((
+        input,
+        TristationHeader {
+            message_type,
+            channel,
+            length,
+            command,
+            data,
+            crc,
+        },
+    ))
+}
+
+pub fn parse_tristation_layer<'a>(
+    input: &'a [u8],
+    link_layer: LinkLayer,
+    network_layer: NetworkLayer<'a>,
+    transport_layer: TransportLayer<'a>,
+    options: &QuinPacketOptions,
+) -> QuinPacket<'a> {
+    let current_prototype = ProtocolType::Application(ApplicationProtocol::Tristation);
+
+    let (input, tristation_header) = match parse_tristation_header(input) {
+        Ok(o) => o,
+        Err(e) => {
+            tracing::error!(
+                target: "PARSER(tristation::parse_tristation_layer)",
+                error = ?e
+            );
+
+            let offset = match e {
+                nom::Err::Error(error) => input.len() - error.input.len(),
+                _ => usize::MAX
+            };
+
+            return QuinPacket::L4(L4Packet {
+                link_layer,
+                network_layer,
+                transport_layer,
+                error: Some(ParseError::ParsingHeader{
+                    protocol: current_prototype,
+                    offset
+                }),
+                remain: input,
+            })
+        }
+    };
+
+    if Some(current_prototype) == options.stop {
+        let application_layer = ApplicationLayer::Tristation(tristation_header);
+        return QuinPacket::L5(L5Packet {
+            link_layer,
+            network_layer,
+            transport_layer,
+            application_layer,
+            error: None,
+            remain: input,
+        });
+    };
+
+    let application_layer = ApplicationLayer::Tristation(tristation_header);
+    parse_l5_eof_layer(
+        input,
+        link_layer,
+        network_layer,
+        transport_layer,
+        application_layer,
+        options,
+    )
+}
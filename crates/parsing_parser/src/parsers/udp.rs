@@ -7,8 +7,9 @@ use crate::protocol::TransportProtocol;
 use crate::ProtocolType;
 
 use super::{
-    parse_bacnet_layer, parse_fins_udp_req_layer, parse_fins_udp_rsp_layer, parse_l4_eof_layer,
-    parse_modbus_req_layer, parse_modbus_rsp_layer,
+    parse_bacnet_layer, parse_enip_io_layer, parse_enip_layer, parse_fins_udp_req_layer,
+    parse_fins_udp_rsp_layer, parse_l4_eof_layer, parse_modbus_req_layer, parse_modbus_rsp_layer,
+    parse_melsec_layer, parse_pnio_cm_layer, parse_tristation_layer,
 };
 
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -87,27 +88,67 @@ pub fn parse_udp_layer<'a>(
         return parse_l4_eof_layer(input, link_layer, network_layer, transport_layer, options);
     }
     match udp_header.src_port {
+        port if options.melsec.ports.contains(port) => {
+            let transport_layer = TransportLayer::Udp(udp_header);
+            parse_melsec_layer(input, link_layer, network_layer, transport_layer, options)
+        }
         502 => {
             let transport_layer = TransportLayer::Udp(udp_header);
             parse_modbus_rsp_layer(input, link_layer, network_layer, transport_layer, options)
         }
+        1502 => {
+            let transport_layer = TransportLayer::Udp(udp_header);
+            parse_tristation_layer(input, link_layer, network_layer, transport_layer, options)
+        }
+        2222 => {
+            let transport_layer = TransportLayer::Udp(udp_header);
+            parse_enip_io_layer(input, link_layer, network_layer, transport_layer, options)
+        }
         9600 => {
             let transport_layer = TransportLayer::Udp(udp_header);
             parse_fins_udp_rsp_layer(input, link_layer, network_layer, transport_layer, options)
         }
+        34964 => {
+            let transport_layer = TransportLayer::Udp(udp_header);
+            parse_pnio_cm_layer(input, link_layer, network_layer, transport_layer, options)
+        }
+        44818 => {
+            let transport_layer = TransportLayer::Udp(udp_header);
+            parse_enip_layer(input, link_layer, network_layer, transport_layer, options)
+        }
         47808 => {
             let transport_layer = TransportLayer::Udp(udp_header);
             parse_bacnet_layer(input, link_layer, network_layer, transport_layer, options)
         }
         _ => match udp_header.dst_port {
+            port if options.melsec.ports.contains(port) => {
+                let transport_layer = TransportLayer::Udp(udp_header);
+                parse_melsec_layer(input, link_layer, network_layer, transport_layer, options)
+            }
             502 => {
                 let transport_layer = TransportLayer::Udp(udp_header);
                 parse_modbus_req_layer(input, link_layer, network_layer, transport_layer, options)
             }
+            1502 => {
+                let transport_layer = TransportLayer::Udp(udp_header);
+                parse_tristation_layer(input, link_layer, network_layer, transport_layer, options)
+            }
+            2222 => {
+                let transport_layer = TransportLayer::Udp(udp_header);
+                parse_enip_io_layer(input, link_layer, network_layer, transport_layer, options)
+            }
             9600 => {
                 let transport_layer = TransportLayer::Udp(udp_header);
                 parse_fins_udp_req_layer(input, link_layer, network_layer, transport_layer, options)
             }
+            34964 => {
+                let transport_layer = TransportLayer::Udp(udp_header);
+                parse_pnio_cm_layer(input, link_layer, network_layer, transport_layer, options)
+            }
+            44818 => {
+                let transport_layer = TransportLayer::Udp(udp_header);
+                parse_enip_layer(input, link_layer, network_layer, transport_layer, options)
+            }
             47808 => {
                 let transport_layer = TransportLayer::Udp(udp_header);
                 parse_bacnet_layer(input, link_layer, network_layer, transport_layer, options)
@@ -44,7 +44,7 @@ use std::ops::BitXor;
 use std::convert::TryInto;
 
 
-use super::{parse_sv_layer, parse_l3_eof_layer};
+use super::{parse_goose_layer, parse_profinet_layer, parse_sv_layer, parse_l3_eof_layer};
 
 #[allow(non_camel_case_types)]
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -117,9 +117,17 @@ pub fn parse_vlan_layer<'a>(input: &'a [u8], link_layer: LinkLayer, options: &Qu
         return parse_l3_eof_layer(input, link_layer, network_layer, options);
     }
     match vlan_header.vtype {
+        0x88b8 => {
+            let network_layer = NetworkLayer::Vlan(vlan_header);
+            parse_goose_layer(input, link_layer, network_layer, TransportLayer::Empty, options)
+        },
         0x88ba => {
             let network_layer = NetworkLayer::Vlan(vlan_header);
-            parse_sv_layer(input, link_layer, network_layer, options)
+            parse_sv_layer(input, link_layer, network_layer, TransportLayer::Empty, options)
+        },
+        0x8892 => {
+            let network_layer = NetworkLayer::Vlan(vlan_header);
+            parse_profinet_layer(input, link_layer, network_layer, TransportLayer::Empty, options)
         },
         _ => {
             let network_layer = NetworkLayer::Vlan(vlan_header);
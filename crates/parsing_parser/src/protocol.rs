@@ -56,8 +56,8 @@ impl ToString for LinkProtocol {
 pub enum NetworkProtocol {
     Ipv4,
     Ipv6,
-    Goose,
     Vlan,
+    Empty,
 }
 
 impl ToString for NetworkProtocol {
@@ -65,8 +65,8 @@ impl ToString for NetworkProtocol {
         match self {
             NetworkProtocol::Ipv4  => "Ipv4",
             NetworkProtocol::Ipv6  => "Ipv6",
-            NetworkProtocol::Goose => "Goose",
             NetworkProtocol::Vlan  => "VLan",
+            NetworkProtocol::Empty => "Empty",
         }.into()
     }
 }
@@ -75,7 +75,7 @@ impl ToString for NetworkProtocol {
 pub enum TransportProtocol {
     Tcp,
     Udp,
-    Sv,
+    Empty,
 }
 
 impl ToString for TransportProtocol {
@@ -83,7 +83,7 @@ impl ToString for TransportProtocol {
         match self {
             TransportProtocol::Tcp => "TCP",
             TransportProtocol::Udp => "UDP",
-            TransportProtocol::Sv  => "SV"
+            TransportProtocol::Empty => "Empty"
         }.into()
     }
 }
@@ -98,14 +98,21 @@ pub enum ApplicationProtocol {
     FinsUdpRsp,
     Mms,
     S7comm,
+    S7commPlus,
+    Enip,
     Bacnet,
     Dnp3,
+    Iec101,
     Iec104,
     Opcua,
     Http,
     IsoOnTcp,
     Sv,
-    Goose
+    Goose,
+    Profinet,
+    PnioCm,
+    Melsec,
+    Tristation
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy, Eq, Hash)]
@@ -114,14 +121,20 @@ pub enum ApplicationNaiveProtocol {
     Fins,
     Mms,
     S7comm,
+    S7commPlus,
+    Enip,
     Bacnet,
     Dnp3,
+    Iec101,
     Iec104,
     Opcua,
     Http,
     IsoOnTcp,
     Sv,
-    Goose
+    Goose,
+    Profinet,
+    Melsec,
+    Tristation
 }
 
 impl ToString for ApplicationNaiveProtocol {
@@ -129,16 +142,22 @@ impl ToString for ApplicationNaiveProtocol {
         match self {
             ApplicationNaiveProtocol::Bacnet   => "BACnet",
             ApplicationNaiveProtocol::Dnp3     => "DNP3",
+            ApplicationNaiveProtocol::Enip     => "ENIP",
             ApplicationNaiveProtocol::Fins     => "FINS",
             ApplicationNaiveProtocol::Http     => "HTTP",
+            ApplicationNaiveProtocol::Iec101   => "IEC101",
             ApplicationNaiveProtocol::Iec104   => "IEC104",
             ApplicationNaiveProtocol::IsoOnTcp => "ISOonTCP",
+            ApplicationNaiveProtocol::Melsec   => "MELSEC",
             ApplicationNaiveProtocol::Mms      => "MMS",
             ApplicationNaiveProtocol::Modbus   => "Modbus",
             ApplicationNaiveProtocol::Opcua    => "OpcUA",
             ApplicationNaiveProtocol::S7comm   => "S7COMM",
+            ApplicationNaiveProtocol::S7commPlus => "S7COMM-PLUS",
             ApplicationNaiveProtocol::Goose   => "GOOSE",
             ApplicationNaiveProtocol::Sv   => "SV",
+            ApplicationNaiveProtocol::Profinet => "PROFINET",
+            ApplicationNaiveProtocol::Tristation => "TriStation",
         }.into()
     }
 }
@@ -154,14 +173,21 @@ impl From<ApplicationProtocol> for ApplicationNaiveProtocol {
             ApplicationProtocol::FinsUdpRsp => ApplicationNaiveProtocol::Fins,
             ApplicationProtocol::Mms => ApplicationNaiveProtocol::Mms,
             ApplicationProtocol::S7comm => ApplicationNaiveProtocol::S7comm,
+            ApplicationProtocol::S7commPlus => ApplicationNaiveProtocol::S7commPlus,
+            ApplicationProtocol::Enip => ApplicationNaiveProtocol::Enip,
             ApplicationProtocol::Bacnet => ApplicationNaiveProtocol::Bacnet,
             ApplicationProtocol::Dnp3 => ApplicationNaiveProtocol::Dnp3,
+            ApplicationProtocol::Iec101 => ApplicationNaiveProtocol::Iec101,
             ApplicationProtocol::Iec104 => ApplicationNaiveProtocol::Iec104,
             ApplicationProtocol::Opcua => ApplicationNaiveProtocol::Opcua,
             ApplicationProtocol::Http => ApplicationNaiveProtocol::Http,
             ApplicationProtocol::IsoOnTcp => ApplicationNaiveProtocol::IsoOnTcp,
             ApplicationProtocol::Goose => ApplicationNaiveProtocol::Goose,
             ApplicationProtocol::Sv => ApplicationNaiveProtocol::Sv,
+            ApplicationProtocol::Profinet => ApplicationNaiveProtocol::Profinet,
+            ApplicationProtocol::PnioCm => ApplicationNaiveProtocol::Profinet,
+            ApplicationProtocol::Melsec => ApplicationNaiveProtocol::Melsec,
+            ApplicationProtocol::Tristation => ApplicationNaiveProtocol::Tristation,
         }
     }
 }
@@ -177,14 +203,21 @@ impl From<&ApplicationProtocol> for ApplicationNaiveProtocol {
             ApplicationProtocol::FinsUdpRsp => ApplicationNaiveProtocol::Fins,
             ApplicationProtocol::Mms => ApplicationNaiveProtocol::Mms,
             ApplicationProtocol::S7comm => ApplicationNaiveProtocol::S7comm,
+            ApplicationProtocol::S7commPlus => ApplicationNaiveProtocol::S7commPlus,
+            ApplicationProtocol::Enip => ApplicationNaiveProtocol::Enip,
             ApplicationProtocol::Bacnet => ApplicationNaiveProtocol::Bacnet,
             ApplicationProtocol::Dnp3 => ApplicationNaiveProtocol::Dnp3,
+            ApplicationProtocol::Iec101 => ApplicationNaiveProtocol::Iec101,
             ApplicationProtocol::Iec104 => ApplicationNaiveProtocol::Iec104,
             ApplicationProtocol::Opcua => ApplicationNaiveProtocol::Opcua,
             ApplicationProtocol::Http => ApplicationNaiveProtocol::Http,
             ApplicationProtocol::IsoOnTcp => ApplicationNaiveProtocol::IsoOnTcp,
             ApplicationProtocol::Goose => ApplicationNaiveProtocol::Goose,
             ApplicationProtocol::Sv => ApplicationNaiveProtocol::Sv,
+            ApplicationProtocol::Profinet => ApplicationNaiveProtocol::Profinet,
+            ApplicationProtocol::PnioCm => ApplicationNaiveProtocol::Profinet,
+            ApplicationProtocol::Melsec => ApplicationNaiveProtocol::Melsec,
+            ApplicationProtocol::Tristation => ApplicationNaiveProtocol::Tristation,
         }
     }
 }
@@ -212,8 +245,8 @@ impl<'a> From<NetworkLayer<'a>> for NetworkProtocol {
         match net_layer {
             NetworkLayer::Ipv4(_) => NetworkProtocol::Ipv4,
             NetworkLayer::Ipv6(_) => NetworkProtocol::Ipv6,
-            NetworkLayer::Goose(_) => NetworkProtocol::Goose,
             NetworkLayer::Vlan(_) => NetworkProtocol::Vlan,
+            NetworkLayer::Empty => NetworkProtocol::Empty,
         }
     }
 }
@@ -231,7 +264,7 @@ impl<'a> From<TransportLayer<'a>> for TransportProtocol {
         match trans_layer {
             TransportLayer::Tcp(_) => TransportProtocol::Tcp,
             TransportLayer::Udp(_) => TransportProtocol::Udp,
-            TransportLayer::Sv(_) => TransportProtocol::Sv,
+            TransportLayer::Empty => TransportProtocol::Empty,
         }
     }
 }
@@ -255,14 +288,21 @@ impl<'a> From<ApplicationLayer<'a>> for ApplicationProtocol {
             ApplicationLayer::FinsUdpRsp(_) => ApplicationProtocol::FinsUdpRsp,
             ApplicationLayer::Mms(_) => ApplicationProtocol::Mms,
             ApplicationLayer::S7comm(_) => ApplicationProtocol::S7comm,
+            ApplicationLayer::S7commPlus(_) => ApplicationProtocol::S7commPlus,
+            ApplicationLayer::Enip(_) => ApplicationProtocol::Enip,
             ApplicationLayer::Bacnet(_) => ApplicationProtocol::Bacnet,
             ApplicationLayer::Dnp3(_) => ApplicationProtocol::Dnp3,
+            ApplicationLayer::Iec101(_) => ApplicationProtocol::Iec101,
             ApplicationLayer::Iec104(_) => ApplicationProtocol::Iec104,
             ApplicationLayer::Opcua(_) => ApplicationProtocol::Opcua,
             ApplicationLayer::Http(_) => ApplicationProtocol::Http,
             ApplicationLayer::IsoOnTcp(_) => ApplicationProtocol::IsoOnTcp,
             ApplicationLayer::Goose(_) => ApplicationProtocol::Goose,
             ApplicationLayer::Sv(_) => ApplicationProtocol::Sv,
+            ApplicationLayer::Profinet(_) => ApplicationProtocol::Profinet,
+            ApplicationLayer::PnioCm(_) => ApplicationProtocol::PnioCm,
+            ApplicationLayer::Melsec(_) => ApplicationProtocol::Melsec,
+            ApplicationLayer::Tristation(_) => ApplicationProtocol::Tristation,
         }
     }
 }
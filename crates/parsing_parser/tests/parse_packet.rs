@@ -3,7 +3,43 @@ use pcap_parser::{LegacyPcapReader, PcapBlockOwned, PcapError};
 
 use std::fs::File;
 
-use parsing_parser::{ParseError, QuinPacket, QuinPacketOptions};
+use parsing_parser::parsers::bacnet::{
+    ApduInfo, ApduOption, AtomicWriteFileAccess, BacnetObjectIdentifier, BacnetValue,
+    ConfirmedServiceRequest, UnconfirmedServiceRequest, WhoHasObject,
+};
+use parsing_parser::parsers::cip::{self, CipMessage, CipRequestData, CipResponseData};
+use parsing_parser::parsers::enip::{self, CpfItemData, EnipData, EnipHeader};
+use parsing_parser::parsers::fins::FinsArea;
+use parsing_parser::parsers::{fins_command, fins_response, fins_tcp_req, FinsFrame, FinsTransactions};
+use parsing_parser::parsers::iec101::Iec101Frame;
+use parsing_parser::parsers::iec104::{parse_iec104_header, IoaTypeEnum, TypeBlock};
+use parsing_parser::parsers::mms::{
+    AccessResult, ConfirmedServiceRequestEnum, ConfirmedServiceResponse, ConfirmedServiceResponseEnum,
+    ListOfIdentifier, MmsPduEnum, ObjectClass, ObjectScope, UnConfirmedEnum,
+};
+use parsing_parser::parsers::{goose, GooseData, MmsData};
+use parsing_parser::parsers::sv::{self, SvChannelMap, SvQuality, SvSample};
+use parsing_parser::parsers::opcua::{
+    LocalizedText, MessageTypeEnum, MsgVariantInfo, NodeId, NodeIdentifier, OpcuaVariant,
+    ServiceEnum, ServiceNodeidInfo, UserIdentityToken,
+};
+use parsing_parser::parsers::{OpcuaHeader, OpcuaSecureChannels};
+use parsing_parser::parsers::melsec::{self, MelsecDevice, MelsecFrameType, MelsecRequestData};
+use parsing_parser::parsers::pnio_cm::{self, PnioBlockData};
+use parsing_parser::parsers::profinet::{self, DcpBlockValue, ProfinetPdu};
+use parsing_parser::parsers::modbus_rsp::{self, MeiRspData};
+use parsing_parser::parsers::s7comm::{Parameter, S7Timestamp, S7commHeader, UserdataData};
+use parsing_parser::parsers::s7comm_plus::{self, PValue, S7commPlusData, S7commPlusItem};
+use parsing_parser::parsers::tristation;
+use parsing_parser::parsers::Dnp3Reassembler;
+use parsing_parser::parsers::http::{
+    parse_http_header, HTTP_MAX_HEADERS, HTTP_MAX_HEADER_SIZE, HTTP_MAX_PENDING_REQUESTS,
+};
+use parsing_parser::parsers::{HttpDirection, HttpFlow, HttpFlowMessage, HttpMessage};
+use parsing_parser::{
+    ApplicationLayer, HttpOptions, IecAsduOptions, L5Packet, MelsecOptions, ParseError, PortList,
+    QuinFlow, QuinPacket, QuinPacketOptions, UtcTime,
+};
 
 fn parse_pcap(path: &str) -> Result<(), ()> {
     let file = File::open(path).unwrap();
@@ -57,6 +93,78 @@ fn parse_fins_pcap() {
     assert!(parse_pcap(fins_pcap_path).is_ok());
 }
 
+#[test]
+fn parse_fins_tcp_udp_pcap() {
+    let packets = read_pcap("./tests/fins_tcp_udp.pcap");
+    let packets: Vec<QuinPacket> = packets
+        .iter()
+        .map(|packet| QuinPacket::parse_from_stream(packet, &QuinPacketOptions::default()))
+        .collect();
+    let l5s: Vec<&L5Packet> = packets
+        .iter()
+        .filter_map(|packet| match packet {
+            QuinPacket::L5(l5) if l5.error.is_none() => Some(l5),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(l5s.len(), 8);
+
+    // FINS/TCP 握手不含 FINS 帧
+    assert!(matches!(
+        &l5s[0].application_layer,
+        ApplicationLayer::FinsTcpReq(fins) if matches!(fins.state, fins_tcp_req::State::Connecting { .. })
+    ));
+    assert_eq!(FinsFrame::from_layer(&l5s[0].application_layer), None);
+    assert_eq!(FinsFrame::from_layer(&l5s[1].application_layer), None);
+
+    // TCP 与 UDP 上的命令使用同一模型
+    match FinsFrame::from_layer(&l5s[2].application_layer) {
+        Some(FinsFrame::Command(command)) => {
+            assert_eq!(command.header.sid, 0x11);
+            assert_eq!(command.cmd_type.order.areas(), vec![FinsArea { code: 0x82, address: 100 }]);
+        }
+        other => panic!("unexpected frame {:?}", other),
+    }
+    match FinsFrame::from_layer(&l5s[4].application_layer) {
+        Some(FinsFrame::Command(command)) => {
+            assert!(matches!(
+                command.cmd_type.order,
+                fins_command::Order::MemoryAreaWrite { memory_area_code: 0x82, beginning_address: 200, .. }
+            ));
+        }
+        other => panic!("unexpected frame {:?}", other),
+    }
+
+    match FinsFrame::from_layer(&l5s[3].application_layer) {
+        Some(FinsFrame::Response(response)) => assert!(response.is_normal_completion()),
+        other => panic!("unexpected frame {:?}", other),
+    }
+    match FinsFrame::from_layer(&l5s[5].application_layer) {
+        Some(FinsFrame::Response(response)) => assert_eq!(response.end_code(), 0x2108),
+        other => panic!("unexpected frame {:?}", other),
+    }
+    // 异常结束且没有响应数据
+    match FinsFrame::from_layer(&l5s[7].application_layer) {
+        Some(FinsFrame::Response(response)) => {
+            assert_eq!(response.end_code(), 0x1101);
+            assert!(matches!(response.cmd_type.order, fins_response::Order::Abnormal { .. }));
+        }
+        other => panic!("unexpected frame {:?}", other),
+    }
+
+    // 响应按 SID 关联到请求
+    let mut transactions = FinsTransactions::new();
+    for l5 in &l5s {
+        transactions.push(l5);
+    }
+    let request = transactions.request(l5s[3]).unwrap();
+    assert_eq!(request.cmd_code, 0x0101);
+    assert_eq!(request.areas, vec![FinsArea { code: 0x82, address: 100 }]);
+    let request = transactions.request(l5s[5]).unwrap();
+    assert_eq!(request.areas, vec![FinsArea { code: 0x82, address: 200 }]);
+    assert_eq!(transactions.request(l5s[4]), None);
+}
+
 #[test]
 fn parse_s7comm_pcap() {
     let s7comm_pcap_path = "./tests/s7comm_all.pcap";
@@ -64,6 +172,135 @@ fn parse_s7comm_pcap() {
     assert!(parse_pcap(s7comm_pcap_path).is_ok());
 }
 
+#[test]
+fn parse_bacnet_services_pcap() {
+    let packets = read_pcap("./tests/bacnet_services.pcap");
+    let packets: Vec<QuinPacket> = packets
+        .iter()
+        .map(|packet| QuinPacket::parse_from_stream(packet, &QuinPacketOptions::default()))
+        .collect();
+    let apdus: Vec<&ApduInfo> = packets
+        .iter()
+        .filter_map(|packet| match packet {
+            QuinPacket::L5(L5Packet {
+                application_layer: ApplicationLayer::Bacnet(bacnet),
+                error: None,
+                ..
+            }) => match &bacnet.apdu_option {
+                ApduOption::Apdu { apdu_info, .. } => Some(apdu_info),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+    assert_eq!(apdus.len(), 10);
+
+    let analog_value_1 = BacnetObjectIdentifier { object_type: 2, instance_number: 1 };
+    match apdus[0] {
+        ApduInfo::ComfirmedServiceRequest {
+            confirmed_service_request: ConfirmedServiceRequest::WriteProperty { object_identifier, property },
+            ..
+        } => {
+            assert_eq!(*object_identifier, analog_value_1);
+            assert_eq!(property.property_identifier, 85);
+            assert_eq!(property.value, vec![BacnetValue::Real(72.5)]);
+            assert_eq!(property.priority, Some(8));
+        }
+        o => panic!("unexpected apdu: {:?}", o),
+    }
+    assert!(matches!(apdus[1], ApduInfo::SimpleAckPdu { invoke_id: 1, service_choice: 0x0f }));
+
+    match apdus[2] {
+        ApduInfo::ComfirmedServiceRequest {
+            confirmed_service_request: ConfirmedServiceRequest::ReadPropertyMultiple { read_access_specs },
+            ..
+        } => {
+            assert_eq!(read_access_specs.len(), 2);
+            assert_eq!(read_access_specs[0].property_references.len(), 2);
+            assert_eq!(read_access_specs[1].object_identifier.instance_number, 1234);
+        }
+        o => panic!("unexpected apdu: {:?}", o),
+    }
+
+    match apdus[3] {
+        ApduInfo::ComfirmedServiceRequest {
+            confirmed_service_request: ConfirmedServiceRequest::WritePropertyMultiple { write_access_specs },
+            ..
+        } => {
+            let properties = &write_access_specs[0].properties;
+            assert_eq!(properties[0].value, vec![BacnetValue::Enumerated(1)]);
+            assert_eq!(properties[0].priority, Some(1));
+            assert_eq!(properties[1].value, vec![BacnetValue::Boolean(true)]);
+        }
+        o => panic!("unexpected apdu: {:?}", o),
+    }
+
+    assert!(matches!(
+        apdus[4],
+        ApduInfo::ComfirmedServiceRequest {
+            confirmed_service_request: ConfirmedServiceRequest::DeviceCommunicationControl {
+                time_duration: Some(60),
+                enable_disable: 1,
+                password: Some(b"secret"),
+            },
+            ..
+        }
+    ));
+    assert!(matches!(
+        apdus[5],
+        ApduInfo::ComfirmedServiceRequest {
+            confirmed_service_request: ConfirmedServiceRequest::ReinitializeDevice {
+                reinitialized_state: 1,
+                password: Some(b"admin"),
+            },
+            ..
+        }
+    ));
+    assert!(matches!(
+        apdus[6],
+        ApduInfo::ComfirmedServiceRequest {
+            confirmed_service_request: ConfirmedServiceRequest::AtomicWriteFile {
+                access: AtomicWriteFileAccess::Stream { file_start_position: 0, file_data: [1, 2, 3, 4] },
+                ..
+            },
+            ..
+        }
+    ));
+
+    assert!(matches!(
+        apdus[7],
+        ApduInfo::UnconfirmedServiceRequest {
+            unconfirmed_service_request: UnconfirmedServiceRequest::WhoIs {
+                device_instance_range_low_limit: Some(100),
+                device_instance_range_high_limit: Some(200),
+            },
+            ..
+        }
+    ));
+    assert!(matches!(
+        apdus[8],
+        ApduInfo::UnconfirmedServiceRequest {
+            unconfirmed_service_request: UnconfirmedServiceRequest::IAm {
+                device_identifier: BacnetObjectIdentifier { object_type: 8, instance_number: 1234 },
+                max_apdu_length_accepted: 1476,
+                vendor_id: 260,
+                ..
+            },
+            ..
+        }
+    ));
+    assert!(matches!(
+        apdus[9],
+        ApduInfo::UnconfirmedServiceRequest {
+            unconfirmed_service_request: UnconfirmedServiceRequest::WhoHas {
+                object: WhoHasObject::Name(b"Zone1 Temp"),
+                ..
+            },
+            ..
+        }
+    ));
+}
+
 #[test]
 fn parse_dnp3_pcap() {
     tracing_subscriber::fmt::init();
@@ -85,6 +322,47 @@ fn parse_iec104_pcap() {
     assert!(parse_pcap(iec104_pcap_path).is_ok());
 }
 
+#[test]
+fn parse_iec104_measured_value() {
+    // IEC 60870-5-4 规定多字节数值低字节在前：SVA=1000，R32=50.0，BCR=123456
+    let input = [
+        0x68, 0x10, 0x00, 0x00, 0x00, 0x00, 0x0b, 0x01, 0x03, 0x00, 0x01, 0x00, 0x01, 0x40, 0x00,
+        0xe8, 0x03, 0x80, 0x68, 0x12, 0x02, 0x00, 0x00, 0x00, 0x0d, 0x01, 0x03, 0x00, 0x01, 0x00,
+        0x02, 0x40, 0x00, 0x00, 0x00, 0x48, 0x42, 0x00, 0x68, 0x12, 0x04, 0x00, 0x00, 0x00, 0x0f,
+        0x01, 0x03, 0x00, 0x01, 0x00, 0x01, 0x64, 0x00, 0x40, 0xe2, 0x01, 0x00, 0x05,
+    ];
+    let (rest, header) = parse_iec104_header(&input, &IecAsduOptions::default()).unwrap();
+    assert!(rest.is_empty());
+
+    let values: Vec<IoaTypeEnum> = header
+        .iec104_blocks
+        .into_iter()
+        .map(|block| match block.type_block {
+            TypeBlock::TypeI { mut iec_asdu, .. } => iec_asdu.ioa_array.remove(0).ioa_type_enum,
+            type_block => panic!("unexpected {:?}", type_block),
+        })
+        .collect();
+    assert_eq!(
+        values[0],
+        IoaTypeEnum::M_ME_NB_1 { sva: 1000, qds_iv: 1, qds_nt: 0, qds_sb: 0, qds_bl: 0, qds_ov: 0 }
+    );
+    assert_eq!(
+        values[1],
+        IoaTypeEnum::M_ME_NC_1 {
+            flt: 50.0f32.to_bits(),
+            qds_iv: 0,
+            qds_nt: 0,
+            qds_sb: 0,
+            qds_bl: 0,
+            qds_ov: 0
+        }
+    );
+    assert_eq!(
+        values[2],
+        IoaTypeEnum::M_IT_NA_1 { bcr_count: 123456, bcr_iv: 0, bcr_ca: 0, bcr_cy: 0, bcr_sq: 5 }
+    );
+}
+
 #[test]
 fn parse_opcua_pcap() {
     let opcua_pcap_path = [
@@ -93,6 +371,8 @@ fn parse_opcua_pcap() {
         "./tests/opcua_error.pcap",
         "./tests/opcua_hello.pcap",
         "./tests/opcua_all.pcap",
+        "./tests/opcua_write_call.pcap",
+        "./tests/opcua_secure_channel.pcap",
     ];
 
     for path in opcua_pcap_path {
@@ -100,6 +380,264 @@ fn parse_opcua_pcap() {
     }
 }
 
+fn opcua_service<'a>(l5: &'a L5Packet) -> Option<&'a ServiceEnum<'a>> {
+    if let ApplicationLayer::Opcua(OpcuaHeader {
+        message_type_enum:
+            MessageTypeEnum::Message {
+                msg_variant_info: MsgVariantInfo::Service { service_nodeid_info, .. },
+                ..
+            },
+        ..
+    }) = &l5.application_layer
+    {
+        return match service_nodeid_info {
+            ServiceNodeidInfo::TB { service_enum, .. }
+            | ServiceNodeidInfo::FB { service_enum, .. }
+            | ServiceNodeidInfo::Numeric { service_enum, .. } => Some(service_enum),
+            _ => None,
+        };
+    }
+    None
+}
+
+#[test]
+fn parse_opcua_write_call_pcap() {
+    let packets = read_pcap("./tests/opcua_write_call.pcap");
+    let packets: Vec<QuinPacket> = packets
+        .iter()
+        .map(|packet| QuinPacket::parse_from_stream(packet, &QuinPacketOptions::default()))
+        .collect();
+    let services: Vec<&ServiceEnum> = packets
+        .iter()
+        .filter_map(|packet| match packet {
+            QuinPacket::L5(l5) => opcua_service(l5),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(services.len(), 3);
+
+    // Write ns=2;s=Line1.Setpoint Value = 42.5
+    if let ServiceEnum::WriteRequest { nodes_to_write, .. } = services[0] {
+        assert_eq!(nodes_to_write.len(), 1);
+        assert_eq!(
+            nodes_to_write[0].node_id,
+            NodeId { namespace: 2, identifier: NodeIdentifier::String("Line1.Setpoint") }
+        );
+        assert_eq!(nodes_to_write[0].attribute_id, 13);
+        assert_eq!(nodes_to_write[0].value.value, Some(OpcuaVariant::Double(42.5)));
+    } else {
+        panic!("unexpected service: {:?}", services[0]);
+    }
+
+    assert!(matches!(
+        services[1],
+        ServiceEnum::WriteResponse { results, .. } if results == &vec![0]
+    ));
+
+    // Call ns=2;i=5001 / ns=2;i=5002 (3, "start")
+    if let ServiceEnum::CallRequest { methods_to_call, .. } = services[2] {
+        assert_eq!(methods_to_call[0].object_id.identifier, NodeIdentifier::Numeric(5001));
+        assert_eq!(methods_to_call[0].method_id.identifier, NodeIdentifier::Numeric(5002));
+        assert_eq!(
+            methods_to_call[0].input_arguments,
+            vec![OpcuaVariant::Int32(3), OpcuaVariant::String("start")]
+        );
+    } else {
+        panic!("unexpected service: {:?}", services[2]);
+    }
+}
+
+#[test]
+fn parse_opcua_session_pcap() {
+    let packets = read_pcap("./tests/opcua_all.pcap");
+    let packets: Vec<QuinPacket> = packets
+        .iter()
+        .map(|packet| QuinPacket::parse_from_stream(packet, &QuinPacketOptions::default()))
+        .collect();
+    let mut session_name = None;
+    let mut user_identity = None;
+    let mut read_value = None;
+    for packet in &packets {
+        if let QuinPacket::L5(l5) = packet {
+            match opcua_service(l5) {
+                Some(ServiceEnum::CreateSessionRequest { session_name: name, endpoint_url, .. }) => {
+                    assert_eq!(*endpoint_url, "opc.tcp://localhost:48400");
+                    session_name = session_name.or(Some(*name));
+                }
+                Some(ServiceEnum::ActivateSessionRequest { user_identity_token, .. }) => {
+                    user_identity = user_identity.or(Some(user_identity_token.clone()));
+                }
+                Some(ServiceEnum::ReadResponse { results, .. }) => {
+                    read_value = read_value.or(results[0].value.clone());
+                }
+                _ => {}
+            }
+        }
+    }
+
+    assert_eq!(session_name, Some("Pure Python Client Session1"));
+    assert_eq!(
+        user_identity,
+        Some(UserIdentityToken::Anonymous { policy_id: "anonymous" })
+    );
+    assert_eq!(
+        read_value,
+        Some(OpcuaVariant::LocalizedText(LocalizedText { locale: None, text: Some("Root") }))
+    );
+}
+
+#[test]
+fn parse_opcua_secure_channel_pcap() {
+    // 通道7: 安全策略None，WriteRequest 拆成 'C' + 'F' 两个块；通道8: Basic256Sha256 加密；
+    // 通道9: Basic256Sha256 Sign，WriteRequest 明文附签名
+    let packets = read_pcap("./tests/opcua_secure_channel.pcap");
+    let mut channels = OpcuaSecureChannels::new();
+    let mut messages = Vec::new();
+    let mut undecoded = 0;
+    for packet in &packets {
+        let packet = QuinPacket::parse_from_stream(packet, &QuinPacketOptions::default());
+        if let QuinPacket::L5(l5) = packet {
+            if let ApplicationLayer::Opcua(opcua) = &l5.application_layer {
+                if let MessageTypeEnum::Message {
+                    msg_variant_info: MsgVariantInfo::Undecoded { .. },
+                    ..
+                } = opcua.message_type_enum
+                {
+                    undecoded += 1;
+                }
+                messages.extend(channels.push(opcua));
+            }
+        }
+    }
+    // 两个块单独均无法解码，加密消息同样无法解码
+    assert_eq!(undecoded, 3);
+
+    let plain = channels.channel(7).unwrap();
+    assert!(plain.is_policy_none());
+    assert_eq!(plain.security_mode, Some(1));
+    assert_eq!(plain.token_ids, vec![9]);
+    assert!(!plain.is_encrypted());
+
+    // OpenSecureChannel 请求已加密，模式未知，不能确定正文加密
+    let encrypted = channels.channel(8).unwrap();
+    assert_eq!(
+        encrypted.security_policy_uri,
+        "http://opcfoundation.org/UA/SecurityPolicy#Basic256Sha256"
+    );
+    assert_eq!(encrypted.receiver_certificate_thumbprint, (0..20).collect::<Vec<u8>>());
+    assert_eq!(encrypted.security_mode, None);
+    assert!(!encrypted.is_encrypted());
+
+    // 模式未知的通道上消息照常输出，加密的正文无法解码
+    assert_eq!(
+        messages.iter().map(|message| message.secure_channel_id).collect::<Vec<_>>(),
+        vec![7, 8, 9]
+    );
+    assert!(messages[1].msg_variant_info().is_none());
+    assert!(matches!(
+        messages[2].msg_variant_info().as_ref().and_then(|info| info.service()),
+        Some(ServiceEnum::WriteRequest { .. })
+    ));
+    assert_eq!(messages[0].secure_channel_id, 7);
+    assert_eq!(messages[0].security_request_id, 4);
+    let info = messages[0].msg_variant_info().unwrap();
+    if let Some(ServiceEnum::WriteRequest { nodes_to_write, .. }) = info.service() {
+        assert_eq!(
+            nodes_to_write[0].node_id,
+            NodeId { namespace: 2, identifier: NodeIdentifier::String("Line1.Setpoint") }
+        );
+        assert_eq!(nodes_to_write[0].value.value, Some(OpcuaVariant::Double(42.5)));
+    } else {
+        panic!("unexpected message: {:?}", info);
+    }
+
+    // 真实抓包: 安全策略None，模式None
+    let packets = read_pcap("./tests/opcua_all.pcap");
+    let mut channels = OpcuaSecureChannels::new();
+    let mut channel_ids = Vec::new();
+    for packet in &packets {
+        let packet = QuinPacket::parse_from_stream(packet, &QuinPacketOptions::default());
+        if let QuinPacket::L5(l5) = packet {
+            if let ApplicationLayer::Opcua(opcua) = &l5.application_layer {
+                if let Some(message) = channels.push(opcua) {
+                    channel_ids.push(message.secure_channel_id);
+                }
+            }
+        }
+    }
+    assert!(!channel_ids.is_empty());
+    let channel = channels.channel(channel_ids[0]).unwrap();
+    assert!(channel.is_policy_none());
+    assert_eq!(channel.security_mode, Some(1));
+    assert!(!channel.token_ids.is_empty());
+}
+
+#[test]
+fn parse_opcua_secure_channel_flow() {
+    // 在连接上下文中解析时，最终块以重组后的完整正文解码；模式未知的通道上正文照常尝试解码
+    let packets = read_pcap("./tests/opcua_secure_channel.pcap");
+    let mut flow = QuinFlow::new();
+    let mut services = Vec::new();
+    let mut undecoded = Vec::new();
+    for packet in &packets {
+        let packet = QuinPacket::parse_from_flow(packet, &QuinPacketOptions::default(), &mut flow);
+        if let QuinPacket::L5(l5) = packet {
+            if let ApplicationLayer::Opcua(OpcuaHeader {
+                message_type_enum:
+                    MessageTypeEnum::Message {
+                        secure_channel_id,
+                        chunk_type,
+                        msg_variant_info,
+                        ..
+                    },
+                ..
+            }) = l5.application_layer
+            {
+                match msg_variant_info {
+                    MsgVariantInfo::Undecoded { .. } => undecoded.push((secure_channel_id, chunk_type)),
+                    info => services.push((
+                        secure_channel_id,
+                        matches!(
+                            info.service(),
+                            Some(ServiceEnum::WriteRequest { nodes_to_write, .. })
+                                if nodes_to_write[0].value.value == Some(OpcuaVariant::Double(42.5))
+                        ),
+                    )),
+                }
+            }
+        }
+    }
+    assert_eq!(undecoded, vec![(7, b'C'), (8, b'F')]);
+    // 通道7 的 WriteRequest 在最终块上解码，Sign 模式的通道9 同样解码
+    assert_eq!(services, vec![(7, true), (9, true)]);
+}
+
+#[test]
+fn parse_opcua_pending_chunks_limit() {
+    let chunk = |chunk_type: u8, security_request_id: u32, body: &'static [u8]| OpcuaHeader {
+        message_type: 0x4d5347,
+        message_type_enum: MessageTypeEnum::Message {
+            chunk_type,
+            transport_size: 24 + body.len() as u32,
+            secure_channel_id: 1,
+            security_token_id: 1,
+            security_sequence_number: security_request_id,
+            security_request_id,
+            body,
+            msg_variant_info: MsgVariantInfo::Undecoded { data: body },
+        },
+    };
+    let mut channels = OpcuaSecureChannels::new();
+    for security_request_id in 0..17 {
+        assert_eq!(channels.push(&chunk(b'C', security_request_id, b"ab")), None);
+    }
+    // 超出上限时最早的消息被淘汰，其最终块只包含自身数据
+    let message = channels.push(&chunk(b'F', 0, b"cd")).unwrap();
+    assert_eq!(message.body, b"cd");
+    let message = channels.push(&chunk(b'F', 16, b"cd")).unwrap();
+    assert_eq!(message.body, b"abcd");
+}
+
 #[test]
 fn parse_mms_pcap() {
     let mms_pcap_path = "./tests/mms_3.pcap";
@@ -114,6 +652,489 @@ fn parse_http() {
     assert!(parse_pcap(http_pcap_path).is_ok());
 }
 
+#[test]
+fn parse_enip_cip_pcap() {
+    let packets = read_pcap("./tests/enip_cip.pcap");
+    let packets: Vec<QuinPacket> = packets
+        .iter()
+        .map(|packet| QuinPacket::parse_from_stream(packet, &QuinPacketOptions::default()))
+        .collect();
+    let enips: Vec<&EnipHeader> = packets
+        .iter()
+        .filter_map(|packet| match packet {
+            QuinPacket::L5(L5Packet {
+                application_layer: ApplicationLayer::Enip(enip),
+                error: None,
+                ..
+            }) => Some(enip),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(enips.len(), 12);
+
+    assert_eq!(enips[0].command(), Some(enip::ENIP_REGISTER_SESSION));
+    assert!(matches!(enips[0].data, EnipData::RegisterSession { protocol_version: 1, .. }));
+
+    // Forward Open 请求与应答
+    match enips[2].cip_messages()[..] {
+        [CipMessage::Request {
+            service: cip::CIP_FORWARD_OPEN,
+            ref path,
+            data: CipRequestData::ForwardOpen(ref forward_open),
+        }] => {
+            assert_eq!(path.class(), Some(cip::CIP_CLASS_CONNECTION_MANAGER));
+            assert_eq!(forward_open.t_o_connection_id, 0x80000001);
+            assert_eq!(forward_open.connection_path.class(), Some(2));
+        }
+        ref other => panic!("unexpected messages {:?}", other),
+    }
+    assert!(matches!(
+        enips[3].cip_messages()[..],
+        [CipMessage::Response {
+            data: CipResponseData::ForwardOpen { o_t_connection_id: 0x11223344, .. },
+            ..
+        }]
+    ));
+
+    // Multiple Service Packet 内嵌的 Logix 标签读写
+    let messages = enips[4].cip_messages();
+    assert_eq!(messages.len(), 3);
+    assert_eq!(messages[1].path().and_then(|path| path.tag_name()).as_deref(), Some("Motor.Speed"));
+    assert_eq!(messages[2].path().and_then(|path| path.tag_name()).as_deref(), Some("Valve[3]"));
+    assert!(matches!(
+        messages[2],
+        CipMessage::Request {
+            data: CipRequestData::WriteTag { data_type: 0xc4, elements: 1, .. },
+            ..
+        }
+    ));
+    assert!(matches!(
+        enips[5].cip_messages()[1],
+        CipMessage::Response { data: CipResponseData::ReadTag { data_type: 0xc4, data: [0xdc, 0x05, 0, 0] }, .. }
+    ));
+
+    // Unconnected Send 内嵌请求与错误状态
+    let messages = enips[6].cip_messages();
+    assert_eq!(
+        messages[1].path().and_then(|path| path.tag_name()).as_deref(),
+        Some("Program:Main.Counter")
+    );
+    assert_eq!(enips[7].cip_messages()[0].general_status(), Some(0x04));
+
+    // ListIdentity 应答
+    match &enips[9].items()[0].data {
+        CpfItemData::Identity(identity) => {
+            assert_eq!(identity.vendor_id, 1);
+            assert_eq!(identity.product_name, "1756-L71/B LOGIX5571");
+        }
+        other => panic!("unexpected item {:?}", other),
+    }
+
+    // UDP 2222 隐式 I/O 没有封装头
+    assert_eq!(enips[10].command(), None);
+    assert!(matches!(enips[10].items()[1].data, CpfItemData::IoData(_)));
+
+    assert!(matches!(
+        enips[11].cip_messages()[0],
+        CipMessage::Request { data: CipRequestData::ForwardClose { connection_serial_number: 0x0101, .. }, .. }
+    ));
+}
+
+#[test]
+fn parse_cip_nested_multiple_service_packet() {
+    // 逐层包裹 1000 层 Multiple Service Packet，最内层为 Read Tag
+    let mut frame = vec![0x4c, 0x02, 0x91, 0x03, b'T', b'a', b'g', 0x00, 0x01, 0x00];
+    for _ in 0..1000 {
+        let mut outer = vec![0x0a, 0x02, 0x20, 0x02, 0x24, 0x01, 0x01, 0x00, 0x04, 0x00];
+        outer.extend_from_slice(&frame);
+        frame = outer;
+    }
+
+    let (_, mut message) = cip::parse_cip_message(&frame).expect("nested msp should parse");
+    let mut depth = 0;
+    loop {
+        match message {
+            CipMessage::Request { data: CipRequestData::MultipleServicePacket { mut services }, .. } => {
+                assert_eq!(services.len(), 1);
+                message = services.remove(0);
+                depth += 1;
+            }
+            CipMessage::Request { service: cip::CIP_MULTIPLE_SERVICE_PACKET, data: CipRequestData::Raw(_), .. } => break,
+            other => panic!("unexpected message {:?}", other),
+        }
+    }
+    assert_eq!(depth, 4);
+}
+
+#[test]
+fn parse_profinet_pcap() {
+    let packets = read_pcap("./tests/profinet.pcap");
+    let packets: Vec<QuinPacket> = packets
+        .iter()
+        .map(|packet| QuinPacket::parse_from_stream(packet, &QuinPacketOptions::default()))
+        .collect();
+    let layers: Vec<&ApplicationLayer> = packets
+        .iter()
+        .filter_map(|packet| match packet {
+            QuinPacket::L5(L5Packet { application_layer, error: None, .. }) => Some(application_layer),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(layers.len(), 14);
+    let profinets: Vec<_> = layers
+        .iter()
+        .filter_map(|layer| match layer {
+            ApplicationLayer::Profinet(profinet) => Some(profinet),
+            _ => None,
+        })
+        .collect();
+    let pnio_cms: Vec<_> = layers
+        .iter()
+        .filter_map(|layer| match layer {
+            ApplicationLayer::PnioCm(pnio_cm) => Some(pnio_cm),
+            _ => None,
+        })
+        .collect();
+    assert_eq!((profinets.len(), pnio_cms.len()), (8, 6));
+
+    // DCP Identify 应答中的站名、设备 ID 与 IP
+    let identify = profinets[1].pdu.dcp().unwrap();
+    assert_eq!(profinets[1].frame_id, profinet::PN_FRAME_ID_DCP_IDENTIFY_RESPONSE);
+    assert_eq!(identify.name_of_station(), Some("plc-cell1"));
+    assert_eq!(identify.blocks[1].value, DcpBlockValue::DeviceId { vendor_id: 0x002a, device_id: 0x0104 });
+
+    // DCP Set 改名与改地址
+    let set_name = profinets[2].pdu.dcp().unwrap();
+    assert!(set_name.is_request());
+    assert_eq!(set_name.service_id, profinet::DCP_SERVICE_SET);
+    assert_eq!(set_name.blocks[0].qualifier, Some(1));
+    assert_eq!(set_name.name_of_station(), Some("evil-station"));
+    match profinets[3].pdu.dcp().unwrap().blocks[0].value {
+        DcpBlockValue::IpParameter { address, .. } => assert_eq!(address.to_string(), "10.6.6.6"),
+        ref other => panic!("unexpected block {:?}", other),
+    }
+    assert_eq!(
+        profinets[4].pdu.dcp().unwrap().blocks[0].value,
+        DcpBlockValue::ControlResponse { option: 2, suboption: 2, error: 0 }
+    );
+    assert_eq!(profinets[5].pdu.dcp().unwrap().blocks[1].value, DcpBlockValue::Empty);
+
+    // VLAN 上的 RT 周期帧
+    assert!(matches!(
+        profinets[6].pdu,
+        ProfinetPdu::Cyclic { data, cycle_counter: 0x1234, data_status: 0x35, .. } if data.len() == 40
+    ));
+    match &profinets[7].pdu {
+        ProfinetPdu::Alarm(alarm) => {
+            let notification = alarm.notification.as_ref().unwrap();
+            assert_eq!((notification.slot, notification.subslot), (1, 1));
+            assert_eq!(notification.alarm_type, 0x0001);
+        }
+        other => panic!("unexpected pdu {:?}", other),
+    }
+
+    // PNIO-CM Connect/Write/Read
+    assert_eq!(pnio_cms[0].rpc.opnum, pnio_cm::PNIO_OPNUM_CONNECT);
+    assert!(matches!(
+        pnio_cms[0].blocks()[0].data,
+        PnioBlockData::Ar { station_name: Some("plc-controller"), .. }
+    ));
+    assert!(!pnio_cms[1].is_request());
+    assert_eq!(pnio_cms[1].pnio.as_ref().unwrap().status, Some(0));
+    assert!(matches!(
+        pnio_cms[2].blocks()[0].data,
+        PnioBlockData::Record { index: 0x8030, record_data: [1, 2, 3, 4], .. }
+    ));
+    // 多记录写入展开为各条记录
+    let indexes: Vec<u16> = pnio_cms[3]
+        .blocks()
+        .iter()
+        .filter_map(|block| match block.data {
+            PnioBlockData::Record { index, .. } => Some(index),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(indexes, vec![pnio_cm::PNIO_INDEX_WRITE_MULTIPLE, 0x8030, 0x0100]);
+    assert_eq!(pnio_cms[4].rpc.opnum, pnio_cm::PNIO_OPNUM_READ);
+    assert!(matches!(
+        pnio_cms[5].blocks()[0].data,
+        PnioBlockData::Record { index: 0xaff0, record_data: b"IM0DATA!", .. }
+    ));
+}
+
+#[test]
+fn parse_melsec_pcap() {
+    let packets = read_pcap("./tests/melsec.pcap");
+    let parsed: Vec<QuinPacket> = packets
+        .iter()
+        .map(|packet| QuinPacket::parse_from_stream(packet, &QuinPacketOptions::default()))
+        .collect();
+    let melsecs: Vec<_> = parsed
+        .iter()
+        .filter_map(|packet| match packet {
+            QuinPacket::L5(L5Packet {
+                application_layer: ApplicationLayer::Melsec(melsec),
+                error: None,
+                ..
+            }) => Some(melsec),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(melsecs.len(), 12);
+
+    // 3E 批量读 D100 起 10 字及其响应
+    let request = melsecs[0].request().unwrap();
+    assert_eq!((melsecs[0].frame_type, melsecs[0].pc), (MelsecFrameType::E3, 0xff));
+    assert_eq!(request.command, melsec::MELSEC_BATCH_READ);
+    assert_eq!(
+        request.devices(),
+        vec![(MelsecDevice { code: melsec::MELSEC_DEVICE_D, number: 100 }, 10)]
+    );
+    assert_eq!(melsecs[1].end_code(), Some(0));
+
+    // 位单位批量写每字节 2 点
+    assert!(matches!(
+        melsecs[2].request().unwrap().data,
+        MelsecRequestData::BatchWrite { points: 8, values: [0x11, 0x00, 0x10, 0x01], .. }
+    ));
+
+    // 4E 随机写及异常响应
+    assert_eq!(melsecs[3].frame_type, MelsecFrameType::E4 { serial: 0x1234 });
+    let numbers: Vec<u32> = melsecs[3]
+        .request()
+        .unwrap()
+        .devices()
+        .iter()
+        .map(|(device, _)| device.number)
+        .collect();
+    assert_eq!(numbers, vec![200, 201, 300]);
+    assert_eq!(melsecs[4].end_code(), Some(0xc059));
+
+    // iQ-R 扩展软元件格式
+    assert_eq!(
+        melsecs[5].request().unwrap().devices()[1].0,
+        MelsecDevice { code: melsec::MELSEC_DEVICE_W, number: 0x100 }
+    );
+
+    // 远程操作与缓冲存储器写入
+    assert!(matches!(
+        melsecs[6].request().unwrap().data,
+        MelsecRequestData::RemoteRun { force: true, clear_mode: 0 }
+    ));
+    assert_eq!(melsecs[7].request().unwrap().command, melsec::MELSEC_REMOTE_STOP);
+    assert_eq!(melsecs[8].request().unwrap().command, melsec::MELSEC_REMOTE_RESET);
+    assert!(matches!(
+        melsecs[9].request().unwrap().data,
+        MelsecRequestData::MemoryWrite { address: 0x0780, length: 2, .. }
+    ));
+
+    // 多块批量读写
+    assert_eq!(melsecs[10].request().unwrap().devices().len(), 2);
+    assert_eq!(melsecs[10].request().unwrap().devices()[1].0.code, melsec::MELSEC_DEVICE_M);
+    assert_eq!(melsecs[11].request().unwrap().devices()[0].1, 5);
+
+    // 端口可配置
+    let options = QuinPacketOptions {
+        melsec: MelsecOptions { ports: PortList::from_slice(&[5006]) },
+        ..Default::default()
+    };
+    assert!(matches!(QuinPacket::parse_from_stream(&packets[0], &options), QuinPacket::L4(_)));
+    assert!(matches!(
+        QuinPacket::parse_from_stream(&packets[3], &options),
+        QuinPacket::L5(L5Packet { application_layer: ApplicationLayer::Melsec(_), .. })
+    ));
+}
+
+#[test]
+fn parse_tristation_pcap() {
+    let packets = read_pcap("./tests/tristation.pcap");
+    let packets: Vec<QuinPacket> = packets
+        .iter()
+        .map(|packet| QuinPacket::parse_from_stream(packet, &QuinPacketOptions::default()))
+        .collect();
+    let tristations: Vec<_> = packets
+        .iter()
+        .filter_map(|packet| match packet {
+            QuinPacket::L5(L5Packet {
+                application_layer: ApplicationLayer::Tristation(tristation),
+                error: None,
+                ..
+            }) => Some(tristation),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(tristations.len(), 10);
+
+    assert_eq!(tristations[0].message_type, tristation::TRISTATION_CONNECT_REQUEST);
+    assert_eq!(tristations[0].command, None);
+    assert_eq!(tristations[2].command_code(), Some(tristation::TRISTATION_GET_CP_STATUS));
+    assert!(!tristations[2].is_program_change());
+
+    // 分配程序、下载变更、停机均视为程序变更
+    let allocate = tristations[4].command.as_ref().unwrap();
+    assert_eq!((allocate.code, allocate.sequence), (tristation::TRISTATION_ALLOCATE_PROGRAM, 2));
+    assert_eq!(allocate.data.len(), 16);
+    assert!(tristations[4..7].iter().all(|tristation| tristation.is_program_change()));
+    assert_eq!(tristations[7].command_code(), Some(tristation::TRISTATION_GET_POINT_VALUES));
+
+    assert_eq!(tristations[8].message_type, tristation::TRISTATION_DISCONNECT_REQUEST);
+    assert_eq!(tristations[9].message_type, tristation::TRISTATION_PING);
+}
+
+#[test]
+fn parse_http_pipeline_pcap() {
+    let packets = read_pcap("./tests/http_pipeline.pcap");
+    let options = QuinPacketOptions::default();
+
+    // 24 个以上的头部与流水线请求
+    match QuinPacket::parse_from_stream(&packets[0], &options) {
+        QuinPacket::L5(l5) => match &l5.application_layer {
+            ApplicationLayer::Http(http) => {
+                assert_eq!(http.messages.len(), 2);
+                assert_eq!(http.messages[0].headers().len(), 25);
+                assert_eq!(http.messages[0].header("x-hmi-23"), Some(&b"value23"[..]));
+                assert!(matches!(http.messages[1], HttpMessage::Request { path: "/io.json", .. }));
+            }
+            other => panic!("unexpected layer {:?}", other),
+        },
+        other => panic!("unexpected packet {:?}", other),
+    }
+
+    // Content-Length 与 chunked + gzip 报文体
+    match QuinPacket::parse_from_stream(&packets[1], &options) {
+        QuinPacket::L5(l5) if l5.error.is_none() => match &l5.application_layer {
+            ApplicationLayer::Http(http) => {
+                assert_eq!(http.messages.len(), 2);
+                assert_eq!(http.messages[0].body().as_deref(), Some(&b"hello"[..]));
+                assert_eq!(
+                    http.messages[1].body().as_deref(),
+                    Some(&br#"{"run":true,"mode":"RUN"}"#[..])
+                );
+            }
+            other => panic!("unexpected layer {:?}", other),
+        },
+        other => panic!("unexpected packet {:?}", other),
+    }
+
+    // 跨报文的请求在单个报文中不完整
+    match QuinPacket::parse_from_stream(&packets[2], &options) {
+        QuinPacket::L5(l5) => match &l5.application_layer {
+            ApplicationLayer::Http(http) => {
+                assert!(!http.messages[0].is_complete());
+                assert_eq!(http.messages[0].body(), None);
+            }
+            other => panic!("unexpected layer {:?}", other),
+        },
+        other => panic!("unexpected packet {:?}", other),
+    }
+
+    // 端口不在配置中时不识别为 HTTP
+    let options_80 = QuinPacketOptions {
+        http: HttpOptions { ports: PortList::from_slice(&[80]) },
+        ..Default::default()
+    };
+    assert!(matches!(
+        QuinPacket::parse_from_stream(&packets[0], &options_80),
+        QuinPacket::L4(_)
+    ));
+
+    // 按连接重组并配对请求与响应
+    let mut flow = QuinFlow::new();
+    let mut messages = Vec::new();
+    for packet in &packets {
+        QuinPacket::parse_from_flow(packet, &QuinPacketOptions::default(), &mut flow);
+        messages.extend(flow.http.completed().iter().cloned());
+    }
+    let exchanges: Vec<(Option<String>, u16)> = messages
+        .iter()
+        .filter_map(|message| match message {
+            HttpFlowMessage::Response { request, response } => {
+                Some((request.as_ref().map(|request| request.path.clone()), response.code))
+            }
+            HttpFlowMessage::Request(_) => None,
+        })
+        .collect();
+    assert_eq!(
+        exchanges,
+        vec![
+            (Some("/status.htm".to_string()), 200),
+            (Some("/io.json".to_string()), 200),
+            (Some("/cgi/write".to_string()), 204),
+        ]
+    );
+    match &messages[4] {
+        HttpFlowMessage::Request(request) => {
+            assert_eq!(request.method, "POST");
+            assert_eq!(request.body.as_deref(), Some(&b"DB1.DBW0=42&"[..]));
+        }
+        other => panic!("unexpected message {:?}", other),
+    }
+}
+
+#[test]
+fn parse_http_flow_incremental() {
+    // 报文体逐字节到达时，消息头只在完整后解析一次，报文体按记录的长度等待
+    let mut flow = HttpFlow::new();
+    let request = b"POST /cgi/write HTTP/1.1\r\nHost: plc\r\nContent-Length: 12\r\n\r\nDB1.DBW0=42&";
+    let mut messages = Vec::new();
+    for byte in request.iter() {
+        messages.extend(flow.push(HttpDirection::ToServer, &[*byte]));
+    }
+    match &messages[..] {
+        [HttpFlowMessage::Request(request)] => {
+            assert_eq!(request.body.as_deref(), Some(&b"DB1.DBW0=42&"[..]))
+        }
+        other => panic!("unexpected messages {:?}", other),
+    }
+
+    let response = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nhello\r\n6\r\n world\r\n0\r\n\r\n";
+    let mut messages = Vec::new();
+    for byte in response.iter() {
+        messages.extend(flow.push(HttpDirection::ToClient, &[*byte]));
+    }
+    match &messages[..] {
+        [HttpFlowMessage::Response { request: Some(request), response }] => {
+            assert_eq!(request.path, "/cgi/write");
+            assert_eq!(response.body.as_deref(), Some(&b"hello world"[..]));
+        }
+        other => panic!("unexpected messages {:?}", other),
+    }
+
+    // 等待响应的请求数有上限，超出时丢弃最早的请求
+    for i in 0..=HTTP_MAX_PENDING_REQUESTS {
+        let request = format!("GET /{} HTTP/1.1\r\n\r\n", i);
+        assert_eq!(flow.push(HttpDirection::ToServer, request.as_bytes()).len(), 1);
+    }
+    match &flow.push(HttpDirection::ToClient, b"HTTP/1.1 204 No Content\r\n\r\n")[..] {
+        [HttpFlowMessage::Response { request: Some(request), .. }] => assert_eq!(request.path, "/1"),
+        other => panic!("unexpected messages {:?}", other),
+    }
+}
+
+#[test]
+fn parse_http_header_limits() {
+    let mut input = b"GET / HTTP/1.1\r\n".to_vec();
+    for i in 0..HTTP_MAX_HEADERS {
+        input.extend_from_slice(format!("X-{}: {}\r\n", i, i).as_bytes());
+    }
+    input.extend_from_slice(b"\r\nGET /next HTTP/1.1\r\n\r\n");
+    let (_, http) = parse_http_header(&input).unwrap();
+    assert_eq!(http.messages.len(), 2);
+    assert_eq!(http.messages[0].headers().len(), HTTP_MAX_HEADERS);
+
+    // 头部数超过上限
+    let index = input.windows(4).position(|window| window == b"\r\n\r\n").unwrap();
+    input.splice(index + 2..index + 2, b"X-Extra: 1\r\n".iter().cloned());
+    assert!(parse_http_header(&input).is_err());
+
+    // 超过长度上限仍不完整的消息头
+    let mut input = b"GET / HTTP/1.1\r\nX-Long: ".to_vec();
+    input.resize(HTTP_MAX_HEADER_SIZE + 1, b'a');
+    assert!(parse_http_header(&input).is_err());
+    input.truncate(HTTP_MAX_HEADER_SIZE);
+    assert!(parse_http_header(&input).is_ok());
+}
+
 #[test]
 fn parse_goose() {
     let goose_pcap_path = "./tests/goose.pcap";
@@ -121,6 +1142,104 @@ fn parse_goose() {
     assert!(parse_pcap(goose_pcap_path).is_ok());
 }
 
+fn goose_all_data(packet: QuinPacket) -> Vec<GooseData> {
+    match packet {
+        QuinPacket::L5(L5Packet {
+            application_layer: ApplicationLayer::Goose(goose),
+            ..
+        }) => goose.goose_pdu.all_data,
+        other => panic!("unexpected packet {:?}", other),
+    }
+}
+
+#[test]
+fn parse_goose_data() {
+    // 抓包中的 F650 GOOSE: 4 组 (stVal, q)
+    let packets = read_pcap("./tests/goose.pcap");
+    let all_data = goose_all_data(QuinPacket::parse_from_stream(&packets[0], &QuinPacketOptions::default()));
+    assert_eq!(all_data.len(), 8);
+    for pair in all_data.chunks(2) {
+        assert_eq!(pair[0], GooseData::Boolean(false));
+        assert_eq!(pair[1], GooseData::BitString { padding: 3, bits: &[0x00, 0x00] });
+        assert_eq!(pair[1].bit(0), Some(false));
+        assert_eq!(pair[1].bit(12), Some(false));
+        assert_eq!(pair[1].bit(13), None);
+    }
+
+    // 同一 GOOSE 控制块，数据集为一个结构: stVal, q, 整数, 单精度浮点, t
+    let frame: &[u8] = &[
+        0x01, 0xa0, 0xf4, 0x08, 0x2f, 0x77, 0x00, 0xa0, 0xf4, 0x08, 0x2f, 0x77,
+        0x88, 0xb8, 0x00, 0x01, 0x00, 0x90, 0x00, 0x00, 0x00, 0x00, 0x61, 0x81,
+        0x85, 0x80, 0x1a, 0x47, 0x45, 0x44, 0x65, 0x76, 0x69, 0x63, 0x65, 0x46,
+        0x36, 0x35, 0x30, 0x2f, 0x4c, 0x4c, 0x4e, 0x30, 0x24, 0x47, 0x4f, 0x24,
+        0x67, 0x63, 0x62, 0x30, 0x31, 0x81, 0x03, 0x00, 0x9c, 0x40, 0x82, 0x18,
+        0x47, 0x45, 0x44, 0x65, 0x76, 0x69, 0x63, 0x65, 0x46, 0x36, 0x35, 0x30,
+        0x2f, 0x4c, 0x4c, 0x4e, 0x30, 0x24, 0x47, 0x4f, 0x4f, 0x53, 0x45, 0x31,
+        0x83, 0x0b, 0x46, 0x36, 0x35, 0x30, 0x5f, 0x47, 0x4f, 0x4f, 0x53, 0x45,
+        0x31, 0x84, 0x08, 0x38, 0x6e, 0xbb, 0xf3, 0x42, 0x17, 0x28, 0x0a, 0x85,
+        0x01, 0x01, 0x86, 0x01, 0x0a, 0x87, 0x01, 0x00, 0x88, 0x01, 0x01, 0x89,
+        0x01, 0x00, 0x8a, 0x01, 0x01, 0xab, 0x1f, 0xa2, 0x1d, 0x83, 0x01, 0x01,
+        0x84, 0x03, 0x03, 0x00, 0x40, 0x85, 0x02, 0xff, 0x38, 0x87, 0x05, 0x08,
+        0x42, 0x48, 0x00, 0x00, 0x91, 0x08, 0x5f, 0x5e, 0x10, 0x00, 0x80, 0x00,
+        0x00, 0x0a,
+    ];
+    let all_data = goose_all_data(QuinPacket::parse_from_stream(frame, &QuinPacketOptions::default()));
+    assert_eq!(
+        all_data,
+        vec![GooseData::Structure(vec![
+            GooseData::Boolean(true),
+            GooseData::BitString { padding: 3, bits: &[0x00, 0x40] },
+            GooseData::Integer(-200),
+            GooseData::Float(50.0),
+            GooseData::UtcTime(UtcTime {
+                seconds: 1_600_000_000,
+                fraction: 0x80_0000,
+                quality: 0x0a,
+            }),
+        ])]
+    );
+    match &all_data[0] {
+        GooseData::Structure(members) => {
+            // q 的第 9 位为 test
+            assert_eq!(members[1].bit(9), Some(true));
+            assert_eq!(members[1].bit(8), Some(false));
+            match &members[4] {
+                GooseData::UtcTime(t) => assert_eq!(t.nanoseconds(), 500_000_000),
+                other => panic!("unexpected data {:?}", other),
+            }
+        }
+        other => panic!("unexpected data {:?}", other),
+    }
+}
+
+#[test]
+fn parse_goose_nested_structure() {
+    // 逐层包裹 0xa2 Structure，最内层为 Boolean
+    fn nest(levels: usize) -> Vec<u8> {
+        let mut data = vec![0x83, 0x01, 0x01];
+        for _ in 0..levels {
+            let mut outer = vec![0xa2, 0x82];
+            outer.extend_from_slice(&(data.len() as u16).to_be_bytes());
+            outer.extend_from_slice(&data);
+            data = outer;
+        }
+        data
+    }
+
+    let frame = nest(16);
+    let (_, mut data) = goose::parse_goose_data(&frame).unwrap();
+    for _ in 0..16 {
+        data = match data {
+            GooseData::Structure(mut members) => members.remove(0),
+            other => panic!("unexpected data {:?}", other),
+        };
+    }
+    assert_eq!(data, GooseData::Boolean(true));
+
+    assert!(goose::parse_goose_data(&nest(17)).is_err());
+    assert!(goose::parse_goose_data(&nest(10000)).is_err());
+}
+
 #[test]
 fn parse_sv() {
     // tracing_subscriber::fmt::init();
@@ -128,3 +1247,463 @@ fn parse_sv() {
 
     assert!(parse_pcap(sv_pcap_path).is_ok());
 }
+
+#[test]
+fn parse_sv_seq_data() {
+    // 1 个 ASDU，2 个通道: 1000 品质良好；-1000 品质可疑且置检修位
+    let frame: &[u8] = &[
+        0x01, 0x0c, 0xcd, 0x04, 0x00, 0x01, 0x00, 0x1e, 0xc9, 0x08, 0x4e, 0x6c,
+        0x88, 0xba, 0x40, 0x00, 0x00, 0x36, 0x00, 0x00, 0x00, 0x00, 0x60, 0x2c,
+        0x80, 0x01, 0x01, 0xa2, 0x27, 0x30, 0x25, 0x80, 0x04, 0x4d, 0x55, 0x30,
+        0x31, 0x82, 0x02, 0x00, 0x0a, 0x83, 0x04, 0x00, 0x00, 0x00, 0x01, 0x85,
+        0x01, 0x02, 0x87, 0x10, 0x00, 0x00, 0x03, 0xe8, 0x00, 0x00, 0x00, 0x00,
+        0xff, 0xff, 0xfc, 0x18, 0x00, 0x00, 0x08, 0x03,
+    ];
+    let sv = match QuinPacket::parse_from_stream(frame, &QuinPacketOptions::default()) {
+        QuinPacket::L5(L5Packet {
+            application_layer: ApplicationLayer::Sv(sv),
+            ..
+        }) => sv,
+        other => panic!("unexpected packet {:?}", other),
+    };
+    assert_eq!(sv.appid, 0x4000);
+    assert_eq!(sv.sav_pdu.no_asdu, 1);
+    let asdu = &sv.sav_pdu.seq_asdu[0];
+    assert_eq!(asdu.sv_id, b"MU01");
+    assert_eq!(asdu.smp_cnt, 10);
+    assert_eq!(asdu.conf_rev, 1);
+    assert_eq!(asdu.smp_synch, 2);
+    assert_eq!(
+        asdu.seq_data,
+        vec![
+            SvSample { value: 1000, quality: SvQuality(0) },
+            SvSample { value: -1000, quality: SvQuality(0x0803) },
+        ]
+    );
+
+    let good = asdu.seq_data[0].quality;
+    assert!(good.is_good() && !good.is_test());
+    let questionable = asdu.seq_data[1].quality;
+    assert_eq!(questionable.validity(), 3);
+    assert!(!questionable.is_good());
+    assert!(questionable.is_test());
+    assert!(!questionable.is_substituted());
+    assert_eq!(questionable.detail_qual(), 0);
+
+    let measurements = asdu.measurements(&SvChannelMap::default());
+    assert_eq!(measurements[0].name, "Ia");
+    assert!((measurements[0].value - 1.0).abs() < 1e-9);
+    assert_eq!(measurements[1].name, "Ib");
+    assert!((measurements[1].value + 1.0).abs() < 1e-9);
+
+    // seqData 长度不是 8 的整数倍
+    assert!(sv::parse_seq_data(&frame[52..67]).is_err());
+}
+
+#[test]
+fn parse_modbus_mei_pcap() {
+    let modbus_mei_pcap_path = "./tests/modbus_mei.pcap";
+
+    assert!(parse_pcap(modbus_mei_pcap_path).is_ok());
+
+    let packets = read_pcap(modbus_mei_pcap_path);
+    assert_eq!(packets.len(), 4);
+    let pdu = match QuinPacket::parse_from_stream(&packets[1], &QuinPacketOptions::default()) {
+        QuinPacket::L5(L5Packet { application_layer: ApplicationLayer::ModbusRsp(header), error: None, .. }) => {
+            header.pdu
+        }
+        other => panic!("unexpected packet {:?}", other),
+    };
+    // Read Device Identification 基本类别应答
+    match pdu.data {
+        modbus_rsp::Data::EncapsulatedInterfaceTransport {
+            mei_type: 0x0e,
+            mei_data:
+                MeiRspData::ReadDeviceIdentification {
+                    read_device_id_code: 1,
+                    conformity_level: 1,
+                    more_follows: false,
+                    next_object_id: 0,
+                    number_of_objects: 3,
+                    objects,
+                },
+        } => {
+            let values: Vec<(Option<&str>, &[u8])> = objects
+                .iter()
+                .map(|object| (object.object_name(), object.object_value))
+                .collect();
+            assert_eq!(
+                values,
+                vec![
+                    (Some("VendorName"), &b"Schneider Electric"[..]),
+                    (Some("ProductCode"), &b"BMX P34 2020"[..]),
+                    (Some("MajorMinorRevision"), &b"v2.70"[..]),
+                ]
+            );
+        }
+        other => panic!("unexpected data {:?}", other),
+    }
+
+    // 请求的对象不存在时返回异常码 02
+    match QuinPacket::parse_from_stream(&packets[3], &QuinPacketOptions::default()) {
+        QuinPacket::L5(L5Packet { application_layer: ApplicationLayer::ModbusRsp(header), .. }) => {
+            assert_eq!(header.pdu.function_code, 0xab);
+            assert_eq!(header.pdu.data, modbus_rsp::Data::EncapsulatedInterfaceTransportExc { exception_code: 2 });
+        }
+        other => panic!("unexpected packet {:?}", other),
+    }
+}
+
+#[test]
+fn parse_s7comm_plus_pcap() {
+    let s7comm_plus_pcap_path = "./tests/s7comm_plus.pcap";
+
+    assert!(parse_pcap(s7comm_plus_pcap_path).is_ok());
+}
+
+#[test]
+fn parse_s7comm_plus_array_size() {
+    // SetVariable 请求，属性值为 Null 数组，数组长度为 u32::MAX
+    let frame: &[u8] = &[
+        0x72, 0x01, 0x00, 0x17, 0x31, 0x00, 0x00, 0x04, 0xbb, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00,
+        0x00, 0x01, 0x00, 0xa3, 0x01, 0x10, 0x00, 0x8f, 0xff, 0xff, 0xff, 0x7f,
+    ];
+    let (_, header) = s7comm_plus::parse_s7comm_plus_header(frame).unwrap();
+    match header.data {
+        S7commPlusData::Request { items, payload, .. } => {
+            assert!(items.is_empty());
+            assert_eq!(payload.len(), 9);
+        }
+        other => panic!("unexpected data {:?}", other),
+    }
+
+    // 数组长度超过剩余字节数
+    let frame: &[u8] = &[
+        0x72, 0x01, 0x00, 0x15, 0x31, 0x00, 0x00, 0x04, 0xbb, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00,
+        0x00, 0x01, 0x00, 0xa3, 0x01, 0x10, 0x02, 0x03, 0x0a, 0x0b,
+    ];
+    let (_, header) = s7comm_plus::parse_s7comm_plus_header(frame).unwrap();
+    assert!(matches!(header.data, S7commPlusData::Request { ref items, .. } if items.is_empty()));
+
+    // 长度在范围内的数组正常解析
+    let frame: &[u8] = &[
+        0x72, 0x01, 0x00, 0x15, 0x31, 0x00, 0x00, 0x04, 0xbb, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00,
+        0x00, 0x01, 0x00, 0xa3, 0x01, 0x10, 0x02, 0x02, 0x0a, 0x0b,
+    ];
+    let (_, header) = s7comm_plus::parse_s7comm_plus_header(frame).unwrap();
+    match header.data {
+        S7commPlusData::Request { items, .. } => assert_eq!(
+            items,
+            vec![S7commPlusItem::Attribute {
+                attribute_id: 1,
+                value: PValue::Array {
+                    datatype: 0x02,
+                    elements: vec![PValue::USInt(0x0a), PValue::USInt(0x0b)],
+                },
+            }]
+        ),
+        other => panic!("unexpected data {:?}", other),
+    }
+}
+
+#[test]
+fn parse_s7comm_plus_fixed_width_int() {
+    // UInt 0x1234 与 Int -200 各占 2 字节，其后的 USInt 属性不受影响
+    let frame: &[u8] = &[
+        0x72, 0x01, 0x00, 0x1f, 0x31, 0x00, 0x00, 0x04, 0xbb, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00,
+        0x00, 0x01, 0x00, 0xa3, 0x01, 0x00, 0x03, 0x12, 0x34, 0xa3, 0x02, 0x00, 0x07, 0xff, 0x38,
+        0xa3, 0x03, 0x00, 0x02, 0x0a,
+    ];
+    let (_, header) = s7comm_plus::parse_s7comm_plus_header(frame).unwrap();
+    match header.data {
+        S7commPlusData::Request { items, .. } => assert_eq!(
+            items,
+            vec![
+                S7commPlusItem::Attribute { attribute_id: 1, value: PValue::UInt(0x1234) },
+                S7commPlusItem::Attribute { attribute_id: 2, value: PValue::Int(-200) },
+                S7commPlusItem::Attribute { attribute_id: 3, value: PValue::USInt(0x0a) },
+            ]
+        ),
+        other => panic!("unexpected data {:?}", other),
+    }
+}
+
+#[test]
+fn parse_s7comm_userdata_pcap() {
+    let s7comm_userdata_pcap_path = "./tests/s7comm_userdata.pcap";
+
+    assert!(parse_pcap(s7comm_userdata_pcap_path).is_ok());
+
+    let packets = read_pcap(s7comm_userdata_pcap_path);
+    let userdata: Vec<UserdataData> = packets
+        .iter()
+        .filter_map(|packet| match QuinPacket::parse_from_stream(packet, &QuinPacketOptions::default()) {
+            QuinPacket::L5(L5Packet {
+                application_layer:
+                    ApplicationLayer::S7comm(S7commHeader {
+                        parameter: Parameter::Userdata { userdata_data, .. },
+                        ..
+                    }),
+                error: None,
+                ..
+            }) => Some(userdata_data),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(userdata.len(), packets.len());
+
+    // 读 SZL 0x0011 模块标识
+    assert_eq!(userdata[0], UserdataData::ReadSzlRequest { szl_id: 0x0011, szl_index: 0 });
+    assert!(matches!(
+        userdata[1],
+        UserdataData::ReadSzlResponse { szl_id: 0x0011, szl_index: 0, record_length: 28, record_count: 1, .. }
+    ));
+    assert_eq!(userdata[1].order_number(), Some("6ES7 315-2EH14-0AB0"));
+
+    let password = userdata
+        .iter()
+        .find(|data| matches!(data, UserdataData::PlcPassword { .. }))
+        .unwrap();
+    assert_eq!(password.decoded_password().as_deref(), Some(&b"plc123"[..]));
+
+    // 写时钟请求与读时钟应答
+    let timestamp = S7Timestamp {
+        year: 2026,
+        month: 10,
+        day: 18,
+        hour: 12,
+        minute: 30,
+        second: 45,
+        millisecond: 123,
+        weekday: 1,
+    };
+    let clocks: Vec<&S7Timestamp> = userdata
+        .iter()
+        .filter_map(|data| match data {
+            UserdataData::Clock { timestamp } => Some(timestamp),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(clocks, vec![&timestamp, &timestamp]);
+}
+
+fn read_pcap(path: &str) -> Vec<Vec<u8>> {
+    let file = File::open(path).unwrap();
+    let mut packets = Vec::new();
+    let mut reader = LegacyPcapReader::new(65536, file).unwrap();
+
+    loop {
+        match reader.next() {
+            Ok((offset, block)) => {
+                if let PcapBlockOwned::Legacy(_b) = block {
+                    packets.push(_b.data.to_vec());
+                }
+                reader.consume(offset);
+            }
+            Err(PcapError::Eof) => break,
+            Err(PcapError::Incomplete) => {
+                reader.refill().unwrap();
+            }
+            Err(e) => panic!("error while reading: {:?}", e),
+        }
+    }
+
+    packets
+}
+
+#[test]
+fn parse_dnp3_multi_frame_pcap() {
+    let dnp3_multi_frame_pcap_path = "./tests/dnp3_multi_frame.pcap";
+
+    assert!(parse_pcap(dnp3_multi_frame_pcap_path).is_ok());
+
+    // 第 1 个数据包内含完整分片，第 2、3 个数据包需要跨报文重组
+    let packets = read_pcap(dnp3_multi_frame_pcap_path);
+    let mut reassembler = Dnp3Reassembler::new();
+    let mut fragments = Vec::new();
+    for packet in &packets {
+        let packet = QuinPacket::parse_from_stream(packet, &QuinPacketOptions::default());
+        if let QuinPacket::L5(l5) = packet {
+            if let ApplicationLayer::Dnp3(dnp3) = &l5.application_layer {
+                fragments.push((dnp3.application_layer.is_some(), reassembler.push(dnp3)));
+            }
+        }
+    }
+
+    assert_eq!(fragments.len(), 3);
+    assert!(fragments[0].0 && fragments[0].1.is_some());
+    assert!(!fragments[1].0 && fragments[1].1.is_none());
+    assert!(!fragments[2].0);
+    assert_eq!(fragments[0].1, fragments[2].1);
+
+    // 在连接上下文中解析时，重组的分片直接出现在解析结果中
+    let mut flow = QuinFlow::new();
+    let mut application_layers = Vec::new();
+    for packet in &packets {
+        let packet = QuinPacket::parse_from_flow(packet, &QuinPacketOptions::default(), &mut flow);
+        if let QuinPacket::L5(l5) = packet {
+            if let ApplicationLayer::Dnp3(dnp3) = l5.application_layer {
+                application_layers.push(dnp3.application_layer);
+            }
+        }
+    }
+    assert_eq!(application_layers.len(), 3);
+    assert_eq!(application_layers[0], fragments[0].1);
+    assert!(application_layers[1].is_none());
+    assert_eq!(application_layers[2], fragments[0].1);
+}
+
+#[test]
+fn parse_dnp3_bad_crc_pcap() {
+    let packets = read_pcap("./tests/dnp3_bad_crc.pcap");
+    let packet = QuinPacket::parse_from_stream(&packets[0], &QuinPacketOptions::default());
+
+    assert!(matches!(
+        packet.get_error(),
+        Some(ParseError::ChecksumMismatch { .. })
+    ));
+}
+
+#[test]
+fn parse_iec101_pcap() {
+    let packets = read_pcap("./tests/iec101_ft12.pcap");
+    let mut options = QuinPacketOptions::default();
+    options.iec101.ports.push(2001);
+
+    let mut frames = Vec::new();
+    for packet in &packets {
+        let packet = QuinPacket::parse_from_stream(packet, &options);
+        assert_eq!(packet.get_error(), None);
+        if let QuinPacket::L5(l5) = packet {
+            if let ApplicationLayer::Iec101(iec101) = l5.application_layer {
+                frames.extend(iec101.frames);
+            }
+        }
+    }
+
+    assert_eq!(frames.len(), 4);
+    assert!(matches!(frames[0], Iec101Frame::Fixed { link_addr: 1, .. }));
+    assert_eq!(frames[0].control().unwrap().function, 9);
+    assert_eq!(frames[1], Iec101Frame::SingleChar);
+
+    // 单点遥控 C_SC_NA_1：传送原因1字节，公共地址1字节，信息对象地址2字节
+    let asdu = frames[2].asdu().unwrap();
+    assert_eq!((asdu.type_id, asdu.cause_tx, asdu.oa, asdu.addr), (45, 6, 0, 1));
+    assert_eq!(asdu.ioa_array[0].ioa, 11);
+
+    // SQ=1 时信息对象地址依次递增
+    let asdu = frames[3].asdu().unwrap();
+    let ioas: Vec<u32> = asdu.ioa_array.iter().map(|ioa| ioa.ioa).collect();
+    assert_eq!(ioas, vec![256, 257, 258]);
+
+    // 未配置端口时不识别为 IEC 101
+    let packet = QuinPacket::parse_from_stream(&packets[0], &QuinPacketOptions::default());
+    assert_eq!(packet.get_error(), Some(ParseError::UnknownPayload));
+}
+
+#[test]
+fn parse_iec101_bad_checksum_pcap() {
+    let packets = read_pcap("./tests/iec101_bad_checksum.pcap");
+    let mut options = QuinPacketOptions::default();
+    options.iec101.ports.push(2001);
+    let packet = QuinPacket::parse_from_stream(&packets[0], &options);
+
+    assert!(matches!(
+        packet.get_error(),
+        Some(ParseError::ChecksumMismatch { .. })
+    ));
+}
+
+#[test]
+fn parse_mms_data_pcap() {
+    let packets = read_pcap("./tests/mms_2.pcap");
+    let mut write_data = None;
+    let mut report_data = None;
+    for packet in &packets {
+        let packet = QuinPacket::parse_from_stream(packet, &QuinPacketOptions::default());
+        if let QuinPacket::L5(l5) = packet {
+            if let ApplicationLayer::Mms(mms) = l5.application_layer {
+                match mms.mms_pdu.mms_pdu_enum {
+                    MmsPduEnum::ConfirmedRequestPDU {
+                        service: ConfirmedServiceRequestEnum::WriteRequest { lod, .. },
+                        ..
+                    } => write_data = write_data.or(Some(lod)),
+                    MmsPduEnum::UnConfirmedPDU {
+                        service: UnConfirmedEnum::InformationReport { list_of_access_result, .. },
+                    } => report_data = report_data.or(Some(list_of_access_result)),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    // ICCP 写请求: 结构体内含嵌套结构体、整数、位串与布尔值
+    let write_data = write_data.unwrap();
+    assert_eq!(write_data.len(), 1);
+    if let MmsData::Structure(members) = &write_data[0] {
+        assert_eq!(members.len(), 12);
+        assert!(matches!(members[0], MmsData::Structure(_)));
+        assert_eq!(members[2], MmsData::Integer(30));
+        assert_eq!(members[6].bit(0), Some(true));
+        assert_eq!(members[10], MmsData::Boolean(true));
+    } else {
+        panic!("unexpected write data: {:?}", write_data[0]);
+    }
+
+    // 信息报告: 浮点数与品质位串
+    let report_data = report_data.unwrap();
+    assert!(matches!(
+        report_data[2].access_result,
+        AccessResult::AccessResultSuccess { data: MmsData::Structure(ref members) }
+            if members[0] == MmsData::Float(0.0)
+    ));
+}
+
+#[test]
+fn parse_mms_get_name_list_pcap() {
+    let packets = read_pcap("./tests/mms_2.pcap");
+    let mut request = None;
+    let mut response = None;
+    for packet in &packets {
+        let packet = QuinPacket::parse_from_stream(packet, &QuinPacketOptions::default());
+        if let QuinPacket::L5(l5) = packet {
+            if let ApplicationLayer::Mms(mms) = l5.application_layer {
+                match mms.mms_pdu.mms_pdu_enum {
+                    MmsPduEnum::ConfirmedRequestPDU {
+                        service: service @ ConfirmedServiceRequestEnum::GetNameListRequest { .. },
+                        ..
+                    } => request = request.or(Some(service)),
+                    MmsPduEnum::ConfirmedResponsePDU {
+                        service: ConfirmedServiceResponse::WithData {
+                            service: service @ ConfirmedServiceResponseEnum::GetNameListResponse { .. },
+                        },
+                        ..
+                    } => response = response.or(Some(service)),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    assert_eq!(
+        request,
+        Some(ConfirmedServiceRequestEnum::GetNameListRequest {
+            object_class: ObjectClass::NamedVariableList {
+                named_variable_list: &[0x02]
+            },
+            object_scope: ObjectScope::ObjectScopeDomain {
+                object_scope_domain_id: b"KIRKLAND",
+                object_scope_item_id: &[]
+            },
+        })
+    );
+    assert_eq!(
+        response,
+        Some(ConfirmedServiceResponseEnum::GetNameListResponse {
+            list_of_identifier: ListOfIdentifier {
+                loar: vec![b"EMS_ANALOG_ICCP_IN", b"EMS_STATUS_ICCP_IN"]
+            },
+            more_follows: 0,
+        })
+    );
+}